@@ -12,9 +12,11 @@ use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_consensus::SelectChain;
 use sp_consensus_babe::BabeApi;
 use sp_keystore::KeystorePtr;
-use sqnc_runtime::{opaque::Block, AccountId, Balance, Hash, Nonce};
+use sqnc_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Nonce, OrgDataKey, OrgDataValue};
 
+mod organisation_data;
 mod sqnc;
+pub use organisation_data::{OrganisationData, OrganisationDataRpcApiServer};
 pub use sqnc::*;
 
 /// Extra dependencies for BABE.
@@ -51,6 +53,13 @@ where
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: BabeApi<Block>,
     C::Api: BlockBuilder<Block>,
+    C::Api: pallet_organisation_data::runtime_api::OrganisationDataApi<
+        Block,
+        AccountId,
+        OrgDataKey,
+        OrgDataValue,
+        BlockNumber,
+    >,
     P: TransactionPool + 'static,
     SC: SelectChain<Block> + 'static,
     SS: SyncStatusProvider<Block> + Send + Sync + Clone + 'static,
@@ -76,6 +85,7 @@ where
     module.merge(System::new(client.clone(), pool).into_rpc())?;
     module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
     module.merge(Babe::new(client.clone(), babe_worker_handle, keystore, select_chain).into_rpc())?;
+    module.merge(OrganisationData::new(client.clone()).into_rpc())?;
     module.merge(Sqnc::new(client.clone(), deps.sqnc).into_rpc())?;
 
     Ok(module)
@@ -104,6 +114,13 @@ where
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: BlockBuilder<Block>,
+    C::Api: pallet_organisation_data::runtime_api::OrganisationDataApi<
+        Block,
+        AccountId,
+        OrgDataKey,
+        OrgDataValue,
+        BlockNumber,
+    >,
     P: TransactionPool + 'static,
     SS: SyncStatusProvider<Block> + Send + Sync + Clone + 'static,
 {
@@ -121,6 +138,7 @@ where
     module.merge(System::new(client.clone(), pool).into_rpc())?;
     module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
     module.merge(ManualSeal::new(command_sink).into_rpc())?;
+    module.merge(OrganisationData::new(client.clone()).into_rpc())?;
     module.merge(Sqnc::new(client.clone(), sqnc).into_rpc())?;
 
     Ok(module)
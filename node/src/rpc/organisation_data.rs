@@ -0,0 +1,132 @@
+use jsonrpsee::proc_macros::rpc;
+use pallet_organisation_data::{runtime_api::OrganisationDataApi, Organisation, OrganisationEntry};
+use sc_rpc_api::system::Error;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::traits::Block as BlockT;
+use sqnc_runtime_types::{AccountId, BlockNumber, OrgDataKey, OrgDataValue};
+use std::sync::Arc;
+
+const RPC_INTERNAL_ERROR: &str = "Error getting organisation data";
+
+/// A value set by an organisation. Values that reference a preimage include its content if it has been noted
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum OrganisationValue {
+    Literal { value: Bytes },
+    Url { value: String },
+    Preimage { hash: H256, content: Option<Bytes> },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganisationEntryResponse {
+    pub key: OrgDataKey,
+    pub value: OrganisationValue,
+    /// Height of the block the value was set in.
+    pub updated_at: BlockNumber,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganisationResponse {
+    pub account: AccountId,
+    pub entries: Vec<OrganisationEntryResponse>,
+}
+
+impl From<OrganisationEntry<OrgDataKey, OrgDataValue, BlockNumber>> for OrganisationEntryResponse {
+    fn from(entry: OrganisationEntry<OrgDataKey, OrgDataValue, BlockNumber>) -> Self {
+        let value = match entry.value {
+            OrgDataValue::Literal(value) => OrganisationValue::Literal {
+                value: value.into_inner().into(),
+            },
+            OrgDataValue::Url(value) => OrganisationValue::Url {
+                value: String::from_utf8_lossy(&value).into_owned(),
+            },
+            OrgDataValue::Preimage(hash) => OrganisationValue::Preimage {
+                hash,
+                content: entry.preimage.map(Into::into),
+            },
+        };
+        Self {
+            key: entry.key,
+            value,
+            updated_at: entry.updated_at,
+        }
+    }
+}
+
+impl From<Organisation<AccountId, OrgDataKey, OrgDataValue, BlockNumber>> for OrganisationResponse {
+    fn from(organisation: Organisation<AccountId, OrgDataKey, OrgDataValue, BlockNumber>) -> Self {
+        Self {
+            account: organisation.account,
+            entries: organisation.entries.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[rpc(client, server)]
+pub trait OrganisationDataRpcApi<BlockHash> {
+    /// Every member organisation and the data it has set, at the best block unless `at` is given.
+    #[method(name = "sqnc_getOrganisations")]
+    fn sqnc_get_organisations(&self, at: Option<BlockHash>) -> Result<Vec<OrganisationResponse>, Error>;
+
+    /// The data set by the organisation of `account` if it is a member, at the best block unless `at` is given.
+    #[method(name = "sqnc_getOrganisation")]
+    fn sqnc_get_organisation(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<OrganisationResponse>, Error>;
+}
+
+pub struct OrganisationData<C, Block> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> OrganisationData<C, Block> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block> OrganisationDataRpcApiServer<Block::Hash> for OrganisationData<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: OrganisationDataApi<Block, AccountId, OrgDataKey, OrgDataValue, BlockNumber>,
+{
+    fn sqnc_get_organisations(&self, at: Option<Block::Hash>) -> Result<Vec<OrganisationResponse>, Error> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .organisations(at)
+            .map(|organisations| organisations.into_iter().map(Into::into).collect())
+            .map_err(|err| {
+                log::warn!("Error getting organisations: {:?}", err);
+                Error::Internal(RPC_INTERNAL_ERROR.into())
+            })
+    }
+
+    fn sqnc_get_organisation(
+        &self,
+        account: AccountId,
+        at: Option<Block::Hash>,
+    ) -> Result<Option<OrganisationResponse>, Error> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .organisation(at, account)
+            .map(|organisation| organisation.map(Into::into))
+            .map_err(|err| {
+                log::warn!("Error getting organisation: {:?}", err);
+                Error::Internal(RPC_INTERNAL_ERROR.into())
+            })
+    }
+}
@@ -1,20 +1,102 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use frame_benchmarking::v1::{account, benchmarks};
+use frame_benchmarking::v1::{account, benchmarks, BenchmarkError};
 use frame_system::RawOrigin;
+use sp_std::vec::Vec;
 
 const SEED: u32 = 0;
 
+// sets a value at `key` with a full history, so that it must be rotated when the value is replaced
+fn set_value_with_full_history<T: Config>(caller: &T::AccountId, key: &T::OrgDataKey) {
+    <OrgDataCount<T>>::set(caller.clone(), 1);
+    <OrgData<T>>::set(caller.clone(), key.clone(), Default::default());
+
+    let history = (0..T::MaxOrgDataHistory::get())
+        .map(|_| HistoricalOrgData {
+            value: Default::default(),
+            set_at: Default::default(),
+            replaced_at: Default::default(),
+        })
+        .collect::<Vec<_>>();
+    <OrgDataHistory<T>>::insert(caller, key, BoundedVec::truncate_from(history));
+}
+
+// fills the delegates of `caller` up to `count`, so that changes to them touch the largest list
+fn set_delegates<T: Config>(caller: &T::AccountId, count: u32) -> Vec<T::AccountId> {
+    <OrgDataCount<T>>::set(caller.clone(), 0);
+
+    let delegates = (0..count)
+        .map(|i| account::<T::AccountId>("delegate", i, SEED))
+        .collect::<Vec<_>>();
+    delegates.iter().for_each(|delegate| {
+        <Delegations<T>>::insert(
+            delegate,
+            Delegation {
+                organisation: caller.clone(),
+                scopes: BoundedVec::new(),
+            },
+        );
+    });
+    <OrgDelegates<T>>::insert(caller, BoundedVec::truncate_from(delegates.clone()));
+    delegates
+}
+
 benchmarks! {
     set_value {
         let caller: T::AccountId = account("owner", 0, SEED);
-        <OrgDataCount<T>>::set(caller.clone(), 0);
-
         let key: T::OrgDataKey = Default::default();
+        set_value_with_full_history::<T>(&caller, &key);
+
         let value: T::OrgDataValue = Default::default();
     }: _(RawOrigin::Signed(caller), key, value)
 
+    remove_value {
+        let caller: T::AccountId = account("owner", 0, SEED);
+        let key: T::OrgDataKey = Default::default();
+        set_value_with_full_history::<T>(&caller, &key);
+    }: _(RawOrigin::Signed(caller), key)
+
+    set_attribute {
+        let member: T::AccountId = account("owner", 0, SEED);
+        <OrgDataCount<T>>::set(member.clone(), 0);
+        let origin = T::SetAttributeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let attribute: T::OrgAttribute = Default::default();
+        let expires_at: BlockNumberFor<T> = 100u32.into();
+    }: _<T::RuntimeOrigin>(origin, member, attribute, expires_at)
+
+    remove_attribute {
+        let member: T::AccountId = account("owner", 0, SEED);
+        let attribute: T::OrgAttribute = Default::default();
+        <OrgAttributes<T>>::insert(&member, &attribute, BlockNumberFor::<T>::from(100u32));
+        let origin = T::SetAttributeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    }: _<T::RuntimeOrigin>(origin, member, attribute)
+
+    offer_delegation {
+        let caller: T::AccountId = account("owner", 0, SEED);
+        <OrgDataCount<T>>::set(caller.clone(), 0);
+
+        let delegate: T::AccountId = account("delegate", 0, SEED);
+    }: _(RawOrigin::Signed(caller), delegate, BoundedVec::new())
+
+    accept_delegation {
+        let organisation: T::AccountId = account("owner", 0, SEED);
+        let max_delegates = T::MaxOrgDelegates::get();
+        set_delegates::<T>(&organisation, max_delegates.saturating_sub(1));
+
+        let caller: T::AccountId = account("delegate", max_delegates, SEED);
+        <DelegateOffers<T>>::insert(&organisation, &caller, DelegateScopes::<T>::new());
+    }: _(RawOrigin::Signed(caller), organisation)
+
+    remove_delegate {
+        let caller: T::AccountId = account("owner", 0, SEED);
+        let delegates = set_delegates::<T>(&caller, T::MaxOrgDelegates::get());
+
+        let delegate = delegates.last().cloned().ok_or(BenchmarkError::Weightless)?;
+        // a pending offer of new scopes is withdrawn along with the delegate
+        <DelegateOffers<T>>::insert(&caller, &delegate, DelegateScopes::<T>::new());
+    }: _(RawOrigin::Signed(caller), delegate)
+
     impl_benchmark_test_suite!(
         OrganisationData,
         crate::mock::new_test_ext(),
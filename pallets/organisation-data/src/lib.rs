@@ -1,6 +1,9 @@
 //! # Organisation Data Pallet
 //!
-//! The Organisation Data Pallet allows setting values against a configured set of keys
+//! The Organisation Data Pallet allows setting values against a configured set of keys, keeping a bounded history
+//! of the values each key previously held. Alongside these self-asserted values governance can grant attributes, such
+//! as certifications, to a member until an expiry block. Members can also offer to make accounts their delegates,
+//! which act for them within a set of scopes once they accept
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -9,20 +12,58 @@ mod mock;
 mod tests;
 
 pub mod migrations;
+pub mod runtime_api;
 pub mod weights;
 
 use frame_support::pallet_prelude::*;
 use frame_support::traits::{ChangeMembers, InitializeMembers};
 use frame_system::pallet_prelude::*;
-use parity_scale_codec::MaxEncodedLen;
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
 use sp_io::MultiRemovalResults;
 use sp_runtime::DispatchResult;
+use sp_std::vec::Vec;
+use sqnc_pallet_traits::{OrganisationAttributeProvider, OrganisationDataProvider, OrganisationDelegateProvider};
 
 pub use pallet::*;
+pub use runtime_api::{Organisation, OrganisationEntry};
 pub use weights::*;
 
 pub const LOG_TARGET: &'static str = "runtime::organisation-data";
 
+/// A value set by a member and the block it was set in
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct OrgDataEntry<Value, BlockNumber> {
+    pub value: Value,
+    pub updated_at: BlockNumber,
+}
+
+/// A value that was replaced or removed at `replaced_at`
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct HistoricalOrgData<Value, BlockNumber> {
+    pub value: Value,
+    pub set_at: BlockNumber,
+    pub replaced_at: BlockNumber,
+}
+
+/// The member organisation a delegate acts for and the scopes it may act within
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct Delegation<AccountId, Scopes> {
+    pub organisation: AccountId,
+    pub scopes: Scopes,
+}
+
+/// Determines the values that may be set at each key
+pub trait ValidateOrgData<OrgDataKey, OrgDataValue> {
+    fn is_valid(key: &OrgDataKey, value: &OrgDataValue) -> bool;
+}
+
+impl<K, V> ValidateOrgData<K, V> for () {
+    fn is_valid(_key: &K, _value: &V) -> bool {
+        true
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 
@@ -37,21 +78,44 @@ pub mod pallet {
 
         type OrgDataKey: Parameter + Default + MaxEncodedLen + Ord;
         type OrgDataValue: Parameter + Default + MaxEncodedLen;
+        type ValidateOrgData: ValidateOrgData<Self::OrgDataKey, Self::OrgDataValue>;
 
         #[pallet::constant]
         type MaxOrgMemberEntries: Get<u32>;
 
+        /// Maximum number of previous values kept for each key of a member
+        #[pallet::constant]
+        type MaxOrgDataHistory: Get<u32>;
+
+        /// The name of an attribute that can be granted to a member
+        type OrgAttribute: Parameter + Default + MaxEncodedLen;
+        /// Origin that grants attributes to members and revokes them
+        type SetAttributeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Something a delegate may do on behalf of its organisation
+        type DelegateScope: Parameter + MaxEncodedLen;
+
+        /// Maximum number of delegates of a member
+        #[pallet::constant]
+        type MaxOrgDelegates: Get<u32>;
+
+        /// Maximum number of scopes a delegate may act within
+        #[pallet::constant]
+        type MaxDelegateScopes: Get<u32>;
+
         /// Information on runtime weights.
         type WeightInfo: WeightInfo;
     }
 
     /// The in-code storage version.
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
+    pub type DelegateScopes<T> = BoundedVec<<T as Config>::DelegateScope, <T as Config>::MaxDelegateScopes>;
+
     #[pallet::storage]
     #[pallet::getter(fn org_data)]
     pub type OrgData<T: Config> = StorageDoubleMap<
@@ -60,7 +124,68 @@ pub mod pallet {
         <T as frame_system::Config>::AccountId,
         Blake2_128Concat,
         T::OrgDataKey,
-        T::OrgDataValue,
+        OrgDataEntry<T::OrgDataValue, BlockNumberFor<T>>,
+        ValueQuery,
+    >;
+
+    /// Previous values of each key of a member, oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn org_data_history)]
+    pub type OrgDataHistory<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Blake2_128Concat,
+        T::OrgDataKey,
+        BoundedVec<HistoricalOrgData<T::OrgDataValue, BlockNumberFor<T>>, T::MaxOrgDataHistory>,
+        ValueQuery,
+    >;
+
+    /// Attributes granted to each member and the block at which each expires
+    #[pallet::storage]
+    #[pallet::getter(fn org_attributes)]
+    pub type OrgAttributes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Blake2_128Concat,
+        T::OrgAttribute,
+        BlockNumberFor<T>,
+        OptionQuery,
+    >;
+
+    /// The member each delegate acts for and the scopes it may act within
+    #[pallet::storage]
+    #[pallet::getter(fn delegations)]
+    pub type Delegations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Delegation<<T as frame_system::Config>::AccountId, DelegateScopes<T>>,
+        OptionQuery,
+    >;
+
+    /// Delegations offered by each member that are yet to be accepted, with the scopes the delegate would act within
+    #[pallet::storage]
+    #[pallet::getter(fn delegate_offers)]
+    pub type DelegateOffers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        DelegateScopes<T>,
+        OptionQuery,
+    >;
+
+    /// The delegates of each member
+    #[pallet::storage]
+    #[pallet::getter(fn org_delegates)]
+    pub type OrgDelegates<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        BoundedVec<<T as frame_system::Config>::AccountId, T::MaxOrgDelegates>,
         ValueQuery,
     >;
 
@@ -77,6 +202,18 @@ pub mod pallet {
     pub enum Event<T: Config> {
         /// A member has updated a metadata value at the specified key
         UpdateOrgData(T::AccountId, T::OrgDataKey),
+        /// A member has removed the metadata value at the specified key
+        RemoveOrgData(T::AccountId, T::OrgDataKey),
+        /// A member has been granted an attribute until the specified block
+        SetOrgAttribute(T::AccountId, T::OrgAttribute, BlockNumberFor<T>),
+        /// An attribute has been revoked from a member
+        RemoveOrgAttribute(T::AccountId, T::OrgAttribute),
+        /// A member has offered to make an account its delegate within the specified scopes
+        OfferDelegation(T::AccountId, T::AccountId, DelegateScopes<T>),
+        /// A delegate has accepted to act for a member within the specified scopes
+        SetDelegate(T::AccountId, T::AccountId, DelegateScopes<T>),
+        /// A member has removed a delegate or withdrawn its offer to one
+        RemoveDelegate(T::AccountId, T::AccountId),
     }
 
     #[pallet::error]
@@ -85,6 +222,24 @@ pub mod pallet {
         NotMember,
         /// Maximum entry count exceeded,
         TooManyEntries,
+        /// The value is not of a type allowed at the key or is malformed
+        InvalidValue,
+        /// No value is set at the key
+        NoValue,
+        /// The expiry of an attribute is not after the current block
+        AttributeExpired,
+        /// The member has not been granted the attribute
+        NoAttribute,
+        /// A member cannot be a delegate
+        DelegateIsMember,
+        /// The account is already a delegate of another member
+        DelegateOfOtherMember,
+        /// Maximum delegate count exceeded
+        TooManyDelegates,
+        /// The account is not a delegate of the member and has not been offered to become one
+        NotDelegate,
+        /// The member has not offered to make the account its delegate
+        NoDelegationOffer,
     }
 
     #[pallet::call]
@@ -97,6 +252,8 @@ pub mod pallet {
             // Check it was signed and get the signer
             let sender = ensure_signed(origin)?;
 
+            ensure!(T::ValidateOrgData::is_valid(&key, &value), Error::<T>::InvalidValue);
+
             let prev_entry_count = <OrgDataCount<T>>::try_get(sender.clone()).map_err(|_| Error::<T>::NotMember)?;
 
             // if we are a member find out if there's a current value and calculate the new entry count
@@ -112,14 +269,263 @@ pub mod pallet {
                 Error::<T>::TooManyEntries
             );
 
-            // update the count and set the new value
-            <OrgData<T>>::set(sender.clone(), key.clone(), value);
+            // keep the previous value in the history, then update the count and set the new value
+            let now = <frame_system::Pallet<T>>::block_number();
+            if let Ok(prev_entry) = maybe_prev_entry {
+                Self::push_history(&sender, &key, prev_entry, now);
+            }
+            <OrgData<T>>::set(sender.clone(), key.clone(), OrgDataEntry { value, updated_at: now });
             <OrgDataCount<T>>::set(sender.clone(), new_entry_count);
 
             Self::deposit_event(Event::UpdateOrgData(sender.clone(), key.clone()));
 
             Ok(())
         }
+
+        /// Removes the value of a member at the supplied key
+        ///
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_value())]
+        pub fn remove_value(origin: OriginFor<T>, key: T::OrgDataKey) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let prev_entry_count = <OrgDataCount<T>>::try_get(sender.clone()).map_err(|_| Error::<T>::NotMember)?;
+            let prev_entry = <OrgData<T>>::try_get(sender.clone(), key.clone()).map_err(|_| Error::<T>::NoValue)?;
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            Self::push_history(&sender, &key, prev_entry, now);
+            <OrgData<T>>::remove(sender.clone(), key.clone());
+            <OrgDataCount<T>>::set(sender.clone(), prev_entry_count.saturating_sub(1));
+
+            Self::deposit_event(Event::RemoveOrgData(sender, key));
+
+            Ok(())
+        }
+
+        /// Grants an attribute to a member until `expires_at`, replacing the expiry of an attribute it already holds
+        ///
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_attribute())]
+        pub fn set_attribute(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            attribute: T::OrgAttribute,
+            expires_at: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            T::SetAttributeOrigin::ensure_origin(origin)?;
+
+            ensure!(<OrgDataCount<T>>::contains_key(&account), Error::<T>::NotMember);
+            ensure!(
+                expires_at > <frame_system::Pallet<T>>::block_number(),
+                Error::<T>::AttributeExpired
+            );
+
+            <OrgAttributes<T>>::insert(&account, &attribute, expires_at);
+
+            Self::deposit_event(Event::SetOrgAttribute(account, attribute, expires_at));
+
+            Ok(())
+        }
+
+        /// Revokes an attribute from a member. Expired attributes are no longer held but remain until revoked
+        ///
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_attribute())]
+        pub fn remove_attribute(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            attribute: T::OrgAttribute,
+        ) -> DispatchResult {
+            T::SetAttributeOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                <OrgAttributes<T>>::contains_key(&account, &attribute),
+                Error::<T>::NoAttribute
+            );
+            <OrgAttributes<T>>::remove(&account, &attribute);
+
+            Self::deposit_event(Event::RemoveOrgAttribute(account, attribute));
+
+            Ok(())
+        }
+
+        /// Offers to make `delegate` a delegate of the member within `scopes`, replacing any previous offer to it. The
+        /// delegation, or the new scopes of an existing delegate, only take effect once the delegate accepts
+        ///
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::offer_delegation())]
+        pub fn offer_delegation(
+            origin: OriginFor<T>,
+            delegate: T::AccountId,
+            scopes: DelegateScopes<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(<OrgDataCount<T>>::contains_key(&sender), Error::<T>::NotMember);
+            ensure!(
+                !<OrgDataCount<T>>::contains_key(&delegate),
+                Error::<T>::DelegateIsMember
+            );
+
+            <DelegateOffers<T>>::insert(&sender, &delegate, scopes.clone());
+
+            Self::deposit_event(Event::OfferDelegation(sender, delegate, scopes));
+
+            Ok(())
+        }
+
+        /// Removes a delegate of the member, withdrawing any offer to it
+        ///
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_delegate())]
+        pub fn remove_delegate(origin: OriginFor<T>, delegate: T::AccountId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let is_delegate =
+                <Delegations<T>>::get(&delegate).is_some_and(|delegation| delegation.organisation == sender);
+            let is_offered = <DelegateOffers<T>>::contains_key(&sender, &delegate);
+            ensure!(is_delegate || is_offered, Error::<T>::NotDelegate);
+
+            <DelegateOffers<T>>::remove(&sender, &delegate);
+            if is_delegate {
+                Self::remove_delegation(&sender, &delegate);
+            }
+
+            Self::deposit_event(Event::RemoveDelegate(sender, delegate));
+
+            Ok(())
+        }
+
+        /// Accepts the offer of `organisation` to make the sender its delegate, acting for it within the offered
+        /// scopes
+        ///
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::accept_delegation())]
+        pub fn accept_delegation(origin: OriginFor<T>, organisation: T::AccountId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let scopes = <DelegateOffers<T>>::get(&organisation, &sender).ok_or(Error::<T>::NoDelegationOffer)?;
+            ensure!(<OrgDataCount<T>>::contains_key(&organisation), Error::<T>::NotMember);
+            ensure!(!<OrgDataCount<T>>::contains_key(&sender), Error::<T>::DelegateIsMember);
+
+            match <Delegations<T>>::get(&sender) {
+                Some(delegation) => ensure!(
+                    delegation.organisation == organisation,
+                    Error::<T>::DelegateOfOtherMember
+                ),
+                None => <OrgDelegates<T>>::try_mutate(&organisation, |delegates| {
+                    delegates
+                        .try_push(sender.clone())
+                        .map_err(|_| Error::<T>::TooManyDelegates)
+                })?,
+            }
+
+            <DelegateOffers<T>>::remove(&organisation, &sender);
+            <Delegations<T>>::insert(
+                &sender,
+                Delegation {
+                    organisation: organisation.clone(),
+                    scopes: scopes.clone(),
+                },
+            );
+
+            Self::deposit_event(Event::SetDelegate(organisation, sender, scopes));
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        // appends a replaced entry to the history of a key, dropping the oldest entry if the history is full
+        fn push_history(
+            account: &T::AccountId,
+            key: &T::OrgDataKey,
+            entry: OrgDataEntry<T::OrgDataValue, BlockNumberFor<T>>,
+            replaced_at: BlockNumberFor<T>,
+        ) {
+            if T::MaxOrgDataHistory::get() == 0 {
+                return;
+            }
+
+            <OrgDataHistory<T>>::mutate(account, key, |history| {
+                if history.is_full() {
+                    history.remove(0);
+                }
+                let _ = history.try_push(HistoricalOrgData {
+                    value: entry.value,
+                    set_at: entry.updated_at,
+                    replaced_at,
+                });
+            });
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Every member organisation ordered by account. `resolve` returns the preimage referenced by a value, if any
+    pub fn organisations(
+        resolve: impl Fn(&T::OrgDataValue) -> Option<Vec<u8>>,
+    ) -> Vec<Organisation<T::AccountId, T::OrgDataKey, T::OrgDataValue, BlockNumberFor<T>>> {
+        let mut accounts = <OrgDataCount<T>>::iter_keys().collect::<Vec<_>>();
+        accounts.sort();
+        accounts
+            .into_iter()
+            .map(|account| Self::organisation_entries(account, &resolve))
+            .collect()
+    }
+
+    /// The organisation of `account` if it is a member. `resolve` returns the preimage referenced by a value, if any
+    pub fn organisation(
+        account: T::AccountId,
+        resolve: impl Fn(&T::OrgDataValue) -> Option<Vec<u8>>,
+    ) -> Option<Organisation<T::AccountId, T::OrgDataKey, T::OrgDataValue, BlockNumberFor<T>>> {
+        if !<OrgDataCount<T>>::contains_key(&account) {
+            return None;
+        }
+        Some(Self::organisation_entries(account, &resolve))
+    }
+
+    // removes `delegate` from the delegates of `organisation`
+    fn remove_delegation(organisation: &T::AccountId, delegate: &T::AccountId) {
+        <Delegations<T>>::remove(delegate);
+        <OrgDelegates<T>>::mutate(organisation, |delegates| delegates.retain(|d| d != delegate));
+    }
+
+    fn organisation_entries(
+        account: T::AccountId,
+        resolve: &impl Fn(&T::OrgDataValue) -> Option<Vec<u8>>,
+    ) -> Organisation<T::AccountId, T::OrgDataKey, T::OrgDataValue, BlockNumberFor<T>> {
+        let mut entries = <OrgData<T>>::iter_prefix(&account)
+            .map(|(key, entry)| OrganisationEntry {
+                key,
+                preimage: resolve(&entry.value),
+                value: entry.value,
+                updated_at: entry.updated_at,
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        Organisation { account, entries }
+    }
+}
+
+impl<T: Config> OrganisationDataProvider<T::AccountId, T::OrgDataKey, T::OrgDataValue> for Pallet<T> {
+    fn org_data(account: &T::AccountId, key: &T::OrgDataKey) -> Option<T::OrgDataValue> {
+        <OrgData<T>>::try_get(account, key).ok().map(|entry| entry.value)
+    }
+}
+
+impl<T: Config> OrganisationAttributeProvider<T::AccountId, T::OrgAttribute> for Pallet<T> {
+    fn has_attribute(account: &T::AccountId, attribute: &T::OrgAttribute) -> bool {
+        let now = <frame_system::Pallet<T>>::block_number();
+        <OrgAttributes<T>>::get(account, attribute).is_some_and(|expires_at| now < expires_at)
+    }
+}
+
+impl<T: Config> OrganisationDelegateProvider<T::AccountId, T::DelegateScope> for Pallet<T> {
+    fn organisation_of(delegate: &T::AccountId, scope: &T::DelegateScope) -> Option<T::AccountId> {
+        <Delegations<T>>::get(delegate)
+            .filter(|delegation| delegation.scopes.contains(scope))
+            .map(|delegation| delegation.organisation)
     }
 }
 
@@ -132,6 +538,11 @@ impl<T: Config> ChangeMembers<T::AccountId> for Pallet<T> {
 
         incoming.iter().for_each(|m| {
             <OrgDataCount<T>>::set(m, 0u32);
+
+            // a member cannot also be a delegate
+            if let Some(delegation) = <Delegations<T>>::get(m) {
+                Self::remove_delegation(&delegation.organisation, m);
+            }
         });
 
         outgoing.iter().for_each(|m| {
@@ -145,6 +556,38 @@ impl<T: Config> ChangeMembers<T::AccountId> for Pallet<T> {
                     "Unexpectedly did not fully clear org data for member {:?}", m
                 );
             }
+
+            // history outlives removed values so is bounded by the number of distinct keys rather than entries
+            let MultiRemovalResults { maybe_cursor, .. } = <OrgDataHistory<T>>::clear_prefix(m, u32::MAX, None);
+
+            if maybe_cursor.is_some() {
+                log::error!(
+                    target: LOG_TARGET,
+                    "Unexpectedly did not fully clear org data history for member {:?}", m
+                );
+            }
+
+            let MultiRemovalResults { maybe_cursor, .. } = <OrgAttributes<T>>::clear_prefix(m, u32::MAX, None);
+
+            if maybe_cursor.is_some() {
+                log::error!(
+                    target: LOG_TARGET,
+                    "Unexpectedly did not fully clear org attributes for member {:?}", m
+                );
+            }
+
+            <OrgDelegates<T>>::take(m).iter().for_each(|delegate| {
+                <Delegations<T>>::remove(delegate);
+            });
+
+            let MultiRemovalResults { maybe_cursor, .. } = <DelegateOffers<T>>::clear_prefix(m, u32::MAX, None);
+
+            if maybe_cursor.is_some() {
+                log::error!(
+                    target: LOG_TARGET,
+                    "Unexpectedly did not fully clear delegation offers for member {:?}", m
+                );
+            }
         });
     }
 }
@@ -33,11 +33,47 @@ pub mod v1 {
     }
 }
 
+pub mod v2 {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::BlockNumberFor;
+
+    /// Migrate the org-data pallet from V1 to V2, wrapping each value in an `OrgDataEntry`. The block a value was
+    /// set in is not known so existing values are recorded as set in the block of the migration
+    pub struct MigrateToV2<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let version = StorageVersion::get::<Pallet<T>>();
+            if version != 1 {
+                log::warn!(
+                    target: TARGET,
+                    "skipping v1 to v2 migration: executed on wrong storage version. Expected version 1, found {:?}",
+                    version,
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            log::debug!(target: TARGET, "Running migration of organisation data pallet version 1 -> 2");
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let mut translated = 0u64;
+            <OrgData<T>>::translate::<T::OrgDataValue, _>(|_, _, value| {
+                translated += 1;
+                Some(OrgDataEntry::<T::OrgDataValue, BlockNumberFor<T>> { value, updated_at: now })
+            });
+            StorageVersion::new(2).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(translated + 2, translated + 1)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{v1::MigrateToV1, *};
+    use super::{v1::MigrateToV1, v2::MigrateToV2, *};
     use crate::mock::*;
-    use frame_support::pallet_prelude::*;
+    use frame_support::{pallet_prelude::*, storage::unhashed};
 
     #[test]
     fn migration_v0_to_v1_works() {
@@ -95,4 +131,66 @@ mod test {
             assert_eq!(values, vec![(0, 0), (1, 0)]);
         })
     }
+
+    #[test]
+    fn migration_v1_to_v2_keeps_existing_values() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(5);
+            StorageVersion::new(1).put::<OrganisationData>();
+            let old_key = |account: u64, key: u8| <OrgData<Test>>::hashed_key_for(account, key);
+            unhashed::put(&old_key(1, 1), &42u32);
+            unhashed::put(&old_key(1, 2), &43u32);
+            unhashed::put(&old_key(2, 1), &44u32);
+
+            MigrateToV2::<Test>::on_runtime_upgrade();
+
+            assert_eq!(StorageVersion::get::<OrganisationData>(), 2);
+            let mut values = <OrgData<Test>>::iter().collect::<Vec<_>>();
+            values.sort_by_key(|(m, k, _)| (*m, *k));
+            assert_eq!(
+                values,
+                vec![
+                    (
+                        1,
+                        1,
+                        OrgDataEntry {
+                            value: 42,
+                            updated_at: 5
+                        }
+                    ),
+                    (
+                        1,
+                        2,
+                        OrgDataEntry {
+                            value: 43,
+                            updated_at: 5
+                        }
+                    ),
+                    (
+                        2,
+                        1,
+                        OrgDataEntry {
+                            value: 44,
+                            updated_at: 5
+                        }
+                    ),
+                ]
+            );
+        })
+    }
+
+    #[test]
+    fn migration_v1_to_v2_skips_other_versions() {
+        new_test_ext().execute_with(|| {
+            StorageVersion::new(2).put::<OrganisationData>();
+            unhashed::put(&<OrgData<Test>>::hashed_key_for(1u64, 1u8), &42u32);
+
+            MigrateToV2::<Test>::on_runtime_upgrade();
+
+            assert_eq!(
+                unhashed::get::<u32>(&<OrgData<Test>>::hashed_key_for(1u64, 1u8)),
+                Some(42)
+            );
+        })
+    }
 }
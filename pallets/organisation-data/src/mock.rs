@@ -3,6 +3,7 @@
 #![cfg(test)]
 
 use crate as pallet_organisation_data;
+use crate::ValidateOrgData;
 
 use frame_support::derive_impl;
 use frame_system::{config_preludes::TestDefaultConfig, DefaultConfig, EnsureRoot};
@@ -39,13 +40,30 @@ impl pallet_membership::Config for Test {
     type WeightInfo = ();
 }
 
+// values over 100 are not valid
+pub struct MaxHundredOrgData;
+impl ValidateOrgData<u8, u32> for MaxHundredOrgData {
+    fn is_valid(_key: &u8, value: &u32) -> bool {
+        *value <= 100
+    }
+}
+
 impl pallet_organisation_data::Config for Test {
     type RuntimeEvent = RuntimeEvent;
 
     type OrgDataKey = u8;
     type OrgDataValue = u32;
+    type ValidateOrgData = MaxHundredOrgData;
 
     type MaxOrgMemberEntries = ConstU32<2u32>;
+    type MaxOrgDataHistory = ConstU32<2u32>;
+
+    type OrgAttribute = u8;
+    type SetAttributeOrigin = EnsureRoot<AccountId>;
+
+    type DelegateScope = u8;
+    type MaxOrgDelegates = ConstU32<2u32>;
+    type MaxDelegateScopes = ConstU32<2u32>;
 
     type WeightInfo = ();
 }
@@ -0,0 +1,39 @@
+//! Runtime API for reading the data of member organisations
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A value set by an organisation. `preimage` holds the content of a value that references a preimage, if the
+/// runtime can resolve it
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct OrganisationEntry<OrgDataKey, OrgDataValue, BlockNumber> {
+    pub key: OrgDataKey,
+    pub value: OrgDataValue,
+    pub updated_at: BlockNumber,
+    pub preimage: Option<Vec<u8>>,
+}
+
+/// A member organisation and every value it has set
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct Organisation<AccountId, OrgDataKey, OrgDataValue, BlockNumber> {
+    pub account: AccountId,
+    pub entries: Vec<OrganisationEntry<OrgDataKey, OrgDataValue, BlockNumber>>,
+}
+
+sp_api::decl_runtime_apis! {
+    pub trait OrganisationDataApi<AccountId, OrgDataKey, OrgDataValue, BlockNumber>
+    where
+        AccountId: Codec,
+        OrgDataKey: Codec,
+        OrgDataValue: Codec,
+        BlockNumber: Codec,
+    {
+        /// Every member organisation, ordered by account
+        fn organisations() -> Vec<Organisation<AccountId, OrgDataKey, OrgDataValue, BlockNumber>>;
+
+        /// The organisation of `account`, if it is a member
+        fn organisation(account: AccountId) -> Option<Organisation<AccountId, OrgDataKey, OrgDataValue, BlockNumber>>;
+    }
+}
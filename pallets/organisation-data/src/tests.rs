@@ -1,10 +1,15 @@
 #![cfg(test)]
 
-use crate::{mock::*, Error, OrgData, OrgDataCount};
+use crate::{
+    mock::*, DelegateOffers, DelegateScopes, Delegation, Delegations, Error, Event, HistoricalOrgData, OrgAttributes,
+    OrgData, OrgDataCount, OrgDataEntry, OrgDataHistory, OrgDelegates, Organisation, OrganisationEntry,
+};
 use frame_support::{
-    assert_err, assert_ok,
+    assert_err, assert_ok, bounded_vec,
     traits::{ChangeMembers, InitializeMembers},
 };
+use sp_runtime::DispatchError;
+use sqnc_pallet_traits::{OrganisationAttributeProvider, OrganisationDelegateProvider};
 
 const INIT_MEMBERS: [u64; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
 const NEW_MEMBERS: [u64; 2] = [10, 11];
@@ -16,6 +21,14 @@ const KEY_1: u8 = 1u8;
 const KEY_2: u8 = 2u8;
 const KEY_3: u8 = 3u8;
 const VALUE: u32 = 42u32;
+const OTHER_VALUE: u32 = 43u32;
+const INVALID_VALUE: u32 = 101u32;
+const ATTRIBUTE: u8 = 1u8;
+const DELEGATE_1: u64 = 20u64;
+const DELEGATE_2: u64 = 21u64;
+const DELEGATE_3: u64 = 22u64;
+const SCOPE_1: u8 = 1u8;
+const SCOPE_2: u8 = 2u8;
 
 #[test]
 fn it_stores_zero_count_for_members_on_initialise() {
@@ -55,7 +68,7 @@ fn it_sets_value() {
         OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, VALUE).unwrap();
 
         let value = <OrgData<Test>>::get(MEMBER_1, KEY_1);
-        assert_eq!(value, VALUE);
+        assert_eq!(value.value, VALUE);
     })
 }
 
@@ -184,3 +197,538 @@ fn it_removes_values_on_change_with_outgoing_members() {
         assert_err!(value3, ());
     })
 }
+
+#[test]
+fn it_errors_if_value_is_invalid() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let result = OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, INVALID_VALUE);
+
+        assert_err!(result, Error::<Test>::InvalidValue);
+        assert_eq!(<OrgDataCount<Test>>::get(MEMBER_1), 0u32);
+    })
+}
+
+#[test]
+fn it_records_the_block_a_value_was_set_in() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, VALUE).unwrap();
+
+        assert_eq!(
+            <OrgData<Test>>::get(MEMBER_1, KEY_1),
+            OrgDataEntry {
+                value: VALUE,
+                updated_at: 3
+            }
+        );
+        assert_eq!(<OrgDataHistory<Test>>::get(MEMBER_1, KEY_1).len(), 0);
+    })
+}
+
+#[test]
+fn it_keeps_replaced_values_in_history() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, VALUE).unwrap();
+        System::set_block_number(2);
+        OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, OTHER_VALUE).unwrap();
+
+        assert_eq!(<OrgData<Test>>::get(MEMBER_1, KEY_1).value, OTHER_VALUE);
+        assert_eq!(
+            <OrgDataHistory<Test>>::get(MEMBER_1, KEY_1).into_inner(),
+            vec![HistoricalOrgData {
+                value: VALUE,
+                set_at: 1,
+                replaced_at: 2
+            }]
+        );
+    })
+}
+
+#[test]
+fn it_drops_the_oldest_value_from_a_full_history() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        for (block, value) in [(1, 1u32), (2, 2), (3, 3), (4, 4)] {
+            System::set_block_number(block);
+            OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, value).unwrap();
+        }
+
+        assert_eq!(
+            <OrgDataHistory<Test>>::get(MEMBER_1, KEY_1).into_inner(),
+            vec![
+                HistoricalOrgData {
+                    value: 2,
+                    set_at: 2,
+                    replaced_at: 3
+                },
+                HistoricalOrgData {
+                    value: 3,
+                    set_at: 3,
+                    replaced_at: 4
+                },
+            ]
+        );
+    })
+}
+
+#[test]
+fn it_removes_value() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, VALUE).unwrap();
+        System::set_block_number(2);
+
+        assert_ok!(OrganisationData::remove_value(RuntimeOrigin::signed(MEMBER_1), KEY_1));
+
+        assert_err!(<OrgData<Test>>::try_get(MEMBER_1, KEY_1), ());
+        assert_eq!(<OrgDataCount<Test>>::get(MEMBER_1), 0u32);
+        assert_eq!(
+            <OrgDataHistory<Test>>::get(MEMBER_1, KEY_1).into_inner(),
+            vec![HistoricalOrgData {
+                value: VALUE,
+                set_at: 1,
+                replaced_at: 2
+            }]
+        );
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            RuntimeEvent::OrganisationData(Event::RemoveOrgData(MEMBER_1, KEY_1))
+        );
+    })
+}
+
+#[test]
+fn it_frees_an_entry_when_removing_value() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, VALUE).unwrap();
+        OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_2, VALUE).unwrap();
+        OrganisationData::remove_value(RuntimeOrigin::signed(MEMBER_1), KEY_1).unwrap();
+
+        assert_ok!(OrganisationData::set_value(
+            RuntimeOrigin::signed(MEMBER_1),
+            KEY_3,
+            VALUE
+        ));
+    })
+}
+
+#[test]
+fn it_errors_removing_value_if_not_member() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let result = OrganisationData::remove_value(RuntimeOrigin::signed(NOT_MEMBER), KEY_1);
+
+        assert_err!(result, Error::<Test>::NotMember);
+    })
+}
+
+#[test]
+fn it_errors_removing_value_that_is_not_set() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let result = OrganisationData::remove_value(RuntimeOrigin::signed(MEMBER_1), KEY_1);
+
+        assert_err!(result, Error::<Test>::NoValue);
+    })
+}
+
+#[test]
+fn it_removes_history_on_change_with_outgoing_members() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, VALUE).unwrap();
+        OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, OTHER_VALUE).unwrap();
+
+        OrganisationData::change_members(&[], &[MEMBER_1], vec![]);
+
+        assert_err!(<OrgDataHistory<Test>>::try_get(MEMBER_1, KEY_1), ());
+    })
+}
+
+#[test]
+fn it_lists_organisations_with_their_entries() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        OrganisationData::initialize_members(&[MEMBER_2, MEMBER_1]);
+        OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_2, VALUE).unwrap();
+        OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, OTHER_VALUE).unwrap();
+
+        // values over 42 resolve to a preimage of their value
+        let organisations = OrganisationData::organisations(|value| (*value > 42).then(|| vec![*value as u8]));
+
+        assert_eq!(
+            organisations,
+            vec![
+                Organisation {
+                    account: MEMBER_1,
+                    entries: vec![
+                        OrganisationEntry {
+                            key: KEY_1,
+                            value: OTHER_VALUE,
+                            updated_at: 1,
+                            preimage: Some(vec![OTHER_VALUE as u8])
+                        },
+                        OrganisationEntry {
+                            key: KEY_2,
+                            value: VALUE,
+                            updated_at: 1,
+                            preimage: None
+                        },
+                    ]
+                },
+                Organisation {
+                    account: MEMBER_2,
+                    entries: vec![]
+                },
+            ]
+        );
+    })
+}
+
+#[test]
+fn it_gets_an_organisation_only_if_a_member() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::set_value(RuntimeOrigin::signed(MEMBER_1), KEY_1, VALUE).unwrap();
+
+        assert_eq!(
+            OrganisationData::organisation(MEMBER_1, |_| None),
+            Some(Organisation {
+                account: MEMBER_1,
+                entries: vec![OrganisationEntry {
+                    key: KEY_1,
+                    value: VALUE,
+                    updated_at: 0,
+                    preimage: None
+                }]
+            })
+        );
+        assert_eq!(OrganisationData::organisation(NOT_MEMBER, |_| None), None);
+    })
+}
+
+#[test]
+fn it_sets_attribute() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let result = OrganisationData::set_attribute(RuntimeOrigin::root(), MEMBER_1, ATTRIBUTE, 10);
+
+        assert_ok!(result);
+        assert_eq!(<OrgAttributes<Test>>::get(MEMBER_1, ATTRIBUTE), Some(10));
+        System::assert_last_event(Event::SetOrgAttribute(MEMBER_1, ATTRIBUTE, 10).into());
+    })
+}
+
+#[test]
+fn it_errors_setting_attribute_from_non_root() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let result = OrganisationData::set_attribute(RuntimeOrigin::signed(MEMBER_1), MEMBER_1, ATTRIBUTE, 10);
+
+        assert_err!(result, DispatchError::BadOrigin);
+    })
+}
+
+#[test]
+fn it_errors_setting_attribute_if_not_member() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let result = OrganisationData::set_attribute(RuntimeOrigin::root(), NOT_MEMBER, ATTRIBUTE, 10);
+
+        assert_err!(result, Error::<Test>::NotMember);
+    })
+}
+
+#[test]
+fn it_errors_setting_attribute_that_has_expired() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(10);
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let result = OrganisationData::set_attribute(RuntimeOrigin::root(), MEMBER_1, ATTRIBUTE, 10);
+
+        assert_err!(result, Error::<Test>::AttributeExpired);
+    })
+}
+
+#[test]
+fn it_holds_attribute_until_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::set_attribute(RuntimeOrigin::root(), MEMBER_1, ATTRIBUTE, 10).unwrap();
+
+        assert!(OrganisationData::has_attribute(&MEMBER_1, &ATTRIBUTE));
+        assert!(!OrganisationData::has_attribute(&MEMBER_2, &ATTRIBUTE));
+        System::set_block_number(9);
+        assert!(OrganisationData::has_attribute(&MEMBER_1, &ATTRIBUTE));
+        System::set_block_number(10);
+        assert!(!OrganisationData::has_attribute(&MEMBER_1, &ATTRIBUTE));
+    })
+}
+
+#[test]
+fn it_removes_attribute() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::set_attribute(RuntimeOrigin::root(), MEMBER_1, ATTRIBUTE, 10).unwrap();
+
+        let result = OrganisationData::remove_attribute(RuntimeOrigin::root(), MEMBER_1, ATTRIBUTE);
+
+        assert_ok!(result);
+        assert!(!OrganisationData::has_attribute(&MEMBER_1, &ATTRIBUTE));
+        System::assert_last_event(Event::RemoveOrgAttribute(MEMBER_1, ATTRIBUTE).into());
+    })
+}
+
+#[test]
+fn it_errors_removing_attribute_that_is_not_set() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let result = OrganisationData::remove_attribute(RuntimeOrigin::root(), MEMBER_1, ATTRIBUTE);
+
+        assert_err!(result, Error::<Test>::NoAttribute);
+    })
+}
+
+#[test]
+fn it_removes_attributes_on_change_with_outgoing_members() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::set_attribute(RuntimeOrigin::root(), MEMBER_1, ATTRIBUTE, 10).unwrap();
+
+        OrganisationData::change_members(&[], &[MEMBER_1], vec![]);
+
+        assert_eq!(<OrgAttributes<Test>>::get(MEMBER_1, ATTRIBUTE), None);
+    })
+}
+
+// offers a delegation from `member` to `delegate` and accepts it
+fn add_delegate(member: u64, delegate: u64, scopes: DelegateScopes<Test>) {
+    OrganisationData::offer_delegation(RuntimeOrigin::signed(member), delegate, scopes).unwrap();
+    OrganisationData::accept_delegation(RuntimeOrigin::signed(delegate), member).unwrap();
+}
+
+#[test]
+fn it_offers_delegation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let scopes: DelegateScopes<Test> = bounded_vec![SCOPE_1];
+        let result = OrganisationData::offer_delegation(RuntimeOrigin::signed(MEMBER_1), DELEGATE_1, scopes.clone());
+
+        assert_ok!(result);
+        assert_eq!(<DelegateOffers<Test>>::get(MEMBER_1, DELEGATE_1), Some(scopes.clone()));
+        assert_eq!(<Delegations<Test>>::get(DELEGATE_1), None);
+        assert_eq!(OrganisationData::organisation_of(&DELEGATE_1, &SCOPE_1), None);
+        System::assert_last_event(Event::OfferDelegation(MEMBER_1, DELEGATE_1, scopes).into());
+    })
+}
+
+#[test]
+fn it_sets_delegate_on_accepting_offer() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let scopes: DelegateScopes<Test> = bounded_vec![SCOPE_1];
+        OrganisationData::offer_delegation(RuntimeOrigin::signed(MEMBER_1), DELEGATE_1, scopes.clone()).unwrap();
+
+        let result = OrganisationData::accept_delegation(RuntimeOrigin::signed(DELEGATE_1), MEMBER_1);
+
+        assert_ok!(result);
+        assert_eq!(
+            <Delegations<Test>>::get(DELEGATE_1),
+            Some(Delegation {
+                organisation: MEMBER_1,
+                scopes: scopes.clone()
+            })
+        );
+        assert_eq!(<OrgDelegates<Test>>::get(MEMBER_1).into_inner(), vec![DELEGATE_1]);
+        assert_eq!(<DelegateOffers<Test>>::get(MEMBER_1, DELEGATE_1), None);
+        System::assert_last_event(Event::SetDelegate(MEMBER_1, DELEGATE_1, scopes).into());
+    })
+}
+
+#[test]
+fn it_replaces_scopes_of_existing_delegate_on_accepting_offer() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        add_delegate(MEMBER_1, DELEGATE_1, bounded_vec![SCOPE_1]);
+        OrganisationData::offer_delegation(RuntimeOrigin::signed(MEMBER_1), DELEGATE_1, bounded_vec![SCOPE_2]).unwrap();
+
+        assert_eq!(OrganisationData::organisation_of(&DELEGATE_1, &SCOPE_1), Some(MEMBER_1));
+
+        let result = OrganisationData::accept_delegation(RuntimeOrigin::signed(DELEGATE_1), MEMBER_1);
+
+        assert_ok!(result);
+        assert_eq!(OrganisationData::organisation_of(&DELEGATE_1, &SCOPE_1), None);
+        assert_eq!(OrganisationData::organisation_of(&DELEGATE_1, &SCOPE_2), Some(MEMBER_1));
+        assert_eq!(<OrgDelegates<Test>>::get(MEMBER_1).into_inner(), vec![DELEGATE_1]);
+    })
+}
+
+#[test]
+fn it_errors_offering_delegation_if_not_member() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let result = OrganisationData::offer_delegation(RuntimeOrigin::signed(NOT_MEMBER), DELEGATE_1, bounded_vec![]);
+
+        assert_err!(result, Error::<Test>::NotMember);
+    })
+}
+
+#[test]
+fn it_errors_offering_delegation_to_member() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        let result = OrganisationData::offer_delegation(RuntimeOrigin::signed(MEMBER_1), MEMBER_2, bounded_vec![]);
+
+        assert_err!(result, Error::<Test>::DelegateIsMember);
+    })
+}
+
+#[test]
+fn it_errors_accepting_without_offer() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::offer_delegation(RuntimeOrigin::signed(MEMBER_1), DELEGATE_1, bounded_vec![]).unwrap();
+
+        let result = OrganisationData::accept_delegation(RuntimeOrigin::signed(DELEGATE_1), MEMBER_2);
+
+        assert_err!(result, Error::<Test>::NoDelegationOffer);
+    })
+}
+
+#[test]
+fn it_errors_accepting_offer_as_member() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::offer_delegation(RuntimeOrigin::signed(MEMBER_1), DELEGATE_1, bounded_vec![]).unwrap();
+        OrganisationData::change_members(&[DELEGATE_1], &[], vec![]);
+
+        let result = OrganisationData::accept_delegation(RuntimeOrigin::signed(DELEGATE_1), MEMBER_1);
+
+        assert_err!(result, Error::<Test>::DelegateIsMember);
+    })
+}
+
+#[test]
+fn it_errors_accepting_offer_of_other_member() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        add_delegate(MEMBER_1, DELEGATE_1, bounded_vec![]);
+        OrganisationData::offer_delegation(RuntimeOrigin::signed(MEMBER_2), DELEGATE_1, bounded_vec![]).unwrap();
+
+        let result = OrganisationData::accept_delegation(RuntimeOrigin::signed(DELEGATE_1), MEMBER_2);
+
+        assert_err!(result, Error::<Test>::DelegateOfOtherMember);
+    })
+}
+
+#[test]
+fn it_errors_if_too_many_delegates() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        add_delegate(MEMBER_1, DELEGATE_1, bounded_vec![]);
+        add_delegate(MEMBER_1, DELEGATE_2, bounded_vec![]);
+        OrganisationData::offer_delegation(RuntimeOrigin::signed(MEMBER_1), DELEGATE_3, bounded_vec![]).unwrap();
+
+        let result = OrganisationData::accept_delegation(RuntimeOrigin::signed(DELEGATE_3), MEMBER_1);
+
+        assert_err!(result, Error::<Test>::TooManyDelegates);
+    })
+}
+
+#[test]
+fn it_resolves_organisation_of_delegate_within_scope() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        add_delegate(MEMBER_1, DELEGATE_1, bounded_vec![SCOPE_1]);
+
+        assert_eq!(OrganisationData::organisation_of(&DELEGATE_1, &SCOPE_1), Some(MEMBER_1));
+        assert_eq!(OrganisationData::organisation_of(&DELEGATE_1, &SCOPE_2), None);
+        assert_eq!(OrganisationData::organisation_of(&DELEGATE_2, &SCOPE_1), None);
+    })
+}
+
+#[test]
+fn it_removes_delegate() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        add_delegate(MEMBER_1, DELEGATE_1, bounded_vec![SCOPE_1]);
+        add_delegate(MEMBER_1, DELEGATE_2, bounded_vec![SCOPE_1]);
+
+        let result = OrganisationData::remove_delegate(RuntimeOrigin::signed(MEMBER_1), DELEGATE_1);
+
+        assert_ok!(result);
+        assert_eq!(<Delegations<Test>>::get(DELEGATE_1), None);
+        assert_eq!(<OrgDelegates<Test>>::get(MEMBER_1).into_inner(), vec![DELEGATE_2]);
+        System::assert_last_event(Event::RemoveDelegate(MEMBER_1, DELEGATE_1).into());
+    })
+}
+
+#[test]
+fn it_withdraws_delegation_offer() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        OrganisationData::offer_delegation(RuntimeOrigin::signed(MEMBER_1), DELEGATE_1, bounded_vec![]).unwrap();
+
+        let result = OrganisationData::remove_delegate(RuntimeOrigin::signed(MEMBER_1), DELEGATE_1);
+
+        assert_ok!(result);
+        assert_eq!(<DelegateOffers<Test>>::get(MEMBER_1, DELEGATE_1), None);
+        assert_err!(
+            OrganisationData::accept_delegation(RuntimeOrigin::signed(DELEGATE_1), MEMBER_1),
+            Error::<Test>::NoDelegationOffer
+        );
+    })
+}
+
+#[test]
+fn it_errors_removing_delegate_of_other_member() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        add_delegate(MEMBER_1, DELEGATE_1, bounded_vec![]);
+
+        let result = OrganisationData::remove_delegate(RuntimeOrigin::signed(MEMBER_2), DELEGATE_1);
+
+        assert_err!(result, Error::<Test>::NotDelegate);
+    })
+}
+
+#[test]
+fn it_removes_delegates_on_change_with_outgoing_members() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        add_delegate(MEMBER_1, DELEGATE_1, bounded_vec![SCOPE_1]);
+        OrganisationData::offer_delegation(RuntimeOrigin::signed(MEMBER_1), DELEGATE_2, bounded_vec![SCOPE_1]).unwrap();
+
+        OrganisationData::change_members(&[], &[MEMBER_1], vec![]);
+
+        assert_eq!(<Delegations<Test>>::get(DELEGATE_1), None);
+        assert_eq!(<OrgDelegates<Test>>::get(MEMBER_1).len(), 0);
+        assert_eq!(<DelegateOffers<Test>>::get(MEMBER_1, DELEGATE_2), None);
+    })
+}
+
+#[test]
+fn it_removes_delegation_of_incoming_member() {
+    new_test_ext().execute_with(|| {
+        OrganisationData::initialize_members(&INIT_MEMBERS);
+        add_delegate(MEMBER_1, DELEGATE_1, bounded_vec![SCOPE_1]);
+
+        OrganisationData::change_members(&[DELEGATE_1], &[], vec![]);
+
+        assert_eq!(<Delegations<Test>>::get(DELEGATE_1), None);
+        assert_eq!(<OrgDelegates<Test>>::get(MEMBER_1).len(), 0);
+    })
+}
@@ -9,6 +9,12 @@ use core::marker::PhantomData;
 /// Weight functions needed for validator_set.
 pub trait WeightInfo {
     fn set_value() -> Weight;
+    fn remove_value() -> Weight;
+    fn set_attribute() -> Weight;
+    fn remove_attribute() -> Weight;
+    fn offer_delegation() -> Weight;
+    fn accept_delegation() -> Weight;
+    fn remove_delegate() -> Weight;
 }
 
 
@@ -17,5 +23,23 @@ impl WeightInfo for () {
     fn set_value() -> Weight {
         Weight::zero()
     } 
+    fn remove_value() -> Weight {
+        Weight::zero()
+    }
+    fn set_attribute() -> Weight {
+        Weight::zero()
+    }
+    fn remove_attribute() -> Weight {
+        Weight::zero()
+    }
+    fn offer_delegation() -> Weight {
+        Weight::zero()
+    }
+    fn accept_delegation() -> Weight {
+        Weight::zero()
+    }
+    fn remove_delegate() -> Weight {
+        Weight::zero()
+    }
 }
 
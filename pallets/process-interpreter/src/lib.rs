@@ -0,0 +1,336 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::Parameter;
+use sp_std::prelude::*;
+
+pub use sqnc_pallet_traits::{ProcessIO, ValidationResult};
+
+// import the restrictions module where all our restriction types are defined
+mod restrictions;
+pub use restrictions::*;
+
+mod binary_expression_tree;
+pub use binary_expression_tree::*;
+
+mod pattern;
+pub use pattern::*;
+
+/// A single evaluated symbol of a program as reported by `execute_program_with_trace`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionStep<'a, RoleKey, TokenMetadataKey, TokenMetadataValue, TokenMetadataValueDiscriminator> {
+    /// position of the symbol in the program
+    pub index: u32,
+    pub symbol:
+        &'a BooleanExpressionSymbol<RoleKey, TokenMetadataKey, TokenMetadataValue, TokenMetadataValueDiscriminator>,
+    /// the value pushed onto the stack as a result of evaluating the symbol
+    pub result: bool,
+    /// height of the stack after the symbol was evaluated
+    pub stack_height: usize,
+}
+
+/// Checks that a program is well formed, i.e. that every operator has two operands and that exactly one
+/// value is left on the stack once the program has executed
+pub fn validate_program<R, T, V, D>(program: &[BooleanExpressionSymbol<R, T, V, D>]) -> bool {
+    let executed_stack_height = program.iter().try_fold(0u8, |stack_height, symbol| match symbol {
+        BooleanExpressionSymbol::Op(_) => {
+            let stack_height = stack_height.checked_sub(2);
+            return stack_height.and_then(|stack_height| stack_height.checked_add(1));
+        }
+        BooleanExpressionSymbol::Restriction(_) => stack_height.checked_add(1),
+    });
+    executed_stack_height == Some(1u8)
+}
+
+/// Executes a program against a transition returning whether it succeeded and how many symbols were evaluated
+pub fn execute_program<I, A, R, T, V, D>(
+    program: &[BooleanExpressionSymbol<R, T, V, D>],
+    sender: &A,
+    inputs: &Vec<ProcessIO<I, A, R, T, V>>,
+    outputs: &Vec<ProcessIO<I, A, R, T, V>>,
+) -> ValidationResult<u32>
+where
+    A: Parameter,
+    R: Parameter + Default + Ord,
+    T: Parameter + Default + Ord,
+    V: Parameter + PartialEq<I> + MetadataValueAccess + MetadataAccountAccess<A>,
+    D: Parameter + From<V>,
+{
+    execute(program, sender, &Default::default(), inputs, outputs, |_| {})
+}
+
+/// As `execute_program` where `signers` are the accounts whose detached signatures over the transition have been
+/// verified
+pub fn execute_program_with_signers<I, A, R, T, V, D>(
+    program: &[BooleanExpressionSymbol<R, T, V, D>],
+    sender: &A,
+    signers: &[A],
+    inputs: &Vec<ProcessIO<I, A, R, T, V>>,
+    outputs: &Vec<ProcessIO<I, A, R, T, V>>,
+) -> ValidationResult<u32>
+where
+    A: Parameter,
+    R: Parameter + Default + Ord,
+    T: Parameter + Default + Ord,
+    V: Parameter + PartialEq<I> + MetadataValueAccess + MetadataAccountAccess<A>,
+    D: Parameter + From<V>,
+{
+    let context = ExecutionContext {
+        signers,
+        ..Default::default()
+    };
+    execute(program, sender, &context, inputs, outputs, |_| {})
+}
+
+/// As `execute_program` with facts about the transition established before the program is executed
+pub fn execute_program_with_context<I, A, R, T, V, D>(
+    program: &[BooleanExpressionSymbol<R, T, V, D>],
+    sender: &A,
+    context: &ExecutionContext<A>,
+    inputs: &Vec<ProcessIO<I, A, R, T, V>>,
+    outputs: &Vec<ProcessIO<I, A, R, T, V>>,
+) -> ValidationResult<u32>
+where
+    A: Parameter,
+    R: Parameter + Default + Ord,
+    T: Parameter + Default + Ord,
+    V: Parameter + PartialEq<I> + MetadataValueAccess + MetadataAccountAccess<A>,
+    D: Parameter + From<V>,
+{
+    execute(program, sender, context, inputs, outputs, |_| {})
+}
+
+/// The distinct organisation attributes checked by the restrictions of a program
+pub fn referenced_org_attributes<R, T, V, D>(program: &[BooleanExpressionSymbol<R, T, V, D>]) -> Vec<OrgAttribute> {
+    let mut attributes: Vec<OrgAttribute> = Vec::new();
+    for symbol in program {
+        if let BooleanExpressionSymbol::Restriction(Restriction::SenderHasOrgAttribute { attribute }) = symbol {
+            if !attributes.contains(attribute) {
+                attributes.push(attribute.clone());
+            }
+        }
+    }
+    attributes
+}
+
+/// As `execute_program_with_context` but calls `on_step` after each symbol is evaluated so that the execution can be
+/// traced
+pub fn execute_program_with_trace<I, A, R, T, V, D, F>(
+    program: &[BooleanExpressionSymbol<R, T, V, D>],
+    sender: &A,
+    context: &ExecutionContext<A>,
+    inputs: &Vec<ProcessIO<I, A, R, T, V>>,
+    outputs: &Vec<ProcessIO<I, A, R, T, V>>,
+    on_step: F,
+) -> ValidationResult<u32>
+where
+    A: Parameter,
+    R: Parameter + Default + Ord,
+    T: Parameter + Default + Ord,
+    V: Parameter + PartialEq<I> + MetadataValueAccess + MetadataAccountAccess<A>,
+    D: Parameter + From<V>,
+    F: FnMut(ExecutionStep<R, T, V, D>),
+{
+    execute(program, sender, context, inputs, outputs, on_step)
+}
+
+fn execute<I, A, R, T, V, D, F>(
+    program: &[BooleanExpressionSymbol<R, T, V, D>],
+    sender: &A,
+    context: &ExecutionContext<A>,
+    inputs: &Vec<ProcessIO<I, A, R, T, V>>,
+    outputs: &Vec<ProcessIO<I, A, R, T, V>>,
+    mut on_step: F,
+) -> ValidationResult<u32>
+where
+    A: Parameter,
+    R: Parameter + Default + Ord,
+    T: Parameter + Default + Ord,
+    V: Parameter + PartialEq<I> + MetadataValueAccess + MetadataAccountAccess<A>,
+    D: Parameter + From<V>,
+    F: FnMut(ExecutionStep<R, T, V, D>),
+{
+    let mut stack: Vec<bool> = Vec::with_capacity(program.len());
+    let mut executed_len: u32 = 0;
+    for symbol in program {
+        executed_len = executed_len + 1;
+        let result = match symbol {
+            BooleanExpressionSymbol::Op(op) => {
+                let (Some(b), Some(a)) = (stack.pop(), stack.pop()) else {
+                    return ValidationResult {
+                        success: false,
+                        executed_len: executed_len,
+                    };
+                };
+                op.eval(a, b)
+            }
+            BooleanExpressionSymbol::Restriction(r) => {
+                validate_restriction_with_context::<I, A, R, T, V, D>(r.clone(), sender, context, inputs, outputs)
+            }
+        };
+        stack.push(result);
+        on_step(ExecutionStep {
+            index: executed_len - 1,
+            symbol,
+            result,
+            stack_height: stack.len(),
+        });
+    }
+
+    ValidationResult {
+        success: stack.pop().unwrap_or(false),
+        executed_len: executed_len,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Symbol = BooleanExpressionSymbol<u32, u32, u64, u64>;
+
+    fn execute(program: &[Symbol]) -> ValidationResult<u32> {
+        execute_program::<u64, u64, u32, u32, u64, u64>(program, &1u64, &Vec::new(), &Vec::new())
+    }
+
+    #[test]
+    fn validate_program_single_restriction() {
+        let program: Vec<Symbol> = vec![BooleanExpressionSymbol::Restriction(Restriction::None)];
+        assert!(validate_program(&program));
+    }
+
+    #[test]
+    fn validate_program_empty() {
+        let program: Vec<Symbol> = vec![];
+        assert!(!validate_program(&program));
+    }
+
+    #[test]
+    fn validate_program_op_underflow() {
+        let program: Vec<Symbol> = vec![
+            BooleanExpressionSymbol::Restriction(Restriction::None),
+            BooleanExpressionSymbol::Op(BooleanOperator::And),
+        ];
+        assert!(!validate_program(&program));
+    }
+
+    #[test]
+    fn validate_program_too_many_values() {
+        let program: Vec<Symbol> = vec![
+            BooleanExpressionSymbol::Restriction(Restriction::None),
+            BooleanExpressionSymbol::Restriction(Restriction::None),
+        ];
+        assert!(!validate_program(&program));
+    }
+
+    #[test]
+    fn execute_program_succeeds() {
+        let result = execute(&[
+            BooleanExpressionSymbol::Restriction(Restriction::None),
+            BooleanExpressionSymbol::Restriction(Restriction::Fail),
+            BooleanExpressionSymbol::Op(BooleanOperator::Or),
+        ]);
+        assert_eq!(
+            result,
+            ValidationResult {
+                success: true,
+                executed_len: 3
+            }
+        );
+    }
+
+    #[test]
+    fn execute_program_fails() {
+        let result = execute(&[
+            BooleanExpressionSymbol::Restriction(Restriction::None),
+            BooleanExpressionSymbol::Restriction(Restriction::Fail),
+            BooleanExpressionSymbol::Op(BooleanOperator::And),
+        ]);
+        assert_eq!(
+            result,
+            ValidationResult {
+                success: false,
+                executed_len: 3
+            }
+        );
+    }
+
+    #[test]
+    fn execute_program_stops_on_stack_underflow() {
+        let result = execute(&[
+            BooleanExpressionSymbol::Restriction(Restriction::None),
+            BooleanExpressionSymbol::Op(BooleanOperator::Identity),
+            BooleanExpressionSymbol::Restriction(Restriction::None),
+        ]);
+        assert_eq!(
+            result,
+            ValidationResult {
+                success: false,
+                executed_len: 2
+            }
+        );
+    }
+
+    #[test]
+    fn execute_program_with_trace_reports_each_step() {
+        let program: Vec<Symbol> = vec![
+            BooleanExpressionSymbol::Restriction(Restriction::None),
+            BooleanExpressionSymbol::Restriction(Restriction::Fail),
+            BooleanExpressionSymbol::Op(BooleanOperator::Xor),
+        ];
+        let mut steps = Vec::new();
+        let result = execute_program_with_trace::<u64, u64, u32, u32, u64, u64, _>(
+            &program,
+            &1u64,
+            &Default::default(),
+            &Vec::new(),
+            &Vec::new(),
+            |step| steps.push((step.index, step.result, step.stack_height)),
+        );
+
+        assert!(result.success);
+        assert_eq!(steps, vec![(0, true, 1), (1, false, 2), (2, true, 1)]);
+    }
+
+    fn has_attribute(name: &[u8]) -> Symbol {
+        BooleanExpressionSymbol::Restriction(Restriction::SenderHasOrgAttribute {
+            attribute: name.to_vec().try_into().unwrap(),
+        })
+    }
+
+    #[test]
+    fn referenced_org_attributes_are_distinct() {
+        let program: Vec<Symbol> = vec![
+            has_attribute(b"iso9001"),
+            has_attribute(b"kyc"),
+            BooleanExpressionSymbol::Op(BooleanOperator::And),
+            has_attribute(b"iso9001"),
+            BooleanExpressionSymbol::Op(BooleanOperator::And),
+        ];
+        let expected: Vec<OrgAttribute> = vec![
+            b"iso9001".to_vec().try_into().unwrap(),
+            b"kyc".to_vec().try_into().unwrap(),
+        ];
+        assert_eq!(referenced_org_attributes(&program), expected);
+    }
+
+    #[test]
+    fn execute_program_with_context_checks_sender_attributes() {
+        let program: Vec<Symbol> = vec![has_attribute(b"iso9001")];
+        let held: Vec<OrgAttribute> = vec![b"iso9001".to_vec().try_into().unwrap()];
+        let execute_with = |attributes: &[OrgAttribute]| {
+            execute_program_with_context::<u64, u64, u32, u32, u64, u64>(
+                &program,
+                &1u64,
+                &ExecutionContext {
+                    sender_attributes: attributes,
+                    ..Default::default()
+                },
+                &Vec::new(),
+                &Vec::new(),
+            )
+        };
+
+        assert!(execute_with(&held).success);
+        assert!(!execute_with(&[]).success);
+    }
+}
@@ -0,0 +1,258 @@
+// A small pattern language for checking the contents of literal metadata values. A pattern must match the whole of a
+// value and is made of items, each being a byte class followed by an optional quantifier:
+//
+// - any other byte matches itself
+// - `.` matches any byte
+// - `[...]` matches any byte in the set, which can contain ranges such as `a-z` and is negated by a leading `^`. A `]`
+//   first in the set and a `-` first or last in the set match themselves
+// - `?`, `*`, `+`, `{n}`, `{n,}` and `{n,m}` repeat the preceding class
+//
+// There are no groups, alternations or escapes so a metacharacter is matched by putting it in a set, e.g. `[.]`.
+// Matching takes time linear in the length of the value for each item in the pattern
+
+use sp_std::vec;
+use sp_std::vec::Vec;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Class<'a> {
+    Any,
+    Byte(u8),
+    Set { negated: bool, members: &'a [u8] },
+}
+
+impl<'a> Class<'a> {
+    fn matches(&self, byte: u8) -> bool {
+        match self {
+            Class::Any => true,
+            Class::Byte(b) => *b == byte,
+            Class::Set { negated, members } => {
+                let mut found = false;
+                let mut i = 0;
+                while i < members.len() {
+                    match members.get(i + 1..i + 3) {
+                        Some([b'-', end]) => {
+                            found |= members[i] <= byte && byte <= *end;
+                            i += 3;
+                        }
+                        _ => {
+                            found |= members[i] == byte;
+                            i += 1;
+                        }
+                    }
+                }
+                found != *negated
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Item<'a> {
+    class: Class<'a>,
+    min: u32,
+    max: u32,
+}
+
+// parses the members of a set starting after its opening `[` returning them along with the position after the `]`
+fn parse_set(pattern: &[u8], start: usize) -> Option<(Class<'_>, usize)> {
+    let negated = pattern.get(start) == Some(&b'^');
+    let start = if negated { start + 1 } else { start };
+    // a leading `]` is a member rather than the end of the set
+    let end = start + 1 + pattern.get(start + 1..)?.iter().position(|b| *b == b']')?;
+    let members = &pattern[start..end];
+
+    // ranges must be ordered
+    let mut i = 0;
+    while i < members.len() {
+        match members.get(i + 1..i + 3) {
+            Some([b'-', range_end]) if members[i] > *range_end => return None,
+            Some([b'-', _]) => i += 3,
+            _ => i += 1,
+        }
+    }
+
+    Some((Class::Set { negated, members }, end + 1))
+}
+
+fn parse_number(pattern: &[u8], start: usize) -> Option<(u32, usize)> {
+    let digits = pattern[start..].iter().take_while(|b| b.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    let value = pattern[start..start + digits]
+        .iter()
+        .try_fold(0u32, |acc, b| acc.checked_mul(10)?.checked_add((b - b'0') as u32))?;
+    Some((value, start + digits))
+}
+
+// parses a `{n}`, `{n,}` or `{n,m}` quantifier starting after its opening `{`
+fn parse_repetition(pattern: &[u8], start: usize) -> Option<(u32, u32, usize)> {
+    let (min, next) = parse_number(pattern, start)?;
+    match pattern.get(next)? {
+        b'}' => Some((min, min, next + 1)),
+        b',' => match pattern.get(next + 1)? {
+            b'}' => Some((min, u32::MAX, next + 2)),
+            _ => {
+                let (max, next) = parse_number(pattern, next + 1)?;
+                match (pattern.get(next)?, min <= max) {
+                    (b'}', true) => Some((min, max, next + 1)),
+                    _ => None,
+                }
+            }
+        },
+        _ => None,
+    }
+}
+
+fn parse(pattern: &[u8]) -> Option<Vec<Item<'_>>> {
+    let mut items = Vec::new();
+    let mut i = 0;
+    while i < pattern.len() {
+        let (class, next) = match pattern[i] {
+            b'.' => (Class::Any, i + 1),
+            b'[' => parse_set(pattern, i + 1)?,
+            b'?' | b'*' | b'+' | b'{' | b'}' | b']' => return None,
+            b => (Class::Byte(b), i + 1),
+        };
+        let (min, max, next) = match pattern.get(next) {
+            Some(b'?') => (0, 1, next + 1),
+            Some(b'*') => (0, u32::MAX, next + 1),
+            Some(b'+') => (1, u32::MAX, next + 1),
+            Some(b'{') => parse_repetition(pattern, next + 1)?,
+            _ => (1, 1, next),
+        };
+        items.push(Item { class, min, max });
+        i = next;
+    }
+    Some(items)
+}
+
+/// Whether `pattern` is well formed. Malformed patterns never match
+pub fn is_valid_pattern(pattern: &[u8]) -> bool {
+    parse(pattern).is_some()
+}
+
+/// Whether the whole of `value` matches `pattern`
+pub fn matches_pattern(pattern: &[u8], value: &[u8]) -> bool {
+    let Some(items) = parse(pattern) else {
+        return false;
+    };
+
+    // positions in the value that the items so far can finish matching at
+    let mut reachable = vec![false; value.len() + 1];
+    reachable[0] = true;
+    for item in items {
+        // number of reachable positions before each position
+        let mut reachable_before = Vec::with_capacity(value.len() + 2);
+        reachable_before.push(0usize);
+        for (position, is_reachable) in reachable.iter().enumerate() {
+            reachable_before.push(reachable_before[position] + *is_reachable as usize);
+        }
+
+        // the item can finish at `end` if it could start at some reachable position that is between `min` and `max`
+        // bytes before and all of the bytes between match its class
+        let mut run = 0usize;
+        for end in 0..=value.len() {
+            if end > 0 {
+                run = match item.class.matches(value[end - 1]) {
+                    true => run + 1,
+                    false => 0,
+                };
+            }
+            let min = item.min as usize;
+            let max = run.min(item.max as usize);
+            reachable[end] = match min <= max {
+                true => reachable_before[end - min + 1] > reachable_before[end - max],
+                false => false,
+            };
+        }
+    }
+    reachable[value.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_bytes() {
+        assert!(matches_pattern(b"PO-", b"PO-"));
+        assert!(!matches_pattern(b"PO-", b"PO-1"));
+        assert!(!matches_pattern(b"PO-", b"PO"));
+        assert!(matches_pattern(b"", b""));
+        assert!(!matches_pattern(b"", b"a"));
+    }
+
+    #[test]
+    fn matches_any() {
+        assert!(matches_pattern(b"a.c", b"abc"));
+        assert!(matches_pattern(b"a.c", b"a.c"));
+        assert!(!matches_pattern(b"a.c", b"ac"));
+    }
+
+    #[test]
+    fn matches_sets() {
+        assert!(matches_pattern(b"[A-Z][A-Z]", b"GB"));
+        assert!(!matches_pattern(b"[A-Z][A-Z]", b"Gb"));
+        assert!(matches_pattern(b"[a-cx]", b"x"));
+        assert!(!matches_pattern(b"[a-cx]", b"d"));
+        assert!(matches_pattern(b"[^0-9]", b"a"));
+        assert!(!matches_pattern(b"[^0-9]", b"5"));
+        assert!(matches_pattern(b"[]]", b"]"));
+        assert!(matches_pattern(b"[^]]", b"a"));
+        assert!(matches_pattern(b"[-a]", b"-"));
+        assert!(matches_pattern(b"[a-]", b"-"));
+        assert!(matches_pattern(b"[.]", b"."));
+        assert!(!matches_pattern(b"[.]", b"a"));
+    }
+
+    #[test]
+    fn matches_quantifiers() {
+        assert!(matches_pattern(b"ab?c", b"ac"));
+        assert!(matches_pattern(b"ab?c", b"abc"));
+        assert!(!matches_pattern(b"ab?c", b"abbc"));
+        assert!(matches_pattern(b"ab*c", b"ac"));
+        assert!(matches_pattern(b"ab*c", b"abbbc"));
+        assert!(!matches_pattern(b"ab+c", b"ac"));
+        assert!(matches_pattern(b"ab+c", b"abbc"));
+        assert!(matches_pattern(b"PO-[0-9]{6}", b"PO-123456"));
+        assert!(!matches_pattern(b"PO-[0-9]{6}", b"PO-12345"));
+        assert!(!matches_pattern(b"PO-[0-9]{6}", b"PO-1234567"));
+        assert!(matches_pattern(b"[0-9]{2,}", b"123"));
+        assert!(!matches_pattern(b"[0-9]{2,}", b"1"));
+        assert!(matches_pattern(b"[0-9]{1,3}", b"123"));
+        assert!(!matches_pattern(b"[0-9]{1,3}", b"1234"));
+        assert!(matches_pattern(b"a{0}b", b"b"));
+    }
+
+    #[test]
+    fn matches_overlapping_items() {
+        assert!(matches_pattern(b".*a.*b", b"xxaxxb"));
+        assert!(matches_pattern(b"[a-z]*[a-c]{2}", b"zzab"));
+        assert!(!matches_pattern(b"[a-z]*[a-c]{2}", b"zzaz"));
+        assert!(matches_pattern(b"a*a*a*b", b"aaaab"));
+        assert!(!matches_pattern(b"a*a*a*b", b"aaaa"));
+    }
+
+    #[test]
+    fn rejects_malformed_patterns() {
+        for pattern in [
+            &b"*a"[..],
+            b"a**",
+            b"[a-z",
+            b"[]",
+            b"[z-a]",
+            b"a{",
+            b"a{2",
+            b"a{,2}",
+            b"a{3,2}",
+            b"a{99999999999}",
+            b"a]",
+            b"a}",
+        ] {
+            assert!(!is_valid_pattern(pattern), "{:?}", core::str::from_utf8(pattern));
+            assert!(!matches_pattern(pattern, b"a"));
+        }
+        assert!(is_valid_pattern(b"PO-[0-9]{6}"));
+    }
+}
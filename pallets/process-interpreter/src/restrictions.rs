@@ -1,13 +1,16 @@
 // This file contains the different types of restrictions that can be evaluated during
 // a call to `validate_process`
 
-use frame_support::Parameter;
+use frame_support::{traits::ConstU32, BoundedVec, Parameter};
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
+use sp_io::hashing::blake2_256;
 use sp_std::vec::Vec;
 use sqnc_pallet_traits::ProcessIO;
 
+use crate::pattern::matches_pattern;
+
 #[derive(Encode, Decode, Debug, Clone, MaxEncodedLen, TypeInfo, PartialEq, Serialize, Deserialize)]
 pub enum Restriction<RoleKey, TokenMetadataKey, TokenMetadataValue, TokenMetadataValueDiscriminator> {
     None,
@@ -79,8 +82,203 @@ pub enum Restriction<RoleKey, TokenMetadataKey, TokenMetadataValue, TokenMetadat
         metadata_key: TokenMetadataKey,
         metadata_value_type: TokenMetadataValueDiscriminator,
     },
+    InputMetadataValueInRange {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+        min: i128,
+        max: i128,
+    },
+    OutputMetadataValueInRange {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+        min: i128,
+        max: i128,
+    },
+    InputMetadataValueMaxLength {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+        max_length: u32,
+    },
+    OutputMetadataValueMaxLength {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+        max_length: u32,
+    },
+    SenderHasAnyInputRole {
+        index: u32,
+        role_keys: RoleSet<RoleKey>,
+    },
+    SenderHasAnyOutputRole {
+        index: u32,
+        role_keys: RoleSet<RoleKey>,
+    },
+    DistinctInputRoles {
+        index: u32,
+        left_role_key: RoleKey,
+        right_role_key: RoleKey,
+    },
+    DistinctOutputRoles {
+        index: u32,
+        left_role_key: RoleKey,
+        right_role_key: RoleKey,
+    },
+    InputMetadataValueHasPrefix {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+        prefix: LiteralPattern,
+    },
+    OutputMetadataValueHasPrefix {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+        prefix: LiteralPattern,
+    },
+    InputMetadataValueHasSuffix {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+        suffix: LiteralPattern,
+    },
+    OutputMetadataValueHasSuffix {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+        suffix: LiteralPattern,
+    },
+    InputMetadataValueMatchesPattern {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+        pattern: LiteralPattern,
+    },
+    OutputMetadataValueMatchesPattern {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+        pattern: LiteralPattern,
+    },
+    SenderIsInputMetadataAccount {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+    },
+    SenderIsOutputMetadataAccount {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+    },
+    MatchInputRoleOutputMetadataAccount {
+        input_index: u32,
+        input_role_key: RoleKey,
+        output_index: u32,
+        output_metadata_key: TokenMetadataKey,
+    },
+    MatchInputMetadataAccountOutputRole {
+        input_index: u32,
+        input_metadata_key: TokenMetadataKey,
+        output_index: u32,
+        output_role_key: RoleKey,
+    },
+    OutputFileIsHashOfMetadata {
+        index: u32,
+        metadata_key: TokenMetadataKey,
+        source_keys: HashSources<TokenMetadataKey>,
+    },
+    InputFileIsHashOfOutputMetadata {
+        input_index: u32,
+        input_metadata_key: TokenMetadataKey,
+        output_index: u32,
+        source_keys: HashSources<TokenMetadataKey>,
+    },
+    SignedByRoleHolder {
+        index: u32,
+        role_key: RoleKey,
+    },
+    SenderHasOrgAttribute {
+        attribute: OrgAttribute,
+    },
+}
+
+/// Maximum number of role keys that can be checked by a single role set restriction
+pub type MaxRoleSetSize = ConstU32<8>;
+
+/// A bounded set of role keys, any of which can satisfy a role set restriction
+pub type RoleSet<RoleKey> = BoundedVec<RoleKey, MaxRoleSetSize>;
+
+/// Maximum length of the bytes a literal value is checked against by a prefix, suffix or pattern restriction
+pub type MaxLiteralPatternLength = ConstU32<32>;
+
+/// Bytes a literal value is checked against by a prefix, suffix or pattern restriction
+pub type LiteralPattern = BoundedVec<u8, MaxLiteralPatternLength>;
+
+/// Maximum number of metadata values that can be hashed by a single hash restriction
+pub type MaxHashSources = ConstU32<8>;
+
+/// Keys of the metadata values whose SCALE encodings, concatenated in order, are hashed by a hash restriction
+pub type HashSources<TokenMetadataKey> = BoundedVec<TokenMetadataKey, MaxHashSources>;
+
+/// Maximum length of the name of an organisation attribute
+pub type MaxOrgAttributeLength = ConstU32<32>;
+
+/// The name of an attribute, such as a certification, that governance has granted to an organisation
+pub type OrgAttribute = BoundedVec<u8, MaxOrgAttributeLength>;
+
+/// Facts about a transition, beyond its inputs and outputs, that are established before its program is executed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionContext<'a, AccountId> {
+    /// Accounts whose detached signatures over the transition have been verified
+    pub signers: &'a [AccountId],
+    /// Organisation attributes currently held by the sender's organisation. Only the attributes returned by
+    /// `referenced_org_attributes` need to be supplied
+    pub sender_attributes: &'a [OrgAttribute],
+    /// The organisation the sender is acting for as its delegate, if any
+    pub sender_org: Option<&'a AccountId>,
+}
+
+impl<AccountId> Default for ExecutionContext<'_, AccountId> {
+    fn default() -> Self {
+        ExecutionContext {
+            signers: &[],
+            sender_attributes: &[],
+            sender_org: None,
+        }
+    }
+}
+
+/// Gives restrictions access to the contents of a metadata value so that they can check more than equality
+pub trait MetadataValueAccess {
+    /// The value as an integer, if it is one
+    fn as_integer(&self) -> Option<i128>;
+    /// The bytes of the value, if it is a literal
+    fn as_literal(&self) -> Option<&[u8]>;
+    /// The bytes of the hash, if the value is a file
+    fn as_hash(&self) -> Option<&[u8]>;
+}
+
+/// Gives restrictions access to an account held in a metadata value so that it can be compared to role holders
+pub trait MetadataAccountAccess<AccountId> {
+    /// The account, if the value is one
+    fn as_account(&self) -> Option<&AccountId>;
+}
+
+macro_rules! impl_integer_metadata_value_access {
+    ($($t:ty),*) => {
+        $(
+            impl MetadataValueAccess for $t {
+                fn as_integer(&self) -> Option<i128> {
+                    i128::try_from(*self).ok()
+                }
+                fn as_literal(&self) -> Option<&[u8]> {
+                    None
+                }
+                fn as_hash(&self) -> Option<&[u8]> {
+                    None
+                }
+            }
+            impl<AccountId> MetadataAccountAccess<AccountId> for $t {
+                fn as_account(&self) -> Option<&AccountId> {
+                    None
+                }
+            }
+        )*
+    };
 }
 
+impl_integer_metadata_value_access!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
 impl<RoleKey, TokenMetadataKey, TokenMetadataValue, TokenMetadataValueDiscriminator> Default
     for Restriction<RoleKey, TokenMetadataKey, TokenMetadataValue, TokenMetadataValueDiscriminator>
 where
@@ -104,9 +302,53 @@ where
     A: Parameter,
     R: Parameter + Default + Ord,
     T: Parameter + Default + Ord,
-    V: Parameter + PartialEq<I>,
+    V: Parameter + PartialEq<I> + MetadataValueAccess + MetadataAccountAccess<A>,
+    D: Parameter + From<V>,
+{
+    validate_restriction_with_signers::<I, A, R, T, V, D>(restriction, sender, &[], inputs, outputs)
+}
+
+/// As `validate_restriction` where `signers` are the accounts whose detached signatures over the transition have
+/// been verified
+pub fn validate_restriction_with_signers<I, A, R, T, V, D>(
+    restriction: Restriction<R, T, V, D>,
+    sender: &A,
+    signers: &[A],
+    inputs: &Vec<ProcessIO<I, A, R, T, V>>,
+    outputs: &Vec<ProcessIO<I, A, R, T, V>>,
+) -> bool
+where
+    A: Parameter,
+    R: Parameter + Default + Ord,
+    T: Parameter + Default + Ord,
+    V: Parameter + PartialEq<I> + MetadataValueAccess + MetadataAccountAccess<A>,
+    D: Parameter + From<V>,
+{
+    let context = ExecutionContext {
+        signers,
+        ..Default::default()
+    };
+    validate_restriction_with_context::<I, A, R, T, V, D>(restriction, sender, &context, inputs, outputs)
+}
+
+/// As `validate_restriction` with facts about the transition established before the restriction is evaluated
+pub fn validate_restriction_with_context<I, A, R, T, V, D>(
+    restriction: Restriction<R, T, V, D>,
+    sender: &A,
+    context: &ExecutionContext<A>,
+    inputs: &Vec<ProcessIO<I, A, R, T, V>>,
+    outputs: &Vec<ProcessIO<I, A, R, T, V>>,
+) -> bool
+where
+    A: Parameter,
+    R: Parameter + Default + Ord,
+    T: Parameter + Default + Ord,
+    V: Parameter + PartialEq<I> + MetadataValueAccess + MetadataAccountAccess<A>,
     D: Parameter + From<V>,
 {
+    // a delegate acting for its organisation satisfies restrictions on the sender that the organisation would
+    let is_sender = |account: &A| account == sender || context.sender_org == Some(account);
+
     match restriction {
         Restriction::<R, T, V, D>::None => true,
         Restriction::<R, T, V, D>::Fail => false,
@@ -165,7 +407,7 @@ where
                 return false;
             };
             match selected_input.roles.get(&role_key) {
-                Some(account) => sender == account,
+                Some(account) => is_sender(account),
                 None => false,
             }
         }
@@ -174,7 +416,7 @@ where
                 return false;
             };
             match selected_output.roles.get(&role_key) {
-                Some(account) => sender == account,
+                Some(account) => is_sender(account),
                 None => false,
             }
         }
@@ -256,145 +498,338 @@ where
             };
             selected_input.metadata.get(&metadata_key).is_some()
         }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use sp_std::collections::btree_map::BTreeMap;
-    use sp_std::iter::FromIterator;
-
-    #[test]
-    fn none_restriction_succeeds() {
-        let result =
-            validate_restriction::<u64, u64, u32, u32, u64, u64>(Restriction::None, &1u64, &Vec::new(), &Vec::new());
-        assert!(result);
-    }
-
-    #[test]
-    fn fail_restriction_fails() {
-        let result =
-            validate_restriction::<u64, u64, u32, u32, u64, u64>(Restriction::Fail, &1u64, &Vec::new(), &Vec::new());
-        assert!(!result);
-    }
-
-    #[test]
-    fn fixed_number_of_inputs_restriction_matches_fixed_input_total() {
-        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
-        is_owner.insert(Default::default(), 1u64);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedNumberOfInputs { num_inputs: 4 },
-            &1u64,
-            &inputs,
-            &Vec::new(),
-        );
-        assert!(result);
-    }
-
-    #[test]
-    fn fixed_number_of_inputs_restriction_matches_fixed_input_total_fail() {
-        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
-        is_owner.insert(Default::default(), 1u64);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedNumberOfInputs { num_inputs: 1 },
-            &1u64,
-            &inputs,
-            &Vec::new(),
-        );
-        assert!(!result);
-    }
-
-    #[test]
-    fn fixed_number_of_outputs_restriction_matches_fixed_output_total() {
-        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
-        is_owner.insert(Default::default(), 1u64);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedNumberOfOutputs { num_outputs: 2 },
-            &1u64,
-            &Vec::new(),
-            &outputs,
-        );
-        assert!(result);
-    }
-
-    #[test]
-    fn fixed_number_of_output_restriction_matches_fixed_output_total_fail() {
-        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
-        is_owner.insert(Default::default(), 1u64);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedNumberOfOutputs { num_outputs: 1 },
-            &1u64,
-            &Vec::new(),
-            &outputs,
-        );
-        assert!(!result);
-    }
-
-    #[test]
-    fn fixed_input_metadata_value_succeeds() {
-        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
-        is_owner.insert(Default::default(), 1u64);
-        let mut real_metadata = BTreeMap::new();
-        real_metadata.insert(2, 110);
+        Restriction::InputMetadataValueInRange {
+            index,
+            metadata_key,
+            min,
+            max,
+        } => {
+            let Some(selected_input) = inputs.get(index as usize) else {
+                return false;
+            };
+            match selected_input.metadata.get(&metadata_key).and_then(|v| v.as_integer()) {
+                Some(value) => min <= value && value <= max,
+                None => false,
+            }
+        }
+        Restriction::OutputMetadataValueInRange {
+            index,
+            metadata_key,
+            min,
+            max,
+        } => {
+            let Some(selected_output) = outputs.get(index as usize) else {
+                return false;
+            };
+            match selected_output.metadata.get(&metadata_key).and_then(|v| v.as_integer()) {
+                Some(value) => min <= value && value <= max,
+                None => false,
+            }
+        }
+        Restriction::InputMetadataValueMaxLength {
+            index,
+            metadata_key,
+            max_length,
+        } => {
+            let Some(selected_input) = inputs.get(index as usize) else {
+                return false;
+            };
+            match selected_input.metadata.get(&metadata_key).and_then(|v| v.as_literal()) {
+                Some(value) => value.len() <= max_length as usize,
+                None => false,
+            }
+        }
+        Restriction::OutputMetadataValueMaxLength {
+            index,
+            metadata_key,
+            max_length,
+        } => {
+            let Some(selected_output) = outputs.get(index as usize) else {
+                return false;
+            };
+            match selected_output.metadata.get(&metadata_key).and_then(|v| v.as_literal()) {
+                Some(value) => value.len() <= max_length as usize,
+                None => false,
+            }
+        }
+        Restriction::SenderHasAnyInputRole { index, role_keys } => {
+            let Some(selected_input) = inputs.get(index as usize) else {
+                return false;
+            };
+            role_keys
+                .iter()
+                .any(|role_key| selected_input.roles.get(role_key).is_some_and(is_sender))
+        }
+        Restriction::SenderHasAnyOutputRole { index, role_keys } => {
+            let Some(selected_output) = outputs.get(index as usize) else {
+                return false;
+            };
+            role_keys
+                .iter()
+                .any(|role_key| selected_output.roles.get(role_key).is_some_and(is_sender))
+        }
+        Restriction::DistinctInputRoles {
+            index,
+            left_role_key,
+            right_role_key,
+        } => {
+            let Some(selected_input) = inputs.get(index as usize) else {
+                return false;
+            };
+            match (
+                selected_input.roles.get(&left_role_key),
+                selected_input.roles.get(&right_role_key),
+            ) {
+                (Some(left_account), Some(right_account)) => left_account != right_account,
+                _ => false,
+            }
+        }
+        Restriction::DistinctOutputRoles {
+            index,
+            left_role_key,
+            right_role_key,
+        } => {
+            let Some(selected_output) = outputs.get(index as usize) else {
+                return false;
+            };
+            match (
+                selected_output.roles.get(&left_role_key),
+                selected_output.roles.get(&right_role_key),
+            ) {
+                (Some(left_account), Some(right_account)) => left_account != right_account,
+                _ => false,
+            }
+        }
+        Restriction::InputMetadataValueHasPrefix {
+            index,
+            metadata_key,
+            prefix,
+        } => {
+            let Some(selected_input) = inputs.get(index as usize) else {
+                return false;
+            };
+            match selected_input.metadata.get(&metadata_key).and_then(|v| v.as_literal()) {
+                Some(value) => value.starts_with(&prefix),
+                None => false,
+            }
+        }
+        Restriction::OutputMetadataValueHasPrefix {
+            index,
+            metadata_key,
+            prefix,
+        } => {
+            let Some(selected_output) = outputs.get(index as usize) else {
+                return false;
+            };
+            match selected_output.metadata.get(&metadata_key).and_then(|v| v.as_literal()) {
+                Some(value) => value.starts_with(&prefix),
+                None => false,
+            }
+        }
+        Restriction::InputMetadataValueHasSuffix {
+            index,
+            metadata_key,
+            suffix,
+        } => {
+            let Some(selected_input) = inputs.get(index as usize) else {
+                return false;
+            };
+            match selected_input.metadata.get(&metadata_key).and_then(|v| v.as_literal()) {
+                Some(value) => value.ends_with(&suffix),
+                None => false,
+            }
+        }
+        Restriction::OutputMetadataValueHasSuffix {
+            index,
+            metadata_key,
+            suffix,
+        } => {
+            let Some(selected_output) = outputs.get(index as usize) else {
+                return false;
+            };
+            match selected_output.metadata.get(&metadata_key).and_then(|v| v.as_literal()) {
+                Some(value) => value.ends_with(&suffix),
+                None => false,
+            }
+        }
+        Restriction::InputMetadataValueMatchesPattern {
+            index,
+            metadata_key,
+            pattern,
+        } => {
+            let Some(selected_input) = inputs.get(index as usize) else {
+                return false;
+            };
+            match selected_input.metadata.get(&metadata_key).and_then(|v| v.as_literal()) {
+                Some(value) => matches_pattern(&pattern, value),
+                None => false,
+            }
+        }
+        Restriction::OutputMetadataValueMatchesPattern {
+            index,
+            metadata_key,
+            pattern,
+        } => {
+            let Some(selected_output) = outputs.get(index as usize) else {
+                return false;
+            };
+            match selected_output.metadata.get(&metadata_key).and_then(|v| v.as_literal()) {
+                Some(value) => matches_pattern(&pattern, value),
+                None => false,
+            }
+        }
+        Restriction::SenderIsInputMetadataAccount { index, metadata_key } => {
+            let Some(selected_input) = inputs.get(index as usize) else {
+                return false;
+            };
+            match selected_input.metadata.get(&metadata_key).and_then(|v| v.as_account()) {
+                Some(account) => is_sender(account),
+                None => false,
+            }
+        }
+        Restriction::SenderIsOutputMetadataAccount { index, metadata_key } => {
+            let Some(selected_output) = outputs.get(index as usize) else {
+                return false;
+            };
+            match selected_output.metadata.get(&metadata_key).and_then(|v| v.as_account()) {
+                Some(account) => is_sender(account),
+                None => false,
+            }
+        }
+        Restriction::MatchInputRoleOutputMetadataAccount {
+            input_index,
+            input_role_key,
+            output_index,
+            output_metadata_key,
+        } => {
+            let (Some(selected_input), Some(selected_output)) =
+                (inputs.get(input_index as usize), outputs.get(output_index as usize))
+            else {
+                return false;
+            };
+            match (
+                selected_input.roles.get(&input_role_key),
+                selected_output
+                    .metadata
+                    .get(&output_metadata_key)
+                    .and_then(|v| v.as_account()),
+            ) {
+                (Some(input_account), Some(output_account)) => input_account == output_account,
+                _ => false,
+            }
+        }
+        Restriction::MatchInputMetadataAccountOutputRole {
+            input_index,
+            input_metadata_key,
+            output_index,
+            output_role_key,
+        } => {
+            let (Some(selected_input), Some(selected_output)) =
+                (inputs.get(input_index as usize), outputs.get(output_index as usize))
+            else {
+                return false;
+            };
+            match (
+                selected_input
+                    .metadata
+                    .get(&input_metadata_key)
+                    .and_then(|v| v.as_account()),
+                selected_output.roles.get(&output_role_key),
+            ) {
+                (Some(input_account), Some(output_account)) => input_account == output_account,
+                _ => false,
+            }
+        }
+        Restriction::OutputFileIsHashOfMetadata {
+            index,
+            metadata_key,
+            source_keys,
+        } => {
+            let Some(selected_output) = outputs.get(index as usize) else {
+                return false;
+            };
+            match (
+                selected_output.metadata.get(&metadata_key).and_then(|v| v.as_hash()),
+                hash_of_metadata(selected_output, &source_keys),
+            ) {
+                (Some(hash), Some(expected)) => hash == expected.as_slice(),
+                _ => false,
+            }
+        }
+        Restriction::InputFileIsHashOfOutputMetadata {
+            input_index,
+            input_metadata_key,
+            output_index,
+            source_keys,
+        } => {
+            let (Some(selected_input), Some(selected_output)) =
+                (inputs.get(input_index as usize), outputs.get(output_index as usize))
+            else {
+                return false;
+            };
+            match (
+                selected_input
+                    .metadata
+                    .get(&input_metadata_key)
+                    .and_then(|v| v.as_hash()),
+                hash_of_metadata(selected_output, &source_keys),
+            ) {
+                (Some(hash), Some(expected)) => hash == expected.as_slice(),
+                _ => false,
+            }
+        }
+        Restriction::SignedByRoleHolder { index, role_key } => {
+            let Some(selected_input) = inputs.get(index as usize) else {
+                return false;
+            };
+            // the sender signs the transition by submitting it
+            match selected_input.roles.get(&role_key) {
+                Some(account) => is_sender(account) || context.signers.contains(account),
+                None => false,
+            }
+        }
+        Restriction::SenderHasOrgAttribute { attribute } => context.sender_attributes.contains(&attribute),
+    }
+}
+
+// blake2_256 of the concatenated SCALE encodings of the values under `keys`, or None if any are missing
+fn hash_of_metadata<I, A, R, T, V>(token: &ProcessIO<I, A, R, T, V>, keys: &[T]) -> Option<[u8; 32]>
+where
+    R: Ord,
+    T: Ord,
+    V: Encode,
+{
+    let mut bytes = Vec::new();
+    for key in keys {
+        token.metadata.get(key)?.encode_to(&mut bytes);
+    }
+    Some(blake2_256(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_std::collections::btree_map::BTreeMap;
+    use sp_std::iter::FromIterator;
+
+    #[test]
+    fn none_restriction_succeeds() {
+        let result =
+            validate_restriction::<u64, u64, u32, u32, u64, u64>(Restriction::None, &1u64, &Vec::new(), &Vec::new());
+        assert!(result);
+    }
+
+    #[test]
+    fn fail_restriction_fails() {
+        let result =
+            validate_restriction::<u64, u64, u32, u32, u64, u64>(Restriction::Fail, &1u64, &Vec::new(), &Vec::new());
+        assert!(!result);
+    }
+
+    #[test]
+    fn fixed_number_of_inputs_restriction_matches_fixed_input_total() {
+        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
+        is_owner.insert(Default::default(), 1u64);
         let inputs = vec![
             ProcessIO {
                 id: 0u64,
@@ -409,258 +844,1761 @@ mod tests {
             ProcessIO {
                 id: 0u64,
                 roles: is_owner.clone(),
-                metadata: real_metadata,
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedNumberOfInputs { num_inputs: 4 },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn fixed_number_of_inputs_restriction_matches_fixed_input_total_fail() {
+        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
+        is_owner.insert(Default::default(), 1u64);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedNumberOfInputs { num_inputs: 1 },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn fixed_number_of_outputs_restriction_matches_fixed_output_total() {
+        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
+        is_owner.insert(Default::default(), 1u64);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedNumberOfOutputs { num_outputs: 2 },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn fixed_number_of_output_restriction_matches_fixed_output_total_fail() {
+        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
+        is_owner.insert(Default::default(), 1u64);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedNumberOfOutputs { num_outputs: 1 },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn fixed_input_metadata_value_succeeds() {
+        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
+        is_owner.insert(Default::default(), 1u64);
+        let mut real_metadata = BTreeMap::new();
+        real_metadata.insert(2, 110);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: real_metadata,
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedInputMetadataValue {
+                index: 2,
+                metadata_key: 2,
+                metadata_value: 110,
+            },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn fixed_input_metadata_value_missing_value_fail() {
+        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
+        is_owner.insert(Default::default(), 1u64);
+        let mut real_metadata = BTreeMap::new();
+        real_metadata.insert(2, 110);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: real_metadata,
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedInputMetadataValue {
+                index: 1,
+                metadata_key: 2,
+                metadata_value: 110,
+            },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn fixed_input_metadata_value_wrong_value_under_right_key_fail() {
+        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
+        is_owner.insert(Default::default(), 1u64);
+        let mut real_metadata = BTreeMap::new();
+        real_metadata.insert(2, 110);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: real_metadata,
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedInputMetadataValue {
+                index: 2,
+                metadata_key: 2,
+                metadata_value: 45,
+            },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn fixed_input_metadata_value_correct_value_under_incorrect_key_fail() {
+        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
+        is_owner.insert(Default::default(), 1u64);
+        let mut real_metadata = BTreeMap::new();
+        real_metadata.insert(1, 200);
+        real_metadata.insert(2, 110);
+        real_metadata.insert(3, 300);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: real_metadata,
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedInputMetadataValue {
+                index: 2,
+                metadata_key: 3,
+                metadata_value: 110,
+            },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn fixed_input_metadata_value_correct_value_under_correct_key_on_wrong_input_fail() {
+        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
+        is_owner.insert(Default::default(), 1u64);
+        let mut real_metadata = BTreeMap::new();
+        real_metadata.insert(1, 200);
+        real_metadata.insert(2, 110);
+        real_metadata.insert(3, 300);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: real_metadata,
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedInputMetadataValue {
+                index: 3,
+                metadata_key: 2,
+                metadata_value: 110,
+            },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn fixed_input_metadata_value_bad_index_fail() {
+        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
+        is_owner.insert(Default::default(), 1u64);
+        let mut real_metadata = BTreeMap::new();
+        real_metadata.insert(1, 200);
+        real_metadata.insert(2, 110);
+        real_metadata.insert(3, 300);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: is_owner.clone(),
+                metadata: real_metadata,
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedInputMetadataValue {
+                index: 1,
+                metadata_key: 2,
+                metadata_value: 110,
+            },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn fixed_output_metadata_value_succeeds() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, 100)]),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedOutputMetadataValue {
+                index: 1,
+                metadata_key: 1,
+                metadata_value: 100,
+            },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn fixed_output_metadata_value_incorrect_index_correct_key_correct_value_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, 100)]),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedOutputMetadataValue {
+                index: 1,
+                metadata_key: 1,
+                metadata_value: 100,
+            },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn fixed_output_metadata_value_correct_index_correct_key_incorrect_value_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, 100)]),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedOutputMetadataValue {
+                index: 0,
+                metadata_key: 1,
+                metadata_value: 99,
+            },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn foxed_output_metadata_value_correct_index_incorrect_key_correct_value_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, 100)]),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedOutputMetadataValue {
+                index: 0,
+                metadata_key: 0,
+                metadata_value: 100,
+            },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn fixed_output_metadata_value_bad_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, 100)]),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::FixedOutputMetadataValue {
+                index: 2,
+                metadata_key: 1,
+                metadata_value: 100,
+            },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[derive(Encode, Decode, Clone, PartialEq, TypeInfo, MaxEncodedLen, Debug, Eq)]
+    pub enum MetadataValue {
+        A,
+        B,
+    }
+    impl Default for MetadataValue {
+        fn default() -> Self {
+            return MetadataValue::A;
+        }
+    }
+
+    impl PartialEq<u64> for MetadataValue {
+        fn eq(&self, _: &u64) -> bool {
+            false
+        }
+    }
+
+    impl MetadataValueAccess for MetadataValue {
+        fn as_integer(&self) -> Option<i128> {
+            None
+        }
+        fn as_literal(&self) -> Option<&[u8]> {
+            None
+        }
+        fn as_hash(&self) -> Option<&[u8]> {
+            None
+        }
+    }
+
+    impl MetadataAccountAccess<u64> for MetadataValue {
+        fn as_account(&self) -> Option<&u64> {
+            None
+        }
+    }
+
+    #[derive(Encode, Decode, Clone, PartialEq, TypeInfo, MaxEncodedLen, Debug, Eq)]
+    pub enum MetadataValueDisc {
+        AA,
+        BB,
+    }
+    impl Default for MetadataValueDisc {
+        fn default() -> Self {
+            return MetadataValueDisc::AA;
+        }
+    }
+
+    impl From<MetadataValue> for MetadataValueDisc {
+        fn from(v: MetadataValue) -> MetadataValueDisc {
+            match v {
+                MetadataValue::A => MetadataValueDisc::AA,
+                MetadataValue::B => MetadataValueDisc::BB,
+            }
+        }
+    }
+
+    #[test]
+    fn output_restrict_metadata_type_succeeds() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
+            Restriction::FixedOutputMetadataValueType {
+                index: 1,
+                metadata_key: 1,
+                metadata_value_type: MetadataValueDisc::AA,
+            },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn output_restrict_metadata_type_incorrect_type_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
+            Restriction::FixedOutputMetadataValueType {
+                index: 1,
+                metadata_key: 1,
+                metadata_value_type: MetadataValueDisc::BB,
+            },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn output_restrict_metadata_type_incorrect_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
+            Restriction::FixedOutputMetadataValueType {
+                index: 0,
+                metadata_key: 1,
+                metadata_value_type: MetadataValueDisc::AA,
+            },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn output_restrict_metadata_type_incorrect_key_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
+            Restriction::FixedOutputMetadataValueType {
+                index: 1,
+                metadata_key: 0,
+                metadata_value_type: MetadataValueDisc::AA,
+            },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn output_restrict_metadata_type_bad_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
+            Restriction::FixedOutputMetadataValueType {
+                index: 2,
+                metadata_key: 0,
+                metadata_value_type: MetadataValueDisc::AA,
+            },
+            &1u64,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn input_restrict_metadata_type_succeeds() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
+            Restriction::FixedInputMetadataValueType {
+                index: 1,
+                metadata_key: 1,
+                metadata_value_type: MetadataValueDisc::AA,
+            },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn input_restrict_metadata_type_incorrect_type_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
+            Restriction::FixedInputMetadataValueType {
+                index: 1,
+                metadata_key: 1,
+                metadata_value_type: MetadataValueDisc::BB,
+            },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn input_restrict_metadata_type_incorrect_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
+            Restriction::FixedInputMetadataValueType {
+                index: 0,
+                metadata_key: 1,
+                metadata_value_type: MetadataValueDisc::AA,
+            },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn input_restrict_metadata_type_incorrect_key_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
+            Restriction::FixedInputMetadataValueType {
+                index: 1,
+                metadata_key: 0,
+                metadata_value_type: MetadataValueDisc::AA,
+            },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn input_restrict_metadata_type_bad_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
+            Restriction::FixedInputMetadataValueType {
+                index: 2,
+                metadata_key: 0,
+                metadata_value_type: MetadataValueDisc::AA,
+            },
+            &1u64,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn sender_has_input_role_succeeds() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasInputRole {
+                index: 0,
+                role_key: Default::default(),
+            },
+            &1,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn sender_has_input_role_incorrect_account_id_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 2)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasInputRole {
+                index: 0,
+                role_key: Default::default(),
+            },
+            &1,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn sender_has_input_role_incorrect_index_fails() {
+        let roles0 = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let roles1 = BTreeMap::from_iter(vec![(Default::default(), 2)]);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles0.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles1.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasInputRole {
+                index: 1,
+                role_key: Default::default(),
+            },
+            &1,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn sender_has_input_role_incorrect_role_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (0, 1), (1, 2)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasInputRole { index: 0, role_key: 1 },
+            &1,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn sender_has_input_role_bad_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (0, 1), (1, 2)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasInputRole { index: 1, role_key: 1 },
+            &1,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn sender_has_output_role_succeeds() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasOutputRole {
+                index: 0,
+                role_key: Default::default(),
+            },
+            &1,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn sender_has_output_role_incorrect_account_id_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 2)]);
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasOutputRole {
+                index: 0,
+                role_key: Default::default(),
+            },
+            &1,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn sender_has_output_role_incorrect_index_fails() {
+        let roles0 = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let roles1 = BTreeMap::from_iter(vec![(Default::default(), 2)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles0.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles1.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasOutputRole {
+                index: 1,
+                role_key: Default::default(),
+            },
+            &1,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn sender_has_output_role_incorrect_role_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (0, 1), (1, 2)]);
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasOutputRole { index: 0, role_key: 1 },
+            &1,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn sender_has_output_role_bad_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (0, 1), (1, 2)]);
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasOutputRole { index: 1, role_key: 1 },
+            &1,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn output_has_role_succeeds() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::OutputHasRole { index: 0, role_key: 1 },
+            &1,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn output_has_role_incorrect_role_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::OutputHasRole { index: 0, role_key: 2 },
+            &1,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn output_has_role_incorrect_index_fails() {
+        let roles0 = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let roles1 = BTreeMap::from_iter(vec![(Default::default(), 1), (2, 1)]);
+        let outputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles0.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles1.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::OutputHasRole { index: 1, role_key: 1 },
+            &1,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn output_has_role_bad_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::OutputHasRole { index: 1, role_key: 1 },
+            &1,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_output_role_same_role_keys_succeeds() {
+        let input_roles = BTreeMap::from_iter(vec![(0, 1)]);
+        let output_roles = BTreeMap::from_iter(vec![(0, 1)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: input_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: output_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputRole {
+                input_index: 0,
+                input_role_key: 0,
+                output_index: 0,
+                output_role_key: 0,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn match_input_output_role_different_role_keys_succeeds() {
+        let input_roles = BTreeMap::from_iter(vec![(0, 2)]);
+        let output_roles = BTreeMap::from_iter(vec![(0, 1), (1, 2)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: input_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: output_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputRole {
+                input_index: 0,
+                input_role_key: 0,
+                output_index: 0,
+                output_role_key: 1,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn match_input_output_role_only_one_has_key_fails() {
+        let input_roles = BTreeMap::from_iter(vec![(0, 1)]);
+        let output_roles = BTreeMap::from_iter(vec![(0, 1), (1, 2)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: input_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: output_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputRole {
+                input_index: 0,
+                input_role_key: 1,
+                output_index: 0,
+                output_role_key: 1,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_output_role_neither_has_key_fails() {
+        let input_roles = BTreeMap::from_iter(vec![(0, 1)]);
+        let output_roles = BTreeMap::from_iter(vec![(0, 1)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: input_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: output_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputRole {
+                input_index: 0,
+                input_role_key: 1,
+                output_index: 0,
+                output_role_key: 1,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_output_role_wrong_index_fails() {
+        let input_roles0 = BTreeMap::from_iter(vec![(0, 1), (1, 2)]);
+        let input_roles1 = BTreeMap::from_iter(vec![(0, 1)]);
+        let output_roles = BTreeMap::from_iter(vec![(0, 1), (1, 2)]);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: input_roles0.clone(),
+                metadata: BTreeMap::new(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: input_roles1.clone(),
+                metadata: BTreeMap::new(),
+            },
+        ];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: output_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputRole {
+                input_index: 1,
+                input_role_key: 1,
+                output_index: 0,
+                output_role_key: 1,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_output_role_bad_input_index_fails() {
+        let input_roles = BTreeMap::from_iter(vec![(0, 1)]);
+        let output_roles = BTreeMap::from_iter(vec![(0, 1)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: input_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: output_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputRole {
+                input_index: 1,
+                input_role_key: 0,
+                output_index: 0,
+                output_role_key: 0,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_output_role_bad_output_index_fails() {
+        let input_roles = BTreeMap::from_iter(vec![(0, 1)]);
+        let output_roles = BTreeMap::from_iter(vec![(0, 1)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: input_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: output_roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputRole {
+                input_index: 0,
+                input_role_key: 0,
+                output_index: 1,
+                output_role_key: 0,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_output_metadata_value_same_metadata_keys_succeeds() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let input_metadata = BTreeMap::from_iter(vec![(0, 0)]);
+        let output_metadata = BTreeMap::from_iter(vec![(0, 0)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: input_metadata.clone(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: output_metadata.clone(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputMetadataValue {
+                input_index: 0,
+                input_metadata_key: 0,
+                output_index: 0,
+                output_metadata_key: 0,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn match_input_output_metadata_value_different_metadata_keys_succeeds() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let input_metadata = BTreeMap::from_iter(vec![(0, 0)]);
+        let output_metadata = BTreeMap::from_iter(vec![(1, 0)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: input_metadata.clone(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: output_metadata.clone(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputMetadataValue {
+                input_index: 0,
+                input_metadata_key: 0,
+                output_index: 0,
+                output_metadata_key: 1,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn match_input_output_metadata_value_only_one_has_key_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let input_metadata = BTreeMap::from_iter(vec![(0, 0)]);
+        let output_metadata = BTreeMap::new();
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: input_metadata.clone(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: output_metadata.clone(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputMetadataValue {
+                input_index: 0,
+                input_metadata_key: 0,
+                output_index: 0,
+                output_metadata_key: 0,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_output_metadata_value_neither_has_key_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let input_metadata = BTreeMap::new();
+        let output_metadata = BTreeMap::new();
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: input_metadata.clone(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: output_metadata.clone(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputMetadataValue {
+                input_index: 0,
+                input_metadata_key: 0,
+                output_index: 0,
+                output_metadata_key: 0,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_output_metadata_value_wrong_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let input_metadata0 = BTreeMap::from_iter(vec![(0, 0)]);
+        let input_metadata1 = BTreeMap::new();
+        let output_metadata = BTreeMap::from_iter(vec![(0, 0)]);
+        let inputs = vec![
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: input_metadata0.clone(),
+            },
+            ProcessIO {
+                id: 0u64,
+                roles: roles.clone(),
+                metadata: input_metadata1.clone(),
+            },
+        ];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: output_metadata.clone(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputMetadataValue {
+                input_index: 1,
+                input_metadata_key: 0,
+                output_index: 0,
+                output_metadata_key: 0,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_output_metadata_value_bad_input_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let input_metadata = BTreeMap::from_iter(vec![(0, 0)]);
+        let output_metadata = BTreeMap::from_iter(vec![(0, 0)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: input_metadata.clone(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: output_metadata.clone(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputMetadataValue {
+                input_index: 1,
+                input_metadata_key: 0,
+                output_index: 0,
+                output_metadata_key: 0,
+            },
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_output_metadata_value_bad_output_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+        let input_metadata = BTreeMap::from_iter(vec![(0, 0)]);
+        let output_metadata = BTreeMap::from_iter(vec![(0, 0)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: input_metadata.clone(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: output_metadata.clone(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputOutputMetadataValue {
+                input_index: 0,
+                input_metadata_key: 0,
+                output_index: 1,
+                output_metadata_key: 0,
             },
-        ];
+            &1,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_id_output_metadata_value_simple_succeeds() {
+        let inputs = vec![ProcessIO {
+            id: 42u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 43u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::from_iter(vec![(1, 42)]),
+        }];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedInputMetadataValue {
-                index: 2,
-                metadata_key: 2,
-                metadata_value: 110,
+            Restriction::MatchInputIdOutputMetadataValue {
+                input_index: 0,
+                output_index: 0,
+                output_metadata_key: 1,
             },
             &1u64,
             &inputs,
-            &Vec::new(),
+            &outputs,
         );
         assert!(result);
     }
 
     #[test]
-    fn fixed_input_metadata_value_missing_value_fail() {
-        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
-        is_owner.insert(Default::default(), 1u64);
-        let mut real_metadata = BTreeMap::new();
-        real_metadata.insert(2, 110);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: real_metadata,
+    fn match_input_id_output_metadata_value_incorrect_metadata_key_fails() {
+        let inputs = vec![ProcessIO {
+            id: 42u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 43u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::from_iter(vec![(2, 42)]),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputIdOutputMetadataValue {
+                input_index: 0,
+                output_index: 0,
+                output_metadata_key: 1,
             },
-        ];
+            &1u64,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_id_output_metadata_value_incorrect_id_fails() {
+        let inputs = vec![ProcessIO {
+            id: 42u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 43u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::from_iter(vec![(1, 40)]),
+        }];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedInputMetadataValue {
-                index: 1,
-                metadata_key: 2,
-                metadata_value: 110,
+            Restriction::MatchInputIdOutputMetadataValue {
+                input_index: 0,
+                output_index: 0,
+                output_metadata_key: 1,
             },
             &1u64,
             &inputs,
-            &Vec::new(),
+            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn fixed_input_metadata_value_wrong_value_under_right_key_fail() {
-        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
-        is_owner.insert(Default::default(), 1u64);
-        let mut real_metadata = BTreeMap::new();
-        real_metadata.insert(2, 110);
+    fn match_input_id_output_metadata_value_incorrect_input_index_fails() {
         let inputs = vec![
             ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
+                id: 42u64,
+                roles: BTreeMap::new(),
                 metadata: BTreeMap::new(),
             },
             ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
+                id: 41u64,
+                roles: BTreeMap::new(),
                 metadata: BTreeMap::new(),
             },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: real_metadata,
-            },
         ];
+        let outputs = vec![ProcessIO {
+            id: 43u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::from_iter(vec![(1, 42)]),
+        }];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedInputMetadataValue {
-                index: 2,
-                metadata_key: 2,
-                metadata_value: 45,
+            Restriction::MatchInputIdOutputMetadataValue {
+                input_index: 1,
+                output_index: 0,
+                output_metadata_key: 1,
             },
             &1u64,
             &inputs,
-            &Vec::new(),
+            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn fixed_input_metadata_value_correct_value_under_incorrect_key_fail() {
-        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
-        is_owner.insert(Default::default(), 1u64);
-        let mut real_metadata = BTreeMap::new();
-        real_metadata.insert(1, 200);
-        real_metadata.insert(2, 110);
-        real_metadata.insert(3, 300);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
+    fn match_input_id_output_metadata_value_incorrect_output_index_fails() {
+        let inputs = vec![ProcessIO {
+            id: 42u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![
             ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
+                id: 43u64,
+                roles: BTreeMap::new(),
+                metadata: BTreeMap::from_iter(vec![(1, 42)]),
             },
             ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: real_metadata,
+                id: 43u64,
+                roles: BTreeMap::new(),
+                metadata: BTreeMap::from_iter(vec![(1, 41)]),
             },
         ];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedInputMetadataValue {
-                index: 2,
-                metadata_key: 3,
-                metadata_value: 110,
+            Restriction::MatchInputIdOutputMetadataValue {
+                input_index: 0,
+                output_index: 1,
+                output_metadata_key: 1,
             },
             &1u64,
             &inputs,
-            &Vec::new(),
+            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn fixed_input_metadata_value_correct_value_under_correct_key_on_wrong_input_fail() {
-        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
-        is_owner.insert(Default::default(), 1u64);
-        let mut real_metadata = BTreeMap::new();
-        real_metadata.insert(1, 200);
-        real_metadata.insert(2, 110);
-        real_metadata.insert(3, 300);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: real_metadata,
+    fn match_input_id_output_metadata_value_bad_input_index_fails() {
+        let inputs = vec![ProcessIO {
+            id: 42u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 43u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::from_iter(vec![(1, 42)]),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::MatchInputIdOutputMetadataValue {
+                input_index: 1,
+                output_index: 0,
+                output_metadata_key: 1,
             },
-        ];
+            &1u64,
+            &inputs,
+            &outputs,
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn match_input_id_output_metadata_value_bad_output_index_fails() {
+        let inputs = vec![ProcessIO {
+            id: 42u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        }];
+        let outputs = vec![ProcessIO {
+            id: 43u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::from_iter(vec![(1, 42)]),
+        }];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedInputMetadataValue {
-                index: 3,
-                metadata_key: 2,
-                metadata_value: 110,
+            Restriction::MatchInputIdOutputMetadataValue {
+                input_index: 0,
+                output_index: 1,
+                output_metadata_key: 1,
             },
             &1u64,
             &inputs,
-            &Vec::new(),
+            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn fixed_input_metadata_value_bad_index_fail() {
-        let mut is_owner: BTreeMap<u32, u64> = BTreeMap::new();
-        is_owner.insert(Default::default(), 1u64);
-        let mut real_metadata = BTreeMap::new();
-        real_metadata.insert(1, 200);
-        real_metadata.insert(2, 110);
-        real_metadata.insert(3, 300);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: is_owner.clone(),
-                metadata: real_metadata,
+    fn output_has_metadata_value_succeeds() {
+        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: BTreeMap::new(),
+            metadata: metadata.clone(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::OutputHasMetadata {
+                index: 0,
+                metadata_key: 1,
             },
-        ];
+            &1,
+            &Vec::new(),
+            &outputs,
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn output_has_metadata_value_incorrect_role_fails() {
+        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: BTreeMap::new(),
+            metadata: metadata.clone(),
+        }];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedInputMetadataValue {
-                index: 1,
+            Restriction::OutputHasMetadata {
+                index: 0,
                 metadata_key: 2,
-                metadata_value: 110,
             },
-            &1u64,
-            &inputs,
+            &1,
             &Vec::new(),
+            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn fixed_output_metadata_value_succeeds() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
+    fn output_has_metadata_value_incorrect_index_fails() {
+        let metadata0 = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let metadata1 = BTreeMap::from_iter(vec![(Default::default(), 1), (2, 1)]);
         let outputs = vec![
             ProcessIO {
                 id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
+                roles: BTreeMap::new(),
+                metadata: metadata0.clone(),
             },
             ProcessIO {
                 id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, 100)]),
+                roles: BTreeMap::new(),
+                metadata: metadata1.clone(),
             },
         ];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedOutputMetadataValue {
+            Restriction::OutputHasMetadata {
                 index: 1,
                 metadata_key: 1,
-                metadata_value: 100,
             },
-            &1u64,
+            &1,
             &Vec::new(),
             &outputs,
         );
-        assert!(result);
+        assert!(!result);
     }
 
     #[test]
-    fn fixed_output_metadata_value_incorrect_index_correct_key_correct_value_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, 100)]),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-        ];
+    fn output_has_metadata_value_bad_index_fails() {
+        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: BTreeMap::new(),
+            metadata: metadata.clone(),
+        }];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedOutputMetadataValue {
+            Restriction::OutputHasMetadata {
                 index: 1,
                 metadata_key: 1,
-                metadata_value: 100,
             },
-            &1u64,
+            &1,
             &Vec::new(),
             &outputs,
         );
@@ -668,1468 +2606,1257 @@ mod tests {
     }
 
     #[test]
-    fn fixed_output_metadata_value_correct_index_correct_key_incorrect_value_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, 100)]),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-        ];
+    fn input_has_role_succeeds() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedOutputMetadataValue {
-                index: 0,
-                metadata_key: 1,
-                metadata_value: 99,
-            },
-            &1u64,
+            Restriction::InputHasRole { index: 0, role_key: 1 },
+            &1,
+            &inputs,
             &Vec::new(),
-            &outputs,
         );
-        assert!(!result);
+        assert!(result);
     }
 
     #[test]
-    fn foxed_output_metadata_value_correct_index_incorrect_key_correct_value_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, 100)]),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-        ];
+    fn input_has_role_incorrect_role_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedOutputMetadataValue {
-                index: 0,
-                metadata_key: 0,
-                metadata_value: 100,
-            },
-            &1u64,
+            Restriction::InputHasRole { index: 0, role_key: 2 },
+            &1,
+            &inputs,
             &Vec::new(),
-            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn fixed_output_metadata_value_bad_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let outputs = vec![
+    fn input_has_role_incorrect_index_fails() {
+        let roles0 = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let roles1 = BTreeMap::from_iter(vec![(Default::default(), 1), (2, 1)]);
+        let inputs = vec![
             ProcessIO {
                 id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, 100)]),
+                roles: roles0.clone(),
+                metadata: BTreeMap::new(),
             },
             ProcessIO {
                 id: 0u64,
-                roles: roles.clone(),
+                roles: roles1.clone(),
                 metadata: BTreeMap::new(),
             },
         ];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::FixedOutputMetadataValue {
-                index: 2,
-                metadata_key: 1,
-                metadata_value: 100,
-            },
-            &1u64,
+            Restriction::InputHasRole { index: 1, role_key: 1 },
+            &1,
+            &inputs,
             &Vec::new(),
-            &outputs,
         );
         assert!(!result);
     }
 
-    #[derive(Encode, Decode, Clone, PartialEq, TypeInfo, MaxEncodedLen, Debug, Eq)]
-    pub enum MetadataValue {
-        A,
-        B,
-    }
-    impl Default for MetadataValue {
-        fn default() -> Self {
-            return MetadataValue::A;
-        }
-    }
-
-    impl PartialEq<u64> for MetadataValue {
-        fn eq(&self, _: &u64) -> bool {
-            false
-        }
-    }
-
-    #[derive(Encode, Decode, Clone, PartialEq, TypeInfo, MaxEncodedLen, Debug, Eq)]
-    pub enum MetadataValueDisc {
-        AA,
-        BB,
-    }
-    impl Default for MetadataValueDisc {
-        fn default() -> Self {
-            return MetadataValueDisc::AA;
-        }
+    #[test]
+    fn input_has_role_bad_index_fails() {
+        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: roles.clone(),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::InputHasRole { index: 1, role_key: 1 },
+            &1,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(!result);
     }
 
-    impl From<MetadataValue> for MetadataValueDisc {
-        fn from(v: MetadataValue) -> MetadataValueDisc {
-            match v {
-                MetadataValue::A => MetadataValueDisc::AA,
-                MetadataValue::B => MetadataValueDisc::BB,
-            }
-        }
+    #[test]
+    fn input_has_metadata_value_succeeds() {
+        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: BTreeMap::new(),
+            metadata: metadata.clone(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::InputHasMetadata {
+                index: 0,
+                metadata_key: 1,
+            },
+            &1,
+            &inputs,
+            &Vec::new(),
+        );
+        assert!(result);
     }
 
     #[test]
-    fn output_restrict_metadata_type_succeeds() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
-            Restriction::FixedOutputMetadataValueType {
-                index: 1,
-                metadata_key: 1,
-                metadata_value_type: MetadataValueDisc::AA,
+    fn input_has_metadata_value_incorrect_role_fails() {
+        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: BTreeMap::new(),
+            metadata: metadata.clone(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::InputHasMetadata {
+                index: 0,
+                metadata_key: 2,
             },
-            &1u64,
+            &1,
+            &inputs,
             &Vec::new(),
-            &outputs,
         );
-        assert!(result);
+        assert!(!result);
     }
 
     #[test]
-    fn output_restrict_metadata_type_incorrect_type_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let outputs = vec![
+    fn input_has_metadata_value_incorrect_index_fails() {
+        let metadata0 = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let metadata1 = BTreeMap::from_iter(vec![(Default::default(), 1), (2, 1)]);
+        let inputs = vec![
             ProcessIO {
                 id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
+                roles: BTreeMap::new(),
+                metadata: metadata0.clone(),
             },
             ProcessIO {
                 id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+                roles: BTreeMap::new(),
+                metadata: metadata1.clone(),
             },
         ];
-        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
-            Restriction::FixedOutputMetadataValueType {
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::InputHasMetadata {
                 index: 1,
                 metadata_key: 1,
-                metadata_value_type: MetadataValueDisc::BB,
             },
-            &1u64,
+            &1,
+            &inputs,
             &Vec::new(),
-            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn output_restrict_metadata_type_incorrect_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
-            Restriction::FixedOutputMetadataValueType {
-                index: 0,
+    fn input_has_metadata_value_bad_index_fails() {
+        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+        let inputs = vec![ProcessIO {
+            id: 0u64,
+            roles: BTreeMap::new(),
+            metadata: metadata.clone(),
+        }];
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::InputHasMetadata {
+                index: 1,
                 metadata_key: 1,
-                metadata_value_type: MetadataValueDisc::AA,
             },
-            &1u64,
+            &1,
+            &inputs,
             &Vec::new(),
-            &outputs,
         );
         assert!(!result);
     }
 
+    #[derive(Encode, Decode, Clone, PartialEq, TypeInfo, Debug, Eq)]
+    pub struct LiteralValue(Vec<u8>);
+
+    impl PartialEq<u64> for LiteralValue {
+        fn eq(&self, _: &u64) -> bool {
+            false
+        }
+    }
+
+    impl MetadataValueAccess for LiteralValue {
+        fn as_integer(&self) -> Option<i128> {
+            None
+        }
+        fn as_literal(&self) -> Option<&[u8]> {
+            Some(&self.0)
+        }
+        fn as_hash(&self) -> Option<&[u8]> {
+            None
+        }
+    }
+
+    impl MetadataAccountAccess<u64> for LiteralValue {
+        fn as_account(&self) -> Option<&u64> {
+            None
+        }
+    }
+
+    impl From<LiteralValue> for u64 {
+        fn from(_: LiteralValue) -> u64 {
+            0
+        }
+    }
+
+    fn with_metadata<V>(value: V) -> Vec<ProcessIO<u64, u64, u32, u32, V>> {
+        vec![ProcessIO {
+            id: 0u64,
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::from_iter(vec![(1, value)]),
+        }]
+    }
+
+    #[test]
+    fn input_metadata_value_in_range_succeeds() {
+        for value in [0u64, 5, 10] {
+            let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+                Restriction::InputMetadataValueInRange {
+                    index: 0,
+                    metadata_key: 1,
+                    min: 0,
+                    max: 10,
+                },
+                &1,
+                &with_metadata(value),
+                &Vec::new(),
+            );
+            assert!(result);
+        }
+    }
+
     #[test]
-    fn output_restrict_metadata_type_incorrect_key_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
-            Restriction::FixedOutputMetadataValueType {
-                index: 1,
-                metadata_key: 0,
-                metadata_value_type: MetadataValueDisc::AA,
+    fn input_metadata_value_in_range_out_of_range_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::InputMetadataValueInRange {
+                index: 0,
+                metadata_key: 1,
+                min: 0,
+                max: 10,
             },
-            &1u64,
+            &1,
+            &with_metadata(11),
             &Vec::new(),
-            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn output_restrict_metadata_type_bad_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
-            Restriction::FixedOutputMetadataValueType {
-                index: 2,
-                metadata_key: 0,
-                metadata_value_type: MetadataValueDisc::AA,
+    fn input_metadata_value_in_range_incorrect_key_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::InputMetadataValueInRange {
+                index: 0,
+                metadata_key: 2,
+                min: 0,
+                max: 10,
             },
-            &1u64,
+            &1,
+            &with_metadata(5),
             &Vec::new(),
-            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn input_restrict_metadata_type_succeeds() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
-            Restriction::FixedInputMetadataValueType {
-                index: 1,
+    fn output_metadata_value_in_range_succeeds() {
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::OutputMetadataValueInRange {
+                index: 0,
                 metadata_key: 1,
-                metadata_value_type: MetadataValueDisc::AA,
+                min: -5,
+                max: 5,
             },
-            &1u64,
-            &inputs,
+            &1,
             &Vec::new(),
+            &with_metadata(5),
         );
         assert!(result);
     }
 
     #[test]
-    fn input_restrict_metadata_type_incorrect_type_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
-            Restriction::FixedInputMetadataValueType {
+    fn output_metadata_value_in_range_bad_index_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::OutputMetadataValueInRange {
                 index: 1,
                 metadata_key: 1,
-                metadata_value_type: MetadataValueDisc::BB,
+                min: 0,
+                max: 10,
             },
-            &1u64,
-            &inputs,
+            &1,
             &Vec::new(),
+            &with_metadata(5),
         );
         assert!(!result);
     }
 
     #[test]
-    fn input_restrict_metadata_type_incorrect_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
-            Restriction::FixedInputMetadataValueType {
+    fn output_metadata_value_in_range_not_integer_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, LiteralValue, u64>(
+            Restriction::OutputMetadataValueInRange {
                 index: 0,
                 metadata_key: 1,
-                metadata_value_type: MetadataValueDisc::AA,
+                min: 0,
+                max: 10,
             },
-            &1u64,
-            &inputs,
+            &1,
             &Vec::new(),
+            &with_metadata(LiteralValue(vec![1])),
         );
         assert!(!result);
     }
 
     #[test]
-    fn input_restrict_metadata_type_incorrect_key_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+    fn input_metadata_value_max_length_succeeds() {
+        let result = validate_restriction::<u64, u64, u32, u32, LiteralValue, u64>(
+            Restriction::InputMetadataValueMaxLength {
+                index: 0,
+                metadata_key: 1,
+                max_length: 4,
             },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
-            Restriction::FixedInputMetadataValueType {
-                index: 1,
-                metadata_key: 0,
-                metadata_value_type: MetadataValueDisc::AA,
+            &1,
+            &with_metadata(LiteralValue(b"abcd".to_vec())),
+            &Vec::new(),
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn input_metadata_value_max_length_too_long_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, LiteralValue, u64>(
+            Restriction::InputMetadataValueMaxLength {
+                index: 0,
+                metadata_key: 1,
+                max_length: 4,
             },
-            &1u64,
-            &inputs,
+            &1,
+            &with_metadata(LiteralValue(b"abcde".to_vec())),
             &Vec::new(),
         );
         assert!(!result);
     }
 
     #[test]
-    fn input_restrict_metadata_type_bad_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: BTreeMap::from_iter(vec![(1, MetadataValue::A)]),
+    fn output_metadata_value_max_length_succeeds() {
+        let result = validate_restriction::<u64, u64, u32, u32, LiteralValue, u64>(
+            Restriction::OutputMetadataValueMaxLength {
+                index: 0,
+                metadata_key: 1,
+                max_length: 4,
             },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, MetadataValue, MetadataValueDisc>(
-            Restriction::FixedInputMetadataValueType {
-                index: 2,
-                metadata_key: 0,
-                metadata_value_type: MetadataValueDisc::AA,
+            &1,
+            &Vec::new(),
+            &with_metadata(LiteralValue(b"ab".to_vec())),
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn output_metadata_value_max_length_not_literal_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::OutputMetadataValueMaxLength {
+                index: 0,
+                metadata_key: 1,
+                max_length: 4,
             },
-            &1u64,
-            &inputs,
+            &1,
             &Vec::new(),
+            &with_metadata(1),
         );
         assert!(!result);
     }
 
-    #[test]
-    fn sender_has_input_role_succeeds() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let inputs = vec![ProcessIO {
+    fn with_roles(roles: Vec<(u32, u64)>) -> Vec<ProcessIO<u64, u64, u32, u32, u64>> {
+        vec![ProcessIO {
             id: 0u64,
-            roles: roles.clone(),
+            roles: BTreeMap::from_iter(roles),
             metadata: BTreeMap::new(),
-        }];
+        }]
+    }
+
+    #[test]
+    fn sender_has_any_input_role_succeeds() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::SenderHasInputRole {
+            Restriction::SenderHasAnyInputRole {
                 index: 0,
-                role_key: Default::default(),
+                role_keys: vec![0, 1].try_into().unwrap(),
             },
             &1,
-            &inputs,
+            &with_roles(vec![(0, 2), (1, 1)]),
             &Vec::new(),
         );
         assert!(result);
     }
 
     #[test]
-    fn sender_has_input_role_incorrect_account_id_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 2)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
+    fn sender_has_any_input_role_no_matching_role_fails() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::SenderHasInputRole {
+            Restriction::SenderHasAnyInputRole {
                 index: 0,
-                role_key: Default::default(),
+                role_keys: vec![0, 1].try_into().unwrap(),
             },
             &1,
-            &inputs,
+            &with_roles(vec![(0, 2), (1, 3), (2, 1)]),
             &Vec::new(),
         );
         assert!(!result);
     }
 
     #[test]
-    fn sender_has_input_role_incorrect_index_fails() {
-        let roles0 = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let roles1 = BTreeMap::from_iter(vec![(Default::default(), 2)]);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles0.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles1.clone(),
-                metadata: BTreeMap::new(),
+    fn sender_has_any_input_role_empty_set_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasAnyInputRole {
+                index: 0,
+                role_keys: Default::default(),
             },
-        ];
+            &1,
+            &with_roles(vec![(0, 1)]),
+            &Vec::new(),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn sender_has_any_input_role_bad_index_fails() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::SenderHasInputRole {
+            Restriction::SenderHasAnyInputRole {
                 index: 1,
-                role_key: Default::default(),
+                role_keys: vec![0].try_into().unwrap(),
             },
             &1,
-            &inputs,
+            &with_roles(vec![(0, 1)]),
             &Vec::new(),
         );
         assert!(!result);
     }
 
     #[test]
-    fn sender_has_input_role_incorrect_role_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (0, 1), (1, 2)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
+    fn sender_has_any_output_role_succeeds() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::SenderHasInputRole { index: 0, role_key: 1 },
+            Restriction::SenderHasAnyOutputRole {
+                index: 0,
+                role_keys: vec![0, 1].try_into().unwrap(),
+            },
             &1,
-            &inputs,
             &Vec::new(),
+            &with_roles(vec![(0, 1), (1, 2)]),
         );
-        assert!(!result);
+        assert!(result);
     }
 
     #[test]
-    fn sender_has_input_role_bad_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (0, 1), (1, 2)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
+    fn sender_has_any_output_role_no_matching_role_fails() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::SenderHasInputRole { index: 1, role_key: 1 },
+            Restriction::SenderHasAnyOutputRole {
+                index: 0,
+                role_keys: vec![0, 1].try_into().unwrap(),
+            },
             &1,
-            &inputs,
             &Vec::new(),
+            &with_roles(vec![(0, 2)]),
         );
         assert!(!result);
     }
 
     #[test]
-    fn sender_has_output_role_succeeds() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
+    fn distinct_input_roles_succeeds() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::SenderHasOutputRole {
+            Restriction::DistinctInputRoles {
                 index: 0,
-                role_key: Default::default(),
+                left_role_key: 0,
+                right_role_key: 1,
             },
             &1,
+            &with_roles(vec![(0, 1), (1, 2)]),
             &Vec::new(),
-            &outputs,
         );
         assert!(result);
     }
 
     #[test]
-    fn sender_has_output_role_incorrect_account_id_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 2)]);
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
+    fn distinct_input_roles_same_account_fails() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::SenderHasOutputRole {
+            Restriction::DistinctInputRoles {
                 index: 0,
-                role_key: Default::default(),
+                left_role_key: 0,
+                right_role_key: 1,
             },
             &1,
+            &with_roles(vec![(0, 2), (1, 2)]),
             &Vec::new(),
-            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn sender_has_output_role_incorrect_index_fails() {
-        let roles0 = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let roles1 = BTreeMap::from_iter(vec![(Default::default(), 2)]);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles0.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles1.clone(),
-                metadata: BTreeMap::new(),
-            },
-        ];
+    fn distinct_input_roles_missing_role_fails() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::SenderHasOutputRole {
-                index: 1,
-                role_key: Default::default(),
+            Restriction::DistinctInputRoles {
+                index: 0,
+                left_role_key: 0,
+                right_role_key: 1,
             },
             &1,
+            &with_roles(vec![(0, 2)]),
             &Vec::new(),
-            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn sender_has_output_role_incorrect_role_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (0, 1), (1, 2)]);
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
+    fn distinct_output_roles_succeeds() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::SenderHasOutputRole { index: 0, role_key: 1 },
+            Restriction::DistinctOutputRoles {
+                index: 0,
+                left_role_key: 0,
+                right_role_key: 1,
+            },
             &1,
             &Vec::new(),
-            &outputs,
+            &with_roles(vec![(0, 1), (1, 2)]),
         );
-        assert!(!result);
+        assert!(result);
     }
 
     #[test]
-    fn sender_has_output_role_bad_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (0, 1), (1, 2)]);
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
+    fn distinct_output_roles_same_account_fails() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::SenderHasOutputRole { index: 1, role_key: 1 },
+            Restriction::DistinctOutputRoles {
+                index: 0,
+                left_role_key: 0,
+                right_role_key: 1,
+            },
             &1,
             &Vec::new(),
-            &outputs,
+            &with_roles(vec![(0, 1), (1, 1)]),
         );
         assert!(!result);
     }
 
     #[test]
-    fn output_has_role_succeeds() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
+    fn distinct_output_roles_bad_index_fails() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::OutputHasRole { index: 0, role_key: 1 },
+            Restriction::DistinctOutputRoles {
+                index: 1,
+                left_role_key: 0,
+                right_role_key: 1,
+            },
             &1,
             &Vec::new(),
-            &outputs,
+            &with_roles(vec![(0, 1), (1, 2)]),
         );
-        assert!(result);
+        assert!(!result);
+    }
+
+    fn literal_pattern(bytes: &[u8]) -> LiteralPattern {
+        bytes.to_vec().try_into().unwrap()
     }
 
     #[test]
-    fn output_has_role_incorrect_role_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::OutputHasRole { index: 0, role_key: 2 },
+    fn input_metadata_value_has_prefix_succeeds() {
+        let result = validate_restriction::<u64, u64, u32, u32, LiteralValue, u64>(
+            Restriction::InputMetadataValueHasPrefix {
+                index: 0,
+                metadata_key: 1,
+                prefix: literal_pattern(b"PO-"),
+            },
             &1,
+            &with_metadata(LiteralValue(b"PO-123".to_vec())),
             &Vec::new(),
-            &outputs,
         );
-        assert!(!result);
+        assert!(result);
     }
 
     #[test]
-    fn output_has_role_incorrect_index_fails() {
-        let roles0 = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
-        let roles1 = BTreeMap::from_iter(vec![(Default::default(), 1), (2, 1)]);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles0.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles1.clone(),
-                metadata: BTreeMap::new(),
+    fn input_metadata_value_has_prefix_mismatch_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, LiteralValue, u64>(
+            Restriction::InputMetadataValueHasPrefix {
+                index: 0,
+                metadata_key: 1,
+                prefix: literal_pattern(b"PO-"),
             },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::OutputHasRole { index: 1, role_key: 1 },
             &1,
+            &with_metadata(LiteralValue(b"SO-123".to_vec())),
             &Vec::new(),
-            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn output_has_role_bad_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
+    fn output_metadata_value_has_prefix_not_literal_fails() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::OutputHasRole { index: 1, role_key: 1 },
+            Restriction::OutputMetadataValueHasPrefix {
+                index: 0,
+                metadata_key: 1,
+                prefix: literal_pattern(b""),
+            },
             &1,
             &Vec::new(),
-            &outputs,
+            &with_metadata(1),
         );
         assert!(!result);
     }
 
     #[test]
-    fn match_input_output_role_same_role_keys_succeeds() {
-        let input_roles = BTreeMap::from_iter(vec![(0, 1)]);
-        let output_roles = BTreeMap::from_iter(vec![(0, 1)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: input_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: output_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputRole {
-                input_index: 0,
-                input_role_key: 0,
-                output_index: 0,
-                output_role_key: 0,
+    fn output_metadata_value_has_suffix_succeeds() {
+        let result = validate_restriction::<u64, u64, u32, u32, LiteralValue, u64>(
+            Restriction::OutputMetadataValueHasSuffix {
+                index: 0,
+                metadata_key: 1,
+                suffix: literal_pattern(b".pdf"),
             },
             &1,
-            &inputs,
-            &outputs,
+            &Vec::new(),
+            &with_metadata(LiteralValue(b"spec.pdf".to_vec())),
         );
         assert!(result);
     }
 
     #[test]
-    fn match_input_output_role_different_role_keys_succeeds() {
-        let input_roles = BTreeMap::from_iter(vec![(0, 2)]);
-        let output_roles = BTreeMap::from_iter(vec![(0, 1), (1, 2)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: input_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: output_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputRole {
-                input_index: 0,
-                input_role_key: 0,
-                output_index: 0,
-                output_role_key: 1,
+    fn input_metadata_value_has_suffix_mismatch_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, LiteralValue, u64>(
+            Restriction::InputMetadataValueHasSuffix {
+                index: 0,
+                metadata_key: 1,
+                suffix: literal_pattern(b".pdf"),
             },
             &1,
-            &inputs,
-            &outputs,
+            &with_metadata(LiteralValue(b"spec.pdf.txt".to_vec())),
+            &Vec::new(),
         );
-        assert!(result);
+        assert!(!result);
     }
 
     #[test]
-    fn match_input_output_role_only_one_has_key_fails() {
-        let input_roles = BTreeMap::from_iter(vec![(0, 1)]);
-        let output_roles = BTreeMap::from_iter(vec![(0, 1), (1, 2)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: input_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: output_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputRole {
-                input_index: 0,
-                input_role_key: 1,
-                output_index: 0,
-                output_role_key: 1,
+    fn input_metadata_value_matches_pattern_succeeds() {
+        let result = validate_restriction::<u64, u64, u32, u32, LiteralValue, u64>(
+            Restriction::InputMetadataValueMatchesPattern {
+                index: 0,
+                metadata_key: 1,
+                pattern: literal_pattern(b"[A-Z]{2}"),
             },
             &1,
-            &inputs,
-            &outputs,
+            &with_metadata(LiteralValue(b"GB".to_vec())),
+            &Vec::new(),
         );
-        assert!(!result);
+        assert!(result);
     }
 
     #[test]
-    fn match_input_output_role_neither_has_key_fails() {
-        let input_roles = BTreeMap::from_iter(vec![(0, 1)]);
-        let output_roles = BTreeMap::from_iter(vec![(0, 1)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: input_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: output_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputRole {
-                input_index: 0,
-                input_role_key: 1,
-                output_index: 0,
-                output_role_key: 1,
+    fn output_metadata_value_matches_pattern_mismatch_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, LiteralValue, u64>(
+            Restriction::OutputMetadataValueMatchesPattern {
+                index: 0,
+                metadata_key: 1,
+                pattern: literal_pattern(b"[A-Z]{2}"),
             },
             &1,
-            &inputs,
-            &outputs,
+            &Vec::new(),
+            &with_metadata(LiteralValue(b"GBR".to_vec())),
         );
         assert!(!result);
     }
 
     #[test]
-    fn match_input_output_role_wrong_index_fails() {
-        let input_roles0 = BTreeMap::from_iter(vec![(0, 1), (1, 2)]);
-        let input_roles1 = BTreeMap::from_iter(vec![(0, 1)]);
-        let output_roles = BTreeMap::from_iter(vec![(0, 1), (1, 2)]);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: input_roles0.clone(),
-                metadata: BTreeMap::new(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: input_roles1.clone(),
-                metadata: BTreeMap::new(),
+    fn output_metadata_value_matches_pattern_bad_index_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, LiteralValue, u64>(
+            Restriction::OutputMetadataValueMatchesPattern {
+                index: 1,
+                metadata_key: 1,
+                pattern: literal_pattern(b"[A-Z]{2}"),
             },
-        ];
-        let outputs = vec![ProcessIO {
+            &1,
+            &Vec::new(),
+            &with_metadata(LiteralValue(b"GB".to_vec())),
+        );
+        assert!(!result);
+    }
+
+    #[derive(Encode, Decode, Clone, PartialEq, TypeInfo, Debug, Eq)]
+    pub enum AccountValue {
+        Account(u64),
+        Other,
+    }
+
+    impl PartialEq<u64> for AccountValue {
+        fn eq(&self, _: &u64) -> bool {
+            false
+        }
+    }
+
+    impl MetadataValueAccess for AccountValue {
+        fn as_integer(&self) -> Option<i128> {
+            None
+        }
+        fn as_literal(&self) -> Option<&[u8]> {
+            None
+        }
+        fn as_hash(&self) -> Option<&[u8]> {
+            None
+        }
+    }
+
+    impl MetadataAccountAccess<u64> for AccountValue {
+        fn as_account(&self) -> Option<&u64> {
+            match self {
+                AccountValue::Account(account) => Some(account),
+                AccountValue::Other => None,
+            }
+        }
+    }
+
+    impl From<AccountValue> for u64 {
+        fn from(_: AccountValue) -> u64 {
+            0
+        }
+    }
+
+    fn with_account(roles: Vec<(u32, u64)>, value: AccountValue) -> Vec<ProcessIO<u64, u64, u32, u32, AccountValue>> {
+        vec![ProcessIO {
             id: 0u64,
-            roles: output_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputRole {
-                input_index: 1,
-                input_role_key: 1,
-                output_index: 0,
-                output_role_key: 1,
+            roles: BTreeMap::from_iter(roles),
+            metadata: BTreeMap::from_iter(vec![(1, value)]),
+        }]
+    }
+
+    #[test]
+    fn sender_is_input_metadata_account_succeeds() {
+        let result = validate_restriction::<u64, u64, u32, u32, AccountValue, u64>(
+            Restriction::SenderIsInputMetadataAccount {
+                index: 0,
+                metadata_key: 1,
             },
             &1,
-            &inputs,
-            &outputs,
+            &with_account(vec![], AccountValue::Account(1)),
+            &Vec::new(),
         );
-        assert!(!result);
+        assert!(result);
     }
 
     #[test]
-    fn match_input_output_role_bad_input_index_fails() {
-        let input_roles = BTreeMap::from_iter(vec![(0, 1)]);
-        let output_roles = BTreeMap::from_iter(vec![(0, 1)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: input_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: output_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputRole {
-                input_index: 1,
-                input_role_key: 0,
-                output_index: 0,
-                output_role_key: 0,
+    fn sender_is_input_metadata_account_other_account_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, AccountValue, u64>(
+            Restriction::SenderIsInputMetadataAccount {
+                index: 0,
+                metadata_key: 1,
             },
             &1,
-            &inputs,
-            &outputs,
+            &with_account(vec![], AccountValue::Account(2)),
+            &Vec::new(),
         );
         assert!(!result);
     }
 
     #[test]
-    fn match_input_output_role_bad_output_index_fails() {
-        let input_roles = BTreeMap::from_iter(vec![(0, 1)]);
-        let output_roles = BTreeMap::from_iter(vec![(0, 1)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: input_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: output_roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputRole {
-                input_index: 0,
-                input_role_key: 0,
-                output_index: 1,
-                output_role_key: 0,
+    fn sender_is_output_metadata_account_not_account_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, AccountValue, u64>(
+            Restriction::SenderIsOutputMetadataAccount {
+                index: 0,
+                metadata_key: 1,
             },
             &1,
-            &inputs,
-            &outputs,
+            &Vec::new(),
+            &with_account(vec![], AccountValue::Other),
         );
         assert!(!result);
     }
 
     #[test]
-    fn match_input_output_metadata_value_same_metadata_keys_succeeds() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let input_metadata = BTreeMap::from_iter(vec![(0, 0)]);
-        let output_metadata = BTreeMap::from_iter(vec![(0, 0)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: input_metadata.clone(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: output_metadata.clone(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputMetadataValue {
-                input_index: 0,
-                input_metadata_key: 0,
-                output_index: 0,
-                output_metadata_key: 0,
+    fn sender_is_output_metadata_account_missing_key_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, AccountValue, u64>(
+            Restriction::SenderIsOutputMetadataAccount {
+                index: 0,
+                metadata_key: 2,
             },
             &1,
-            &inputs,
-            &outputs,
+            &Vec::new(),
+            &with_account(vec![], AccountValue::Account(1)),
         );
-        assert!(result);
+        assert!(!result);
     }
 
     #[test]
-    fn match_input_output_metadata_value_different_metadata_keys_succeeds() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let input_metadata = BTreeMap::from_iter(vec![(0, 0)]);
-        let output_metadata = BTreeMap::from_iter(vec![(1, 0)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: input_metadata.clone(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: output_metadata.clone(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputMetadataValue {
+    fn match_input_role_output_metadata_account_succeeds() {
+        let result = validate_restriction::<u64, u64, u32, u32, AccountValue, u64>(
+            Restriction::MatchInputRoleOutputMetadataAccount {
                 input_index: 0,
-                input_metadata_key: 0,
+                input_role_key: 0,
                 output_index: 0,
                 output_metadata_key: 1,
             },
             &1,
-            &inputs,
-            &outputs,
+            &with_account(vec![(0, 2)], AccountValue::Other),
+            &with_account(vec![], AccountValue::Account(2)),
         );
         assert!(result);
     }
 
     #[test]
-    fn match_input_output_metadata_value_only_one_has_key_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let input_metadata = BTreeMap::from_iter(vec![(0, 0)]);
-        let output_metadata = BTreeMap::new();
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: input_metadata.clone(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: output_metadata.clone(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputMetadataValue {
+    fn match_input_role_output_metadata_account_mismatch_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, AccountValue, u64>(
+            Restriction::MatchInputRoleOutputMetadataAccount {
                 input_index: 0,
-                input_metadata_key: 0,
+                input_role_key: 0,
                 output_index: 0,
-                output_metadata_key: 0,
+                output_metadata_key: 1,
             },
             &1,
-            &inputs,
-            &outputs,
+            &with_account(vec![(0, 2)], AccountValue::Other),
+            &with_account(vec![], AccountValue::Account(3)),
         );
         assert!(!result);
     }
 
     #[test]
-    fn match_input_output_metadata_value_neither_has_key_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let input_metadata = BTreeMap::new();
-        let output_metadata = BTreeMap::new();
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: input_metadata.clone(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: output_metadata.clone(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputMetadataValue {
+    fn match_input_role_output_metadata_account_missing_role_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, AccountValue, u64>(
+            Restriction::MatchInputRoleOutputMetadataAccount {
                 input_index: 0,
-                input_metadata_key: 0,
+                input_role_key: 1,
                 output_index: 0,
-                output_metadata_key: 0,
+                output_metadata_key: 1,
             },
             &1,
-            &inputs,
-            &outputs,
+            &with_account(vec![(0, 2)], AccountValue::Other),
+            &with_account(vec![], AccountValue::Account(2)),
         );
         assert!(!result);
     }
 
     #[test]
-    fn match_input_output_metadata_value_wrong_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let input_metadata0 = BTreeMap::from_iter(vec![(0, 0)]);
-        let input_metadata1 = BTreeMap::new();
-        let output_metadata = BTreeMap::from_iter(vec![(0, 0)]);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: input_metadata0.clone(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: roles.clone(),
-                metadata: input_metadata1.clone(),
-            },
-        ];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: output_metadata.clone(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputMetadataValue {
-                input_index: 1,
-                input_metadata_key: 0,
+    fn match_input_metadata_account_output_role_succeeds() {
+        let result = validate_restriction::<u64, u64, u32, u32, AccountValue, u64>(
+            Restriction::MatchInputMetadataAccountOutputRole {
+                input_index: 0,
+                input_metadata_key: 1,
                 output_index: 0,
-                output_metadata_key: 0,
+                output_role_key: 0,
             },
             &1,
-            &inputs,
-            &outputs,
+            &with_account(vec![], AccountValue::Account(2)),
+            &with_account(vec![(0, 2)], AccountValue::Other),
         );
-        assert!(!result);
+        assert!(result);
     }
 
     #[test]
-    fn match_input_output_metadata_value_bad_input_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let input_metadata = BTreeMap::from_iter(vec![(0, 0)]);
-        let output_metadata = BTreeMap::from_iter(vec![(0, 0)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: input_metadata.clone(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: output_metadata.clone(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputMetadataValue {
-                input_index: 1,
-                input_metadata_key: 0,
+    fn match_input_metadata_account_output_role_not_account_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, AccountValue, u64>(
+            Restriction::MatchInputMetadataAccountOutputRole {
+                input_index: 0,
+                input_metadata_key: 1,
                 output_index: 0,
-                output_metadata_key: 0,
+                output_role_key: 0,
             },
             &1,
-            &inputs,
-            &outputs,
+            &with_account(vec![], AccountValue::Other),
+            &with_account(vec![(0, 2)], AccountValue::Other),
         );
         assert!(!result);
     }
 
     #[test]
-    fn match_input_output_metadata_value_bad_output_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1)]);
-        let input_metadata = BTreeMap::from_iter(vec![(0, 0)]);
-        let output_metadata = BTreeMap::from_iter(vec![(0, 0)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: input_metadata.clone(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: output_metadata.clone(),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputOutputMetadataValue {
+    fn match_input_metadata_account_output_role_bad_index_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, AccountValue, u64>(
+            Restriction::MatchInputMetadataAccountOutputRole {
                 input_index: 0,
-                input_metadata_key: 0,
+                input_metadata_key: 1,
                 output_index: 1,
-                output_metadata_key: 0,
+                output_role_key: 0,
             },
             &1,
-            &inputs,
-            &outputs,
+            &with_account(vec![], AccountValue::Account(2)),
+            &with_account(vec![(0, 2)], AccountValue::Other),
         );
         assert!(!result);
     }
 
-    #[test]
-    fn match_input_id_output_metadata_value_simple_succeeds() {
-        let inputs = vec![ProcessIO {
-            id: 42u64,
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 43u64,
+    #[derive(Encode, Decode, Clone, PartialEq, TypeInfo, Debug, Eq)]
+    pub enum HashValue {
+        File([u8; 32]),
+        Literal(Vec<u8>),
+    }
+
+    impl PartialEq<u64> for HashValue {
+        fn eq(&self, _: &u64) -> bool {
+            false
+        }
+    }
+
+    impl MetadataValueAccess for HashValue {
+        fn as_integer(&self) -> Option<i128> {
+            None
+        }
+        fn as_literal(&self) -> Option<&[u8]> {
+            match self {
+                HashValue::Literal(literal) => Some(literal),
+                HashValue::File(_) => None,
+            }
+        }
+        fn as_hash(&self) -> Option<&[u8]> {
+            match self {
+                HashValue::File(hash) => Some(hash),
+                HashValue::Literal(_) => None,
+            }
+        }
+    }
+
+    impl MetadataAccountAccess<u64> for HashValue {
+        fn as_account(&self) -> Option<&u64> {
+            None
+        }
+    }
+
+    impl From<HashValue> for u64 {
+        fn from(_: HashValue) -> u64 {
+            0
+        }
+    }
+
+    fn literal(value: &[u8]) -> HashValue {
+        HashValue::Literal(value.to_vec())
+    }
+
+    fn hash_of(values: &[HashValue]) -> HashValue {
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.encode()).collect();
+        HashValue::File(blake2_256(&bytes))
+    }
+
+    fn with_hash_metadata(metadata: Vec<(u32, HashValue)>) -> Vec<ProcessIO<u64, u64, u32, u32, HashValue>> {
+        vec![ProcessIO {
+            id: 0u64,
             roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(1, 42)]),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputIdOutputMetadataValue {
-                input_index: 0,
-                output_index: 0,
-                output_metadata_key: 1,
+            metadata: BTreeMap::from_iter(metadata),
+        }]
+    }
+
+    fn hash_sources(keys: Vec<u32>) -> HashSources<u32> {
+        keys.try_into().unwrap()
+    }
+
+    #[test]
+    fn output_file_is_hash_of_metadata_succeeds() {
+        let result = validate_restriction::<u64, u64, u32, u32, HashValue, u64>(
+            Restriction::OutputFileIsHashOfMetadata {
+                index: 0,
+                metadata_key: 0,
+                source_keys: hash_sources(vec![1, 2]),
             },
-            &1u64,
-            &inputs,
-            &outputs,
+            &1,
+            &Vec::new(),
+            &with_hash_metadata(vec![
+                (0, hash_of(&[literal(b"100"), literal(b"salt")])),
+                (1, literal(b"100")),
+                (2, literal(b"salt")),
+            ]),
         );
         assert!(result);
     }
 
     #[test]
-    fn match_input_id_output_metadata_value_incorrect_metadata_key_fails() {
-        let inputs = vec![ProcessIO {
-            id: 42u64,
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 43u64,
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(2, 42)]),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputIdOutputMetadataValue {
-                input_index: 0,
-                output_index: 0,
-                output_metadata_key: 1,
+    fn output_file_is_hash_of_metadata_wrong_order_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, HashValue, u64>(
+            Restriction::OutputFileIsHashOfMetadata {
+                index: 0,
+                metadata_key: 0,
+                source_keys: hash_sources(vec![2, 1]),
             },
-            &1u64,
-            &inputs,
-            &outputs,
+            &1,
+            &Vec::new(),
+            &with_hash_metadata(vec![
+                (0, hash_of(&[literal(b"100"), literal(b"salt")])),
+                (1, literal(b"100")),
+                (2, literal(b"salt")),
+            ]),
         );
         assert!(!result);
     }
 
     #[test]
-    fn match_input_id_output_metadata_value_incorrect_id_fails() {
-        let inputs = vec![ProcessIO {
-            id: 42u64,
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 43u64,
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(1, 40)]),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputIdOutputMetadataValue {
-                input_index: 0,
-                output_index: 0,
-                output_metadata_key: 1,
+    fn output_file_is_hash_of_metadata_wrong_value_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, HashValue, u64>(
+            Restriction::OutputFileIsHashOfMetadata {
+                index: 0,
+                metadata_key: 0,
+                source_keys: hash_sources(vec![1, 2]),
             },
-            &1u64,
-            &inputs,
-            &outputs,
+            &1,
+            &Vec::new(),
+            &with_hash_metadata(vec![
+                (0, hash_of(&[literal(b"100"), literal(b"salt")])),
+                (1, literal(b"101")),
+                (2, literal(b"salt")),
+            ]),
         );
         assert!(!result);
     }
 
     #[test]
-    fn match_input_id_output_metadata_value_incorrect_input_index_fails() {
-        let inputs = vec![
-            ProcessIO {
-                id: 42u64,
-                roles: BTreeMap::new(),
-                metadata: BTreeMap::new(),
+    fn output_file_is_hash_of_metadata_missing_source_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, HashValue, u64>(
+            Restriction::OutputFileIsHashOfMetadata {
+                index: 0,
+                metadata_key: 0,
+                source_keys: hash_sources(vec![1, 2]),
             },
-            ProcessIO {
-                id: 41u64,
-                roles: BTreeMap::new(),
-                metadata: BTreeMap::new(),
+            &1,
+            &Vec::new(),
+            &with_hash_metadata(vec![(0, hash_of(&[literal(b"100")])), (1, literal(b"100"))]),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn output_file_is_hash_of_metadata_not_file_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, HashValue, u64>(
+            Restriction::OutputFileIsHashOfMetadata {
+                index: 0,
+                metadata_key: 0,
+                source_keys: hash_sources(vec![1]),
             },
-        ];
-        let outputs = vec![ProcessIO {
-            id: 43u64,
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(1, 42)]),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputIdOutputMetadataValue {
-                input_index: 1,
+            &1,
+            &Vec::new(),
+            &with_hash_metadata(vec![(0, literal(b"100")), (1, literal(b"100"))]),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn output_file_is_hash_of_metadata_bad_index_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, HashValue, u64>(
+            Restriction::OutputFileIsHashOfMetadata {
+                index: 1,
+                metadata_key: 0,
+                source_keys: hash_sources(vec![1]),
+            },
+            &1,
+            &Vec::new(),
+            &with_hash_metadata(vec![(0, hash_of(&[literal(b"100")])), (1, literal(b"100"))]),
+        );
+        assert!(!result);
+    }
+
+    #[test]
+    fn input_file_is_hash_of_output_metadata_succeeds() {
+        let result = validate_restriction::<u64, u64, u32, u32, HashValue, u64>(
+            Restriction::InputFileIsHashOfOutputMetadata {
+                input_index: 0,
+                input_metadata_key: 0,
                 output_index: 0,
-                output_metadata_key: 1,
+                source_keys: hash_sources(vec![1, 2]),
             },
-            &1u64,
-            &inputs,
-            &outputs,
+            &1,
+            &with_hash_metadata(vec![(0, hash_of(&[literal(b"100"), literal(b"salt")]))]),
+            &with_hash_metadata(vec![(1, literal(b"100")), (2, literal(b"salt"))]),
         );
-        assert!(!result);
+        assert!(result);
     }
 
     #[test]
-    fn match_input_id_output_metadata_value_incorrect_output_index_fails() {
-        let inputs = vec![ProcessIO {
-            id: 42u64,
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![
-            ProcessIO {
-                id: 43u64,
-                roles: BTreeMap::new(),
-                metadata: BTreeMap::from_iter(vec![(1, 42)]),
-            },
-            ProcessIO {
-                id: 43u64,
-                roles: BTreeMap::new(),
-                metadata: BTreeMap::from_iter(vec![(1, 41)]),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputIdOutputMetadataValue {
+    fn input_file_is_hash_of_output_metadata_wrong_reveal_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, HashValue, u64>(
+            Restriction::InputFileIsHashOfOutputMetadata {
                 input_index: 0,
-                output_index: 1,
-                output_metadata_key: 1,
+                input_metadata_key: 0,
+                output_index: 0,
+                source_keys: hash_sources(vec![1, 2]),
             },
-            &1u64,
-            &inputs,
-            &outputs,
+            &1,
+            &with_hash_metadata(vec![(0, hash_of(&[literal(b"100"), literal(b"salt")]))]),
+            &with_hash_metadata(vec![(1, literal(b"90")), (2, literal(b"salt"))]),
         );
         assert!(!result);
     }
 
     #[test]
-    fn match_input_id_output_metadata_value_bad_input_index_fails() {
-        let inputs = vec![ProcessIO {
-            id: 42u64,
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 43u64,
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(1, 42)]),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputIdOutputMetadataValue {
-                input_index: 1,
+    fn input_file_is_hash_of_output_metadata_missing_commitment_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, HashValue, u64>(
+            Restriction::InputFileIsHashOfOutputMetadata {
+                input_index: 0,
+                input_metadata_key: 0,
                 output_index: 0,
-                output_metadata_key: 1,
+                source_keys: hash_sources(vec![1, 2]),
             },
-            &1u64,
-            &inputs,
-            &outputs,
+            &1,
+            &with_hash_metadata(vec![]),
+            &with_hash_metadata(vec![(1, literal(b"100")), (2, literal(b"salt"))]),
         );
         assert!(!result);
     }
 
     #[test]
-    fn match_input_id_output_metadata_value_bad_output_index_fails() {
-        let inputs = vec![ProcessIO {
-            id: 42u64,
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::new(),
-        }];
-        let outputs = vec![ProcessIO {
-            id: 43u64,
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(1, 42)]),
-        }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::MatchInputIdOutputMetadataValue {
+    fn input_file_is_hash_of_output_metadata_bad_index_fails() {
+        let result = validate_restriction::<u64, u64, u32, u32, HashValue, u64>(
+            Restriction::InputFileIsHashOfOutputMetadata {
                 input_index: 0,
+                input_metadata_key: 0,
                 output_index: 1,
-                output_metadata_key: 1,
+                source_keys: hash_sources(vec![1, 2]),
             },
-            &1u64,
-            &inputs,
-            &outputs,
+            &1,
+            &with_hash_metadata(vec![(0, hash_of(&[literal(b"100"), literal(b"salt")]))]),
+            &with_hash_metadata(vec![(1, literal(b"100")), (2, literal(b"salt"))]),
         );
         assert!(!result);
     }
 
     #[test]
-    fn output_has_metadata_value_succeeds() {
-        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
-        let outputs = vec![ProcessIO {
+    fn signed_by_role_holder_succeeds_with_signature() {
+        let inputs = vec![ProcessIO {
             id: 0u64,
-            roles: BTreeMap::new(),
-            metadata: metadata.clone(),
+            roles: BTreeMap::from_iter(vec![(0, 2)]),
+            metadata: BTreeMap::new(),
         }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::OutputHasMetadata {
-                index: 0,
-                metadata_key: 1,
-            },
+        let result = validate_restriction_with_signers::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SignedByRoleHolder { index: 0, role_key: 0 },
             &1,
+            &[3, 2],
+            &inputs,
             &Vec::new(),
-            &outputs,
         );
         assert!(result);
     }
 
     #[test]
-    fn output_has_metadata_value_incorrect_role_fails() {
-        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
-        let outputs = vec![ProcessIO {
+    fn signed_by_role_holder_succeeds_as_sender() {
+        let inputs = vec![ProcessIO {
             id: 0u64,
-            roles: BTreeMap::new(),
-            metadata: metadata.clone(),
+            roles: BTreeMap::from_iter(vec![(0, 1)]),
+            metadata: BTreeMap::new(),
         }];
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::OutputHasMetadata {
-                index: 0,
-                metadata_key: 2,
-            },
-            &1,
-            &Vec::new(),
-            &outputs,
-        );
-        assert!(!result);
-    }
-
-    #[test]
-    fn output_has_metadata_value_incorrect_index_fails() {
-        let metadata0 = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
-        let metadata1 = BTreeMap::from_iter(vec![(Default::default(), 1), (2, 1)]);
-        let outputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: BTreeMap::new(),
-                metadata: metadata0.clone(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: BTreeMap::new(),
-                metadata: metadata1.clone(),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::OutputHasMetadata {
-                index: 1,
-                metadata_key: 1,
-            },
+            Restriction::SignedByRoleHolder { index: 0, role_key: 0 },
             &1,
+            &inputs,
             &Vec::new(),
-            &outputs,
         );
-        assert!(!result);
+        assert!(result);
     }
 
     #[test]
-    fn output_has_metadata_value_bad_index_fails() {
-        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
-        let outputs = vec![ProcessIO {
+    fn signed_by_role_holder_without_signature_fails() {
+        let inputs = vec![ProcessIO {
             id: 0u64,
-            roles: BTreeMap::new(),
-            metadata: metadata.clone(),
+            roles: BTreeMap::from_iter(vec![(0, 2)]),
+            metadata: BTreeMap::new(),
         }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::OutputHasMetadata {
-                index: 1,
-                metadata_key: 1,
-            },
+        let result = validate_restriction_with_signers::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SignedByRoleHolder { index: 0, role_key: 0 },
             &1,
+            &[3],
+            &inputs,
             &Vec::new(),
-            &outputs,
         );
         assert!(!result);
     }
 
     #[test]
-    fn input_has_role_succeeds() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+    fn signed_by_role_holder_missing_role_fails() {
         let inputs = vec![ProcessIO {
             id: 0u64,
-            roles: roles.clone(),
+            roles: BTreeMap::from_iter(vec![(0, 2)]),
             metadata: BTreeMap::new(),
         }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::InputHasRole { index: 0, role_key: 1 },
+        let result = validate_restriction_with_signers::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SignedByRoleHolder { index: 0, role_key: 1 },
             &1,
+            &[2],
             &inputs,
             &Vec::new(),
         );
-        assert!(result);
+        assert!(!result);
     }
 
     #[test]
-    fn input_has_role_incorrect_role_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+    fn signed_by_role_holder_bad_index_fails() {
         let inputs = vec![ProcessIO {
             id: 0u64,
-            roles: roles.clone(),
+            roles: BTreeMap::from_iter(vec![(0, 2)]),
             metadata: BTreeMap::new(),
         }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::InputHasRole { index: 0, role_key: 2 },
+        let result = validate_restriction_with_signers::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SignedByRoleHolder { index: 1, role_key: 0 },
             &1,
+            &[2],
             &inputs,
             &Vec::new(),
         );
         assert!(!result);
     }
 
+    fn org_attribute(name: &[u8]) -> OrgAttribute {
+        name.to_vec().try_into().unwrap()
+    }
+
     #[test]
-    fn input_has_role_incorrect_index_fails() {
-        let roles0 = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
-        let roles1 = BTreeMap::from_iter(vec![(Default::default(), 1), (2, 1)]);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: roles0.clone(),
-                metadata: BTreeMap::new(),
+    fn sender_has_org_attribute_succeeds() {
+        let attributes = [org_attribute(b"kyc"), org_attribute(b"iso9001")];
+        let result = validate_restriction_with_context::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasOrgAttribute {
+                attribute: org_attribute(b"iso9001"),
             },
-            ProcessIO {
-                id: 0u64,
-                roles: roles1.clone(),
-                metadata: BTreeMap::new(),
+            &1,
+            &ExecutionContext {
+                sender_attributes: &attributes,
+                ..Default::default()
+            },
+            &Vec::new(),
+            &Vec::new(),
+        );
+        assert!(result);
+    }
+
+    #[test]
+    fn sender_has_org_attribute_missing_attribute_fails() {
+        let attributes = [org_attribute(b"kyc")];
+        let result = validate_restriction_with_context::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasOrgAttribute {
+                attribute: org_attribute(b"iso9001"),
             },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::InputHasRole { index: 1, role_key: 1 },
             &1,
-            &inputs,
+            &ExecutionContext {
+                sender_attributes: &attributes,
+                ..Default::default()
+            },
+            &Vec::new(),
             &Vec::new(),
         );
         assert!(!result);
     }
 
     #[test]
-    fn input_has_role_bad_index_fails() {
-        let roles = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
-        let inputs = vec![ProcessIO {
-            id: 0u64,
-            roles: roles.clone(),
-            metadata: BTreeMap::new(),
-        }];
+    fn sender_has_org_attribute_without_attributes_fails() {
         let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::InputHasRole { index: 1, role_key: 1 },
+            Restriction::SenderHasOrgAttribute {
+                attribute: org_attribute(b"iso9001"),
+            },
             &1,
-            &inputs,
+            &Vec::new(),
             &Vec::new(),
         );
         assert!(!result);
     }
 
+    fn delegate_context(org: &u64) -> ExecutionContext<'_, u64> {
+        ExecutionContext {
+            sender_org: Some(org),
+            ..Default::default()
+        }
+    }
+
     #[test]
-    fn input_has_metadata_value_succeeds() {
-        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+    fn sender_has_input_role_succeeds_for_delegate_of_role_holder() {
         let inputs = vec![ProcessIO {
             id: 0u64,
-            roles: BTreeMap::new(),
-            metadata: metadata.clone(),
+            roles: BTreeMap::from_iter(vec![(0, 2)]),
+            metadata: BTreeMap::new(),
         }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::InputHasMetadata {
-                index: 0,
-                metadata_key: 1,
-            },
+        let result = validate_restriction_with_context::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasInputRole { index: 0, role_key: 0 },
             &1,
+            &delegate_context(&2),
             &inputs,
             &Vec::new(),
         );
@@ -2137,19 +3864,16 @@ mod tests {
     }
 
     #[test]
-    fn input_has_metadata_value_incorrect_role_fails() {
-        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+    fn sender_has_input_role_fails_for_delegate_of_other_org() {
         let inputs = vec![ProcessIO {
             id: 0u64,
-            roles: BTreeMap::new(),
-            metadata: metadata.clone(),
+            roles: BTreeMap::from_iter(vec![(0, 2)]),
+            metadata: BTreeMap::new(),
         }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::InputHasMetadata {
-                index: 0,
-                metadata_key: 2,
-            },
+        let result = validate_restriction_with_context::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasInputRole { index: 0, role_key: 0 },
             &1,
+            &delegate_context(&3),
             &inputs,
             &Vec::new(),
         );
@@ -2157,50 +3881,39 @@ mod tests {
     }
 
     #[test]
-    fn input_has_metadata_value_incorrect_index_fails() {
-        let metadata0 = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
-        let metadata1 = BTreeMap::from_iter(vec![(Default::default(), 1), (2, 1)]);
-        let inputs = vec![
-            ProcessIO {
-                id: 0u64,
-                roles: BTreeMap::new(),
-                metadata: metadata0.clone(),
-            },
-            ProcessIO {
-                id: 0u64,
-                roles: BTreeMap::new(),
-                metadata: metadata1.clone(),
-            },
-        ];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::InputHasMetadata {
-                index: 1,
-                metadata_key: 1,
+    fn sender_has_any_output_role_succeeds_for_delegate_of_role_holder() {
+        let outputs = vec![ProcessIO {
+            id: 0u64,
+            roles: BTreeMap::from_iter(vec![(0, 3), (1, 2)]),
+            metadata: BTreeMap::new(),
+        }];
+        let result = validate_restriction_with_context::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SenderHasAnyOutputRole {
+                index: 0,
+                role_keys: vec![0, 1].try_into().unwrap(),
             },
             &1,
-            &inputs,
+            &delegate_context(&2),
             &Vec::new(),
+            &outputs,
         );
-        assert!(!result);
+        assert!(result);
     }
 
     #[test]
-    fn input_has_metadata_value_bad_index_fails() {
-        let metadata = BTreeMap::from_iter(vec![(Default::default(), 1), (1, 1)]);
+    fn signed_by_role_holder_succeeds_for_delegate_of_role_holder() {
         let inputs = vec![ProcessIO {
             id: 0u64,
-            roles: BTreeMap::new(),
-            metadata: metadata.clone(),
+            roles: BTreeMap::from_iter(vec![(0, 2)]),
+            metadata: BTreeMap::new(),
         }];
-        let result = validate_restriction::<u64, u64, u32, u32, u64, u64>(
-            Restriction::InputHasMetadata {
-                index: 1,
-                metadata_key: 1,
-            },
+        let result = validate_restriction_with_context::<u64, u64, u32, u32, u64, u64>(
+            Restriction::SignedByRoleHolder { index: 0, role_key: 0 },
             &1,
+            &delegate_context(&2),
             &inputs,
             &Vec::new(),
         );
-        assert!(!result);
+        assert!(result);
     }
 }
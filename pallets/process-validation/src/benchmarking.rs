@@ -23,15 +23,25 @@ type ProcessIO<T> = sqnc_pallet_traits::ProcessIO<
     <T as Config>::TokenMetadataValue,
 >;
 
+type ProcessPermissions<T> = BoundedVec<
+    ProcessPermission<<T as frame_system::Config>::AccountId, <T as Config>::OrgDataKey, <T as Config>::OrgDataValue>,
+    <T as Config>::MaxPermissionCount,
+>;
+
 type ProcessFullyQualifiedId<T> =
     sqnc_pallet_traits::ProcessFullyQualifiedId<<T as Config>::ProcessIdentifier, <T as Config>::ProcessVersion>;
 
+// runs are signed by at most this many accounts in the runtime. Validation is linear in the signer count so the
+// benchmarked weight extends to any bound
+const MAX_SIGNERS: u32 = 16;
+
+// programs are built from the most expensive restriction to validate, which hashes as many of the largest metadata
+// values as it can
 fn prepare_program<T: Config>(l: u32) -> BoundedVec<BooleanExpressionSymbol<T>, T::MaxProcessProgramLength> {
-    let metadata_restriction = Restriction::MatchInputOutputMetadataValue {
-        input_index: 0u32,
-        input_metadata_key: Default::default(),
-        output_index: 0u32,
-        output_metadata_key: Default::default(),
+    let metadata_restriction = Restriction::OutputFileIsHashOfMetadata {
+        index: 0u32,
+        metadata_key: Default::default(),
+        source_keys: BoundedVec::truncate_from(vec![Default::default(); MaxHashSources::get() as usize]),
     };
 
     let mut program = BoundedVec::<_, _>::with_bounded_capacity(T::MaxProcessProgramLength::get() as usize);
@@ -51,6 +61,88 @@ fn prepare_program<T: Config>(l: u32) -> BoundedVec<BooleanExpressionSymbol<T>,
     program
 }
 
+// programs of `l` restrictions that each check a different attribute of the sender, so that every restriction reads
+// an attribute before the program is executed
+fn prepare_attribute_program<T: Config>(
+    l: u32,
+) -> (
+    BoundedVec<BooleanExpressionSymbol<T>, T::MaxProcessProgramLength>,
+    Vec<OrgAttribute>,
+) {
+    let attributes = (0..l)
+        .map(|i| {
+            let mut attribute = vec![u8::MAX; MaxOrgAttributeLength::get() as usize];
+            attribute[..4].copy_from_slice(&i.to_le_bytes());
+            OrgAttribute::truncate_from(attribute)
+        })
+        .collect::<Vec<_>>();
+
+    let mut program = BoundedVec::<_, _>::with_bounded_capacity(T::MaxProcessProgramLength::get() as usize);
+    for (i, attribute) in attributes.iter().enumerate() {
+        program
+            .try_push(BooleanExpressionSymbol::<T>::Restriction(
+                Restriction::SenderHasOrgAttribute {
+                    attribute: attribute.clone(),
+                },
+            ))
+            .unwrap();
+        if i > 0 {
+            program
+                .try_push(BooleanExpressionSymbol::<T>::Op(BooleanOperator::And))
+                .unwrap();
+        }
+    }
+
+    (program, attributes)
+}
+
+fn prepare_io<T: Config>() -> Vec<ProcessIO<T>> {
+    vec![
+        ProcessIO::<T> {
+            id: Default::default(),
+            roles: BTreeMap::new(),
+            metadata: BTreeMap::from_iter(vec![(Default::default(), T::BenchmarkHelper::largest_metadata_value())]),
+        };
+        10
+    ]
+}
+
+// an allow-list of `p` entries that each read organisation data to check
+fn prepare_permissions<T: Config>(p: u32) -> ProcessPermissions<T> {
+    let (key, value) = T::BenchmarkHelper::largest_org_data();
+    BoundedVec::truncate_from(vec![ProcessPermission::OrgData { key, value }; p as usize])
+}
+
+// permits `account` to run a process by the most expensive allow-list to check, in which it is matched by the last entry
+fn set_permissions_fixture<T: Config>(process: &ProcessFullyQualifiedId<T>, account: &T::AccountId) {
+    let mut permissions = prepare_permissions::<T>(T::MaxPermissionCount::get().saturating_sub(1)).into_inner();
+    permissions.push(ProcessPermission::Account(account.clone()));
+    ProcessValidation::<T>::set_process_permissions(
+        RawOrigin::Root.into(),
+        process.id.clone(),
+        process.version.clone(),
+        BoundedVec::truncate_from(permissions),
+    )
+    .unwrap();
+}
+
+// allows delegates to run `process` and makes `delegate` act for `organisation`, so that validation resolves the
+// organisation of the sender and checks the allow-list for both
+fn set_delegation_fixture<T: Config>(
+    process: &ProcessFullyQualifiedId<T>,
+    delegate: &T::AccountId,
+    organisation: &T::AccountId,
+) {
+    ProcessValidation::<T>::set_process_delegation(
+        RawOrigin::Root.into(),
+        process.id.clone(),
+        process.version.clone(),
+        true,
+    )
+    .unwrap();
+    T::BenchmarkHelper::set_delegate(delegate, organisation);
+}
+
 fn create_process_fixture<T: Config>(
     program: &BoundedVec<BooleanExpressionSymbol<T>, T::MaxProcessProgramLength>,
 ) -> ProcessFullyQualifiedId<T> {
@@ -76,6 +168,22 @@ benchmarks! {
       assert_eq!(process.program, program);
     }
 
+    create_process_with_approvals {
+      let r in 1 .. (1 + T::MaxProcessProgramLength::get() / 2);
+      let a in 1 .. T::MaxApprovalCount::get();
+      let program = prepare_program::<T>(r);
+      let approvals = BoundedVec::<_, T::MaxApprovalCount>::truncate_from(vec![ProcessApproval {
+          input_index: 0u32,
+          role_key: T::RoleKey::default(),
+      }; a as usize]);
+    }: _(RawOrigin::Root, T::ProcessIdentifier::default(), program.clone(), approvals.clone())
+    verify {
+      let version = VersionModel::<T>::get(T::ProcessIdentifier::default());
+      let process = ProcessModel::<T>::get(T::ProcessIdentifier::default(), version.clone());
+      assert_eq!(process.program, program);
+      assert_eq!(ProcessApprovals::<T>::get(T::ProcessIdentifier::default(), version), approvals);
+    }
+
     disable_process {
         let program = prepare_program::<T>(1);
         let process = create_process_fixture::<T>(&program);
@@ -87,64 +195,112 @@ benchmarks! {
         assert_eq!(process.program, program);
     }
 
+    set_process_permissions {
+        let p in 1 .. T::MaxPermissionCount::get();
+        let program = prepare_program::<T>(1);
+        let process = create_process_fixture::<T>(&program);
+        let permissions = prepare_permissions::<T>(p);
+    }: _(RawOrigin::Root, process.id.clone(), process.version.clone(), permissions.clone())
+    verify {
+        assert_eq!(ProcessPermissionsModel::<T>::get(&process.id, &process.version), permissions);
+    }
+
+    set_process_delegation {
+        let program = prepare_program::<T>(1);
+        let process = create_process_fixture::<T>(&program);
+    }: _(RawOrigin::Root, process.id.clone(), process.version.clone(), true)
+    verify {
+        assert!(ProcessDelegationModel::<T>::get(&process.id, &process.version));
+    }
+
+    // the sender is a delegate that is not on the allow-list itself, so it is checked for both the sender and its
+    // organisation
     validate_process {
         let r in 1 .. (1 + T::MaxProcessProgramLength::get() / 2);
 
         let account_id: T::AccountId = account("owner", 0, 0);
+        let organisation: T::AccountId = account("organisation", 0, 0);
         let program = prepare_program::<T>(r);
         let process = create_process_fixture::<T>(&program);
+        set_delegation_fixture::<T>(&process, &account_id, &organisation);
+        set_permissions_fixture::<T>(&process, &organisation);
 
-        let inputs = vec![ProcessIO::<T> {
-            id: Default::default(),
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(Default::default(), Default::default())])
-        }; 10];
-        let outputs = vec![ProcessIO::<T> {
-            id: Default::default(),
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(Default::default(), Default::default())])
-        }; 10];
+        let inputs = prepare_io::<T>();
+        let outputs = prepare_io::<T>();
     }: {
         let _ = ProcessValidation::<T>::validate_process(&process, &account_id, &inputs, &outputs);
     }
 
+    // unlike the other validate benchmarks the process has no allow-list and does not allow delegates, so that the
+    // difference from the minimum covers checking one and resolving the organisation of the sender
     validate_process_min {
         let account_id: T::AccountId = account("owner", 0, 0);
         let program = prepare_program::<T>(1);
         let process = create_process_fixture::<T>(&program);
 
-        let inputs = vec![ProcessIO::<T> {
-            id: Default::default(),
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(Default::default(), Default::default())])
-        }; 10];
-        let outputs = vec![ProcessIO::<T> {
-            id: Default::default(),
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(Default::default(), Default::default())])
-        }; 10];
+        let inputs = prepare_io::<T>();
+        let outputs = prepare_io::<T>();
     }: {
         let _ = ProcessValidation::<T>::validate_process(&process, &account_id, &inputs, &outputs);
     }
 
     validate_process_max {
         let account_id: T::AccountId = account("owner", 0, 0);
+        let organisation: T::AccountId = account("organisation", 0, 0);
         let program = prepare_program::<T>(1 + T::MaxProcessProgramLength::get() / 2);
         let process = create_process_fixture::<T>(&program);
+        set_delegation_fixture::<T>(&process, &account_id, &organisation);
+        set_permissions_fixture::<T>(&process, &organisation);
 
-        let inputs = vec![ProcessIO::<T> {
-            id: Default::default(),
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(Default::default(), Default::default())])
-        }; 10];
-        let outputs = vec![ProcessIO::<T> {
-            id: Default::default(),
-            roles: BTreeMap::new(),
-            metadata: BTreeMap::from_iter(vec![(Default::default(), Default::default())])
-        }; 10];
+        let inputs = prepare_io::<T>();
+        let outputs = prepare_io::<T>();
+    }: {
+        let _ = ProcessValidation::<T>::validate_process(&process, &account_id, &inputs, &outputs);
+    }
+
+    // as `validate_process` but every restriction checks a different attribute that the organisation of the sender
+    // holds, which is the most storage a program of `r` restrictions can read
+    validate_process_attributes {
+        let r in 1 .. (1 + T::MaxProcessProgramLength::get() / 2);
+
+        let account_id: T::AccountId = account("owner", 0, 0);
+        let organisation: T::AccountId = account("organisation", 0, 0);
+        let (program, attributes) = prepare_attribute_program::<T>(r);
+        let process = create_process_fixture::<T>(&program);
+        set_delegation_fixture::<T>(&process, &account_id, &organisation);
+        set_permissions_fixture::<T>(&process, &organisation);
+        attributes.iter().for_each(|attribute| T::BenchmarkHelper::grant_attribute(&organisation, attribute));
+
+        let inputs = prepare_io::<T>();
+        let outputs = prepare_io::<T>();
     }: {
         let _ = ProcessValidation::<T>::validate_process(&process, &account_id, &inputs, &outputs);
     }
+
+    // each signer is a delegate whose organisation is resolved and counted as a signer
+    validate_process_with_signers {
+        let s in 0 .. MAX_SIGNERS;
+
+        let account_id: T::AccountId = account("owner", 0, 0);
+        let organisation: T::AccountId = account("organisation", 0, 0);
+        let program = prepare_program::<T>(1);
+        let process = create_process_fixture::<T>(&program);
+        set_delegation_fixture::<T>(&process, &account_id, &organisation);
+
+        let signers = (0..s).map(|i| account::<T::AccountId>("signer", i, 0)).collect::<Vec<_>>();
+        signers.iter().for_each(|signer| T::BenchmarkHelper::set_delegate(signer, &organisation));
+
+        let inputs = prepare_io::<T>();
+        let outputs = prepare_io::<T>();
+    }: {
+        let _ = ProcessValidation::<T>::validate_process_with_signers(
+            &process,
+            &account_id,
+            &signers,
+            &inputs,
+            &outputs,
+        );
+    }
 }
 
 impl_benchmark_test_suite!(ProcessValidation, crate::mock::new_test_ext(), crate::mock::Test,);
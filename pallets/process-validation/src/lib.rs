@@ -10,7 +10,10 @@ use sp_runtime::{
 };
 use sp_std::prelude::*;
 
-use sqnc_pallet_traits::{ProcessFullyQualifiedId, ProcessIO, ProcessValidator, ValidationResult};
+use sqnc_pallet_traits::{
+    OrganisationAttributeProvider, OrganisationDataProvider, OrganisationDelegateProvider, ProcessApproval,
+    ProcessFullyQualifiedId, ProcessIO, ProcessValidator, ValidationResult,
+};
 
 #[cfg(test)]
 mod tests;
@@ -18,12 +21,8 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-// import the restrictions module where all our restriction types are defined
-mod restrictions;
-pub use restrictions::*;
-
-mod binary_expression_tree;
-pub use binary_expression_tree::*;
+// restriction types and the program interpreter are shared with off-chain tooling
+pub use sqnc_process_interpreter::*;
 
 #[derive(Encode, Debug, Decode, Clone, MaxEncodedLen, TypeInfo, PartialEq)]
 pub enum ProcessStatus {
@@ -91,6 +90,43 @@ where
     }
 }
 
+/// An entry of the allow-list of accounts that may run a process
+#[derive(Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo, PartialEq)]
+pub enum ProcessPermission<AccountId, OrgDataKey, OrgDataValue> {
+    /// The account may run the process
+    Account(AccountId),
+    /// Organisations that have set the value at the key may run the process
+    OrgData { key: OrgDataKey, value: OrgDataValue },
+}
+
+/// Builds the values benchmarks need to set up their worst case
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<TokenMetadataValue, OrgDataKey, OrgDataValue, AccountId> {
+    /// The metadata value with the largest encoding, which is the most expensive to hash
+    fn largest_metadata_value() -> TokenMetadataValue;
+    /// An organisation data key and the value with the largest encoding for it, which is the most expensive to compare
+    fn largest_org_data() -> (OrgDataKey, OrgDataValue);
+    /// Makes `delegate` act for `organisation` within every scope, so that runs it sends or signs resolve to it
+    fn set_delegate(delegate: &AccountId, organisation: &AccountId);
+    /// Grants `attribute` to `account` without an expiry, so that `SenderHasOrgAttribute` restrictions read it
+    fn grant_attribute(account: &AccountId, attribute: &OrgAttribute);
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl<V: Default, K: Default, O: Default, A> BenchmarkHelper<V, K, O, A> for () {
+    fn largest_metadata_value() -> V {
+        V::default()
+    }
+
+    fn largest_org_data() -> (K, O) {
+        (K::default(), O::default())
+    }
+
+    fn set_delegate(_delegate: &A, _organisation: &A) {}
+
+    fn grant_attribute(_account: &A, _attribute: &OrgAttribute) {}
+}
+
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -114,6 +150,10 @@ pub mod pallet {
         #[pallet::constant]
         type MaxProcessProgramLength: Get<u32>;
 
+        // Maximum number of approvals a process can require before a run of it executes
+        #[pallet::constant]
+        type MaxApprovalCount: Get<u32>;
+
         // Origins for calling these extrinsics. For now these are expected to be root
         type CreateProcessOrigin: EnsureOrigin<Self::RuntimeOrigin>;
         type DisableProcessOrigin: EnsureOrigin<Self::RuntimeOrigin>;
@@ -125,17 +165,55 @@ pub mod pallet {
             + Default
             + MaxEncodedLen
             + MaybeSerializeDeserialize
-            + PartialEq<Self::TokenId>;
+            + PartialEq<Self::TokenId>
+            + MetadataValueAccess
+            + MetadataAccountAccess<Self::AccountId>;
         type TokenMetadataValueDiscriminator: Parameter
             + Default
             + From<Self::TokenMetadataValue>
             + MaxEncodedLen
             + MaybeSerializeDeserialize;
 
+        // Organisation data that accounts may be permitted to run a process by
+        type OrgDataKey: Parameter + MaxEncodedLen;
+        type OrgDataValue: Parameter + MaxEncodedLen;
+        type OrganisationData: OrganisationDataProvider<Self::AccountId, Self::OrgDataKey, Self::OrgDataValue>;
+        // Attributes granted to organisations that `SenderHasOrgAttribute` restrictions check
+        type OrganisationAttributes: OrganisationAttributeProvider<Self::AccountId, OrgAttribute>;
+
+        // Delegates of member organisations, which a process may treat as acting for their organisation. A delegate
+        // needs `RunProcessScope` to run a process for its organisation and `SignProcessScope` to sign a run for it
+        type DelegateScope: Parameter;
+        type OrganisationDelegates: OrganisationDelegateProvider<Self::AccountId, Self::DelegateScope>;
+        type RunProcessScope: Get<Self::DelegateScope>;
+        type SignProcessScope: Get<Self::DelegateScope>;
+
+        // Maximum number of entries in the allow-list of a process
+        #[pallet::constant]
+        type MaxPermissionCount: Get<u32>;
+
         // Origin for overriding weight calculation implementation
         type WeightInfo: WeightInfo;
+
+        // Builds the worst case metadata, organisation data and delegates for benchmarks
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<
+            Self::TokenMetadataValue,
+            Self::OrgDataKey,
+            Self::OrgDataValue,
+            Self::AccountId,
+        >;
     }
 
+    type ProcessPermissions<T> = BoundedVec<
+        ProcessPermission<
+            <T as frame_system::Config>::AccountId,
+            <T as Config>::OrgDataKey,
+            <T as Config>::OrgDataValue,
+        >,
+        <T as Config>::MaxPermissionCount,
+    >;
+
     /// The in-code storage version.
     const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
@@ -167,6 +245,44 @@ pub mod pallet {
     pub(super) type VersionModel<T: Config> =
         StorageMap<_, Blake2_128Concat, T::ProcessIdentifier, T::ProcessVersion, ValueQuery>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn process_approvals)]
+    pub(super) type ProcessApprovals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::ProcessIdentifier,
+        Blake2_128Concat,
+        T::ProcessVersion,
+        BoundedVec<ProcessApproval<T::RoleKey>, T::MaxApprovalCount>,
+        ValueQuery,
+    >;
+
+    // Allow-list of the accounts that may run a process. Anyone may run a process with an empty allow-list
+    #[pallet::storage]
+    #[pallet::getter(fn process_permissions)]
+    pub(super) type ProcessPermissionsModel<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::ProcessIdentifier,
+        Blake2_128Concat,
+        T::ProcessVersion,
+        ProcessPermissions<T>,
+        ValueQuery,
+    >;
+
+    // Whether delegates of an organisation act for it when running a process and signing runs of it
+    #[pallet::storage]
+    #[pallet::getter(fn process_delegation)]
+    pub(super) type ProcessDelegationModel<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::ProcessIdentifier,
+        Blake2_128Concat,
+        T::ProcessVersion,
+        bool,
+        ValueQuery,
+    >;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub processes: Vec<(
@@ -222,6 +338,16 @@ pub mod pallet {
         ),
         //id, version
         ProcessDisabled(T::ProcessIdentifier, T::ProcessVersion),
+        // id, version, approvals
+        ProcessApprovalsRequired(
+            T::ProcessIdentifier,
+            T::ProcessVersion,
+            BoundedVec<ProcessApproval<T::RoleKey>, T::MaxApprovalCount>,
+        ),
+        // id, version, permissions
+        ProcessPermissionsSet(T::ProcessIdentifier, T::ProcessVersion, ProcessPermissions<T>),
+        // id, version, allow_delegates
+        ProcessDelegationSet(T::ProcessIdentifier, T::ProcessVersion, bool),
     }
 
     #[pallet::error]
@@ -257,19 +383,7 @@ pub mod pallet {
             >,
         ) -> DispatchResultWithPostInfo {
             T::CreateProcessOrigin::ensure_origin(origin)?;
-
-            ensure!(Pallet::<T>::validate_program(&program), Error::<T>::InvalidProgram);
-
-            let version: T::ProcessVersion = Pallet::<T>::update_version(&id).unwrap();
-            Pallet::<T>::persist_process(&id, &version, &program)?;
-
-            Self::deposit_event(Event::ProcessCreated(
-                id,
-                version.clone(),
-                program,
-                version == One::one(),
-            ));
-
+            Pallet::<T>::create_process_internal(id, program)?;
             return Ok(().into());
         }
 
@@ -287,10 +401,102 @@ pub mod pallet {
             Self::deposit_event(Event::ProcessDisabled(id, version));
             return Ok(().into());
         }
+
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::create_process_with_approvals(program.len() as u32, approvals.len() as u32))]
+        pub fn create_process_with_approvals(
+            origin: OriginFor<T>,
+            id: T::ProcessIdentifier,
+            program: BoundedVec<
+                BooleanExpressionSymbol<
+                    T::RoleKey,
+                    T::TokenMetadataKey,
+                    T::TokenMetadataValue,
+                    T::TokenMetadataValueDiscriminator,
+                >,
+                T::MaxProcessProgramLength,
+            >,
+            approvals: BoundedVec<ProcessApproval<T::RoleKey>, T::MaxApprovalCount>,
+        ) -> DispatchResultWithPostInfo {
+            T::CreateProcessOrigin::ensure_origin(origin)?;
+            let version = Pallet::<T>::create_process_internal(id.clone(), program)?;
+
+            <ProcessApprovals<T>>::insert(&id, &version, approvals.clone());
+            Self::deposit_event(Event::ProcessApprovalsRequired(id, version, approvals));
+
+            return Ok(().into());
+        }
+
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_process_permissions(permissions.len() as u32))]
+        pub fn set_process_permissions(
+            origin: OriginFor<T>,
+            id: T::ProcessIdentifier,
+            version: T::ProcessVersion,
+            permissions: ProcessPermissions<T>,
+        ) -> DispatchResultWithPostInfo {
+            T::CreateProcessOrigin::ensure_origin(origin)?;
+            Pallet::<T>::validate_version_and_process(&id, &version)?;
+
+            <ProcessPermissionsModel<T>>::insert(&id, &version, permissions.clone());
+
+            Self::deposit_event(Event::ProcessPermissionsSet(id, version, permissions));
+            return Ok(().into());
+        }
+
+        /// Sets whether delegates of an organisation act for it when running a version of a process and signing runs
+        /// of it. A delegate sending a run that allows delegates is permitted if either it or its organisation is, and
+        /// is checked by `SenderHasOrgAttribute` restrictions against the attributes of its organisation alone. Its
+        /// own attributes are not used, so it cannot lend an attribute to an organisation that does not hold it
+        ///
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::set_process_delegation())]
+        pub fn set_process_delegation(
+            origin: OriginFor<T>,
+            id: T::ProcessIdentifier,
+            version: T::ProcessVersion,
+            allow_delegates: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::CreateProcessOrigin::ensure_origin(origin)?;
+            Pallet::<T>::validate_version_and_process(&id, &version)?;
+
+            <ProcessDelegationModel<T>>::insert(&id, &version, allow_delegates);
+
+            Self::deposit_event(Event::ProcessDelegationSet(id, version, allow_delegates));
+            return Ok(().into());
+        }
     }
 
     // helper methods
     impl<T: Config> Pallet<T> {
+        // validates and stores the next version of a process, returning its version
+        fn create_process_internal(
+            id: T::ProcessIdentifier,
+            program: BoundedVec<
+                BooleanExpressionSymbol<
+                    T::RoleKey,
+                    T::TokenMetadataKey,
+                    T::TokenMetadataValue,
+                    T::TokenMetadataValueDiscriminator,
+                >,
+                T::MaxProcessProgramLength,
+            >,
+        ) -> Result<T::ProcessVersion, Error<T>> {
+            ensure!(Pallet::<T>::validate_program(&program), Error::<T>::InvalidProgram);
+
+            let version: T::ProcessVersion = Pallet::<T>::update_version(&id).unwrap();
+            Pallet::<T>::persist_process(&id, &version, &program)?;
+
+            Self::deposit_event(Event::ProcessCreated(
+                id,
+                version.clone(),
+                program,
+                version == One::one(),
+            ));
+
+            Ok(version)
+        }
+
         pub fn validate_program(
             program: &BoundedVec<
                 BooleanExpressionSymbol<
@@ -302,14 +508,7 @@ pub mod pallet {
                 T::MaxProcessProgramLength,
             >,
         ) -> bool {
-            let executed_stack_height = program.iter().try_fold(0u8, |stack_height, symbol| match symbol {
-                BooleanExpressionSymbol::Op(_) => {
-                    let stack_height = stack_height.checked_sub(2);
-                    return stack_height.and_then(|stack_height| stack_height.checked_add(1));
-                }
-                BooleanExpressionSymbol::Restriction(_) => stack_height.checked_add(1),
-            });
-            executed_stack_height == Some(1u8)
+            sqnc_process_interpreter::validate_program(program)
         }
 
         pub fn get_next_version(id: &T::ProcessIdentifier) -> T::ProcessVersion {
@@ -372,6 +571,18 @@ pub mod pallet {
             };
         }
 
+        // whether `account` is on the allow-list of a process, if it has one
+        pub fn is_permitted(id: &T::ProcessIdentifier, version: &T::ProcessVersion, account: &T::AccountId) -> bool {
+            let permissions = <ProcessPermissionsModel<T>>::get(id, version);
+            permissions.is_empty()
+                || permissions.iter().any(|permission| match permission {
+                    ProcessPermission::Account(permitted) => permitted == account,
+                    ProcessPermission::OrgData { key, value } => {
+                        T::OrganisationData::org_data(account, key).as_ref() == Some(value)
+                    }
+                })
+        }
+
         pub fn validate_version_and_process(
             id: &T::ProcessIdentifier,
             version: &T::ProcessVersion,
@@ -402,50 +613,72 @@ impl<T: Config> ProcessValidator<T::TokenId, T::AccountId, T::RoleKey, T::TokenM
         sender: &T::AccountId,
         inputs: &Vec<ProcessIO<T::TokenId, T::AccountId, T::RoleKey, T::TokenMetadataKey, T::TokenMetadataValue>>,
         outputs: &Vec<ProcessIO<T::TokenId, T::AccountId, T::RoleKey, T::TokenMetadataKey, T::TokenMetadataValue>>,
+    ) -> ValidationResult<u32> {
+        Self::validate_process_with_signers(id, sender, &Vec::new(), inputs, outputs)
+    }
+
+    fn validate_process_with_signers(
+        id: &ProcessFullyQualifiedId<Self::ProcessIdentifier, Self::ProcessVersion>,
+        sender: &T::AccountId,
+        signers: &Vec<T::AccountId>,
+        inputs: &Vec<ProcessIO<T::TokenId, T::AccountId, T::RoleKey, T::TokenMetadataKey, T::TokenMetadataValue>>,
+        outputs: &Vec<ProcessIO<T::TokenId, T::AccountId, T::RoleKey, T::TokenMetadataKey, T::TokenMetadataValue>>,
     ) -> ValidationResult<u32> {
         let maybe_process = <ProcessModel<T>>::try_get(id.id.clone(), id.version.clone());
 
         match maybe_process {
             Ok(process) => {
-                if process.status == ProcessStatus::Disabled {
+                // a delegate acts for its organisation if the process allows it
+                let allow_delegates = <ProcessDelegationModel<T>>::get(&id.id, &id.version);
+                let sender_org = match allow_delegates {
+                    true => T::OrganisationDelegates::organisation_of(sender, &T::RunProcessScope::get()),
+                    false => None,
+                };
+
+                let is_permitted = Self::is_permitted(&id.id, &id.version, sender)
+                    || sender_org
+                        .as_ref()
+                        .is_some_and(|org| Self::is_permitted(&id.id, &id.version, org));
+                if process.status == ProcessStatus::Disabled || !is_permitted {
                     return ValidationResult {
                         success: false,
                         executed_len: 0,
                     };
                 }
 
-                let mut stack: Vec<bool> = Vec::with_capacity(T::MaxProcessProgramLength::get() as usize);
-                let mut executed_len: u32 = 0;
-                for symbol in process.program {
-                    executed_len = executed_len + 1;
-                    match symbol {
-                        BooleanExpressionSymbol::Op(op) => {
-                            if let (Some(b), Some(a)) = (stack.pop(), stack.pop()) {
-                                stack.push(op.eval(a, b));
-                            } else {
-                                return ValidationResult {
-                                    success: false,
-                                    executed_len: executed_len,
-                                };
-                            }
-                        }
-                        BooleanExpressionSymbol::Restriction(r) => {
-                            stack.push(validate_restriction::<
-                                T::TokenId,
-                                T::AccountId,
-                                T::RoleKey,
-                                T::TokenMetadataKey,
-                                T::TokenMetadataValue,
-                                T::TokenMetadataValueDiscriminator,
-                            >(r, &sender, inputs, outputs));
-                        }
-                    }
+                // a signature from a delegate also counts as a signature from its organisation
+                let mut signers = signers.clone();
+                if allow_delegates {
+                    let scope = T::SignProcessScope::get();
+                    let orgs = signers
+                        .iter()
+                        .filter_map(|signer| T::OrganisationDelegates::organisation_of(signer, &scope))
+                        .collect::<Vec<_>>();
+                    signers.extend(orgs);
                 }
 
-                ValidationResult {
-                    success: stack.pop().unwrap_or(false),
-                    executed_len: executed_len,
-                }
+                // only the attributes the program checks are looked up, and they are those of the organisation a
+                // delegate acts for rather than its own
+                let attribute_holder = sender_org.as_ref().unwrap_or(sender);
+                let sender_attributes = sqnc_process_interpreter::referenced_org_attributes(&process.program)
+                    .into_iter()
+                    .filter(|attribute| T::OrganisationAttributes::has_attribute(attribute_holder, attribute))
+                    .collect::<Vec<_>>();
+
+                let context = ExecutionContext {
+                    signers: &signers,
+                    sender_attributes: &sender_attributes,
+                    sender_org: sender_org.as_ref(),
+                };
+
+                sqnc_process_interpreter::execute_program_with_context::<
+                    T::TokenId,
+                    T::AccountId,
+                    T::RoleKey,
+                    T::TokenMetadataKey,
+                    T::TokenMetadataValue,
+                    T::TokenMetadataValueDiscriminator,
+                >(&process.program, sender, &context, inputs, outputs)
             }
             Err(_) => ValidationResult {
                 success: false,
@@ -453,4 +686,10 @@ impl<T: Config> ProcessValidator<T::TokenId, T::AccountId, T::RoleKey, T::TokenM
             },
         }
     }
+
+    fn required_approvals(
+        id: &ProcessFullyQualifiedId<Self::ProcessIdentifier, Self::ProcessVersion>,
+    ) -> Vec<ProcessApproval<T::RoleKey>> {
+        <ProcessApprovals<T>>::get(&id.id, &id.version).into_inner()
+    }
 }
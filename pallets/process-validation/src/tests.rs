@@ -9,10 +9,14 @@ use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 
 use sp_runtime::BuildStorage;
+use sqnc_pallet_traits::{OrganisationAttributeProvider, OrganisationDataProvider, OrganisationDelegateProvider};
 
 mod create_process;
+mod create_process_with_approvals;
 mod disable_process;
 mod genesis;
+mod set_process_delegation;
+mod set_process_permissions;
 mod validate_process;
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -31,6 +35,8 @@ frame_support::construct_runtime!(
 );
 parameter_types! {
     pub const SS58Prefix: u8 = 42;
+    pub const RunProcessScope: u8 = 0;
+    pub const SignProcessScope: u8 = 1;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
@@ -62,6 +68,41 @@ impl From<u128> for TokenMetadataValueDiscriminator {
     }
 }
 
+// account 1 has set the value 1 at key 0, account 2 the value 2
+pub struct MockOrganisationData {}
+impl OrganisationDataProvider<u64, u32, u32> for MockOrganisationData {
+    fn org_data(account: &u64, key: &u32) -> Option<u32> {
+        match (account, key) {
+            (1, 0) => Some(1),
+            (2, 0) => Some(2),
+            _ => None,
+        }
+    }
+}
+
+// accounts 1 and 3 hold the attribute iso9001 and account 2 the attribute kyc
+pub struct MockOrganisationAttributes {}
+impl OrganisationAttributeProvider<u64, pallet_process_validation::OrgAttribute> for MockOrganisationAttributes {
+    fn has_attribute(account: &u64, attribute: &pallet_process_validation::OrgAttribute) -> bool {
+        matches!(
+            (account, attribute.as_slice()),
+            (1, b"iso9001") | (2, b"kyc") | (3, b"iso9001")
+        )
+    }
+}
+
+// account 3 may run processes for organisation 2 and account 4 may sign runs for it
+pub struct MockOrganisationDelegates {}
+impl OrganisationDelegateProvider<u64, u8> for MockOrganisationDelegates {
+    fn organisation_of(delegate: &u64, scope: &u8) -> Option<u64> {
+        match (delegate, scope) {
+            (3, 0) => Some(2),
+            (4, 1) => Some(2),
+            _ => None,
+        }
+    }
+}
+
 impl pallet_process_validation::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type ProcessIdentifier = ProcessIdentifier;
@@ -77,6 +118,19 @@ impl pallet_process_validation::Config for Test {
     type TokenMetadataValueDiscriminator = TokenMetadataValueDiscriminator;
 
     type MaxProcessProgramLength = ConstU32<8>;
+    type MaxApprovalCount = ConstU32<2>;
+
+    type OrgDataKey = u32;
+    type OrgDataValue = u32;
+    type OrganisationData = MockOrganisationData;
+    type OrganisationAttributes = MockOrganisationAttributes;
+    type DelegateScope = u8;
+    type OrganisationDelegates = MockOrganisationDelegates;
+    type RunProcessScope = RunProcessScope;
+    type SignProcessScope = SignProcessScope;
+    type MaxPermissionCount = ConstU32<2>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
 }
 
 // This function basically just builds a genesis storage key/value store according to
@@ -1,8 +1,8 @@
 use super::*;
-use crate::binary_expression_tree::BooleanExpressionSymbol;
-use crate::binary_expression_tree::BooleanOperator;
 use crate::tests::ProcessIdentifier;
 use crate::tests::RuntimeEvent as TestEvent;
+use crate::BooleanExpressionSymbol;
+use crate::BooleanOperator;
 use crate::Error;
 use crate::Event::*;
 use crate::{Process, ProcessModel, ProcessStatus, Restriction::None, VersionModel};
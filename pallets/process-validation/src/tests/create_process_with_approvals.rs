@@ -0,0 +1,137 @@
+use super::*;
+use crate::tests::RuntimeEvent as TestEvent;
+use crate::BooleanExpressionSymbol;
+use crate::Error;
+use crate::Event::*;
+use crate::{Process, ProcessApprovals, ProcessModel, ProcessStatus, Restriction::None, VersionModel};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::{bounded_vec, DispatchError};
+use sqnc_pallet_traits::{ProcessApproval, ProcessFullyQualifiedId, ProcessValidator};
+
+// -- fixtures --
+const PROCESS_ID: ProcessIdentifier = ProcessIdentifier::A;
+
+#[test]
+fn returns_error_if_origin_validation_fails_and_no_data_added() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            ProcessValidation::create_process_with_approvals(
+                RuntimeOrigin::none(),
+                PROCESS_ID,
+                bounded_vec![BooleanExpressionSymbol::Restriction(None)],
+                bounded_vec![ProcessApproval {
+                    input_index: 0,
+                    role_key: 1
+                }],
+            ),
+            DispatchError::BadOrigin,
+        );
+        assert_eq!(<VersionModel<Test>>::get(PROCESS_ID), 0u32);
+        assert_eq!(<ProcessApprovals<Test>>::get(PROCESS_ID, 1u32).len(), 0);
+        assert_eq!(System::events().len(), 0);
+    });
+}
+
+#[test]
+fn returns_error_if_program_is_invalid() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            ProcessValidation::create_process_with_approvals(
+                RuntimeOrigin::root(),
+                PROCESS_ID,
+                bounded_vec![],
+                bounded_vec![ProcessApproval {
+                    input_index: 0,
+                    role_key: 1
+                }],
+            ),
+            Error::<Test>::InvalidProgram,
+        );
+    });
+}
+
+#[test]
+fn creates_process_and_stores_approvals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let approvals: BoundedVec<_, _> = bounded_vec![
+            ProcessApproval {
+                input_index: 0,
+                role_key: 1
+            },
+            ProcessApproval {
+                input_index: 1,
+                role_key: 2
+            },
+        ];
+        assert_ok!(ProcessValidation::create_process_with_approvals(
+            RuntimeOrigin::root(),
+            PROCESS_ID,
+            bounded_vec![BooleanExpressionSymbol::Restriction(None)],
+            approvals.clone(),
+        ));
+
+        assert_eq!(
+            <ProcessModel<Test>>::get(PROCESS_ID, 1u32),
+            Process {
+                status: ProcessStatus::Enabled,
+                program: bounded_vec![BooleanExpressionSymbol::Restriction(None)],
+            }
+        );
+        assert_eq!(<ProcessApprovals<Test>>::get(PROCESS_ID, 1u32), approvals);
+        assert_eq!(
+            System::events()[0].event,
+            TestEvent::ProcessValidation(ProcessCreated(
+                PROCESS_ID,
+                1u32,
+                bounded_vec![BooleanExpressionSymbol::Restriction(None)],
+                true,
+            ))
+        );
+        assert_eq!(
+            System::events()[1].event,
+            TestEvent::ProcessValidation(ProcessApprovalsRequired(PROCESS_ID, 1u32, approvals))
+        );
+    });
+}
+
+#[test]
+fn required_approvals_are_per_version() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(ProcessValidation::create_process_with_approvals(
+            RuntimeOrigin::root(),
+            PROCESS_ID,
+            bounded_vec![BooleanExpressionSymbol::Restriction(None)],
+            bounded_vec![ProcessApproval {
+                input_index: 0,
+                role_key: 1
+            }],
+        ));
+        assert_ok!(ProcessValidation::create_process(
+            RuntimeOrigin::root(),
+            PROCESS_ID,
+            bounded_vec![BooleanExpressionSymbol::Restriction(None)],
+        ));
+
+        assert_eq!(
+            ProcessValidation::required_approvals(&ProcessFullyQualifiedId {
+                id: PROCESS_ID,
+                version: 1u32
+            }),
+            vec![ProcessApproval {
+                input_index: 0,
+                role_key: 1
+            }]
+        );
+        assert_eq!(
+            ProcessValidation::required_approvals(&ProcessFullyQualifiedId {
+                id: PROCESS_ID,
+                version: 2u32
+            }),
+            vec![]
+        );
+    });
+}
@@ -2,7 +2,7 @@ use super::*;
 use crate::tests::{ProcessIdentifier, RuntimeEvent as TestEvent};
 use crate::Error;
 use crate::Event::*;
-use crate::{binary_expression_tree::*, Process, ProcessModel, ProcessStatus, Restriction::None, VersionModel};
+use crate::{BooleanExpressionSymbol, Process, ProcessModel, ProcessStatus, Restriction::None, VersionModel};
 use frame_support::{assert_noop, assert_ok};
 use sp_runtime::{bounded_vec, DispatchError};
 
@@ -1,7 +1,7 @@
 use super::*;
-use crate::binary_expression_tree::BooleanExpressionSymbol;
-use crate::binary_expression_tree::BooleanOperator;
 use crate::tests::ProcessIdentifier;
+use crate::BooleanExpressionSymbol;
+use crate::BooleanOperator;
 use crate::{Process, ProcessModel, ProcessStatus, Restriction, VersionModel};
 use sp_runtime::bounded_vec;
 
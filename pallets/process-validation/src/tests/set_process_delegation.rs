@@ -0,0 +1,198 @@
+use super::*;
+use crate::tests::RuntimeEvent as TestEvent;
+use crate::Error;
+use crate::Event::*;
+use crate::{BooleanExpressionSymbol, ProcessDelegationModel, ProcessPermission, Restriction};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::{bounded_vec, DispatchError};
+use sp_std::collections::btree_map::BTreeMap;
+use sqnc_pallet_traits::{ProcessFullyQualifiedId, ProcessIO, ProcessValidator, ValidationResult};
+
+// -- fixtures --
+const PROCESS_ID: ProcessIdentifier = ProcessIdentifier::A;
+const ORG: u64 = 2;
+const RUN_DELEGATE: u64 = 3;
+const SIGN_DELEGATE: u64 = 4;
+
+fn create_process(restriction: Restriction<u32, u32, u128, TokenMetadataValueDiscriminator>) {
+    assert_ok!(ProcessValidation::create_process(
+        RuntimeOrigin::root(),
+        PROCESS_ID,
+        bounded_vec![BooleanExpressionSymbol::Restriction(restriction)],
+    ));
+}
+
+fn allow_delegates() {
+    assert_ok!(ProcessValidation::set_process_delegation(
+        RuntimeOrigin::root(),
+        PROCESS_ID,
+        1u32,
+        true,
+    ));
+}
+
+// an input whose role 0 is held by the organisation
+fn org_input() -> Vec<ProcessIO<u128, u64, u32, u32, u128>> {
+    vec![ProcessIO {
+        id: 1u128,
+        roles: BTreeMap::from([(0, ORG)]),
+        metadata: BTreeMap::new(),
+    }]
+}
+
+fn validate_as(sender: u64, signers: Vec<u64>) -> ValidationResult<u32> {
+    ProcessValidation::validate_process_with_signers(
+        &ProcessFullyQualifiedId {
+            id: PROCESS_ID,
+            version: 1u32,
+        },
+        &sender,
+        &signers,
+        &org_input(),
+        &Vec::new(),
+    )
+}
+
+const FAILED: ValidationResult<u32> = ValidationResult {
+    success: false,
+    executed_len: 1u32,
+};
+
+const DENIED: ValidationResult<u32> = ValidationResult {
+    success: false,
+    executed_len: 0u32,
+};
+
+const SUCCEEDED: ValidationResult<u32> = ValidationResult {
+    success: true,
+    executed_len: 1u32,
+};
+
+#[test]
+fn returns_error_if_origin_validation_fails_and_no_data_added() {
+    new_test_ext().execute_with(|| {
+        create_process(Restriction::None);
+        assert_noop!(
+            ProcessValidation::set_process_delegation(RuntimeOrigin::signed(1), PROCESS_ID, 1u32, true),
+            DispatchError::BadOrigin,
+        );
+        assert!(!<ProcessDelegationModel<Test>>::get(PROCESS_ID, 1u32));
+    });
+}
+
+#[test]
+fn returns_error_if_process_does_not_exist() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            ProcessValidation::set_process_delegation(RuntimeOrigin::root(), PROCESS_ID, 1u32, true),
+            Error::<Test>::NonExistingProcess,
+        );
+    });
+}
+
+#[test]
+fn sets_delegation_and_emits_event() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_process(Restriction::None);
+
+        allow_delegates();
+
+        assert!(<ProcessDelegationModel<Test>>::get(PROCESS_ID, 1u32));
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            TestEvent::ProcessValidation(ProcessDelegationSet(PROCESS_ID, 1u32, true))
+        );
+    });
+}
+
+#[test]
+fn delegate_does_not_act_for_organisation_by_default() {
+    new_test_ext().execute_with(|| {
+        create_process(Restriction::SenderHasInputRole { index: 0, role_key: 0 });
+
+        assert_eq!(validate_as(RUN_DELEGATE, vec![]), FAILED);
+    });
+}
+
+#[test]
+fn delegate_has_role_of_organisation() {
+    new_test_ext().execute_with(|| {
+        create_process(Restriction::SenderHasInputRole { index: 0, role_key: 0 });
+        allow_delegates();
+
+        assert_eq!(validate_as(RUN_DELEGATE, vec![]), SUCCEEDED);
+        assert_eq!(validate_as(ORG, vec![]), SUCCEEDED);
+        assert_eq!(validate_as(1, vec![]), FAILED);
+    });
+}
+
+#[test]
+fn delegate_without_run_scope_does_not_have_role_of_organisation() {
+    new_test_ext().execute_with(|| {
+        create_process(Restriction::SenderHasInputRole { index: 0, role_key: 0 });
+        allow_delegates();
+
+        assert_eq!(validate_as(SIGN_DELEGATE, vec![]), FAILED);
+    });
+}
+
+#[test]
+fn delegate_signs_for_organisation() {
+    new_test_ext().execute_with(|| {
+        create_process(Restriction::SignedByRoleHolder { index: 0, role_key: 0 });
+        assert_eq!(validate_as(1, vec![SIGN_DELEGATE]), FAILED);
+
+        allow_delegates();
+
+        assert_eq!(validate_as(1, vec![SIGN_DELEGATE]), SUCCEEDED);
+        assert_eq!(validate_as(1, vec![RUN_DELEGATE]), FAILED);
+    });
+}
+
+#[test]
+fn delegate_is_permitted_if_organisation_is() {
+    new_test_ext().execute_with(|| {
+        create_process(Restriction::None);
+        let permissions: BoundedVec<_, _> = bounded_vec![ProcessPermission::Account(ORG)];
+        assert_ok!(ProcessValidation::set_process_permissions(
+            RuntimeOrigin::root(),
+            PROCESS_ID,
+            1u32,
+            permissions,
+        ));
+        assert_eq!(validate_as(RUN_DELEGATE, vec![]), DENIED);
+
+        allow_delegates();
+
+        assert_eq!(validate_as(RUN_DELEGATE, vec![]), SUCCEEDED);
+    });
+}
+
+#[test]
+fn delegate_has_attributes_of_organisation() {
+    new_test_ext().execute_with(|| {
+        create_process(Restriction::SenderHasOrgAttribute {
+            attribute: b"kyc".to_vec().try_into().unwrap(),
+        });
+        assert_eq!(validate_as(RUN_DELEGATE, vec![]), FAILED);
+
+        allow_delegates();
+
+        assert_eq!(validate_as(RUN_DELEGATE, vec![]), SUCCEEDED);
+    });
+}
+
+#[test]
+fn delegate_does_not_have_its_own_attributes_when_acting_for_organisation() {
+    new_test_ext().execute_with(|| {
+        create_process(Restriction::SenderHasOrgAttribute {
+            attribute: b"iso9001".to_vec().try_into().unwrap(),
+        });
+        assert_eq!(validate_as(RUN_DELEGATE, vec![]), SUCCEEDED);
+
+        allow_delegates();
+
+        assert_eq!(validate_as(RUN_DELEGATE, vec![]), FAILED);
+    });
+}
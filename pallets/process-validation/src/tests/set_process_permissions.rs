@@ -0,0 +1,190 @@
+use super::*;
+use crate::tests::RuntimeEvent as TestEvent;
+use crate::BooleanExpressionSymbol;
+use crate::Error;
+use crate::Event::*;
+use crate::{ProcessPermission, ProcessPermissionsModel, Restriction::None};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::{bounded_vec, DispatchError};
+use sqnc_pallet_traits::{ProcessFullyQualifiedId, ProcessValidator, ValidationResult};
+
+// -- fixtures --
+const PROCESS_ID: ProcessIdentifier = ProcessIdentifier::A;
+
+fn create_process() {
+    assert_ok!(ProcessValidation::create_process(
+        RuntimeOrigin::root(),
+        PROCESS_ID,
+        bounded_vec![BooleanExpressionSymbol::Restriction(None)],
+    ));
+}
+
+fn validate_as(sender: u64) -> ValidationResult<u32> {
+    ProcessValidation::validate_process(
+        &ProcessFullyQualifiedId {
+            id: PROCESS_ID,
+            version: 1u32,
+        },
+        &sender,
+        &Vec::new(),
+        &Vec::new(),
+    )
+}
+
+const DENIED: ValidationResult<u32> = ValidationResult {
+    success: false,
+    executed_len: 0u32,
+};
+
+const ALLOWED: ValidationResult<u32> = ValidationResult {
+    success: true,
+    executed_len: 1u32,
+};
+
+#[test]
+fn returns_error_if_origin_validation_fails_and_no_data_added() {
+    new_test_ext().execute_with(|| {
+        create_process();
+        assert_noop!(
+            ProcessValidation::set_process_permissions(
+                RuntimeOrigin::signed(1),
+                PROCESS_ID,
+                1u32,
+                bounded_vec![ProcessPermission::Account(1)],
+            ),
+            DispatchError::BadOrigin,
+        );
+        assert_eq!(<ProcessPermissionsModel<Test>>::get(PROCESS_ID, 1u32).len(), 0);
+    });
+}
+
+#[test]
+fn returns_error_if_process_does_not_exist() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            ProcessValidation::set_process_permissions(
+                RuntimeOrigin::root(),
+                PROCESS_ID,
+                1u32,
+                bounded_vec![ProcessPermission::Account(1)],
+            ),
+            Error::<Test>::NonExistingProcess,
+        );
+    });
+}
+
+#[test]
+fn sets_permissions_and_emits_event() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_process();
+        let permissions: BoundedVec<_, _> = bounded_vec![
+            ProcessPermission::Account(1),
+            ProcessPermission::OrgData { key: 0, value: 2 },
+        ];
+
+        assert_ok!(ProcessValidation::set_process_permissions(
+            RuntimeOrigin::root(),
+            PROCESS_ID,
+            1u32,
+            permissions.clone(),
+        ));
+
+        assert_eq!(<ProcessPermissionsModel<Test>>::get(PROCESS_ID, 1u32), permissions);
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            TestEvent::ProcessValidation(ProcessPermissionsSet(PROCESS_ID, 1u32, permissions))
+        );
+    });
+}
+
+#[test]
+fn allows_any_sender_without_permissions() {
+    new_test_ext().execute_with(|| {
+        create_process();
+        assert_eq!(validate_as(1), ALLOWED);
+        assert_eq!(validate_as(3), ALLOWED);
+    });
+}
+
+#[test]
+fn allows_only_permitted_accounts() {
+    new_test_ext().execute_with(|| {
+        create_process();
+        assert_ok!(ProcessValidation::set_process_permissions(
+            RuntimeOrigin::root(),
+            PROCESS_ID,
+            1u32,
+            bounded_vec![ProcessPermission::Account(3)],
+        ));
+
+        assert_eq!(validate_as(3), ALLOWED);
+        assert_eq!(validate_as(1), DENIED);
+    });
+}
+
+#[test]
+fn allows_only_organisations_with_permitted_org_data() {
+    new_test_ext().execute_with(|| {
+        create_process();
+        assert_ok!(ProcessValidation::set_process_permissions(
+            RuntimeOrigin::root(),
+            PROCESS_ID,
+            1u32,
+            bounded_vec![ProcessPermission::OrgData { key: 0, value: 2 }],
+        ));
+
+        assert_eq!(validate_as(2), ALLOWED);
+        assert_eq!(validate_as(1), DENIED);
+        assert_eq!(validate_as(3), DENIED);
+    });
+}
+
+#[test]
+fn clears_permissions_with_an_empty_list() {
+    new_test_ext().execute_with(|| {
+        create_process();
+        assert_ok!(ProcessValidation::set_process_permissions(
+            RuntimeOrigin::root(),
+            PROCESS_ID,
+            1u32,
+            bounded_vec![ProcessPermission::Account(3)],
+        ));
+        assert_ok!(ProcessValidation::set_process_permissions(
+            RuntimeOrigin::root(),
+            PROCESS_ID,
+            1u32,
+            bounded_vec![],
+        ));
+
+        assert_eq!(validate_as(1), ALLOWED);
+    });
+}
+
+#[test]
+fn permissions_are_per_version() {
+    new_test_ext().execute_with(|| {
+        create_process();
+        assert_ok!(ProcessValidation::set_process_permissions(
+            RuntimeOrigin::root(),
+            PROCESS_ID,
+            1u32,
+            bounded_vec![ProcessPermission::Account(3)],
+        ));
+        create_process();
+
+        assert_eq!(validate_as(1), DENIED);
+        assert_eq!(
+            ProcessValidation::validate_process(
+                &ProcessFullyQualifiedId {
+                    id: PROCESS_ID,
+                    version: 2u32,
+                },
+                &1u64,
+                &Vec::new(),
+                &Vec::new(),
+            ),
+            ALLOWED
+        );
+    });
+}
@@ -4,8 +4,8 @@ use sp_runtime::bounded_vec;
 use sp_std::collections::btree_map::BTreeMap;
 use sqnc_pallet_traits::{ProcessFullyQualifiedId, ProcessIO, ProcessValidator, ValidationResult};
 
-use crate::binary_expression_tree::{BooleanExpressionSymbol, BooleanOperator};
-use crate::restrictions::Restriction;
+use crate::Restriction;
+use crate::{BooleanExpressionSymbol, BooleanOperator};
 use crate::{Process, ProcessModel, ProcessStatus};
 
 #[test]
@@ -471,3 +471,87 @@ fn it_fails_with_handed_expressions_l() {
         );
     });
 }
+
+#[test]
+fn it_succeeds_when_role_holder_has_signed() {
+    new_test_ext().execute_with(|| {
+        ProcessModel::<Test>::insert(
+            ProcessIdentifier::A,
+            1u32,
+            Process {
+                status: ProcessStatus::Enabled,
+                program: bounded_vec![BooleanExpressionSymbol::Restriction(Restriction::SignedByRoleHolder {
+                    index: 0,
+                    role_key: 0
+                })],
+            },
+        );
+        let inputs = vec![ProcessIO {
+            id: 1u128,
+            roles: BTreeMap::from([(0, 2u64)]),
+            metadata: BTreeMap::new(),
+        }];
+        let id = ProcessFullyQualifiedId {
+            id: ProcessIdentifier::A,
+            version: 1u32,
+        };
+
+        let result = ProcessValidation::validate_process_with_signers(&id, &1u64, &vec![2u64], &inputs, &Vec::new());
+        assert_eq!(
+            result,
+            ValidationResult::<u32> {
+                success: true,
+                executed_len: 1u32
+            }
+        );
+
+        let result = ProcessValidation::validate_process(&id, &1u64, &inputs, &Vec::new());
+        assert_eq!(
+            result,
+            ValidationResult::<u32> {
+                success: false,
+                executed_len: 1u32
+            }
+        );
+    });
+}
+
+#[test]
+fn it_succeeds_when_sender_has_org_attribute() {
+    new_test_ext().execute_with(|| {
+        ProcessModel::<Test>::insert(
+            ProcessIdentifier::A,
+            1u32,
+            Process {
+                status: ProcessStatus::Enabled,
+                program: bounded_vec![BooleanExpressionSymbol::Restriction(
+                    Restriction::SenderHasOrgAttribute {
+                        attribute: b"iso9001".to_vec().try_into().unwrap()
+                    }
+                )],
+            },
+        );
+        let id = ProcessFullyQualifiedId {
+            id: ProcessIdentifier::A,
+            version: 1u32,
+        };
+
+        let result = ProcessValidation::validate_process(&id, &1u64, &Vec::new(), &Vec::new());
+        assert_eq!(
+            result,
+            ValidationResult::<u32> {
+                success: true,
+                executed_len: 1u32
+            }
+        );
+
+        let result = ProcessValidation::validate_process(&id, &2u64, &Vec::new(), &Vec::new());
+        assert_eq!(
+            result,
+            ValidationResult::<u32> {
+                success: false,
+                executed_len: 1u32
+            }
+        );
+    });
+}
@@ -11,9 +11,14 @@ use crate::Config;
 pub trait WeightInfo: ValidateProcessWeights<u32> {
     fn create_process(i: u32) -> Weight;
     fn disable_process() -> Weight;
+    fn create_process_with_approvals(i: u32, a: u32) -> Weight;
+    fn set_process_permissions(p: u32) -> Weight;
+    fn set_process_delegation() -> Weight;
     fn validate_process(p: u32) -> Weight;
     fn validate_process_min() -> Weight;
     fn validate_process_max() -> Weight;
+    fn validate_process_with_signers(s: u32) -> Weight;
+    fn validate_process_attributes(r: u32) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -23,6 +28,15 @@ impl WeightInfo for () {
     fn disable_process() -> Weight {
         Weight::from_parts(0, 0)
     }
+    fn create_process_with_approvals(_: u32, _: u32) -> Weight {
+        Weight::from_parts(0, 0)
+    }
+    fn set_process_permissions(_: u32) -> Weight {
+        Weight::from_parts(0, 0)
+    }
+    fn set_process_delegation() -> Weight {
+        Weight::from_parts(0, 0)
+    }
 
     fn validate_process(_p: u32) -> Weight {
         Weight::from_parts(0, 0)
@@ -35,4 +49,12 @@ impl WeightInfo for () {
     fn validate_process_max() -> Weight {
         Weight::from_parts(0, 0)
     }
+
+    fn validate_process_with_signers(_s: u32) -> Weight {
+        Weight::from_parts(0, 0)
+    }
+
+    fn validate_process_attributes(_r: u32) -> Weight {
+        Weight::from_parts(0, 0)
+    }
 }
@@ -26,6 +26,49 @@ pub struct ProcessFullyQualifiedId<
     pub version: ProcessVersion,
 }
 
+/// A role on an input of a process run whose holder must approve the run before it executes
+#[derive(Encode, Decode, Default, RuntimeDebug, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
+pub struct ProcessApproval<RoleKey> {
+    pub input_index: u32,
+    pub role_key: RoleKey,
+}
+
+/// Read access to the data organisations have set against their accounts
+pub trait OrganisationDataProvider<AccountId, OrgDataKey, OrgDataValue> {
+    /// The value set by `account` at `key`, if any
+    fn org_data(account: &AccountId, key: &OrgDataKey) -> Option<OrgDataValue>;
+}
+
+impl<A, K, V> OrganisationDataProvider<A, K, V> for () {
+    fn org_data(_account: &A, _key: &K) -> Option<V> {
+        None
+    }
+}
+
+/// Read access to the attributes governance has granted to organisations
+pub trait OrganisationAttributeProvider<AccountId, Attribute> {
+    /// Whether `account` currently holds `attribute`, i.e. it has been granted and has not expired
+    fn has_attribute(account: &AccountId, attribute: &Attribute) -> bool;
+}
+
+impl<A, N> OrganisationAttributeProvider<A, N> for () {
+    fn has_attribute(_account: &A, _attribute: &N) -> bool {
+        false
+    }
+}
+
+/// Resolves the member organisation that a delegate account acts for
+pub trait OrganisationDelegateProvider<AccountId, Scope> {
+    /// The organisation `delegate` may act for within `scope`, if any
+    fn organisation_of(delegate: &AccountId, scope: &Scope) -> Option<AccountId>;
+}
+
+impl<A, S> OrganisationDelegateProvider<A, S> for () {
+    fn organisation_of(_delegate: &A, _scope: &S) -> Option<A> {
+        None
+    }
+}
+
 #[derive(PartialEq, RuntimeDebug)]
 pub struct ValidationResult<W> {
     pub success: bool,
@@ -36,6 +79,8 @@ pub trait ValidateProcessWeights<WeightArg> {
     fn validate_process(p: WeightArg) -> Weight;
     fn validate_process_min() -> Weight;
     fn validate_process_max() -> Weight;
+    /// Weight of resolving the organisations of `s` signers on top of validating a process without any
+    fn validate_process_signers(s: u32) -> Weight;
 }
 
 impl ValidateProcessWeights<u32> for () {
@@ -48,6 +93,9 @@ impl ValidateProcessWeights<u32> for () {
     fn validate_process_max() -> Weight {
         Weight::from_parts(0, 0)
     }
+    fn validate_process_signers(_: u32) -> Weight {
+        Weight::from_parts(0, 0)
+    }
 }
 
 pub trait ProcessValidator<I, A, R, T, V>
@@ -69,6 +117,25 @@ where
         inputs: &Vec<ProcessIO<I, A, R, T, V>>,
         outputs: &Vec<ProcessIO<I, A, R, T, V>>,
     ) -> ValidationResult<Self::WeightArg>;
+
+    /// As `validate_process` where `signers` are the accounts whose detached signatures over the transition have
+    /// been verified. Validators that do not support signatures ignore them
+    fn validate_process_with_signers(
+        id: &ProcessFullyQualifiedId<Self::ProcessIdentifier, Self::ProcessVersion>,
+        sender: &A,
+        _signers: &Vec<A>,
+        inputs: &Vec<ProcessIO<I, A, R, T, V>>,
+        outputs: &Vec<ProcessIO<I, A, R, T, V>>,
+    ) -> ValidationResult<Self::WeightArg> {
+        Self::validate_process(id, sender, inputs, outputs)
+    }
+
+    /// Approvals required before a run of the process executes. Processes requiring none can be run directly
+    fn required_approvals(
+        _id: &ProcessFullyQualifiedId<Self::ProcessIdentifier, Self::ProcessVersion>,
+    ) -> Vec<ProcessApproval<R>> {
+        Vec::new()
+    }
 }
 
 impl<I, A, R, T, V> ProcessValidator<I, A, R, T, V> for ()
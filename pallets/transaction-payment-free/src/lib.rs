@@ -1,7 +1,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::dispatch::{DispatchInfo, PostDispatchInfo};
+use frame_support::traits::Get;
 use frame_support::weights::Weight;
+use frame_system::pallet_prelude::BlockNumberFor;
 use log;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
@@ -13,17 +15,25 @@ mod mock;
 mod tests;
 
 use sp_runtime::{
-    traits::{Dispatchable, TransactionExtension},
-    transaction_validity::{TransactionSource, TransactionValidityError, ValidTransaction},
+    traits::{AsSystemOriginSigner, Dispatchable, TransactionExtension, Zero},
+    transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidityError, ValidTransaction},
     FixedPointOperand,
 };
 
 mod payment;
+mod policy;
+mod quota;
 
 pub use pallet::*;
 pub use payment::*;
+pub use policy::*;
+pub use quota::*;
 
 type BalanceOf<T> = <<T as Config>::OnFreeTransaction as OnFreeTransaction<T>>::Balance;
+type CallClassOf<T> = <<T as Config>::TransactionPolicy as TransactionPolicy<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::RuntimeCall,
+>>::CallClass;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -38,17 +48,176 @@ pub mod pallet {
     /// The pallet's configuration trait.
     #[pallet::config]
     pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         type OnFreeTransaction: OnFreeTransaction<Self>;
+        /// Determines which calls count towards the quota of their signer
+        type QuotaMeter: QuotaMeter<Self::RuntimeCall>;
+        /// Origin that may set the quota
+        type UpdateQuotaOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Determines the class of each call and which accounts transact for free
+        type TransactionPolicy: TransactionPolicy<Self::AccountId, Self::RuntimeCall>;
+        /// Origin that may set the policy of a class of calls
+        type UpdatePolicyOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+    }
+
+    /// The quota of each account without its own quota. Such accounts are not limited if unset
+    #[pallet::storage]
+    #[pallet::getter(fn quota)]
+    pub type Quota<T: Config> = StorageValue<_, QuotaLimit<BlockNumberFor<T>>, OptionQuery>;
+
+    /// Quotas of accounts that replace `Quota` for them
+    #[pallet::storage]
+    #[pallet::getter(fn account_quota)]
+    pub type QuotaByAccount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, QuotaLimit<BlockNumberFor<T>>, OptionQuery>;
+
+    /// Usage of each account's quota in the latest period it transacted in
+    #[pallet::storage]
+    #[pallet::getter(fn quota_usage)]
+    pub type QuotaUsageByAccount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, QuotaUsage<BlockNumberFor<T>>, OptionQuery>;
+
+    /// The priority and non-member fee of each class of calls. Unset classes have their default priority and no fee
+    #[pallet::storage]
+    #[pallet::getter(fn call_class_policy)]
+    pub type CallClassPolicies<T: Config> =
+        StorageMap<_, Blake2_128Concat, CallClassOf<T>, CallClassPolicy<BalanceOf<T>>, OptionQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The quota was set, or removed if `None`
+        QuotaSet {
+            quota: Option<QuotaLimit<BlockNumberFor<T>>>,
+        },
+        /// The quota of an account was set, or removed if `None` so that `Quota` applies to it
+        AccountQuotaSet {
+            who: T::AccountId,
+            quota: Option<QuotaLimit<BlockNumberFor<T>>>,
+        },
+        /// The policy of a class of calls was set, or reset to its default if `None`
+        CallClassPolicySet {
+            class: CallClassOf<T>,
+            policy: Option<CallClassPolicy<BalanceOf<T>>>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The period of a quota must be at least one block
+        InvalidQuotaPeriod,
     }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
 
     #[pallet::call]
-    impl<T: Config> Pallet<T> {}
+    impl<T: Config> Pallet<T> {
+        /// Set the quota of metered transactions and minted tokens per account, or remove it with `None`
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_quota(origin: OriginFor<T>, quota: Option<QuotaLimit<BlockNumberFor<T>>>) -> DispatchResult {
+            T::UpdateQuotaOrigin::ensure_origin(origin)?;
+
+            match quota {
+                Some(limit) => {
+                    ensure!(!limit.period.is_zero(), Error::<T>::InvalidQuotaPeriod);
+                    <Quota<T>>::put(limit);
+                }
+                None => <Quota<T>>::kill(),
+            }
+
+            Self::deposit_event(Event::QuotaSet { quota });
+            Ok(())
+        }
+
+        /// Set the priority and non-member fee of a class of calls, or reset it to its default with `None`
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_call_class_policy(
+            origin: OriginFor<T>,
+            class: CallClassOf<T>,
+            policy: Option<CallClassPolicy<BalanceOf<T>>>,
+        ) -> DispatchResult {
+            T::UpdatePolicyOrigin::ensure_origin(origin)?;
+
+            <CallClassPolicies<T>>::set(&class, policy);
+
+            Self::deposit_event(Event::CallClassPolicySet { class, policy });
+            Ok(())
+        }
+
+        /// Set the quota of an account in place of the quota of every other account, or remove it with `None`
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_account_quota(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            quota: Option<QuotaLimit<BlockNumberFor<T>>>,
+        ) -> DispatchResult {
+            T::UpdateQuotaOrigin::ensure_origin(origin)?;
+
+            if let Some(limit) = quota {
+                ensure!(!limit.period.is_zero(), Error::<T>::InvalidQuotaPeriod);
+            }
+            <QuotaByAccount<T>>::set(&who, quota);
+
+            Self::deposit_event(Event::AccountQuotaSet { who, quota });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// The policy applied to `call`
+        pub fn policy_of(call: &T::RuntimeCall) -> CallClassPolicy<BalanceOf<T>> {
+            let class = T::TransactionPolicy::call_class(call);
+            <CallClassPolicies<T>>::get(&class).unwrap_or_else(|| CallClassPolicy {
+                priority: T::TransactionPolicy::default_priority(&class),
+                fee: Zero::zero(),
+            })
+        }
+
+        /// The usage of `who`'s quota after a metered call minting `tokens_minted` tokens in the current block.
+        /// Returns `None` if no quota applies to `who` and an `InvalidTransaction::Custom` error if the call is over
+        /// quota
+        pub fn use_quota(
+            who: &T::AccountId,
+            tokens_minted: u32,
+        ) -> Result<Option<QuotaUsage<BlockNumberFor<T>>>, TransactionValidityError> {
+            let Some(limit) = <QuotaByAccount<T>>::get(who).or_else(<Quota<T>>::get) else {
+                return Ok(None);
+            };
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let period_start = now - now % limit.period;
+            let usage = <QuotaUsageByAccount<T>>::get(who)
+                .filter(|usage| usage.period_start == period_start)
+                .unwrap_or(QuotaUsage {
+                    period_start,
+                    transactions: 0,
+                    tokens_minted: 0,
+                });
+
+            let transactions = usage.transactions.saturating_add(1);
+            if transactions > limit.max_transactions {
+                return Err(InvalidTransaction::Custom(TRANSACTION_QUOTA_EXCEEDED).into());
+            }
+            let tokens_minted = usage.tokens_minted.saturating_add(tokens_minted);
+            if tokens_minted > limit.max_tokens_minted {
+                return Err(InvalidTransaction::Custom(TOKEN_QUOTA_EXCEEDED).into());
+            }
+
+            Ok(Some(QuotaUsage {
+                period_start,
+                transactions,
+                tokens_minted,
+            }))
+        }
+    }
 }
 
-/// Require the transactor have balance. All transactions are free - they have no fee
+/// Require the transactor have balance. Transactions by members are free, non-members pay the fee of the call's
+/// class. Metered calls count towards the quota of their signer
 #[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct ChargeTransactionPayment<T: Config>(#[codec(compact)] BalanceOf<T>);
@@ -78,23 +247,29 @@ impl<T: Config> sp_std::fmt::Debug for ChargeTransactionPayment<T> {
 impl<T: Config> TransactionExtension<T::RuntimeCall> for ChargeTransactionPayment<T>
 where
     T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+    <T::RuntimeCall as Dispatchable>::RuntimeOrigin: AsSystemOriginSigner<T::AccountId> + Clone,
     BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
 {
     const IDENTIFIER: &'static str = "ChargeTransactionPayment";
     type Implicit = ();
-    type Val = ();
+    // the signer of a metered call and its quota usage once the call is included, and the fee owed by the signer
+    type Val = (Option<(T::AccountId, QuotaUsage<BlockNumberFor<T>>)>, BalanceOf<T>);
     type Pre = ();
 
-    fn weight(&self, _: &T::RuntimeCall) -> Weight {
-        log::debug!(target: "runtime", "ChargeTransactionPayment::weight - returning zero");
-        Weight::zero()
+    fn weight(&self, call: &T::RuntimeCall) -> Weight {
+        // the call's class policy and the signer's membership are always read
+        let policy = T::DbWeight::get().reads(2);
+        match T::QuotaMeter::tokens_minted(call) {
+            Some(_) => policy.saturating_add(T::DbWeight::get().reads_writes(3, 1)),
+            None => policy,
+        }
     }
 
     fn validate(
         &self,
         origin: <T::RuntimeCall as Dispatchable>::RuntimeOrigin,
-        _call: &T::RuntimeCall,
-        _info: &DispatchInfo,
+        call: &T::RuntimeCall,
+        info: &DispatchInfo,
         _len: usize,
         _: (),
         _implication: &impl Encode,
@@ -107,19 +282,56 @@ where
         ),
         TransactionValidityError,
     > {
-        log::debug!(target: "runtime", "ChargeTransactionPayment::validate - skipping fee check");
-        Ok((ValidTransaction::default(), (), origin))
+        let policy = Pallet::<T>::policy_of(call);
+        let signer = origin.as_system_origin_signer();
+
+        let fee = match signer {
+            Some(who) if !T::TransactionPolicy::is_member(who) => {
+                T::OnFreeTransaction::can_withdraw_fee(who, call, info, policy.fee, self.0)?;
+                policy.fee
+            }
+            _ => Zero::zero(),
+        };
+        log::debug!(target: "runtime", "ChargeTransactionPayment::validate - fee of {:?}", fee);
+
+        let quota = match (signer, T::QuotaMeter::tokens_minted(call)) {
+            (Some(who), Some(tokens_minted)) => {
+                Pallet::<T>::use_quota(who, tokens_minted)?.map(|usage| (who.clone(), usage))
+            }
+            _ => None,
+        };
+
+        // usage is only recorded once a transaction is included, so every metered transaction of an account that is
+        // pending in the pool would use the same slot of its quota. Providing the slot lets the pool hold only one of
+        // them at a time, rather than any number that are each within quota on their own
+        let provides = match &quota {
+            Some((who, usage)) => vec![(QUOTA_TAG, who, usage.period_start, usage.transactions).encode()],
+            None => Vec::new(),
+        };
+        let validity = ValidTransaction {
+            priority: policy.priority,
+            provides,
+            ..Default::default()
+        };
+        Ok((validity, (quota, fee), origin))
     }
 
     fn prepare(
         self,
-        _val: Self::Val,
-        _origin: &<T::RuntimeCall as Dispatchable>::RuntimeOrigin,
-        _call: &T::RuntimeCall,
-        _info: &DispatchInfo,
+        val: Self::Val,
+        origin: &<T::RuntimeCall as Dispatchable>::RuntimeOrigin,
+        call: &T::RuntimeCall,
+        info: &DispatchInfo,
         _len: usize,
     ) -> Result<Self::Pre, TransactionValidityError> {
-        log::debug!(target: "runtime", "ChargeTransactionPayment::prepare - no-op");
+        log::debug!(target: "runtime", "ChargeTransactionPayment::prepare - withdrawing fee and recording quota usage");
+        let (quota, fee) = val;
+        if let (false, Some(who)) = (fee.is_zero(), origin.as_system_origin_signer()) {
+            T::OnFreeTransaction::withdraw_fee(who, call, info, fee, self.0)?;
+        }
+        if let Some((who, usage)) = quota {
+            <QuotaUsageByAccount<T>>::insert(who, usage);
+        }
         Ok(())
     }
 
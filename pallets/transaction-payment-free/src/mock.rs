@@ -1,9 +1,10 @@
 use super::*;
 use crate as pallet_transaction_payment_free;
 use frame_support::dispatch::DispatchInfo;
-use frame_support::{derive_impl, parameter_types, weights::Weight};
+use frame_support::{derive_impl, pallet_prelude::TransactionPriority, parameter_types, weights::Weight};
 use frame_system as system;
 use pallet_balances::Call as BalancesCall;
+use parity_scale_codec::MaxEncodedLen;
 use sp_runtime::BuildStorage;
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -12,13 +13,17 @@ frame_support::construct_runtime!(
     pub enum Test {
         System: system::{Pallet, Call, Config<T>, Storage, Event<T>},
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-        TransactionPaymentFree: pallet_transaction_payment_free::{Pallet},
+        TransactionPaymentFree: pallet_transaction_payment_free::{Pallet, Call, Storage, Event<T>},
     }
 );
 
 pub const CALL: &<Test as frame_system::Config>::RuntimeCall =
     &RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest: 2, value: 69 });
 
+pub fn unmetered_call() -> <Test as frame_system::Config>::RuntimeCall {
+    RuntimeCall::System(system::Call::remark { remark: vec![] })
+}
+
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
 impl system::Config for Test {
     type Block = Block;
@@ -46,8 +51,54 @@ impl pallet_balances::Config for Test {
     type DoneSlashHandler = ();
 }
 
+// transfers are metered and mint as many tokens as their value
+pub struct MockQuotaMeter;
+impl QuotaMeter<RuntimeCall> for MockQuotaMeter {
+    fn tokens_minted(call: &RuntimeCall) -> Option<u32> {
+        match call {
+            RuntimeCall::Balances(BalancesCall::transfer_allow_death { value, .. }) => Some(*value as u32),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+pub enum CallClass {
+    Governance,
+    Bulk,
+}
+
+// account 1 is the only member. Remarks outrank transfers by default
+pub struct MockTransactionPolicy;
+impl TransactionPolicy<u64, RuntimeCall> for MockTransactionPolicy {
+    type CallClass = CallClass;
+
+    fn call_class(call: &RuntimeCall) -> CallClass {
+        match call {
+            RuntimeCall::System(..) => CallClass::Governance,
+            _ => CallClass::Bulk,
+        }
+    }
+
+    fn default_priority(class: &CallClass) -> TransactionPriority {
+        match class {
+            CallClass::Governance => 100,
+            CallClass::Bulk => 1,
+        }
+    }
+
+    fn is_member(who: &u64) -> bool {
+        *who == 1
+    }
+}
+
 impl Config for Test {
+    type RuntimeEvent = RuntimeEvent;
     type OnFreeTransaction = CurrencyAdapter<Balances, ()>;
+    type QuotaMeter = MockQuotaMeter;
+    type UpdateQuotaOrigin = system::EnsureRoot<u64>;
+    type TransactionPolicy = MockTransactionPolicy;
+    type UpdatePolicyOrigin = system::EnsureRoot<u64>;
 }
 
 /// create a transaction info struct from weight. Handy to avoid building the whole struct.
@@ -61,7 +112,7 @@ pub fn info_from_weight(w: Weight) -> DispatchInfo {
 pub fn new_test_ext() -> sp_io::TestExternalities {
     let mut t = system::GenesisConfig::<Test>::default().build_storage().unwrap();
     pallet_balances::GenesisConfig::<Test> {
-        balances: vec![(1, 10)],
+        balances: vec![(1, 10), (2, 10), (3, 1)],
     }
     .assimilate_storage(&mut t)
     .unwrap();
@@ -1,11 +1,11 @@
 use crate::Config;
 use frame_support::{
-    traits::{Currency, Imbalance, OnUnbalanced},
+    traits::{Currency, ExistenceRequirement, Imbalance, OnUnbalanced, WithdrawReasons},
     unsigned::TransactionValidityError,
 };
 use parity_scale_codec::FullCodec;
 use sp_runtime::{
-    traits::{AtLeast32BitUnsigned, DispatchInfoOf, MaybeSerializeDeserialize, Zero},
+    traits::{AtLeast32BitUnsigned, CheckedSub, DispatchInfoOf, MaybeSerializeDeserialize, Zero},
     transaction_validity::InvalidTransaction,
 };
 use sp_std::{fmt::Debug, marker::PhantomData};
@@ -30,6 +30,24 @@ pub trait OnFreeTransaction<T: Config> {
         fee: Self::Balance,
         tip: Self::Balance,
     ) -> Result<Self::LiquidityInfo, TransactionValidityError>;
+
+    /// Check `who` can pay `fee` without withdrawing it.
+    fn can_withdraw_fee(
+        who: &T::AccountId,
+        call: &T::RuntimeCall,
+        dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
+        fee: Self::Balance,
+        tip: Self::Balance,
+    ) -> Result<(), TransactionValidityError>;
+
+    /// Withdraw `fee` from `who`.
+    fn withdraw_fee(
+        who: &T::AccountId,
+        call: &T::RuntimeCall,
+        dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
+        fee: Self::Balance,
+        tip: Self::Balance,
+    ) -> Result<Self::LiquidityInfo, TransactionValidityError>;
 }
 
 /// Implements the transaction payment for a pallet implementing the `Currency`
@@ -66,4 +84,49 @@ where
         }
         Ok(None)
     }
+
+    /// Check the account can withdraw the fee while remaining alive.
+    fn can_withdraw_fee(
+        who: &T::AccountId,
+        _call: &T::RuntimeCall,
+        _info: &DispatchInfoOf<T::RuntimeCall>,
+        fee: Self::Balance,
+        _tip: Self::Balance,
+    ) -> Result<(), TransactionValidityError> {
+        if fee.is_zero() {
+            return Ok(());
+        }
+
+        let new_balance = C::free_balance(who)
+            .checked_sub(&fee)
+            .ok_or(InvalidTransaction::Payment)?;
+        if new_balance < C::minimum_balance() {
+            return Err(InvalidTransaction::Payment.into());
+        }
+        C::ensure_can_withdraw(who, fee, WithdrawReasons::TRANSACTION_PAYMENT, new_balance)
+            .map_err(|_| InvalidTransaction::Payment.into())
+    }
+
+    /// Withdraw the fee and pass it to the unbalance handler.
+    fn withdraw_fee(
+        who: &T::AccountId,
+        _call: &T::RuntimeCall,
+        _info: &DispatchInfoOf<T::RuntimeCall>,
+        fee: Self::Balance,
+        _tip: Self::Balance,
+    ) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+        if fee.is_zero() {
+            return Ok(None);
+        }
+
+        let imbalance = C::withdraw(
+            who,
+            fee,
+            WithdrawReasons::TRANSACTION_PAYMENT,
+            ExistenceRequirement::KeepAlive,
+        )
+        .map_err(|_| InvalidTransaction::Payment)?;
+        OU::on_unbalanced(imbalance);
+        Ok(None)
+    }
 }
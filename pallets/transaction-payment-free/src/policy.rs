@@ -0,0 +1,39 @@
+use frame_support::{pallet_prelude::TransactionPriority, Parameter};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// The pool priority of the calls of a class and the fee paid for them by non-members
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct CallClassPolicy<Balance> {
+    pub priority: TransactionPriority,
+    pub fee: Balance,
+}
+
+/// Groups calls into classes that share a priority and fee, and determines who transacts for free
+pub trait TransactionPolicy<AccountId, Call> {
+    /// The class of a call
+    type CallClass: Parameter + MaxEncodedLen;
+
+    fn call_class(call: &Call) -> Self::CallClass;
+
+    /// Priority of the calls of `class` until a policy is set for it
+    fn default_priority(class: &Self::CallClass) -> TransactionPriority;
+
+    /// Members never pay a fee
+    fn is_member(who: &AccountId) -> bool;
+}
+
+impl<AccountId, Call> TransactionPolicy<AccountId, Call> for () {
+    type CallClass = ();
+
+    fn call_class(_call: &Call) -> Self::CallClass {}
+
+    fn default_priority(_class: &Self::CallClass) -> TransactionPriority {
+        0
+    }
+
+    fn is_member(_who: &AccountId) -> bool {
+        true
+    }
+}
@@ -0,0 +1,39 @@
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// `InvalidTransaction::Custom` code of a transaction over the transaction quota of its signer
+pub const TRANSACTION_QUOTA_EXCEEDED: u8 = 1;
+/// `InvalidTransaction::Custom` code of a transaction over the token minting quota of its signer
+pub const TOKEN_QUOTA_EXCEEDED: u8 = 2;
+
+/// Prefix of the tag a metered transaction provides to the pool for the slot of its signer's quota it uses
+pub const QUOTA_TAG: &[u8] = b"sqnc/quota";
+
+/// The number of metered transactions and minted tokens allowed per account in each period of blocks
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct QuotaLimit<BlockNumber> {
+    pub period: BlockNumber,
+    pub max_transactions: u32,
+    pub max_tokens_minted: u32,
+}
+
+/// Usage of an account's quota in the period starting at `period_start`
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct QuotaUsage<BlockNumber> {
+    pub period_start: BlockNumber,
+    pub transactions: u32,
+    pub tokens_minted: u32,
+}
+
+/// Determines which calls count towards the quota of their signer
+pub trait QuotaMeter<Call> {
+    /// The number of tokens minted by `call` if it is metered, `None` otherwise
+    fn tokens_minted(call: &Call) -> Option<u32>;
+}
+
+impl<Call> QuotaMeter<Call> for () {
+    fn tokens_minted(_call: &Call) -> Option<u32> {
+        None
+    }
+}
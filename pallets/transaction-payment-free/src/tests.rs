@@ -1,7 +1,62 @@
 use super::*;
-use frame_support::weights::Weight;
-use mock::{info_from_weight, new_test_ext, Balances, Test, CALL};
-use sp_runtime::traits::TxBaseImplication;
+use frame_support::{assert_noop, assert_ok, pallet_prelude::TransactionPriority, weights::Weight};
+use mock::{
+    info_from_weight, new_test_ext, unmetered_call, Balances, CallClass, RuntimeEvent, RuntimeOrigin, System, Test,
+    TransactionPaymentFree, CALL,
+};
+use sp_runtime::{traits::TxBaseImplication, transaction_validity::InvalidTransaction, DispatchError};
+
+const QUOTA: QuotaLimit<u64> = QuotaLimit {
+    period: 10,
+    max_transactions: 2,
+    max_tokens_minted: 100,
+};
+
+// validates and prepares `call` as if it were included in the current block
+fn submit(user: u64, call: &<Test as frame_system::Config>::RuntimeCall) -> Result<(), TransactionValidityError> {
+    let implication = TxBaseImplication(call.clone());
+    let info = info_from_weight(Weight::zero());
+    let ext = ChargeTransactionPayment::<Test>::from(0);
+    let (_, val, origin) = ext.validate(
+        frame_system::RawOrigin::Signed(user).into(),
+        call,
+        &info,
+        0,
+        (),
+        &implication,
+        TransactionSource::External,
+    )?;
+    ext.prepare(val, &origin, call, &info, 0)
+}
+
+// the pool validity of `call` signed by `user`
+fn validity(user: u64, call: &<Test as frame_system::Config>::RuntimeCall) -> ValidTransaction {
+    let implication = TxBaseImplication(call.clone());
+    let ext = ChargeTransactionPayment::<Test>::from(0);
+    let (validity, _, _) = ext
+        .validate(
+            frame_system::RawOrigin::Signed(user).into(),
+            call,
+            &info_from_weight(Weight::zero()),
+            0,
+            (),
+            &implication,
+            TransactionSource::External,
+        )
+        .unwrap();
+    validity
+}
+
+// the pool priority of `call` signed by `user`
+fn priority(user: u64, call: &<Test as frame_system::Config>::RuntimeCall) -> TransactionPriority {
+    validity(user, call).priority
+}
+
+const FEE_POLICY: CallClassPolicy<u64> = CallClassPolicy { priority: 5, fee: 3 };
+
+fn quota_exceeded(code: u8) -> Result<(), TransactionValidityError> {
+    Err(InvalidTransaction::Custom(code).into())
+}
 
 #[test]
 fn no_fee_is_charged_for_transaction() {
@@ -25,3 +80,406 @@ fn no_fee_is_charged_for_transaction() {
         assert_eq!(Balances::free_balance(user), initial_balance);
     });
 }
+
+#[test]
+fn set_quota_requires_update_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            TransactionPaymentFree::set_quota(RuntimeOrigin::signed(1), Some(QUOTA)),
+            DispatchError::BadOrigin
+        );
+        assert_eq!(Pallet::<Test>::quota(), None);
+    });
+}
+
+#[test]
+fn set_quota_rejects_an_empty_period() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            TransactionPaymentFree::set_quota(RuntimeOrigin::root(), Some(QuotaLimit { period: 0, ..QUOTA })),
+            Error::<Test>::InvalidQuotaPeriod
+        );
+    });
+}
+
+#[test]
+fn set_quota_stores_and_removes_quota() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TransactionPaymentFree::set_quota(RuntimeOrigin::root(), Some(QUOTA)));
+        assert_eq!(Pallet::<Test>::quota(), Some(QUOTA));
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            RuntimeEvent::TransactionPaymentFree(Event::QuotaSet { quota: Some(QUOTA) })
+        );
+
+        assert_ok!(TransactionPaymentFree::set_quota(RuntimeOrigin::root(), None));
+        assert_eq!(Pallet::<Test>::quota(), None);
+    });
+}
+
+#[test]
+fn transactions_are_unlimited_without_quota() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..5 {
+            assert_ok!(submit(1, CALL));
+        }
+        assert_eq!(Pallet::<Test>::quota_usage(1), None);
+    });
+}
+
+#[test]
+fn transactions_over_quota_are_rejected() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TransactionPaymentFree::set_quota(
+            RuntimeOrigin::root(),
+            Some(QuotaLimit {
+                max_tokens_minted: 1000,
+                ..QUOTA
+            })
+        ));
+
+        assert_ok!(submit(1, CALL));
+        assert_ok!(submit(1, CALL));
+        assert_eq!(submit(1, CALL), quota_exceeded(TRANSACTION_QUOTA_EXCEEDED));
+        assert_eq!(
+            Pallet::<Test>::quota_usage(1),
+            Some(QuotaUsage {
+                period_start: 0,
+                transactions: 2,
+                tokens_minted: 138
+            })
+        );
+
+        // quotas are per account
+        assert_ok!(submit(2, CALL));
+    });
+}
+
+#[test]
+fn tokens_minted_over_quota_are_rejected() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TransactionPaymentFree::set_quota(RuntimeOrigin::root(), Some(QUOTA)));
+
+        assert_ok!(submit(1, CALL));
+        assert_eq!(submit(1, CALL), quota_exceeded(TOKEN_QUOTA_EXCEEDED));
+        assert_eq!(Pallet::<Test>::quota_usage(1).unwrap().transactions, 1);
+    });
+}
+
+#[test]
+fn quota_resets_each_period() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TransactionPaymentFree::set_quota(RuntimeOrigin::root(), Some(QUOTA)));
+        assert_ok!(submit(1, CALL));
+
+        System::set_block_number(9);
+        assert_eq!(submit(1, CALL), quota_exceeded(TOKEN_QUOTA_EXCEEDED));
+
+        System::set_block_number(10);
+        assert_ok!(submit(1, CALL));
+        assert_eq!(
+            Pallet::<Test>::quota_usage(1),
+            Some(QuotaUsage {
+                period_start: 10,
+                transactions: 1,
+                tokens_minted: 69
+            })
+        );
+    });
+}
+
+#[test]
+fn unmetered_calls_do_not_use_quota() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TransactionPaymentFree::set_quota(
+            RuntimeOrigin::root(),
+            Some(QuotaLimit {
+                max_transactions: 0,
+                ..QUOTA
+            })
+        ));
+
+        assert_ok!(submit(1, &unmetered_call()));
+        assert_eq!(submit(1, CALL), quota_exceeded(TRANSACTION_QUOTA_EXCEEDED));
+        assert_eq!(Pallet::<Test>::quota_usage(1), None);
+    });
+}
+
+#[test]
+fn set_account_quota_requires_update_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            TransactionPaymentFree::set_account_quota(RuntimeOrigin::signed(1), 1, Some(QUOTA)),
+            DispatchError::BadOrigin
+        );
+        assert_eq!(Pallet::<Test>::account_quota(1), None);
+    });
+}
+
+#[test]
+fn set_account_quota_rejects_an_empty_period() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            TransactionPaymentFree::set_account_quota(
+                RuntimeOrigin::root(),
+                1,
+                Some(QuotaLimit { period: 0, ..QUOTA })
+            ),
+            Error::<Test>::InvalidQuotaPeriod
+        );
+    });
+}
+
+#[test]
+fn set_account_quota_stores_and_removes_quota() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TransactionPaymentFree::set_account_quota(
+            RuntimeOrigin::root(),
+            1,
+            Some(QUOTA)
+        ));
+        assert_eq!(Pallet::<Test>::account_quota(1), Some(QUOTA));
+        assert_eq!(Pallet::<Test>::account_quota(2), None);
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            RuntimeEvent::TransactionPaymentFree(Event::AccountQuotaSet {
+                who: 1,
+                quota: Some(QUOTA)
+            })
+        );
+
+        assert_ok!(TransactionPaymentFree::set_account_quota(
+            RuntimeOrigin::root(),
+            1,
+            None
+        ));
+        assert_eq!(Pallet::<Test>::account_quota(1), None);
+    });
+}
+
+#[test]
+fn account_quota_replaces_quota() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TransactionPaymentFree::set_quota(
+            RuntimeOrigin::root(),
+            Some(QuotaLimit {
+                max_tokens_minted: 1000,
+                ..QUOTA
+            })
+        ));
+        assert_ok!(TransactionPaymentFree::set_account_quota(
+            RuntimeOrigin::root(),
+            1,
+            Some(QuotaLimit {
+                max_transactions: 3,
+                max_tokens_minted: 1000,
+                ..QUOTA
+            })
+        ));
+
+        for _ in 0..3 {
+            assert_ok!(submit(1, CALL));
+        }
+        assert_eq!(submit(1, CALL), quota_exceeded(TRANSACTION_QUOTA_EXCEEDED));
+
+        assert_ok!(submit(2, CALL));
+        assert_ok!(submit(2, CALL));
+        assert_eq!(submit(2, CALL), quota_exceeded(TRANSACTION_QUOTA_EXCEEDED));
+    });
+}
+
+#[test]
+fn account_quota_applies_without_quota() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TransactionPaymentFree::set_account_quota(
+            RuntimeOrigin::root(),
+            1,
+            Some(QUOTA)
+        ));
+
+        assert_ok!(submit(1, CALL));
+        assert_eq!(submit(1, CALL), quota_exceeded(TOKEN_QUOTA_EXCEEDED));
+
+        for _ in 0..5 {
+            assert_ok!(submit(2, CALL));
+        }
+        assert_eq!(Pallet::<Test>::quota_usage(2), None);
+    });
+}
+
+#[test]
+fn removed_account_quota_falls_back_to_quota() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TransactionPaymentFree::set_quota(
+            RuntimeOrigin::root(),
+            Some(QuotaLimit {
+                max_transactions: 1,
+                max_tokens_minted: 1000,
+                ..QUOTA
+            })
+        ));
+        assert_ok!(TransactionPaymentFree::set_account_quota(
+            RuntimeOrigin::root(),
+            1,
+            Some(QuotaLimit {
+                max_tokens_minted: 1000,
+                ..QUOTA
+            })
+        ));
+        assert_ok!(submit(1, CALL));
+        assert_ok!(submit(1, CALL));
+
+        assert_ok!(TransactionPaymentFree::set_account_quota(
+            RuntimeOrigin::root(),
+            1,
+            None
+        ));
+        assert_eq!(submit(1, CALL), quota_exceeded(TRANSACTION_QUOTA_EXCEEDED));
+    });
+}
+
+#[test]
+fn metered_calls_provide_the_quota_slot_they_use() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TransactionPaymentFree::set_quota(
+            RuntimeOrigin::root(),
+            Some(QuotaLimit {
+                max_tokens_minted: 1000,
+                ..QUOTA
+            })
+        ));
+
+        // pending transactions of an account use the same slot, so the pool keeps only one of them
+        let first = validity(1, CALL).provides;
+        assert_eq!(first, vec![(QUOTA_TAG, 1u64, 0u64, 1u32).encode()]);
+        assert_eq!(validity(1, CALL).provides, first);
+        assert_ne!(validity(2, CALL).provides, first);
+
+        // once one is included the next uses the following slot
+        assert_ok!(submit(1, CALL));
+        assert_eq!(validity(1, CALL).provides, vec![(QUOTA_TAG, 1u64, 0u64, 2u32).encode()]);
+
+        assert!(validity(1, &unmetered_call()).provides.is_empty());
+    });
+}
+
+#[test]
+fn metered_calls_provide_nothing_without_quota() {
+    new_test_ext().execute_with(|| {
+        assert!(validity(1, CALL).provides.is_empty());
+    });
+}
+
+#[test]
+fn set_call_class_policy_requires_update_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            TransactionPaymentFree::set_call_class_policy(RuntimeOrigin::signed(1), CallClass::Bulk, Some(FEE_POLICY)),
+            DispatchError::BadOrigin
+        );
+        assert_eq!(Pallet::<Test>::call_class_policy(CallClass::Bulk), None);
+    });
+}
+
+#[test]
+fn set_call_class_policy_stores_and_resets_policy() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(TransactionPaymentFree::set_call_class_policy(
+            RuntimeOrigin::root(),
+            CallClass::Bulk,
+            Some(FEE_POLICY)
+        ));
+        assert_eq!(Pallet::<Test>::call_class_policy(CallClass::Bulk), Some(FEE_POLICY));
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            RuntimeEvent::TransactionPaymentFree(Event::CallClassPolicySet {
+                class: CallClass::Bulk,
+                policy: Some(FEE_POLICY)
+            })
+        );
+
+        assert_ok!(TransactionPaymentFree::set_call_class_policy(
+            RuntimeOrigin::root(),
+            CallClass::Bulk,
+            None
+        ));
+        assert_eq!(Pallet::<Test>::call_class_policy(CallClass::Bulk), None);
+    });
+}
+
+#[test]
+fn calls_have_the_default_priority_of_their_class() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(priority(1, &unmetered_call()), 100);
+        assert_eq!(priority(1, CALL), 1);
+    });
+}
+
+#[test]
+fn calls_have_the_priority_of_their_class_policy() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(TransactionPaymentFree::set_call_class_policy(
+            RuntimeOrigin::root(),
+            CallClass::Bulk,
+            Some(FEE_POLICY)
+        ));
+        assert_eq!(priority(1, CALL), 5);
+        assert_eq!(priority(1, &unmetered_call()), 100);
+    });
+}
+
+#[test]
+fn members_pay_no_fee() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(TransactionPaymentFree::set_call_class_policy(
+            RuntimeOrigin::root(),
+            CallClass::Bulk,
+            Some(FEE_POLICY)
+        ));
+        assert_ok!(submit(1, CALL));
+        assert_eq!(Balances::free_balance(1), 10);
+    });
+}
+
+#[test]
+fn non_members_pay_the_fee_of_the_call_class() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(submit(2, CALL));
+        assert_eq!(Balances::free_balance(2), 10);
+
+        assert_ok!(TransactionPaymentFree::set_call_class_policy(
+            RuntimeOrigin::root(),
+            CallClass::Bulk,
+            Some(FEE_POLICY)
+        ));
+        assert_ok!(submit(2, CALL));
+        assert_eq!(Balances::free_balance(2), 7);
+
+        assert_ok!(submit(2, &unmetered_call()));
+        assert_eq!(Balances::free_balance(2), 7);
+    });
+}
+
+#[test]
+fn non_members_that_cannot_pay_the_fee_are_rejected() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(TransactionPaymentFree::set_call_class_policy(
+            RuntimeOrigin::root(),
+            CallClass::Bulk,
+            Some(FEE_POLICY)
+        ));
+        assert_eq!(submit(3, CALL), Err(InvalidTransaction::Payment.into()));
+        assert_eq!(Balances::free_balance(3), 1);
+    });
+}
@@ -10,7 +10,7 @@ use sp_runtime::traits::Bounded;
 use sp_std::convert::TryFrom;
 use sp_std::vec::Vec;
 
-use sqnc_pallet_traits::{ProcessFullyQualifiedId, ProcessValidator};
+use sqnc_pallet_traits::{ProcessApproval, ProcessFullyQualifiedId, ProcessValidator};
 
 use crate::output::Output;
 #[allow(unused)]
@@ -118,6 +118,80 @@ fn mk_outputs<T: Config>(
     Ok(outputs)
 }
 
+// mints a token with `owner` as its `role_key` role
+fn add_nft_owned_by<T: Config>(owner: &T::AccountId, role_key: T::RoleKey) -> Result<(), &'static str>
+where
+    ProcessIdentifier<T>: From<BoundedVec<u8, ConstU32<32>>>,
+    ProcessVersion<T>: From<u32>,
+{
+    let mut roles = BoundedBTreeMap::<_, _, _>::new();
+    roles.try_insert(role_key, owner.clone()).unwrap();
+    let outputs = vec![Output {
+        roles,
+        metadata: BoundedBTreeMap::<_, _, _>::new(),
+    }];
+
+    let default_process = BoundedVec::<u8, ConstU32<32>>::try_from("default".as_bytes().to_vec()).unwrap();
+    UtxoNFT::<T>::run_process(
+        RawOrigin::Signed(owner.clone()).into(),
+        ProcessFullyQualifiedId {
+            id: default_process.into(),
+            version: 1u32.into(),
+        },
+        BoundedVec::<_, _>::with_max_capacity(),
+        outputs.try_into().unwrap(),
+    )?;
+    Ok(())
+}
+
+// mints `l` tokens owned by "owner" and locks each in favour of "holder"
+fn add_locks<T: Config>(l: u32) -> Result<(), &'static str>
+where
+    ProcessIdentifier<T>: From<BoundedVec<u8, ConstU32<32>>>,
+    ProcessVersion<T>: From<u32>,
+{
+    let owner: T::AccountId = account("owner", 0, SEED);
+    let holder: T::AccountId = account("holder", 0, SEED);
+    let process = T::BenchmarkHelper::process_requiring_approval(Vec::new());
+    for n in 1..=l {
+        add_nft_owned_by::<T>(&owner, T::RoleKey::default())?;
+        UtxoNFT::<T>::lock_token(
+            RawOrigin::Signed(owner.clone()).into(),
+            process.clone(),
+            nth_token_id::<T>(n)?,
+            holder.clone(),
+        )?;
+    }
+    Ok(())
+}
+
+// approval of a run by the holder of the default role on an input
+fn approval<T: Config>(input_index: u32) -> ProcessApproval<T::RoleKey> {
+    ProcessApproval {
+        input_index,
+        role_key: T::RoleKey::default(),
+    }
+}
+
+// proposes a run consuming `i` tokens and creating `o` as an account other than their owner, so that the run must be
+// approved by the owner
+fn add_proposal<T: Config>(i: u32, o: u32) -> Result<(), &'static str>
+where
+    ProcessIdentifier<T>: From<BoundedVec<u8, ConstU32<32>>>,
+    ProcessVersion<T>: From<u32>,
+{
+    add_nfts::<T>(i)?;
+    let process = T::BenchmarkHelper::process_requiring_approval(vec![approval::<T>(0)]);
+    let proposer: T::AccountId = account("proposer", 0, SEED);
+    UtxoNFT::<T>::propose_process(
+        RawOrigin::Signed(proposer).into(),
+        process,
+        mk_inputs::<T>(i)?,
+        mk_outputs::<T>(o)?,
+    )?;
+    Ok(())
+}
+
 fn nth_token_id<T: Config>(iteration: u32) -> Result<T::TokenId, &'static str> {
     let token_id = (0..iteration).fold(T::TokenId::default(), |acc, _| acc + One::one());
     Ok(token_id)
@@ -173,6 +247,128 @@ benchmarks! {
     verify {
         assert_eq!(TokensById::<T>::get(token_id).is_none(), true);
     }
+
+    run_process_with_signatures {
+        let i in 1..10;
+        let o in 1..10;
+        let s in 0..T::MaxSignatureCount::get();
+
+        let default_process = BoundedVec::<u8, ConstU32<32>>::try_from("default".as_bytes().to_vec()).unwrap();
+        let process = ProcessFullyQualifiedId {
+            id: default_process.into(),
+            version: 1u32.into()
+        };
+
+        add_nfts::<T>(i)?;
+        let inputs = mk_inputs::<T>(i)?;
+        let outputs = mk_outputs::<T>(o)?;
+        let caller: T::AccountId = account("owner", 0, SEED);
+        // each signature is by a new account, which has not signed any run
+        let payload = UtxoNFT::<T>::run_process_payload(&caller, &process, &inputs, &outputs, 0);
+        let signatures: BoundedVec<_, T::MaxSignatureCount> = (0..s)
+            .map(|_| T::BenchmarkHelper::sign(&payload))
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+    }: _(RawOrigin::Signed(caller), process, inputs, outputs, signatures)
+    verify {
+        assert_eq!(LastToken::<T>::get(), nth_token_id::<T>(i + o)?);
+    }
+
+    propose_process {
+        let i in 1..T::MaxInputCount::get();
+        let o in 1..T::MaxOutputCount::get();
+
+        add_nfts::<T>(i)?;
+        let process = T::BenchmarkHelper::process_requiring_approval(vec![approval::<T>(0)]);
+        let inputs = mk_inputs::<T>(i)?;
+        let outputs = mk_outputs::<T>(o)?;
+        let proposer: T::AccountId = account("proposer", 0, SEED);
+    }: _(RawOrigin::Signed(proposer), process, inputs, outputs)
+    verify {
+        assert_eq!(LastProposal::<T>::get(), 1);
+        assert_eq!(LockedTokens::<T>::get(nth_token_id::<T>(i)?), Some(1));
+    }
+
+    approve_process {
+        // the first of two approvals, which only records the approval
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let other_owner: T::AccountId = account("owner", 1, SEED);
+        add_nfts::<T>(1)?;
+        add_nft_owned_by::<T>(&other_owner, T::RoleKey::default())?;
+
+        let process = T::BenchmarkHelper::process_requiring_approval(vec![approval::<T>(0), approval::<T>(1)]);
+        let proposer: T::AccountId = account("proposer", 0, SEED);
+        UtxoNFT::<T>::propose_process(
+            RawOrigin::Signed(proposer).into(),
+            process,
+            mk_inputs::<T>(2)?,
+            mk_outputs::<T>(T::MaxOutputCount::get())?,
+        )?;
+    }: _(RawOrigin::Signed(owner), 1)
+    verify {
+        assert_eq!(ProposalsById::<T>::get(1).unwrap().approved.len(), 1);
+    }
+
+    approve_process_and_run {
+        // the last approval, which executes the run
+        let i in 1..T::MaxInputCount::get();
+        let o in 1..T::MaxOutputCount::get();
+
+        add_proposal::<T>(i, o)?;
+        let owner: T::AccountId = account("owner", 0, SEED);
+    }: approve_process(RawOrigin::Signed(owner), 1)
+    verify {
+        assert!(ProposalsById::<T>::get(1).is_none());
+        assert_eq!(LastToken::<T>::get(), nth_token_id::<T>(i + o)?);
+    }
+
+    cancel_proposal {
+        let i in 1..T::MaxInputCount::get();
+
+        add_proposal::<T>(i, 1)?;
+        let proposer: T::AccountId = account("proposer", 0, SEED);
+    }: _(RawOrigin::Signed(proposer), 1)
+    verify {
+        assert!(ProposalsById::<T>::get(1).is_none());
+        assert_eq!(LockedTokens::<T>::get(nth_token_id::<T>(i)?), None);
+    }
+
+    // the process permitting the lock is the cheapest to validate, as validating it beyond that is charged separately
+    lock_token {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let holder: T::AccountId = account("holder", 0, SEED);
+        add_nft_owned_by::<T>(&owner, T::RoleKey::default())?;
+        let process = T::BenchmarkHelper::process_requiring_approval(Vec::new());
+        let token_id = nth_token_id::<T>(1)?;
+    }: _(RawOrigin::Signed(owner), process, token_id, holder.clone())
+    verify {
+        assert_eq!(TokenLocks::<T>::get(token_id).unwrap().holder, holder);
+        assert_eq!(CurrentLockQueueState::<T>::get().end_index, 1);
+    }
+
+    unlock_token {
+        add_locks::<T>(1)?;
+        let token_id = nth_token_id::<T>(1)?;
+        let holder: T::AccountId = account("holder", 0, SEED);
+    }: _(RawOrigin::Signed(holder), token_id)
+    verify {
+        assert!(TokenLocks::<T>::get(token_id).is_none());
+    }
+
+    expire_token_locks {
+        // expiring `l` locks from the lock queue in idle block time
+        let l in 0..100;
+
+        add_locks::<T>(l)?;
+        let now = System::<T>::block_number() + T::TokenLockDuration::get();
+    }: {
+        UtxoNFT::<T>::expire_token_locks(now, frame_support::weights::Weight::MAX);
+    }
+    verify {
+        assert_eq!(CurrentLockQueueState::<T>::get().start_index, l as u64);
+        assert!(TokenLocks::<T>::iter().next().is_none());
+    }
 }
 
 impl_benchmark_test_suite!(UtxoNFT, crate::tests::mock::new_test_ext(), crate::tests::mock::Test,);
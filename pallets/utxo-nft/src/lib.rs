@@ -6,9 +6,9 @@ use frame_support::{
 };
 pub use pallet::*;
 use parity_scale_codec::{Codec, MaxEncodedLen};
-use sp_runtime::traits::{AtLeast32Bit, Hash, One};
+use sp_runtime::traits::{AtLeast32Bit, Hash, IdentifyAccount, One, Verify, Zero};
 use sqnc_pallet_traits as traits;
-use sqnc_pallet_traits::{ProcessFullyQualifiedId, ProcessValidator, ValidateProcessWeights};
+use sqnc_pallet_traits::{ProcessApproval, ProcessFullyQualifiedId, ProcessValidator, ValidateProcessWeights};
 
 /// A FRAME pallet for handling non-fungible tokens
 use sp_std::prelude::*;
@@ -17,9 +17,16 @@ mod token;
 
 mod output;
 
+mod proposal;
+
 mod graveyard;
 pub use graveyard::GraveyardState;
 
+mod lock;
+pub use lock::LockQueueState;
+
+pub mod migration;
+
 #[cfg(test)]
 mod tests;
 
@@ -29,12 +36,22 @@ mod benchmarking;
 pub mod weights;
 pub use weights::WeightInfo;
 
+/// Sets up the processes and signatures benchmarks run
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<ProcessId, RoleKey, AccountId, Signature> {
+    /// Creates a process that any run of is valid but that must be given `approvals`
+    fn process_requiring_approval(approvals: Vec<ProcessApproval<RoleKey>>) -> ProcessId;
+    /// Signs `payload` as an account that has not signed for any previous call
+    fn sign(payload: &[u8]) -> (AccountId, Signature);
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 
     use super::*;
-    use frame_support::{ensure, pallet_prelude::*, Parameter};
+    use frame_support::{ensure, pallet_prelude::*, weights::Weight, Parameter};
     use frame_system::pallet_prelude::{BlockNumberFor, *};
+    use sp_runtime::DispatchError;
 
     /// The pallet's configuration trait.
     #[pallet::config]
@@ -77,6 +94,32 @@ pub mod pallet {
         // Maximum number of process outputs
         #[pallet::constant]
         type TokenTombstoneDuration: Get<BlockNumberFor<Self>>;
+
+        // Maximum number of distinct accounts that must approve a proposed process run
+        #[pallet::constant]
+        type MaxApprovalCount: Get<u32>;
+
+        // Number of blocks after which a proposed process run that has not been approved expires
+        #[pallet::constant]
+        type ProposalExpiry: Get<BlockNumberFor<Self>>;
+
+        // Number of blocks after which a lock on a token expires
+        #[pallet::constant]
+        type TokenLockDuration: Get<BlockNumberFor<Self>>;
+
+        // Signature of a detached approval of a process run
+        type OffchainSignature: Parameter + Verify<Signer = Self::SigningPublicKey>;
+
+        // Public key identifying the account that made a detached signature
+        type SigningPublicKey: IdentifyAccount<AccountId = Self::AccountId>;
+
+        // Maximum number of detached signatures that can accompany a process run
+        #[pallet::constant]
+        type MaxSignatureCount: Get<u32>;
+
+        // Creates the processes and signatures benchmarks run
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<ProcessId<Self>, Self::RoleKey, Self::AccountId, Self::OffchainSignature>;
     }
 
     // Define some derived types off of the Config trait to clean up declarations later
@@ -126,6 +169,18 @@ pub mod pallet {
         <T as Config>::TokenMetadataValue,
     >;
 
+    // The specific Proposal is derived from Config and the generic Proposal struct in this crate
+    type Proposal<T> = proposal::Proposal<
+        <T as frame_system::Config>::AccountId,
+        ProcessId<T>,
+        <T as Config>::TokenId,
+        Output<T>,
+        BlockNumberFor<T>,
+        <T as Config>::MaxInputCount,
+        <T as Config>::MaxOutputCount,
+        <T as Config>::MaxApprovalCount,
+    >;
+
     // The specific ProcessIO type can be derived from Config
     type ProcessIO<T> = traits::ProcessIO<
         <T as Config>::TokenId,
@@ -135,6 +190,9 @@ pub mod pallet {
         <T as Config>::TokenMetadataValue,
     >;
 
+    // The specific TokenLock is derived from Config and the generic TokenLock struct in this crate
+    type TokenLock<T> = lock::TokenLock<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
     type ProcessValidatorWeights<T> = <<T as Config>::ProcessValidator as ProcessValidator<
         <T as Config>::TokenId,
         <T as frame_system::Config>::AccountId,
@@ -144,7 +202,7 @@ pub mod pallet {
     >>::Weights;
 
     /// The in-code storage version.
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
@@ -177,6 +235,47 @@ pub mod pallet {
     #[pallet::getter(fn current_graveyard_state)]
     pub(super) type CurrentGraveyardState<T: Config> = StorageValue<_, GraveyardState, ValueQuery>;
 
+    /// Storage value definition
+    #[pallet::storage]
+    #[pallet::getter(fn last_proposal)]
+    pub(super) type LastProposal<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    // Storage map definition
+    #[pallet::storage]
+    #[pallet::getter(fn proposals_by_id)]
+    pub(super) type ProposalsById<T: Config> = StorageMap<_, Blake2_128Concat, u64, Proposal<T>, OptionQuery>;
+
+    // Tokens that are inputs of a pending proposal, by the id of that proposal
+    #[pallet::storage]
+    #[pallet::getter(fn locked_tokens)]
+    pub(super) type LockedTokens<T: Config> = StorageMap<_, Blake2_128Concat, T::TokenId, u64, OptionQuery>;
+
+    // Id of the oldest proposal that may not have expired. Proposals expire in the order they are made
+    #[pallet::storage]
+    #[pallet::getter(fn next_expiring_proposal)]
+    pub(super) type NextExpiringProposal<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    // Locks on tokens by the id of the token
+    #[pallet::storage]
+    #[pallet::getter(fn token_locks)]
+    pub(super) type TokenLocks<T: Config> = StorageMap<_, Blake2_128Concat, T::TokenId, TokenLock<T>, OptionQuery>;
+
+    // Queue of locked tokens along with the block their lock expires at, in the order they were locked
+    #[pallet::storage]
+    #[pallet::getter(fn lock_queue)]
+    pub(super) type LockQueue<T: Config> =
+        StorageMap<_, Blake2_128Concat, u64, (T::TokenId, BlockNumberFor<T>), OptionQuery>;
+
+    // Storage map definition
+    #[pallet::storage]
+    #[pallet::getter(fn current_lock_queue_state)]
+    pub(super) type CurrentLockQueueState<T: Config> = StorageValue<_, LockQueueState, ValueQuery>;
+
+    // Number of runs each account has signed, which the payload of the next run it signs must include
+    #[pallet::storage]
+    #[pallet::getter(fn signature_nonces)]
+    pub(super) type SignatureNonces<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
     #[pallet::event]
     pub enum Event<T: Config> {
         /// A process was successfully run
@@ -189,6 +288,46 @@ pub mod pallet {
         TokenDeleted {
             token_id: T::TokenId,
         },
+        /// A process run was proposed and its inputs locked until it is approved
+        ProcessProposed {
+            proposal_id: u64,
+            proposer: T::AccountId,
+            process: ProcessId<T>,
+            inputs: BoundedVec<T::TokenId, T::MaxInputCount>,
+        },
+        /// A proposed process run was approved by one of its approvers
+        ProcessApproved {
+            proposal_id: u64,
+            approver: T::AccountId,
+        },
+        /// A proposed process run was cancelled by its proposer
+        ProposalCancelled {
+            proposal_id: u64,
+        },
+        /// A proposed process run expired before it was approved
+        ProposalExpired {
+            proposal_id: u64,
+        },
+        /// A proposed process run was approved by every approver but was no longer valid so was closed without
+        /// being executed
+        ProposalFailed {
+            proposal_id: u64,
+            error: DispatchError,
+        },
+        /// A token was locked so that it can only be used as an input by the lock holder
+        TokenLocked {
+            token_id: T::TokenId,
+            holder: T::AccountId,
+            expires_at: BlockNumberFor<T>,
+        },
+        /// A lock on a token was released before it expired
+        TokenUnlocked {
+            token_id: T::TokenId,
+        },
+        /// A lock on a token expired
+        TokenLockExpired {
+            token_id: T::TokenId,
+        },
     }
 
     #[pallet::error]
@@ -203,14 +342,50 @@ pub mod pallet {
         NotBurnt,
         /// A token was burnt too recently to be deleted perminantly
         BurntTooRecently,
+        /// An input is locked by a pending proposal or by a lock held by another account
+        InputLocked,
+        /// The process must be approved by accounts other than the sender so must be proposed
+        ApprovalRequired,
+        /// The process requires no approvals from accounts other than the sender so can be run directly
+        NoApprovalRequired,
+        /// A role the process requires approval from is not held on the specified input
+        InvalidApproval,
+        /// The process requires approval from more accounts than a proposal can hold
+        TooManyApprovers,
+        /// No pending proposal exists with the given id
+        ProposalNotFound,
+        /// The proposal has expired
+        ProposalExpired,
+        /// The sender is not an approver of the proposal
+        NotApprover,
+        /// The sender has already approved the proposal
+        AlreadyApproved,
+        /// Only the proposer can cancel a proposal
+        NotProposer,
+        /// The token is already locked
+        AlreadyLocked,
+        /// The token is not locked
+        NotLocked,
+        /// Only the holder of a lock or the account that locked the token can unlock it
+        NotLockHolder,
+        /// A detached signature is not a valid signature of the process run by its account
+        InvalidSignature,
     }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_idle(
-            _block_number: BlockNumberFor<T>,
+            block_number: BlockNumberFor<T>,
             remaining_weight: frame_support::weights::Weight,
         ) -> frame_support::weights::Weight {
+            // expired proposals and token locks are removed first so that their inputs are unlocked
+            let expiry_weight = Self::expire_proposals(block_number, remaining_weight);
+            let expiry_weight = expiry_weight.saturating_add(Self::expire_token_locks(
+                block_number,
+                remaining_weight.saturating_sub(expiry_weight),
+            ));
+            let remaining_weight = remaining_weight.saturating_sub(expiry_weight);
+
             // 1 read and 1 write to get/set the graveyard state
             let base_weight = T::DbWeight::get().reads(1) + T::DbWeight::get().writes(1);
             let available_iter_weight = remaining_weight.checked_sub(&base_weight);
@@ -227,7 +402,7 @@ pub mod pallet {
             };
 
             if iter_count == 0 {
-                return remaining_weight;
+                return expiry_weight.saturating_add(remaining_weight);
             }
 
             // read graveyard state (base_weight)
@@ -263,7 +438,7 @@ pub mod pallet {
             });
 
             let spent_weight = base_weight.saturating_add(weight_per_iter.mul(delete_op_count));
-            spent_weight
+            expiry_weight.saturating_add(spent_weight)
         }
     }
 
@@ -273,6 +448,7 @@ pub mod pallet {
         #[pallet::call_index(0)]
         #[pallet::weight(
             T::WeightInfo::run_process(inputs.len() as u32, outputs.len() as u32) +
+            T::DbWeight::get().reads_writes(1 + 3 * inputs.len() as u64, inputs.len() as u64) +
             ProcessValidatorWeights::<T>::validate_process_max() -
             ProcessValidatorWeights::<T>::validate_process_min()
         )]
@@ -284,8 +460,378 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             // Check it was signed and get the signer
             let sender = ensure_signed(origin)?;
+
+            let actual_weight = T::WeightInfo::run_process(inputs.len() as u32, outputs.len() as u32)
+                + T::DbWeight::get().reads_writes(1 + 3 * inputs.len() as u64, inputs.len() as u64);
+            let validation_weight = Self::run_process_internal(sender, process, inputs, outputs, &Vec::new(), false)?;
+            Ok(Some(actual_weight + validation_weight).into())
+        }
+
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::delete_token())]
+        pub fn delete_token(origin: OriginFor<T>, token_id: <T as Config>::TokenId) -> DispatchResultWithPostInfo {
+            // Check it was signed and get the signer
+            ensure_signed(origin)?;
+            Self::delete_token_internal(token_id)
+                .map(|r| r.into())
+                .map_err(|e| e.into())
+        }
+
+        #[pallet::call_index(2)]
+        #[pallet::weight(
+            T::WeightInfo::propose_process(inputs.len() as u32, outputs.len() as u32) +
+            T::DbWeight::get().reads(1 + 3 * inputs.len() as u64) +
+            ProcessValidatorWeights::<T>::validate_process_max() -
+            ProcessValidatorWeights::<T>::validate_process_min()
+        )]
+        pub fn propose_process(
+            origin: OriginFor<T>,
+            process: ProcessId<T>,
+            inputs: BoundedVec<T::TokenId, T::MaxInputCount>,
+            outputs: BoundedVec<Output<T>, T::MaxOutputCount>,
+        ) -> DispatchResultWithPostInfo {
+            // Check it was signed and get the signer
+            let proposer = ensure_signed(origin)?;
+            // Get the current block number
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            let (io_inputs, _, _, validation_weight) =
+                Self::validate_run(&proposer, &process, &inputs, &outputs, &Vec::new())?;
+
+            // the proposer approves the run by proposing it
+            let approvers = Self::resolve_approvers(&process, &io_inputs)?;
+            ensure!(
+                approvers.iter().any(|approver| *approver != proposer),
+                Error::<T>::NoApprovalRequired
+            );
+            let approved = approvers
+                .iter()
+                .filter(|approver| **approver == proposer)
+                .cloned()
+                .try_collect()
+                .unwrap();
+
+            // STORAGE MUTATIONS
+
+            let proposal_id = Self::last_proposal() + 1;
+            inputs
+                .iter()
+                .for_each(|input| <LockedTokens<T>>::insert(input, proposal_id));
+            <ProposalsById<T>>::insert(
+                proposal_id,
+                Proposal::<T> {
+                    proposer: proposer.clone(),
+                    process: process.clone(),
+                    inputs: inputs.clone(),
+                    outputs: outputs.clone(),
+                    approvers,
+                    approved,
+                    expires_at: now + T::ProposalExpiry::get(),
+                },
+            );
+            <LastProposal<T>>::put(proposal_id);
+
+            // the inputs are checked for locks as when running a process
+            let actual_weight = T::WeightInfo::propose_process(inputs.len() as u32, outputs.len() as u32)
+                + T::DbWeight::get().reads(1 + 3 * inputs.len() as u64)
+                + validation_weight;
+
+            // EVENTS
+            let process_id = &process.id;
+            Self::deposit_event(
+                vec![
+                    T::Hashing::hash_of(&b"utxoNFT.ProcessProposed"),
+                    T::Hashing::hash_of(&(b"utxoNFT.ProcessProposed", process_id)),
+                    T::Hashing::hash_of(&(b"utxoNFT.ProcessProposed", proposal_id)),
+                ],
+                Event::ProcessProposed {
+                    proposal_id,
+                    proposer,
+                    process,
+                    inputs,
+                },
+            );
+
+            Ok(Some(actual_weight).into())
+        }
+
+        #[pallet::call_index(3)]
+        #[pallet::weight(
+            T::WeightInfo::approve_process_and_run(T::MaxInputCount::get(), T::MaxOutputCount::get()) +
+            ProcessValidatorWeights::<T>::validate_process_max() -
+            ProcessValidatorWeights::<T>::validate_process_min()
+        )]
+        pub fn approve_process(origin: OriginFor<T>, proposal_id: u64) -> DispatchResultWithPostInfo {
+            // Check it was signed and get the signer
+            let approver = ensure_signed(origin)?;
             // Get the current block number
             let now = <frame_system::Pallet<T>>::block_number();
+
+            let mut proposal = Self::proposals_by_id(proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+            ensure!(now < proposal.expires_at, Error::<T>::ProposalExpired);
+            ensure!(proposal.approvers.contains(&approver), Error::<T>::NotApprover);
+            ensure!(!proposal.approved.contains(&approver), Error::<T>::AlreadyApproved);
+
+            // STORAGE MUTATIONS
+
+            // approved is a subset of approvers so is within bounds
+            proposal.approved.try_push(approver.clone()).unwrap();
+            let is_complete = proposal.approved.len() == proposal.approvers.len();
+
+            Self::deposit_event(
+                vec![
+                    T::Hashing::hash_of(&b"utxoNFT.ProcessApproved"),
+                    T::Hashing::hash_of(&(b"utxoNFT.ProcessApproved", proposal_id)),
+                ],
+                Event::ProcessApproved { proposal_id, approver },
+            );
+
+            if !is_complete {
+                <ProposalsById<T>>::insert(proposal_id, proposal);
+                return Ok(Some(T::WeightInfo::approve_process()).into());
+            }
+
+            // the run is executed as the proposer once the inputs it locks are released
+            Self::remove_proposal(proposal_id, &proposal);
+            let Proposal::<T> {
+                proposer,
+                process,
+                inputs,
+                outputs,
+                ..
+            } = proposal;
+            let approve_weight = T::WeightInfo::approve_process_and_run(inputs.len() as u32, outputs.len() as u32);
+
+            // a run that is no longer valid, for example because its process was disabled, closes the proposal rather
+            // than reverting the approval and leaving the proposal to expire. Runs are validated before any storage
+            // is mutated so a failed run leaves no changes behind
+            match Self::run_process_internal(proposer, process, inputs, outputs, &Vec::new(), true) {
+                Ok(validation_weight) => Ok(Some(approve_weight + validation_weight).into()),
+                Err(error) => {
+                    Self::deposit_event(
+                        vec![
+                            T::Hashing::hash_of(&b"utxoNFT.ProposalFailed"),
+                            T::Hashing::hash_of(&(b"utxoNFT.ProposalFailed", proposal_id)),
+                        ],
+                        Event::ProposalFailed {
+                            proposal_id,
+                            error: error.into(),
+                        },
+                    );
+                    Ok(().into())
+                }
+            }
+        }
+
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::cancel_proposal(T::MaxInputCount::get()))]
+        pub fn cancel_proposal(origin: OriginFor<T>, proposal_id: u64) -> DispatchResultWithPostInfo {
+            // Check it was signed and get the signer
+            let sender = ensure_signed(origin)?;
+
+            let proposal = Self::proposals_by_id(proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+            ensure!(proposal.proposer == sender, Error::<T>::NotProposer);
+
+            Self::remove_proposal(proposal_id, &proposal);
+
+            Self::deposit_event(
+                vec![
+                    T::Hashing::hash_of(&b"utxoNFT.ProposalCancelled"),
+                    T::Hashing::hash_of(&(b"utxoNFT.ProposalCancelled", proposal_id)),
+                ],
+                Event::ProposalCancelled { proposal_id },
+            );
+
+            Ok(Some(T::WeightInfo::cancel_proposal(proposal.inputs.len() as u32)).into())
+        }
+
+        #[pallet::call_index(5)]
+        #[pallet::weight(
+            T::WeightInfo::lock_token() +
+            ProcessValidatorWeights::<T>::validate_process_max() -
+            ProcessValidatorWeights::<T>::validate_process_min()
+        )]
+        pub fn lock_token(
+            origin: OriginFor<T>,
+            process: ProcessId<T>,
+            token_id: T::TokenId,
+            holder: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            // Check it was signed and get the signer
+            let sender = ensure_signed(origin)?;
+            // Get the current block number
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            let token = Self::tokens_by_id(token_id).ok_or(Error::<T>::InvalidInput)?;
+            ensure!(token.children.is_none(), Error::<T>::AlreadyBurnt);
+            ensure!(!<LockedTokens<T>>::contains_key(token_id), Error::<T>::InputLocked);
+            ensure!(
+                Self::token_locks(token_id).map_or(true, |lock| lock.expires_at <= now),
+                Error::<T>::AlreadyLocked
+            );
+
+            // the sender may lock the token if `process` is valid for it with the token as its only input, so that
+            // who may lock a token is decided by the process rather than fixed for the runtime
+            let io_inputs = vec![ProcessIO::<T> {
+                id: token.id,
+                roles: token.roles.into(),
+                metadata: token.metadata.into(),
+            }];
+            let lock_is_valid = T::ProcessValidator::validate_process(&process, &sender, &io_inputs, &Vec::new());
+            ensure!(lock_is_valid.success, Error::<T>::ProcessInvalid);
+
+            // STORAGE MUTATIONS
+
+            let expires_at = now + T::TokenLockDuration::get();
+            <TokenLocks<T>>::insert(
+                token_id,
+                TokenLock::<T> {
+                    holder: holder.clone(),
+                    locked_by: sender,
+                    expires_at,
+                },
+            );
+
+            // queue the lock to be expired in idle block time
+            let LockQueueState { start_index, end_index } = Self::current_lock_queue_state();
+            <LockQueue<T>>::insert(end_index, (token_id, expires_at));
+            <CurrentLockQueueState<T>>::put(LockQueueState {
+                start_index,
+                end_index: end_index + 1,
+            });
+
+            // EVENTS
+            Self::deposit_event(
+                vec![
+                    T::Hashing::hash_of(&b"utxoNFT.TokenLocked"),
+                    T::Hashing::hash_of(&(b"utxoNFT.TokenLocked", token_id)),
+                ],
+                Event::TokenLocked {
+                    token_id,
+                    holder,
+                    expires_at,
+                },
+            );
+
+            let validation_weight = ProcessValidatorWeights::<T>::validate_process(lock_is_valid.executed_len)
+                - ProcessValidatorWeights::<T>::validate_process_min();
+            Ok(Some(T::WeightInfo::lock_token() + validation_weight).into())
+        }
+
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::unlock_token())]
+        pub fn unlock_token(origin: OriginFor<T>, token_id: T::TokenId) -> DispatchResultWithPostInfo {
+            // Check it was signed and get the signer
+            let sender = ensure_signed(origin)?;
+            // Get the current block number
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            let lock = Self::token_locks(token_id)
+                .filter(|lock| lock.expires_at > now)
+                .ok_or(Error::<T>::NotLocked)?;
+            ensure!(
+                lock.holder == sender || lock.locked_by == sender,
+                Error::<T>::NotLockHolder
+            );
+
+            // STORAGE MUTATIONS
+
+            // the lock's entry in the lock queue is skipped once it expires
+            <TokenLocks<T>>::remove(token_id);
+
+            // EVENTS
+            Self::deposit_event(
+                vec![
+                    T::Hashing::hash_of(&b"utxoNFT.TokenUnlocked"),
+                    T::Hashing::hash_of(&(b"utxoNFT.TokenUnlocked", token_id)),
+                ],
+                Event::TokenUnlocked { token_id },
+            );
+
+            Ok(().into())
+        }
+
+        #[pallet::call_index(7)]
+        #[pallet::weight(
+            T::WeightInfo::run_process_with_signatures(inputs.len() as u32, outputs.len() as u32, signatures.len() as u32) +
+            T::DbWeight::get().reads_writes(1 + 3 * inputs.len() as u64, inputs.len() as u64) +
+            ProcessValidatorWeights::<T>::validate_process_max() -
+            ProcessValidatorWeights::<T>::validate_process_min() +
+            ProcessValidatorWeights::<T>::validate_process_signers(signatures.len() as u32)
+        )]
+        pub fn run_process_with_signatures(
+            origin: OriginFor<T>,
+            process: ProcessId<T>,
+            inputs: BoundedVec<T::TokenId, T::MaxInputCount>,
+            outputs: BoundedVec<Output<T>, T::MaxOutputCount>,
+            signatures: BoundedVec<(T::AccountId, T::OffchainSignature), T::MaxSignatureCount>,
+        ) -> DispatchResultWithPostInfo {
+            // Check it was signed and get the signer
+            let sender = ensure_signed(origin)?;
+
+            // each signature must be of the run being made by this sender and the next run its account signs
+            let signers = signatures
+                .iter()
+                .map(|(account, signature)| {
+                    let nonce = Self::signature_nonces(account);
+                    let payload = Self::run_process_payload(&sender, &process, &inputs, &outputs, nonce);
+                    match signature.verify(&payload[..], account) {
+                        true => Ok(account.clone()),
+                        false => Err(Error::<T>::InvalidSignature),
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let actual_weight = T::WeightInfo::run_process_with_signatures(
+                inputs.len() as u32,
+                outputs.len() as u32,
+                signatures.len() as u32,
+            ) + T::DbWeight::get().reads_writes(1 + 3 * inputs.len() as u64, inputs.len() as u64);
+            let validation_weight = Self::run_process_internal(sender, process, inputs, outputs, &signers, false)?;
+
+            // a signature can only be used once
+            signers
+                .iter()
+                .for_each(|signer| <SignatureNonces<T>>::mutate(signer, |nonce| *nonce += 1));
+
+            Ok(Some(actual_weight + validation_weight).into())
+        }
+    }
+
+    // helper methods
+    impl<T: Config> Pallet<T> {
+        /// The payload a detached signature of a run of `process` by `sender` must sign. It is bound to the chain by
+        /// its genesis hash, to the sender that may submit it and to `nonce`, the current `signature_nonces` of the
+        /// signing account, so a signature is used at most once and only by `sender`
+        pub fn run_process_payload(
+            sender: &T::AccountId,
+            process: &ProcessId<T>,
+            inputs: &BoundedVec<T::TokenId, T::MaxInputCount>,
+            outputs: &BoundedVec<Output<T>, T::MaxOutputCount>,
+            nonce: u64,
+        ) -> Vec<u8> {
+            let genesis_hash = <frame_system::Pallet<T>>::block_hash(BlockNumberFor::<T>::zero());
+            (
+                b"sqnc/run_process",
+                genesis_hash,
+                sender,
+                process,
+                inputs,
+                outputs,
+                nonce,
+            )
+                .encode()
+        }
+
+        // validates a run of a process by `sender`, returning its inputs and outputs, the id of the last token once
+        // its outputs are minted and the weight of validating the process
+        fn validate_run(
+            sender: &T::AccountId,
+            process: &ProcessId<T>,
+            inputs: &BoundedVec<T::TokenId, T::MaxInputCount>,
+            outputs: &BoundedVec<Output<T>, T::MaxOutputCount>,
+            signers: &Vec<T::AccountId>,
+        ) -> Result<(Vec<ProcessIO<T>>, Vec<ProcessIO<T>>, T::TokenId, Weight), Error<T>> {
             // Helper closures function
             let _next_token = |id: T::TokenId| -> T::TokenId { id + One::one() };
 
@@ -307,6 +853,20 @@ pub mod pallet {
                 .collect::<Vec<_>>();
             ensure!(io_inputs.len() == inputs.len(), Error::<T>::AlreadyBurnt);
 
+            // Ensure no input is held by a pending proposal or locked by an account other than the sender
+            ensure!(
+                inputs.iter().all(|i| !<LockedTokens<T>>::contains_key(i)),
+                Error::<T>::InputLocked
+            );
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                inputs.iter().all(|i| match Self::token_locks(i) {
+                    Some(lock) => lock.holder == *sender || lock.expires_at <= now,
+                    None => true,
+                }),
+                Error::<T>::InputLocked
+            );
+
             let (last, io_outputs) = outputs.iter().fold(
                 (LastToken::<T>::get(), Vec::<ProcessIO<T>>::new()),
                 |(last, mut outputs), output| {
@@ -321,14 +881,69 @@ pub mod pallet {
                 },
             );
 
-            let process_is_valid = T::ProcessValidator::validate_process(&process, &sender, &io_inputs, &io_outputs);
+            let process_is_valid =
+                T::ProcessValidator::validate_process_with_signers(process, sender, signers, &io_inputs, &io_outputs);
             ensure!(process_is_valid.success, Error::<T>::ProcessInvalid);
 
+            let validation_weight = ProcessValidatorWeights::<T>::validate_process(process_is_valid.executed_len)
+                - ProcessValidatorWeights::<T>::validate_process_min()
+                + ProcessValidatorWeights::<T>::validate_process_signers(signers.len() as u32);
+            Ok((io_inputs, io_outputs, last, validation_weight))
+        }
+
+        // the distinct accounts that must approve a run of a process, being the holders of the roles it requires
+        // approval from on its inputs
+        fn resolve_approvers(
+            process: &ProcessId<T>,
+            io_inputs: &Vec<ProcessIO<T>>,
+        ) -> Result<BoundedVec<T::AccountId, T::MaxApprovalCount>, Error<T>> {
+            let mut approvers = BoundedVec::<T::AccountId, T::MaxApprovalCount>::new();
+            for ProcessApproval { input_index, role_key } in T::ProcessValidator::required_approvals(process) {
+                let approver = io_inputs
+                    .get(input_index as usize)
+                    .and_then(|input| input.roles.get(&role_key))
+                    .ok_or(Error::<T>::InvalidApproval)?;
+                if !approvers.contains(approver) {
+                    approvers
+                        .try_push(approver.clone())
+                        .map_err(|_| Error::<T>::TooManyApprovers)?;
+                }
+            }
+            Ok(approvers)
+        }
+
+        // validates and applies a run of a process by `sender`, with the detached signatures of `signers`, returning
+        // the weight of validating its process beyond the minimum. Unless `is_approved`, runs that must be approved
+        // by accounts other than `sender` and `signers` are rejected
+        fn run_process_internal(
+            sender: T::AccountId,
+            process: ProcessId<T>,
+            inputs: BoundedVec<T::TokenId, T::MaxInputCount>,
+            outputs: BoundedVec<Output<T>, T::MaxOutputCount>,
+            signers: &Vec<T::AccountId>,
+            is_approved: bool,
+        ) -> Result<Weight, Error<T>> {
+            // Get the current block number
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            let (io_inputs, io_outputs, last, validation_weight) =
+                Self::validate_run(&sender, &process, &inputs, &outputs, signers)?;
+
+            if !is_approved {
+                let approvers = Self::resolve_approvers(&process, &io_inputs)?;
+                ensure!(
+                    approvers
+                        .iter()
+                        .all(|approver| *approver == sender || signers.contains(approver)),
+                    Error::<T>::ApprovalRequired
+                );
+            }
+
             let graveyard_state = Self::current_graveyard_state();
 
             // STORAGE MUTATIONS
 
-            // Burn inputs
+            // Burn inputs, releasing any lock on them
             let children: BoundedVec<T::TokenId, T::MaxOutputCount> =
                 io_outputs.iter().map(|output| output.id.clone()).try_collect().unwrap();
             io_inputs.iter().enumerate().for_each(|(index, input)| {
@@ -337,6 +952,7 @@ pub mod pallet {
                     token.children = Some(children.clone());
                     token.destroyed_at = Some(now);
                 });
+                <TokenLocks<T>>::remove(input.id);
                 let graveyard_insert_index = graveyard_state.end_index + (index as u64);
                 <Graveyard<T>>::insert(graveyard_insert_index, input.id);
             });
@@ -368,10 +984,6 @@ pub mod pallet {
             // Update last token
             <LastToken<T>>::put(last);
 
-            let actual_weight = T::WeightInfo::run_process(inputs.len() as u32, outputs.len() as u32)
-                + ProcessValidatorWeights::<T>::validate_process(process_is_valid.executed_len)
-                - ProcessValidatorWeights::<T>::validate_process_min();
-
             // EVENTS
             let process_id = &process.id;
             let process_version = &process.version;
@@ -389,17 +1001,98 @@ pub mod pallet {
                 },
             );
 
-            Ok(Some(actual_weight).into())
+            Ok(validation_weight)
         }
 
-        #[pallet::call_index(1)]
-        #[pallet::weight(T::WeightInfo::delete_token())]
-        pub fn delete_token(origin: OriginFor<T>, token_id: <T as Config>::TokenId) -> DispatchResultWithPostInfo {
-            // Check it was signed and get the signer
-            ensure_signed(origin)?;
-            Self::delete_token_internal(token_id)
-                .map(|r| r.into())
-                .map_err(|e| e.into())
+        // removes a proposal and unlocks its inputs
+        fn remove_proposal(proposal_id: u64, proposal: &Proposal<T>) {
+            proposal
+                .inputs
+                .iter()
+                .for_each(|input| <LockedTokens<T>>::remove(input));
+            <ProposalsById<T>>::remove(proposal_id);
+        }
+
+        // removes expired proposals, oldest first, within `remaining_weight` returning the weight used
+        fn expire_proposals(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            // 2 reads to get the oldest proposal that may not have expired and the last proposal
+            let base_weight = T::DbWeight::get().reads(2);
+            // for each proposal we fetch it then delete it, unlocking its inputs, and update the oldest proposal
+            let weight_per_iter = T::DbWeight::get().reads_writes(1, 2 + T::MaxInputCount::get() as u64);
+            if base_weight.any_gt(remaining_weight) {
+                return Weight::zero();
+            }
+
+            let first = Self::next_expiring_proposal();
+            let last = Self::last_proposal();
+            let mut next = first;
+            let mut spent_weight = base_weight;
+            while next <= last && !spent_weight.saturating_add(weight_per_iter).any_gt(remaining_weight) {
+                spent_weight = spent_weight.saturating_add(weight_per_iter);
+                match Self::proposals_by_id(next) {
+                    Some(proposal) if proposal.expires_at > now => break,
+                    Some(proposal) => {
+                        Self::remove_proposal(next, &proposal);
+                        Self::deposit_event(
+                            vec![
+                                T::Hashing::hash_of(&b"utxoNFT.ProposalExpired"),
+                                T::Hashing::hash_of(&(b"utxoNFT.ProposalExpired", next)),
+                            ],
+                            Event::ProposalExpired { proposal_id: next },
+                        );
+                    }
+                    None => {}
+                }
+                next += 1;
+            }
+
+            if next != first {
+                <NextExpiringProposal<T>>::put(next);
+            }
+            spent_weight
+        }
+
+        // removes expired token locks, oldest first, within `remaining_weight` returning the weight used
+        pub(crate) fn expire_token_locks(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            // getting and setting the lock queue state, then for each entry fetching it and the lock on its token and
+            // deleting both
+            let base_weight = T::WeightInfo::expire_token_locks(0);
+            let weight_per_iter = T::WeightInfo::expire_token_locks(1).saturating_sub(base_weight);
+            if base_weight.any_gt(remaining_weight) {
+                return Weight::zero();
+            }
+
+            let LockQueueState { start_index, end_index } = Self::current_lock_queue_state();
+            let mut next = start_index;
+            let mut spent_weight = base_weight;
+            while next < end_index && !spent_weight.saturating_add(weight_per_iter).any_gt(remaining_weight) {
+                spent_weight = spent_weight.saturating_add(weight_per_iter);
+                let (token_id, expires_at) = Self::lock_queue(next).unwrap();
+                if expires_at > now {
+                    break;
+                }
+                <LockQueue<T>>::remove(next);
+                // the token may have been unlocked, used or locked again since this entry was queued
+                if Self::token_locks(token_id).map_or(false, |lock| lock.expires_at == expires_at) {
+                    <TokenLocks<T>>::remove(token_id);
+                    Self::deposit_event(
+                        vec![
+                            T::Hashing::hash_of(&b"utxoNFT.TokenLockExpired"),
+                            T::Hashing::hash_of(&(b"utxoNFT.TokenLockExpired", token_id)),
+                        ],
+                        Event::TokenLockExpired { token_id },
+                    );
+                }
+                next += 1;
+            }
+
+            if next != start_index {
+                <CurrentLockQueueState<T>>::put(LockQueueState {
+                    start_index: next,
+                    end_index,
+                });
+            }
+            spent_weight
         }
     }
 }
@@ -0,0 +1,17 @@
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// A lock on a token that prevents it from being used as an input by anyone other than its holder until it expires
+#[derive(Encode, Decode, Default, RuntimeDebug, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
+pub struct TokenLock<AccountId, BlockNumber> {
+    pub(crate) holder: AccountId,
+    pub(crate) locked_by: AccountId,
+    pub(crate) expires_at: BlockNumber,
+}
+
+#[derive(Encode, Decode, Default, RuntimeDebug, MaxEncodedLen, TypeInfo, Clone, PartialEq)]
+pub struct LockQueueState {
+    pub(crate) start_index: u64,
+    pub(crate) end_index: u64,
+}
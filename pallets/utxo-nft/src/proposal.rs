@@ -0,0 +1,28 @@
+use frame_support::{traits::Get, BoundedVec};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+use sp_std::prelude::*;
+
+/// A run of a process that has been proposed and executes once every approver has approved it
+#[derive(Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo, Clone)]
+#[scale_info(skip_type_params(MaxInputCount, MaxOutputCount, MaxApprovalCount))]
+pub struct Proposal<
+    AccountId,
+    ProcessId,
+    TokenId,
+    Output,
+    BlockNumber,
+    MaxInputCount: Get<u32>,
+    MaxOutputCount: Get<u32>,
+    MaxApprovalCount: Get<u32>,
+> {
+    pub(crate) proposer: AccountId,
+    pub(crate) process: ProcessId,
+    pub(crate) inputs: BoundedVec<TokenId, MaxInputCount>,
+    pub(crate) outputs: BoundedVec<Output, MaxOutputCount>,
+    pub(crate) approvers: BoundedVec<AccountId, MaxApprovalCount>,
+    pub(crate) approved: BoundedVec<AccountId, MaxApprovalCount>,
+    pub(crate) expires_at: BlockNumber,
+}
@@ -0,0 +1,393 @@
+use crate::{lock::TokenLock, output::Output, tests::mock::*, Error, Event, LockQueueState};
+use frame_support::{assert_err, assert_ok};
+use sp_runtime::{bounded_btree_map, bounded_vec};
+use sqnc_pallet_traits::ProcessFullyQualifiedId;
+
+const SUCCEED_PROCESS: ProcessFullyQualifiedId<ProcessIdentifier, u32> = ProcessFullyQualifiedId {
+    id: ProcessIdentifier::ShouldSucceed,
+    version: 0u32,
+};
+const APPROVAL_PROCESS: ProcessFullyQualifiedId<ProcessIdentifier, u32> = ProcessFullyQualifiedId {
+    id: ProcessIdentifier::RequiresApproval,
+    version: 0u32,
+};
+const FAIL_PROCESS: ProcessFullyQualifiedId<ProcessIdentifier, u32> = ProcessFullyQualifiedId {
+    id: ProcessIdentifier::ShouldFail,
+    version: 0u32,
+};
+// lets the `Owner` of a token lock it
+const OWNER_LOCK_PROCESS: ProcessFullyQualifiedId<ProcessIdentifier, u32> = ProcessFullyQualifiedId {
+    id: ProcessIdentifier::SenderHoldsRole(Role::Owner),
+    version: 0u32,
+};
+// lets the `NotOwner` of a token lock it
+const NOT_OWNER_LOCK_PROCESS: ProcessFullyQualifiedId<ProcessIdentifier, u32> = ProcessFullyQualifiedId {
+    id: ProcessIdentifier::SenderHoldsRole(Role::NotOwner),
+    version: 0u32,
+};
+
+// creates a token owned by 1 with 2 as its `NotOwner`
+fn create_token() {
+    assert_ok!(UtxoNFT::run_process(
+        RuntimeOrigin::signed(1),
+        SUCCEED_PROCESS,
+        bounded_vec![],
+        bounded_vec![Output {
+            roles: bounded_btree_map!(Role::Owner => 1, Role::NotOwner => 2),
+            metadata: bounded_btree_map!(0 => MetadataValue::Literal([0]))
+        }]
+    ));
+}
+
+// consumes token 1 as `sender`
+fn consume(sender: u64) -> frame_support::dispatch::DispatchResultWithPostInfo {
+    UtxoNFT::run_process(
+        RuntimeOrigin::signed(sender),
+        SUCCEED_PROCESS,
+        bounded_vec![1],
+        bounded_vec![],
+    )
+}
+
+#[test]
+fn it_locks_a_token() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token();
+
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+
+        assert_eq!(
+            UtxoNFT::token_locks(1),
+            Some(TokenLock {
+                holder: 3,
+                locked_by: 1,
+                expires_at: 6
+            })
+        );
+        assert_eq!(UtxoNFT::lock_queue(0), Some((1, 6)));
+        assert_eq!(
+            UtxoNFT::current_lock_queue_state(),
+            LockQueueState {
+                start_index: 0,
+                end_index: 1
+            }
+        );
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            RuntimeEvent::UtxoNFT(Event::TokenLocked {
+                token_id: 1,
+                holder: 3,
+                expires_at: 6
+            }),
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_lock_as_the_holder_of_another_role() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_err!(
+            UtxoNFT::lock_token(RuntimeOrigin::signed(2), OWNER_LOCK_PROCESS, 1, 2),
+            Error::<Test>::ProcessInvalid
+        );
+        assert_eq!(UtxoNFT::token_locks(1), None);
+    });
+}
+
+#[test]
+fn it_fails_to_lock_without_a_role() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_err!(
+            UtxoNFT::lock_token(RuntimeOrigin::signed(3), OWNER_LOCK_PROCESS, 1, 3),
+            Error::<Test>::ProcessInvalid
+        );
+        assert_eq!(UtxoNFT::token_locks(1), None);
+    });
+}
+
+#[test]
+fn it_locks_a_token_as_a_non_owner_holding_the_role_the_process_requires() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token();
+
+        assert_ok!(UtxoNFT::lock_token(
+            RuntimeOrigin::signed(2),
+            NOT_OWNER_LOCK_PROCESS,
+            1,
+            3
+        ));
+
+        assert_eq!(
+            UtxoNFT::token_locks(1),
+            Some(TokenLock {
+                holder: 3,
+                locked_by: 2,
+                expires_at: 6
+            })
+        );
+        assert_err!(consume(1), Error::<Test>::InputLocked);
+        assert_ok!(consume(3));
+    });
+}
+
+#[test]
+fn it_fails_to_lock_as_the_owner_if_the_process_requires_another_role() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_err!(
+            UtxoNFT::lock_token(RuntimeOrigin::signed(1), NOT_OWNER_LOCK_PROCESS, 1, 1),
+            Error::<Test>::ProcessInvalid
+        );
+        assert_eq!(UtxoNFT::token_locks(1), None);
+    });
+}
+
+#[test]
+fn it_fails_to_lock_with_an_invalid_process() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_err!(
+            UtxoNFT::lock_token(RuntimeOrigin::signed(1), FAIL_PROCESS, 1, 1),
+            Error::<Test>::ProcessInvalid
+        );
+        assert_eq!(UtxoNFT::token_locks(1), None);
+    });
+}
+
+#[test]
+fn it_fails_to_lock_a_missing_token() {
+    new_test_ext().execute_with(|| {
+        assert_err!(
+            UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 1),
+            Error::<Test>::InvalidInput
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_lock_a_burnt_token() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_ok!(consume(1));
+        assert_err!(
+            UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 1),
+            Error::<Test>::AlreadyBurnt
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_lock_a_locked_token() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+        assert_err!(
+            UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 2),
+            Error::<Test>::AlreadyLocked
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_lock_a_token_held_by_a_proposal() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_ok!(UtxoNFT::propose_process(
+            RuntimeOrigin::signed(1),
+            APPROVAL_PROCESS,
+            bounded_vec![1],
+            bounded_vec![]
+        ));
+        assert_err!(
+            UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 1),
+            Error::<Test>::InputLocked
+        );
+    });
+}
+
+#[test]
+fn it_relocks_a_token_once_its_lock_has_expired() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token();
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+
+        run_to_block(6, false);
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 2));
+        assert_eq!(
+            UtxoNFT::token_locks(1),
+            Some(TokenLock {
+                holder: 2,
+                locked_by: 1,
+                expires_at: 11
+            })
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_use_a_token_locked_by_another_account() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+
+        assert_err!(consume(1), Error::<Test>::InputLocked);
+        assert_err!(
+            UtxoNFT::propose_process(
+                RuntimeOrigin::signed(1),
+                APPROVAL_PROCESS,
+                bounded_vec![1],
+                bounded_vec![]
+            ),
+            Error::<Test>::InputLocked
+        );
+        assert_eq!(UtxoNFT::tokens_by_id(1).unwrap().children, None);
+    });
+}
+
+#[test]
+fn it_uses_a_token_as_the_lock_holder() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+
+        assert_ok!(consume(3));
+        assert_eq!(UtxoNFT::tokens_by_id(1).unwrap().children, Some(bounded_vec![]));
+        assert_eq!(UtxoNFT::token_locks(1), None);
+    });
+}
+
+#[test]
+fn it_uses_a_token_once_its_lock_has_expired() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token();
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+
+        run_to_block(5, false);
+        assert_err!(consume(1), Error::<Test>::InputLocked);
+
+        run_to_block(6, false);
+        assert_ok!(consume(1));
+    });
+}
+
+#[test]
+fn it_unlocks_a_token_as_holder_or_locker() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+        assert_ok!(UtxoNFT::unlock_token(RuntimeOrigin::signed(3), 1));
+        assert_eq!(UtxoNFT::token_locks(1), None);
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            RuntimeEvent::UtxoNFT(Event::TokenUnlocked { token_id: 1 }),
+        );
+
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+        assert_ok!(UtxoNFT::unlock_token(RuntimeOrigin::signed(1), 1));
+        assert_eq!(UtxoNFT::token_locks(1), None);
+
+        assert_ok!(consume(1));
+    });
+}
+
+#[test]
+fn it_fails_to_unlock_as_another_account() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+        assert_err!(
+            UtxoNFT::unlock_token(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::NotLockHolder
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_unlock_a_token_that_is_not_locked() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token();
+        assert_err!(
+            UtxoNFT::unlock_token(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::NotLocked
+        );
+
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+        run_to_block(6, false);
+        assert_err!(
+            UtxoNFT::unlock_token(RuntimeOrigin::signed(3), 1),
+            Error::<Test>::NotLocked
+        );
+    });
+}
+
+#[test]
+fn it_does_not_expire_a_lock_in_on_idle_before_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token();
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+
+        run_to_block(5, true);
+
+        assert!(UtxoNFT::token_locks(1).is_some());
+        assert_eq!(UtxoNFT::lock_queue(0), Some((1, 6)));
+        assert_eq!(UtxoNFT::current_lock_queue_state().start_index, 0);
+    });
+}
+
+#[test]
+fn it_expires_a_lock_in_on_idle_after_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token();
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+
+        run_to_block(6, true);
+
+        assert_eq!(UtxoNFT::token_locks(1), None);
+        assert_eq!(UtxoNFT::lock_queue(0), None);
+        assert_eq!(
+            UtxoNFT::current_lock_queue_state(),
+            LockQueueState {
+                start_index: 1,
+                end_index: 1
+            }
+        );
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            RuntimeEvent::UtxoNFT(Event::TokenLockExpired { token_id: 1 }),
+        );
+    });
+}
+
+#[test]
+fn it_skips_released_locks_when_expiring() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token();
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+        assert_ok!(UtxoNFT::unlock_token(RuntimeOrigin::signed(1), 1));
+
+        // the token is locked again before the released lock would have expired
+        run_to_block(3, false);
+        assert_ok!(UtxoNFT::lock_token(RuntimeOrigin::signed(1), OWNER_LOCK_PROCESS, 1, 3));
+
+        run_to_block(6, true);
+        assert_eq!(UtxoNFT::token_locks(1).unwrap().expires_at, 8);
+        assert_eq!(UtxoNFT::current_lock_queue_state().start_index, 1);
+        assert!(!System::events()
+            .iter()
+            .any(|record| record.event == RuntimeEvent::UtxoNFT(Event::TokenLockExpired { token_id: 1 })));
+
+        run_to_block(8, true);
+        assert_eq!(UtxoNFT::token_locks(1), None);
+        assert_eq!(UtxoNFT::current_lock_queue_state().start_index, 2);
+    });
+}
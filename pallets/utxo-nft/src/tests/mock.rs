@@ -9,10 +9,14 @@ use frame_support::{
 use frame_system as system;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
-use sp_runtime::BuildStorage;
+use sp_runtime::{
+    testing::{TestSignature, UintAuthorityId},
+    BuildStorage,
+};
+use std::cell::Cell;
 
 type Block = frame_system::mocking::MockBlock<Test>;
-use sqnc_pallet_traits::{ProcessFullyQualifiedId, ProcessIO, ProcessValidator, ValidationResult};
+use sqnc_pallet_traits::{ProcessApproval, ProcessFullyQualifiedId, ProcessIO, ProcessValidator, ValidationResult};
 
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
@@ -71,6 +75,10 @@ impl<T> Default for MetadataValue<T> {
 pub enum ProcessIdentifier {
     ShouldSucceed,
     ShouldFail,
+    // succeeds once approved by the `Owner` and `NotOwner` of the first input
+    RequiresApproval,
+    // succeeds if the sender holds the role on the first input
+    SenderHoldsRole(Role),
 }
 
 impl Default for ProcessIdentifier {
@@ -79,6 +87,14 @@ impl Default for ProcessIdentifier {
     }
 }
 
+thread_local! {
+    // whether the `RequiresApproval` process has been disabled, such as after a run of it is proposed
+    pub static APPROVAL_PROCESS_DISABLED: Cell<bool> = Cell::new(false);
+    // the account the next signature made for a benchmark is made by
+    #[cfg(feature = "runtime-benchmarks")]
+    static NEXT_BENCHMARK_SIGNER: Cell<u64> = Cell::new(100);
+}
+
 pub struct MockProcessValidator {}
 
 type TestProcessId = ProcessFullyQualifiedId<ProcessIdentifier, u32>;
@@ -92,15 +108,39 @@ impl ProcessValidator<u64, u64, Role, u64, MetadataValue<u64>> for MockProcessVa
 
     fn validate_process(
         id: &TestProcessId,
-        _sender: &u64,
-        _inputs: &Vec<TestProcessIO>,
+        sender: &u64,
+        inputs: &Vec<TestProcessIO>,
         _outputs: &Vec<TestProcessIO>,
     ) -> ValidationResult<u32> {
+        let success = match &id.id {
+            ProcessIdentifier::ShouldFail => false,
+            ProcessIdentifier::RequiresApproval => !APPROVAL_PROCESS_DISABLED.with(Cell::get),
+            ProcessIdentifier::SenderHoldsRole(role) => inputs
+                .first()
+                .is_some_and(|input| input.roles.get(role) == Some(sender)),
+            _ => true,
+        };
         ValidationResult {
-            success: id.id.clone() == ProcessIdentifier::ShouldSucceed,
+            success,
             executed_len: 0u32,
         }
     }
+
+    fn required_approvals(id: &TestProcessId) -> Vec<ProcessApproval<Role>> {
+        match id.id {
+            ProcessIdentifier::RequiresApproval => vec![
+                ProcessApproval {
+                    input_index: 0,
+                    role_key: Role::Owner,
+                },
+                ProcessApproval {
+                    input_index: 0,
+                    role_key: Role::NotOwner,
+                },
+            ],
+            _ => Vec::new(),
+        }
+    }
 }
 
 pub struct TestWeights {}
@@ -111,6 +151,49 @@ impl pallet_utxo_nft::WeightInfo for TestWeights {
     fn delete_token() -> Weight {
         Weight::from_parts(1, 1)
     }
+    fn run_process_with_signatures(_: u32, _: u32, s: u32) -> Weight {
+        Weight::from_parts(1 + s as u64, 1)
+    }
+    fn propose_process(_: u32, _: u32) -> Weight {
+        Weight::from_parts(1, 1)
+    }
+    fn approve_process() -> Weight {
+        Weight::from_parts(1, 1)
+    }
+    fn approve_process_and_run(_: u32, _: u32) -> Weight {
+        Weight::from_parts(1, 1)
+    }
+    fn cancel_proposal(_: u32) -> Weight {
+        Weight::from_parts(1, 1)
+    }
+    fn lock_token() -> Weight {
+        Weight::from_parts(1, 1)
+    }
+    fn unlock_token() -> Weight {
+        Weight::from_parts(1, 1)
+    }
+    fn expire_token_locks(l: u32) -> Weight {
+        Weight::from_parts(1, 1).saturating_mul(l as u64)
+    }
+}
+
+// the mock validator requires approval from both roles on the first input whatever approvals are given. Each
+// signature is made by a new account
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_utxo_nft::BenchmarkHelper<TestProcessId, Role, u64, TestSignature> for MockBenchmarkHelper {
+    fn process_requiring_approval(_approvals: Vec<ProcessApproval<Role>>) -> TestProcessId {
+        ProcessFullyQualifiedId {
+            id: ProcessIdentifier::RequiresApproval,
+            version: 0u32,
+        }
+    }
+
+    fn sign(payload: &[u8]) -> (u64, TestSignature) {
+        let signer = NEXT_BENCHMARK_SIGNER.with(|next| next.replace(next.get() + 1));
+        (signer, TestSignature(signer, payload.to_vec()))
+    }
 }
 
 impl pallet_utxo_nft::Config for Test {
@@ -129,6 +212,14 @@ impl pallet_utxo_nft::Config for Test {
     type MaxInputCount = ConstU32<5>;
     type MaxOutputCount = ConstU32<5>;
     type TokenTombstoneDuration = ConstU64<100u64>;
+    type MaxApprovalCount = ConstU32<2>;
+    type ProposalExpiry = ConstU64<10u64>;
+    type TokenLockDuration = ConstU64<5u64>;
+    type OffchainSignature = TestSignature;
+    type SigningPublicKey = UintAuthorityId;
+    type MaxSignatureCount = ConstU32<2>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = MockBenchmarkHelper;
 }
 
 // This function basically just builds a genesis storage key/value store according to
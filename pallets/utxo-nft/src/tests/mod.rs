@@ -1,3 +1,6 @@
 pub mod delete_token;
+pub mod lock_token;
 pub mod mock;
+pub mod propose_process;
 pub mod run_process;
+pub mod run_process_with_signatures;
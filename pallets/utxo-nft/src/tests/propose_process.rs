@@ -0,0 +1,418 @@
+use crate::{output::Output, tests::mock::*, Error, Event};
+use frame_support::{assert_err, assert_ok, traits::ConstU32};
+use sp_runtime::{bounded_btree_map, bounded_vec};
+use sqnc_pallet_traits::ProcessFullyQualifiedId;
+
+const SUCCEED_PROCESS: ProcessFullyQualifiedId<ProcessIdentifier, u32> = ProcessFullyQualifiedId {
+    id: ProcessIdentifier::ShouldSucceed,
+    version: 0u32,
+};
+const FAIL_PROCESS: ProcessFullyQualifiedId<ProcessIdentifier, u32> = ProcessFullyQualifiedId {
+    id: ProcessIdentifier::ShouldFail,
+    version: 0u32,
+};
+const APPROVAL_PROCESS: ProcessFullyQualifiedId<ProcessIdentifier, u32> = ProcessFullyQualifiedId {
+    id: ProcessIdentifier::RequiresApproval,
+    version: 0u32,
+};
+
+// creates token 1 owned by `owner` with `not_owner` as its `NotOwner`
+fn create_token(owner: u64, not_owner: u64) {
+    assert_ok!(UtxoNFT::run_process(
+        RuntimeOrigin::signed(owner),
+        SUCCEED_PROCESS,
+        bounded_vec![],
+        bounded_vec![Output {
+            roles: bounded_btree_map!(Role::Owner => owner, Role::NotOwner => not_owner),
+            metadata: bounded_btree_map!(0 => MetadataValue::Literal([0]))
+        }]
+    ));
+}
+
+fn output(owner: u64) -> Output<ConstU32<2>, u64, Role, ConstU32<4>, u64, MetadataValue<u64>> {
+    Output {
+        roles: bounded_btree_map!(Role::Owner => owner),
+        metadata: bounded_btree_map!(0 => MetadataValue::Literal([1])),
+    }
+}
+
+// proposes consuming token 1 to create a token owned by 2, as 1
+fn propose() {
+    assert_ok!(UtxoNFT::propose_process(
+        RuntimeOrigin::signed(1),
+        APPROVAL_PROCESS,
+        bounded_vec![1],
+        bounded_vec![output(2)]
+    ));
+}
+
+#[test]
+fn it_stores_a_proposal_and_locks_its_inputs() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token(1, 2);
+        propose();
+
+        let proposal = UtxoNFT::proposals_by_id(1).unwrap();
+        assert_eq!(proposal.proposer, 1);
+        assert_eq!(proposal.process, APPROVAL_PROCESS);
+        assert_eq!(proposal.inputs.to_vec(), vec![1]);
+        assert_eq!(proposal.outputs.to_vec(), vec![output(2)]);
+        assert_eq!(proposal.approvers.to_vec(), vec![1, 2]);
+        assert_eq!(proposal.approved.to_vec(), vec![1]);
+        assert_eq!(proposal.expires_at, 11);
+        assert_eq!(UtxoNFT::last_proposal(), 1);
+        assert_eq!(UtxoNFT::locked_tokens(1), Some(1));
+
+        // the input is not burnt until the proposal is approved
+        assert_eq!(UtxoNFT::tokens_by_id(1).unwrap().children, None);
+        assert_eq!(UtxoNFT::last_token(), 1);
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            RuntimeEvent::UtxoNFT(Event::ProcessProposed {
+                proposal_id: 1,
+                proposer: 1,
+                process: APPROVAL_PROCESS,
+                inputs: bounded_vec![1],
+            }),
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_propose_an_invalid_process() {
+    new_test_ext().execute_with(|| {
+        create_token(1, 2);
+        assert_err!(
+            UtxoNFT::propose_process(RuntimeOrigin::signed(1), FAIL_PROCESS, bounded_vec![1], bounded_vec![]),
+            Error::<Test>::ProcessInvalid
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_propose_a_process_requiring_no_other_approval() {
+    new_test_ext().execute_with(|| {
+        create_token(1, 2);
+        assert_err!(
+            UtxoNFT::propose_process(
+                RuntimeOrigin::signed(1),
+                SUCCEED_PROCESS,
+                bounded_vec![1],
+                bounded_vec![]
+            ),
+            Error::<Test>::NoApprovalRequired
+        );
+
+        // the proposer holds every role approval is required from
+        create_token(3, 3);
+        assert_err!(
+            UtxoNFT::propose_process(
+                RuntimeOrigin::signed(3),
+                APPROVAL_PROCESS,
+                bounded_vec![2],
+                bounded_vec![]
+            ),
+            Error::<Test>::NoApprovalRequired
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_propose_if_an_approving_role_is_missing() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(UtxoNFT::run_process(
+            RuntimeOrigin::signed(1),
+            SUCCEED_PROCESS,
+            bounded_vec![],
+            bounded_vec![output(1)]
+        ));
+        assert_err!(
+            UtxoNFT::propose_process(
+                RuntimeOrigin::signed(1),
+                APPROVAL_PROCESS,
+                bounded_vec![1],
+                bounded_vec![]
+            ),
+            Error::<Test>::InvalidApproval
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_use_a_locked_input() {
+    new_test_ext().execute_with(|| {
+        create_token(1, 2);
+        propose();
+
+        assert_err!(
+            UtxoNFT::run_process(
+                RuntimeOrigin::signed(1),
+                SUCCEED_PROCESS,
+                bounded_vec![1],
+                bounded_vec![]
+            ),
+            Error::<Test>::InputLocked
+        );
+        assert_err!(
+            UtxoNFT::propose_process(
+                RuntimeOrigin::signed(1),
+                APPROVAL_PROCESS,
+                bounded_vec![1],
+                bounded_vec![output(1)]
+            ),
+            Error::<Test>::InputLocked
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_run_a_process_requiring_approval() {
+    new_test_ext().execute_with(|| {
+        create_token(1, 2);
+        assert_err!(
+            UtxoNFT::run_process(
+                RuntimeOrigin::signed(1),
+                APPROVAL_PROCESS,
+                bounded_vec![1],
+                bounded_vec![]
+            ),
+            Error::<Test>::ApprovalRequired
+        );
+    });
+}
+
+#[test]
+fn it_runs_a_process_requiring_approval_only_from_the_sender() {
+    new_test_ext().execute_with(|| {
+        create_token(1, 1);
+        assert_ok!(UtxoNFT::run_process(
+            RuntimeOrigin::signed(1),
+            APPROVAL_PROCESS,
+            bounded_vec![1],
+            bounded_vec![]
+        ));
+        assert_eq!(UtxoNFT::tokens_by_id(1).unwrap().children, Some(bounded_vec![]));
+    });
+}
+
+#[test]
+fn it_executes_a_proposal_once_approved() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token(1, 2);
+        propose();
+
+        assert_ok!(UtxoNFT::approve_process(RuntimeOrigin::signed(2), 1));
+
+        // the run is made by the proposer
+        let input = UtxoNFT::tokens_by_id(1).unwrap();
+        assert_eq!(input.children, Some(bounded_vec![2]));
+        assert_eq!(input.destroyed_at, Some(1));
+        let minted = UtxoNFT::tokens_by_id(2).unwrap();
+        assert_eq!(minted.creator, 1);
+        assert_eq!(minted.roles, output(2).roles);
+        assert_eq!(minted.parents.to_vec(), vec![1]);
+        assert_eq!(UtxoNFT::last_token(), 2);
+
+        assert!(UtxoNFT::proposals_by_id(1).is_none());
+        assert_eq!(UtxoNFT::locked_tokens(1), None);
+
+        let events = System::events();
+        assert_eq!(
+            events[events.len() - 2].event,
+            RuntimeEvent::UtxoNFT(Event::ProcessApproved {
+                proposal_id: 1,
+                approver: 2
+            }),
+        );
+        assert_eq!(
+            events[events.len() - 1].event,
+            RuntimeEvent::UtxoNFT(Event::ProcessRan {
+                sender: 1,
+                process: APPROVAL_PROCESS,
+                inputs: bounded_vec![1],
+                outputs: bounded_vec![2],
+            }),
+        );
+    });
+}
+
+#[test]
+fn it_closes_a_proposal_whose_run_fails_once_approved() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token(1, 2);
+        propose();
+
+        APPROVAL_PROCESS_DISABLED.with(|disabled| disabled.set(true));
+        assert_ok!(UtxoNFT::approve_process(RuntimeOrigin::signed(2), 1));
+
+        // the run is not executed but the proposal is closed and its input unlocked
+        assert_eq!(UtxoNFT::tokens_by_id(1).unwrap().children, None);
+        assert_eq!(UtxoNFT::last_token(), 1);
+        assert!(UtxoNFT::proposals_by_id(1).is_none());
+        assert_eq!(UtxoNFT::locked_tokens(1), None);
+
+        let events = System::events();
+        assert_eq!(
+            events[events.len() - 2].event,
+            RuntimeEvent::UtxoNFT(Event::ProcessApproved {
+                proposal_id: 1,
+                approver: 2
+            }),
+        );
+        assert_eq!(
+            events[events.len() - 1].event,
+            RuntimeEvent::UtxoNFT(Event::ProposalFailed {
+                proposal_id: 1,
+                error: Error::<Test>::ProcessInvalid.into(),
+            }),
+        );
+
+        // the input can be used again
+        assert_ok!(UtxoNFT::run_process(
+            RuntimeOrigin::signed(1),
+            SUCCEED_PROCESS,
+            bounded_vec![1],
+            bounded_vec![]
+        ));
+    });
+}
+
+#[test]
+fn it_fails_to_approve_if_not_an_approver() {
+    new_test_ext().execute_with(|| {
+        create_token(1, 2);
+        propose();
+        assert_err!(
+            UtxoNFT::approve_process(RuntimeOrigin::signed(3), 1),
+            Error::<Test>::NotApprover
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_approve_twice() {
+    new_test_ext().execute_with(|| {
+        create_token(1, 2);
+        propose();
+        assert_err!(
+            UtxoNFT::approve_process(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::AlreadyApproved
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_approve_a_missing_proposal() {
+    new_test_ext().execute_with(|| {
+        assert_err!(
+            UtxoNFT::approve_process(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::ProposalNotFound
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_approve_an_expired_proposal() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token(1, 2);
+        propose();
+
+        run_to_block(11, false);
+        assert_err!(
+            UtxoNFT::approve_process(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::ProposalExpired
+        );
+    });
+}
+
+#[test]
+fn it_cancels_a_proposal() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token(1, 2);
+        propose();
+
+        assert_err!(
+            UtxoNFT::cancel_proposal(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::NotProposer
+        );
+        assert_ok!(UtxoNFT::cancel_proposal(RuntimeOrigin::signed(1), 1));
+
+        assert!(UtxoNFT::proposals_by_id(1).is_none());
+        assert_eq!(UtxoNFT::locked_tokens(1), None);
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            RuntimeEvent::UtxoNFT(Event::ProposalCancelled { proposal_id: 1 }),
+        );
+
+        // the input can be used again
+        assert_ok!(UtxoNFT::run_process(
+            RuntimeOrigin::signed(1),
+            SUCCEED_PROCESS,
+            bounded_vec![1],
+            bounded_vec![]
+        ));
+    });
+}
+
+#[test]
+fn it_does_not_expire_a_proposal_in_on_idle_before_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token(1, 2);
+        propose();
+
+        run_to_block(10, true);
+
+        assert!(UtxoNFT::proposals_by_id(1).is_some());
+        assert_eq!(UtxoNFT::locked_tokens(1), Some(1));
+        assert_eq!(UtxoNFT::next_expiring_proposal(), 1);
+    });
+}
+
+#[test]
+fn it_expires_a_proposal_in_on_idle_after_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token(1, 2);
+        propose();
+
+        run_to_block(11, true);
+
+        assert!(UtxoNFT::proposals_by_id(1).is_none());
+        assert_eq!(UtxoNFT::locked_tokens(1), None);
+        assert_eq!(UtxoNFT::next_expiring_proposal(), 2);
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            RuntimeEvent::UtxoNFT(Event::ProposalExpired { proposal_id: 1 }),
+        );
+
+        // the input can be proposed again
+        propose();
+        assert_eq!(UtxoNFT::locked_tokens(1), Some(2));
+    });
+}
+
+#[test]
+fn it_skips_removed_proposals_when_expiring() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token(1, 2);
+        propose();
+        assert_ok!(UtxoNFT::cancel_proposal(RuntimeOrigin::signed(1), 1));
+        propose();
+
+        run_to_block(11, true);
+        assert_eq!(UtxoNFT::next_expiring_proposal(), 2);
+        assert!(UtxoNFT::proposals_by_id(2).is_some());
+
+        assert_err!(
+            UtxoNFT::approve_process(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::ProposalNotFound
+        );
+    });
+}
@@ -0,0 +1,187 @@
+use crate::{output::Output, tests::mock::*, Error, Event};
+use frame_support::{assert_err, assert_ok};
+use sp_runtime::{bounded_btree_map, bounded_vec, testing::TestSignature, BoundedVec};
+use sqnc_pallet_traits::ProcessFullyQualifiedId;
+
+const SUCCEED_PROCESS: ProcessFullyQualifiedId<ProcessIdentifier, u32> = ProcessFullyQualifiedId {
+    id: ProcessIdentifier::ShouldSucceed,
+    version: 0u32,
+};
+const APPROVAL_PROCESS: ProcessFullyQualifiedId<ProcessIdentifier, u32> = ProcessFullyQualifiedId {
+    id: ProcessIdentifier::RequiresApproval,
+    version: 0u32,
+};
+
+// creates token 1 owned by 1 with 2 as its `NotOwner`
+fn create_token() {
+    assert_ok!(UtxoNFT::run_process(
+        RuntimeOrigin::signed(1),
+        SUCCEED_PROCESS,
+        bounded_vec![],
+        bounded_vec![Output {
+            roles: bounded_btree_map!(Role::Owner => 1, Role::NotOwner => 2),
+            metadata: bounded_btree_map!(0 => MetadataValue::Literal([0]))
+        }]
+    ));
+}
+
+// signs a run of `APPROVAL_PROCESS` by 1 consuming token 1 as `account`
+fn sign(account: u64) -> TestSignature {
+    let nonce = UtxoNFT::signature_nonces(account);
+    let payload = UtxoNFT::run_process_payload(&1, &APPROVAL_PROCESS, &bounded_vec![1], &BoundedVec::new(), nonce);
+    TestSignature(account, payload)
+}
+
+#[test]
+fn it_runs_a_process_approved_by_a_detached_signature() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_token();
+
+        assert_ok!(UtxoNFT::run_process_with_signatures(
+            RuntimeOrigin::signed(1),
+            APPROVAL_PROCESS,
+            bounded_vec![1],
+            bounded_vec![],
+            bounded_vec![(2, sign(2))]
+        ));
+
+        assert_eq!(UtxoNFT::tokens_by_id(1).unwrap().children, Some(bounded_vec![]));
+        assert_eq!(
+            System::events().iter().last().unwrap().event,
+            RuntimeEvent::UtxoNFT(Event::ProcessRan {
+                sender: 1,
+                process: APPROVAL_PROCESS,
+                inputs: bounded_vec![1],
+                outputs: bounded_vec![],
+            }),
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_run_with_a_signature_of_another_account() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_err!(
+            UtxoNFT::run_process_with_signatures(
+                RuntimeOrigin::signed(1),
+                APPROVAL_PROCESS,
+                bounded_vec![1],
+                bounded_vec![],
+                bounded_vec![(2, sign(3))]
+            ),
+            Error::<Test>::InvalidSignature
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_run_with_a_signature_of_another_run() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        let payload = UtxoNFT::run_process_payload(&1, &SUCCEED_PROCESS, &bounded_vec![1], &BoundedVec::new(), 0);
+        assert_err!(
+            UtxoNFT::run_process_with_signatures(
+                RuntimeOrigin::signed(1),
+                APPROVAL_PROCESS,
+                bounded_vec![1],
+                bounded_vec![],
+                bounded_vec![(2, TestSignature(2, payload))]
+            ),
+            Error::<Test>::InvalidSignature
+        );
+        assert_eq!(UtxoNFT::tokens_by_id(1).unwrap().children, None);
+    });
+}
+
+#[test]
+fn it_fails_to_run_without_a_signature_of_every_approver() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_err!(
+            UtxoNFT::run_process_with_signatures(
+                RuntimeOrigin::signed(1),
+                APPROVAL_PROCESS,
+                bounded_vec![1],
+                bounded_vec![],
+                bounded_vec![(3, sign(3))]
+            ),
+            Error::<Test>::ApprovalRequired
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_reuse_a_signature_once_its_inputs_are_burnt() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_ok!(UtxoNFT::run_process_with_signatures(
+            RuntimeOrigin::signed(1),
+            APPROVAL_PROCESS,
+            bounded_vec![1],
+            bounded_vec![],
+            bounded_vec![(2, sign(2))]
+        ));
+        assert_err!(
+            UtxoNFT::run_process_with_signatures(
+                RuntimeOrigin::signed(1),
+                APPROVAL_PROCESS,
+                bounded_vec![1],
+                bounded_vec![],
+                bounded_vec![(2, sign(2))]
+            ),
+            Error::<Test>::AlreadyBurnt
+        );
+    });
+}
+
+#[test]
+fn it_fails_to_replay_a_signature_of_a_run_without_inputs() {
+    new_test_ext().execute_with(|| {
+        let outputs = bounded_vec![Output {
+            roles: bounded_btree_map!(Role::Owner => 1),
+            metadata: bounded_btree_map!(0 => MetadataValue::Literal([0]))
+        }];
+        let payload = UtxoNFT::run_process_payload(&1, &SUCCEED_PROCESS, &BoundedVec::new(), &outputs, 0);
+        let signature = TestSignature(2, payload);
+        assert_ok!(UtxoNFT::run_process_with_signatures(
+            RuntimeOrigin::signed(1),
+            SUCCEED_PROCESS,
+            bounded_vec![],
+            outputs.clone(),
+            bounded_vec![(2, signature.clone())]
+        ));
+        assert_eq!(UtxoNFT::signature_nonces(2), 1);
+
+        assert_err!(
+            UtxoNFT::run_process_with_signatures(
+                RuntimeOrigin::signed(1),
+                SUCCEED_PROCESS,
+                bounded_vec![],
+                outputs,
+                bounded_vec![(2, signature)]
+            ),
+            Error::<Test>::InvalidSignature
+        );
+        assert_eq!(UtxoNFT::last_token(), 1);
+    });
+}
+
+#[test]
+fn it_fails_to_run_with_a_signature_for_another_sender() {
+    new_test_ext().execute_with(|| {
+        create_token();
+        assert_err!(
+            UtxoNFT::run_process_with_signatures(
+                RuntimeOrigin::signed(3),
+                APPROVAL_PROCESS,
+                bounded_vec![1],
+                bounded_vec![],
+                bounded_vec![(2, sign(2))]
+            ),
+            Error::<Test>::InvalidSignature
+        );
+        assert_eq!(UtxoNFT::signature_nonces(2), 0);
+    });
+}
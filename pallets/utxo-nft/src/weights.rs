@@ -8,6 +8,14 @@ use frame_support::{traits::Get, weights::Weight};
 pub trait WeightInfo {
     fn run_process(i: u32, o: u32) -> Weight;
     fn delete_token() -> Weight;
+    fn run_process_with_signatures(i: u32, o: u32, s: u32) -> Weight;
+    fn propose_process(i: u32, o: u32) -> Weight;
+    fn approve_process() -> Weight;
+    fn approve_process_and_run(i: u32, o: u32) -> Weight;
+    fn cancel_proposal(i: u32) -> Weight;
+    fn lock_token() -> Weight;
+    fn unlock_token() -> Weight;
+    fn expire_token_locks(l: u32) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -17,4 +25,28 @@ impl WeightInfo for () {
     fn delete_token() -> Weight {
         Weight::from_parts(0, 0)
     }
+    fn run_process_with_signatures(_: u32, _: u32, _: u32) -> Weight {
+        Weight::from_parts(0, 0)
+    }
+    fn propose_process(_: u32, _: u32) -> Weight {
+        Weight::from_parts(0, 0)
+    }
+    fn approve_process() -> Weight {
+        Weight::from_parts(0, 0)
+    }
+    fn approve_process_and_run(_: u32, _: u32) -> Weight {
+        Weight::from_parts(0, 0)
+    }
+    fn cancel_proposal(_: u32) -> Weight {
+        Weight::from_parts(0, 0)
+    }
+    fn lock_token() -> Weight {
+        Weight::from_parts(0, 0)
+    }
+    fn unlock_token() -> Weight {
+        Weight::from_parts(0, 0)
+    }
+    fn expire_token_locks(_: u32) -> Weight {
+        Weight::from_parts(0, 0)
+    }
 }
@@ -8,7 +8,10 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use frame_support::{
     derive_impl,
-    traits::{ConstU128, ConstU32, ConstU64, EitherOfDiverse, EqualPrivilegeOnly, InstanceFilter},
+    traits::{
+        ConstU128, ConstU32, ConstU64, EitherOfDiverse, EqualPrivilegeOnly, InstanceFilter, QueryPreimage,
+        SortedMembers,
+    },
 };
 use frame_system::EnsureRoot;
 use pallet_grandpa::AuthorityId as GrandpaId;
@@ -17,11 +20,11 @@ use scale_info::TypeInfo;
 use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::codec::{Decode, Encode, MaxEncodedLen};
-use sp_runtime::traits::{BlakeTwo256, Block as BlockT, NumberFor};
+use sp_runtime::traits::{BlakeTwo256, Block as BlockT, NumberFor, Verify};
 use sp_runtime::{
     generic, impl_opaque_keys,
     traits::OpaqueKeys,
-    transaction_validity::{TransactionSource, TransactionValidity},
+    transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
     ApplyExtrinsicResult, RuntimeDebug,
 };
 use sp_std::borrow::Cow;
@@ -46,14 +49,20 @@ pub use frame_support::{
 };
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
+use pallet_organisation_data::ValidateOrgData;
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment_free::CurrencyAdapter;
+use pallet_transaction_payment_free::{CurrencyAdapter, QuotaMeter, TransactionPolicy};
 
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
 
 pub use sqnc_pallet_traits::ValidateProcessWeights;
+#[cfg(feature = "runtime-benchmarks")]
+use {
+    frame_support::traits::EnsureOrigin,
+    sqnc_pallet_traits::{ProcessApproval, ProcessFullyQualifiedId},
+};
 
 pub use sqnc_runtime_types::*;
 
@@ -93,7 +102,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: Cow::Borrowed("sqnc"),
     impl_name: Cow::Borrowed("sqnc"),
     authoring_version: 1,
-    spec_version: 1144,
+    spec_version: 1145,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -232,8 +241,74 @@ impl pallet_balances::Config for Runtime {
     type DoneSlashHandler = ();
 }
 
+// Calls to `UtxoNFT`, including those made through a proxy, count towards the quota of their signer
+pub struct UtxoNFTQuotaMeter;
+impl QuotaMeter<RuntimeCall> for UtxoNFTQuotaMeter {
+    fn tokens_minted(call: &RuntimeCall) -> Option<u32> {
+        match call {
+            RuntimeCall::UtxoNFT(pallet_utxo_nft::Call::run_process { outputs, .. })
+            | RuntimeCall::UtxoNFT(pallet_utxo_nft::Call::run_process_with_signatures { outputs, .. })
+            | RuntimeCall::UtxoNFT(pallet_utxo_nft::Call::propose_process { outputs, .. }) => {
+                Some(outputs.len() as u32)
+            }
+            RuntimeCall::UtxoNFT(..) => Some(0),
+            RuntimeCall::Proxy(pallet_proxy::Call::proxy { call, .. })
+            | RuntimeCall::Proxy(pallet_proxy::Call::proxy_announced { call, .. }) => Self::tokens_minted(call),
+            _ => None,
+        }
+    }
+}
+
+/// The classes of calls that share a transaction pool priority and non-member fee
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum CallClass {
+    Governance,
+    KeyRotation,
+    RunProcess,
+    Other,
+}
+
+// Governance and key rotation outrank process runs. Members of the governance membership transact for free
+pub struct MembersFreePolicy;
+impl TransactionPolicy<AccountId, RuntimeCall> for MembersFreePolicy {
+    type CallClass = CallClass;
+
+    fn call_class(call: &RuntimeCall) -> CallClass {
+        match call {
+            RuntimeCall::TechnicalCommittee(..)
+            | RuntimeCall::Membership(..)
+            | RuntimeCall::Sudo(..)
+            | RuntimeCall::Doas(..) => CallClass::Governance,
+            RuntimeCall::IpfsKey(pallet_symmetric_key::Call::rotate_key { .. }) | RuntimeCall::Session(..) => {
+                CallClass::KeyRotation
+            }
+            RuntimeCall::UtxoNFT(..) => CallClass::RunProcess,
+            RuntimeCall::Proxy(pallet_proxy::Call::proxy { call, .. })
+            | RuntimeCall::Proxy(pallet_proxy::Call::proxy_announced { call, .. }) => Self::call_class(call),
+            _ => CallClass::Other,
+        }
+    }
+
+    fn default_priority(class: &CallClass) -> TransactionPriority {
+        match class {
+            CallClass::Governance | CallClass::KeyRotation => 2,
+            CallClass::Other => 1,
+            CallClass::RunProcess => 0,
+        }
+    }
+
+    fn is_member(who: &AccountId) -> bool {
+        <Membership as SortedMembers<AccountId>>::contains(who)
+    }
+}
+
 impl pallet_transaction_payment_free::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
     type OnFreeTransaction = CurrencyAdapter<Balances, ()>;
+    type QuotaMeter = UtxoNFTQuotaMeter;
+    type UpdateQuotaOrigin = MoreThanTwoMembers;
+    type TransactionPolicy = MembersFreePolicy;
+    type UpdatePolicyOrigin = MoreThanTwoMembers;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -342,6 +417,10 @@ impl pallet_preimage::Config for Runtime {
 
 parameter_types! {
     pub const TokenTombstoneDuration: BlockNumber = 7 * DAYS;
+    pub const ProposalExpiry: BlockNumber = 7 * DAYS;
+    pub const TokenLockDuration: BlockNumber = 1 * DAYS;
+    pub const RunProcessScope: DelegateScope = DelegateScope::RunProcess;
+    pub const SignProcessScope: DelegateScope = DelegateScope::SignProcess;
 }
 
 impl pallet_utxo_nft::Config for Runtime {
@@ -357,6 +436,56 @@ impl pallet_utxo_nft::Config for Runtime {
     type MaxInputCount = ConstU32<64>;
     type MaxOutputCount = ConstU32<64>;
     type TokenTombstoneDuration = TokenTombstoneDuration;
+    type MaxApprovalCount = ConstU32<16>;
+    type ProposalExpiry = ProposalExpiry;
+    type TokenLockDuration = TokenLockDuration;
+    type OffchainSignature = Signature;
+    type SigningPublicKey = <Signature as Verify>::Signer;
+    type MaxSignatureCount = ConstU32<16>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = UtxoNFTBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct UtxoNFTBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl
+    pallet_utxo_nft::BenchmarkHelper<
+        ProcessFullyQualifiedId<ProcessIdentifier, ProcessVersion>,
+        Role,
+        AccountId,
+        Signature,
+    > for UtxoNFTBenchmarkHelper
+{
+    fn process_requiring_approval(
+        approvals: Vec<ProcessApproval<Role>>,
+    ) -> ProcessFullyQualifiedId<ProcessIdentifier, ProcessVersion> {
+        let id = ProcessIdentifier::truncate_from(b"approval".to_vec());
+        let origin = <Runtime as pallet_process_validation::Config>::CreateProcessOrigin::try_successful_origin()
+            .expect("process creation origin can be benchmarked");
+        ProcessValidation::create_process_with_approvals(
+            origin,
+            id.clone(),
+            RuntimeProgram::truncate_from(vec![RuntimeExpressionSymbol::Restriction(RuntimeRestriction::None)]),
+            frame_support::BoundedVec::truncate_from(approvals),
+        )
+        .expect("process requiring approval can be created");
+
+        let version = ProcessValidation::version_model(&id);
+        ProcessFullyQualifiedId { id, version }
+    }
+
+    fn sign(payload: &[u8]) -> (AccountId, Signature) {
+        use sp_runtime::app_crypto::RuntimePublic;
+
+        // a new key is generated in the benchmark keystore for each signature
+        let key_type = KeyTypeId(*b"bnch");
+        let public = sp_core::sr25519::Public::generate_pair(key_type, None);
+        let signature = public
+            .sign(key_type, &payload)
+            .expect("generated key is in the keystore");
+        (public.into(), signature.into())
+    }
 }
 
 impl pallet_process_validation::Config for Runtime {
@@ -372,13 +501,78 @@ impl pallet_process_validation::Config for Runtime {
     type TokenMetadataValue = TokenMetadataValue;
     type TokenMetadataValueDiscriminator = MetadataValueType;
     type MaxProcessProgramLength = MaxProcessProgramLength;
+    type MaxApprovalCount = ConstU32<16>;
+    type OrgDataKey = OrgDataKey;
+    type OrgDataValue = OrgDataValue;
+    type OrganisationData = OrganisationData;
+    type OrganisationAttributes = OrganisationData;
+    type MaxPermissionCount = ConstU32<16>;
+    type DelegateScope = DelegateScope;
+    type OrganisationDelegates = OrganisationData;
+    type RunProcessScope = RunProcessScope;
+    type SignProcessScope = SignProcessScope;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ProcessValidationBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ProcessValidationBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_process_validation::BenchmarkHelper<TokenMetadataValue, OrgDataKey, OrgDataValue, AccountId>
+    for ProcessValidationBenchmarkHelper
+{
+    fn largest_metadata_value() -> TokenMetadataValue {
+        // a full list of full literals encodes to more bytes than any other metadata value
+        let literal = frame_support::BoundedVec::truncate_from(vec![u8::MAX; 32]);
+        let literals = vec![literal; MAX_METADATA_LIST_LENGTH as usize];
+        MetadataValue::List(frame_support::BoundedVec::truncate_from(literals))
+    }
+
+    fn largest_org_data() -> (OrgDataKey, OrgDataValue) {
+        let literal = vec![u8::MAX; ORG_DATA_VALUE_MAX_LENGTH as usize];
+        (
+            OrgDataKey::DisplayName,
+            OrgDataValue::Literal(frame_support::BoundedVec::truncate_from(literal)),
+        )
+    }
+
+    fn set_delegate(delegate: &AccountId, organisation: &AccountId) {
+        // written directly as the organisation need not be a member for its delegates to be resolved
+        let scopes = vec![DelegateScope::RunProcess, DelegateScope::SignProcess];
+        pallet_organisation_data::Delegations::<Runtime>::insert(
+            delegate,
+            pallet_organisation_data::Delegation {
+                organisation: organisation.clone(),
+                scopes: frame_support::BoundedVec::truncate_from(scopes),
+            },
+        );
+    }
+
+    fn grant_attribute(account: &AccountId, attribute: &OrgAttribute) {
+        // written directly as governance grants attributes only to members
+        pallet_organisation_data::OrgAttributes::<Runtime>::insert(account, attribute, BlockNumber::MAX);
+    }
+}
+
+pub struct OrgDataValidator;
+impl ValidateOrgData<OrgDataKey, OrgDataValue> for OrgDataValidator {
+    fn is_valid(key: &OrgDataKey, value: &OrgDataValue) -> bool {
+        value.is_valid_for(key)
+    }
 }
 
 impl pallet_organisation_data::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type OrgDataKey = OrgDataKey;
     type OrgDataValue = OrgDataValue;
+    type ValidateOrgData = OrgDataValidator;
     type MaxOrgMemberEntries = ConstU32<ORG_DATA_KEY_COUNT>;
+    type MaxOrgDataHistory = ConstU32<8>;
+    type OrgAttribute = OrgAttribute;
+    type SetAttributeOrigin = MoreThanHalfMembers;
+    type DelegateScope = DelegateScope;
+    type MaxOrgDelegates = ConstU32<32>;
+    type MaxDelegateScopes = ConstU32<DELEGATE_SCOPE_COUNT>;
     type WeightInfo = weights::pallet_organisation_data::WeightInfo<Runtime>;
 }
 
@@ -513,6 +707,8 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 type Migrations = (
     pallet_symmetric_key::migrations::v1::MigrateToV1<Runtime>,
     pallet_organisation_data::migrations::v1::MigrateToV1<Runtime, GovernanceMembershipInstance>,
+    pallet_organisation_data::migrations::v2::MigrateToV2<Runtime>,
+    pallet_utxo_nft::migration::v2::MigrateToV2<Runtime>,
 );
 
 /// Executive: handles dispatch to the various modules.
@@ -557,6 +753,16 @@ impl frame_system_benchmarking::Config for Runtime {}
 #[cfg(feature = "runtime-benchmarks")]
 impl frame_benchmarking::baseline::Config for Runtime {}
 
+// The content of the preimage referenced by an organisation data value, if it has been noted
+fn resolve_org_data_preimage(value: &OrgDataValue) -> Option<Vec<u8>> {
+    match value {
+        OrgDataValue::Preimage(hash) => <Preimage as QueryPreimage>::fetch(hash, None)
+            .ok()
+            .map(|preimage| preimage.into_owned()),
+        _ => None,
+    }
+}
+
 impl_runtime_apis! {
     impl sp_api::Core<Block> for Runtime {
         fn version() -> RuntimeVersion {
@@ -746,6 +952,16 @@ impl_runtime_apis! {
         fn query_length_to_fee(_: u32) -> Balance { 0 }
     }
 
+    impl pallet_organisation_data::runtime_api::OrganisationDataApi<Block, AccountId, OrgDataKey, OrgDataValue, BlockNumber> for Runtime {
+        fn organisations() -> Vec<pallet_organisation_data::Organisation<AccountId, OrgDataKey, OrgDataValue, BlockNumber>> {
+            OrganisationData::organisations(resolve_org_data_preimage)
+        }
+
+        fn organisation(account: AccountId) -> Option<pallet_organisation_data::Organisation<AccountId, OrgDataKey, OrgDataValue, BlockNumber>> {
+            OrganisationData::organisation(account, resolve_org_data_preimage)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn benchmark_metadata(extra: bool) -> (
@@ -847,4 +1063,45 @@ mod tests {
         // System Events
         assert!(whitelist.contains("26aa394eea5630e07c48ae0c9558cef780d41e5e16056765bc8461851072c9d7"));
     }
+
+    #[test]
+    fn check_org_data_endpoints_accept_existing_values() {
+        let endpoints = [OrgDataKey::AttachmentEndpoint, OrgDataKey::OidcConfigurationEndpoint];
+        for key in endpoints.iter() {
+            assert!(OrgDataValidator::is_valid(
+                key,
+                &OrgDataValue::Literal(Default::default())
+            ));
+            assert!(OrgDataValidator::is_valid(
+                key,
+                &OrgDataValue::Preimage(Default::default())
+            ));
+        }
+        assert!(!OrgDataValidator::is_valid(
+            &OrgDataKey::DisplayName,
+            &OrgDataValue::Preimage(Default::default())
+        ));
+    }
+
+    #[test]
+    fn check_pallet_indices() {
+        let create_process = RuntimeCall::ProcessValidation(pallet_process_validation::Call::create_process {
+            id: Default::default(),
+            program: Default::default(),
+        });
+        assert_eq!(
+            create_process.encode()[0],
+            sqnc_runtime_types::PROCESS_VALIDATION_PALLET_INDEX
+        );
+
+        let propose = RuntimeCall::TechnicalCommittee(pallet_collective::Call::propose {
+            threshold: 1,
+            proposal: Box::new(create_process),
+            length_bound: 0,
+        });
+        assert_eq!(
+            propose.encode()[0],
+            sqnc_runtime_types::TECHNICAL_COMMITTEE_PALLET_INDEX
+        );
+    }
 }
@@ -1,11 +1,19 @@
-use frame_support::weights::Weight;
+use frame_support::{traits::Get, weights::Weight};
 
 use super::pallet_process_validation::WeightInfo;
 use sqnc_pallet_traits::ValidateProcessWeights;
+use sqnc_runtime_types::MaxProcessProgramLength;
+
+/// A program of `p` symbols has at most `(p + 1) / 2` restrictions, each of which may read an attribute of the sender
+/// before it is executed. The attribute benchmark is for programs whose every restriction does so, so validation is
+/// charged whichever of it and the metadata hashing benchmark is heavier
+fn validate_process_attributes<T: frame_system::Config>(p: u32) -> Weight {
+    <WeightInfo<T> as pallet_process_validation::WeightInfo>::validate_process_attributes(p.saturating_add(1) / 2)
+}
 
 impl<T: frame_system::Config> ValidateProcessWeights<u32> for WeightInfo<T> {
     fn validate_process(p: u32) -> Weight {
-        <Self as pallet_process_validation::WeightInfo>::validate_process(p)
+        <Self as pallet_process_validation::WeightInfo>::validate_process(p).max(validate_process_attributes::<T>(p))
     }
 
     fn validate_process_min() -> Weight {
@@ -14,5 +22,11 @@ impl<T: frame_system::Config> ValidateProcessWeights<u32> for WeightInfo<T> {
 
     fn validate_process_max() -> Weight {
         <Self as pallet_process_validation::WeightInfo>::validate_process_max()
+            .max(validate_process_attributes::<T>(MaxProcessProgramLength::get()))
+    }
+
+    fn validate_process_signers(s: u32) -> Weight {
+        <Self as pallet_process_validation::WeightInfo>::validate_process_with_signers(s)
+            .saturating_sub(<Self as pallet_process_validation::WeightInfo>::validate_process_with_signers(0))
     }
 }
@@ -6,6 +6,11 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `FNQGF7746D.local`, CPU: `<UNKNOWN>`
 //! WASM-EXECUTION: `Compiled`, CHAIN: `None`, DB CACHE: 1024
+//!
+//! Every weight in this file is a hand estimate, not benchmark output: `set_value` was edited for the value history
+//! and `remove_value`, `set_attribute`, `remove_attribute`, `offer_delegation`, `accept_delegation` and
+//! `remove_delegate` were added without being benchmarked. They are rounded, marked `Hand estimate, not measured` and
+//! carry no measured proof sizes. Regenerate this file to replace them.
 
 // Executed Command:
 // ./target/production/sqnc-node
@@ -34,15 +39,86 @@ impl<T: frame_system::Config> pallet_organisation_data::WeightInfo for WeightInf
 	/// Storage: `OrganisationData::OrgDataCount` (r:1 w:1)
 	/// Proof: `OrganisationData::OrgDataCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
 	/// Storage: `OrganisationData::OrgData` (r:1 w:1)
-	/// Proof: `OrganisationData::OrgData` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Proof: `OrganisationData::OrgData` (`max_values`: None, `max_size`: Some(327), added: 2802, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::OrgDataHistory` (r:1 w:1)
+	/// Proof: `OrganisationData::OrgDataHistory` (`max_values`: None, `max_size`: Some(2721), added: 5196, mode: `MaxEncodedLen`)
 	fn set_value() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `258`
-		//  Estimated: `3564`
-		// Minimum execution time: 11_000_000 picoseconds.
-		Weight::from_parts(12_000_000, 0)
-			.saturating_add(Weight::from_parts(0, 3564))
+		// Hand estimate, not measured
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6186))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `OrganisationData::OrgDataCount` (r:1 w:1)
+	/// Proof: `OrganisationData::OrgDataCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::OrgData` (r:1 w:1)
+	/// Proof: `OrganisationData::OrgData` (`max_values`: None, `max_size`: Some(327), added: 2802, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::OrgDataHistory` (r:1 w:1)
+	/// Proof: `OrganisationData::OrgDataHistory` (`max_values`: None, `max_size`: Some(2721), added: 5196, mode: `MaxEncodedLen`)
+	fn remove_value() -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6186))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `OrganisationData::OrgDataCount` (r:1 w:0)
+	/// Proof: `OrganisationData::OrgDataCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::OrgAttributes` (r:0 w:1)
+	/// Proof: `OrganisationData::OrgAttributes` (`max_values`: None, `max_size`: Some(101), added: 2576, mode: `MaxEncodedLen`)
+	fn set_attribute() -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3517))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `OrganisationData::OrgAttributes` (r:1 w:1)
+	/// Proof: `OrganisationData::OrgAttributes` (`max_values`: None, `max_size`: Some(101), added: 2576, mode: `MaxEncodedLen`)
+	fn remove_attribute() -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3566))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `OrganisationData::OrgDataCount` (r:2 w:0)
+	/// Proof: `OrganisationData::OrgDataCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::DelegateOffers` (r:0 w:1)
+	/// Proof: `OrganisationData::DelegateOffers` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	fn offer_delegation() -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 5044))
 			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `OrganisationData::DelegateOffers` (r:1 w:1)
+	/// Proof: `OrganisationData::DelegateOffers` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::OrgDataCount` (r:2 w:0)
+	/// Proof: `OrganisationData::OrgDataCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::Delegations` (r:1 w:1)
+	/// Proof: `OrganisationData::Delegations` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::OrgDelegates` (r:1 w:1)
+	/// Proof: `OrganisationData::OrgDelegates` (`max_values`: None, `max_size`: Some(1073), added: 3548, mode: `MaxEncodedLen`)
+	fn accept_delegation() -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(24_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6044))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `OrganisationData::Delegations` (r:1 w:1)
+	/// Proof: `OrganisationData::Delegations` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::DelegateOffers` (r:1 w:1)
+	/// Proof: `OrganisationData::DelegateOffers` (`max_values`: None, `max_size`: Some(99), added: 2574, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::OrgDelegates` (r:1 w:1)
+	/// Proof: `OrganisationData::OrgDelegates` (`max_values`: None, `max_size`: Some(1073), added: 3548, mode: `MaxEncodedLen`)
+	fn remove_delegate() -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4538))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
 	}
 }
@@ -6,6 +6,11 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `FNQGF7746D.local`, CPU: `<UNKNOWN>`
 //! WASM-EXECUTION: `Compiled`, CHAIN: `None`, DB CACHE: 1024
+//!
+//! `create_process_with_approvals`, `set_process_permissions`, `set_process_delegation`, `validate_process`,
+//! `validate_process_min`, `validate_process_max`, `validate_process_with_signers` and `validate_process_attributes`
+//! are hand estimates, not benchmark output. They are rounded, marked `Hand estimate, not measured` and carry no
+//! measured proof sizes. Regenerate this file to replace them.
 
 // Executed Command:
 // ./target/production/sqnc-node
@@ -48,6 +53,23 @@ impl<T: frame_system::Config> pallet_process_validation::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	/// Storage: `ProcessValidation::VersionModel` (r:1 w:1)
+	/// Proof: `ProcessValidation::VersionModel` (`max_values`: None, `max_size`: Some(53), added: 2528, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:1)
+	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessApprovals` (r:0 w:1)
+	/// Proof: `ProcessValidation::ProcessApprovals` (`max_values`: None, `max_size`: Some(662), added: 3137, mode: `MaxEncodedLen`)
+	/// The range of component `r` is `[1, 251]`.
+	/// The range of component `a` is `[1, 16]`.
+	fn create_process_with_approvals(r: u32, a: u32, ) -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 41613))
+			.saturating_add(Weight::from_parts(300_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(100_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
 	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:1)
 	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
 	/// Storage: `ProcessValidation::VersionModel` (r:1 w:0)
@@ -64,38 +86,116 @@ impl<T: frame_system::Config> pallet_process_validation::WeightInfo for WeightIn
 	}
 	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:0)
 	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::VersionModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::VersionModel` (`max_values`: None, `max_size`: Some(53), added: 2528, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessPermissionsModel` (r:0 w:1)
+	/// Proof: `ProcessValidation::ProcessPermissionsModel` (`max_values`: None, `max_size`: Some(4341), added: 6816, mode: `MaxEncodedLen`)
+	/// The range of component `p` is `[1, 16]`.
+	fn set_process_permissions(p: u32, ) -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 41613))
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::VersionModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::VersionModel` (`max_values`: None, `max_size`: Some(53), added: 2528, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessDelegationModel` (r:0 w:1)
+	/// Proof: `ProcessValidation::ProcessDelegationModel` (`max_values`: None, `max_size`: Some(70), added: 2545, mode: `MaxEncodedLen`)
+	fn set_process_delegation() -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 41613))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessDelegationModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessDelegationModel` (`max_values`: None, `max_size`: Some(70), added: 2545, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::Delegations` (r:1 w:0)
+	/// Proof: `OrganisationData::Delegations` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessPermissionsModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessPermissionsModel` (`max_values`: None, `max_size`: Some(4341), added: 6816, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::OrgData` (r:30 w:0)
+	/// Proof: `OrganisationData::OrgData` (`max_values`: None, `max_size`: Some(327), added: 2802, mode: `MaxEncodedLen`)
 	/// The range of component `r` is `[1, 251]`.
 	fn validate_process(r: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `136 + r * (14 ±0)`
-		//  Estimated: `41613`
-		// Minimum execution time: 5_000_000 picoseconds.
-		Weight::from_parts(5_940_348, 0)
-			.saturating_add(Weight::from_parts(0, 41613))
-			// Standard Error: 157
-			.saturating_add(Weight::from_parts(149_739, 0).saturating_mul(r.into()))
-			.saturating_add(T::DbWeight::get().reads(1))
+		// Hand estimate, not measured
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 137592))
+			.saturating_add(Weight::from_parts(22_000_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(34))
 	}
 	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:0)
 	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessDelegationModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessDelegationModel` (`max_values`: None, `max_size`: Some(70), added: 2545, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessPermissionsModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessPermissionsModel` (`max_values`: None, `max_size`: Some(4341), added: 6816, mode: `MaxEncodedLen`)
 	fn validate_process_min() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `146`
-		//  Estimated: `41613`
-		// Minimum execution time: 5_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 0)
-			.saturating_add(Weight::from_parts(0, 41613))
-			.saturating_add(T::DbWeight::get().reads(1))
+		// Hand estimate, not measured
+		Weight::from_parts(7_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 49984))
+			.saturating_add(T::DbWeight::get().reads(3))
 	}
 	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:0)
 	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessDelegationModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessDelegationModel` (`max_values`: None, `max_size`: Some(70), added: 2545, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::Delegations` (r:1 w:0)
+	/// Proof: `OrganisationData::Delegations` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessPermissionsModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessPermissionsModel` (`max_values`: None, `max_size`: Some(4341), added: 6816, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::OrgData` (r:30 w:0)
+	/// Proof: `OrganisationData::OrgData` (`max_values`: None, `max_size`: Some(327), added: 2802, mode: `MaxEncodedLen`)
 	fn validate_process_max() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `3650`
-		//  Estimated: `41613`
-		// Minimum execution time: 40_000_000 picoseconds.
-		Weight::from_parts(43_000_000, 0)
-			.saturating_add(Weight::from_parts(0, 41613))
-			.saturating_add(T::DbWeight::get().reads(1))
+		// Hand estimate, not measured
+		Weight::from_parts(5_600_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 137592))
+			.saturating_add(T::DbWeight::get().reads(34))
+	}
+	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessDelegationModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessDelegationModel` (`max_values`: None, `max_size`: Some(70), added: 2545, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::Delegations` (r:17 w:0)
+	/// Proof: `OrganisationData::Delegations` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessPermissionsModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessPermissionsModel` (`max_values`: None, `max_size`: Some(4341), added: 6816, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 16]`.
+	fn validate_process_with_signers(s: u32, ) -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 52542))
+			.saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2558).saturating_mul(s.into()))
+	}
+	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessDelegationModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessDelegationModel` (`max_values`: None, `max_size`: Some(70), added: 2545, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::Delegations` (r:1 w:0)
+	/// Proof: `OrganisationData::Delegations` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessPermissionsModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessPermissionsModel` (`max_values`: None, `max_size`: Some(4341), added: 6816, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::OrgData` (r:30 w:0)
+	/// Proof: `OrganisationData::OrgData` (`max_values`: None, `max_size`: Some(327), added: 2802, mode: `MaxEncodedLen`)
+	/// Storage: `OrganisationData::OrgAttributes` (r:251 w:0)
+	/// Proof: `OrganisationData::OrgAttributes` (`max_values`: None, `max_size`: Some(101), added: 2576, mode: `MaxEncodedLen`)
+	/// The range of component `r` is `[1, 251]`.
+	fn validate_process_attributes(r: u32, ) -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 137592))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(34))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2576).saturating_mul(r.into()))
 	}
 }
@@ -6,6 +6,10 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `FNQGF7746D.local`, CPU: `<UNKNOWN>`
 //! WASM-EXECUTION: `Compiled`, CHAIN: `None`, DB CACHE: 1024
+//!
+//! `run_process_with_signatures`, `propose_process`, `approve_process`, `approve_process_and_run`, `cancel_proposal`,
+//! `lock_token`, `unlock_token` and `expire_token_locks` are hand estimates, not benchmark output. They are rounded,
+//! marked `Hand estimate, not measured` and carry no measured proof sizes. Regenerate this file to replace them.
 
 // Executed Command:
 // ./target/production/sqnc-node
@@ -77,4 +81,185 @@ impl<T: frame_system::Config> pallet_utxo_nft::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(3))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
+	/// Storage: `UtxoNFT::TokensById` (r:10 w:20)
+	/// Proof: `UtxoNFT::TokensById` (`max_values`: None, `max_size`: Some(7473), added: 9948, mode: `MaxEncodedLen`)
+	/// Storage: `System::BlockHash` (r:1 w:0)
+	/// Proof: `System::BlockHash` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::SignatureNonces` (r:16 w:16)
+	/// Proof: `UtxoNFT::SignatureNonces` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::LastToken` (r:1 w:1)
+	/// Proof: `UtxoNFT::LastToken` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::CurrentGraveyardState` (r:1 w:1)
+	/// Proof: `UtxoNFT::CurrentGraveyardState` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::EventTopics` (r:3 w:3)
+	/// Proof: `System::EventTopics` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `UtxoNFT::Graveyard` (r:0 w:10)
+	/// Proof: `UtxoNFT::Graveyard` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// The range of component `i` is `[1, 10]`.
+	/// The range of component `o` is `[1, 10]`.
+	/// The range of component `s` is `[0, 16]`.
+	fn run_process_with_signatures(i: u32, o: u32, s: u32, ) -> Weight {
+		// Hand estimate, not measured. The per-signature slope is a guess at one sr25519 verification
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 41613))
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(i.into()))
+			.saturating_add(Weight::from_parts(3_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(i.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(i.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 9948).saturating_mul(i.into()))
+			.saturating_add(Weight::from_parts(0, 2531).saturating_mul(s.into()))
+	}
+	/// Storage: `UtxoNFT::TokensById` (r:10 w:0)
+	/// Proof: `UtxoNFT::TokensById` (`max_values`: None, `max_size`: Some(7473), added: 9948, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::LockedTokens` (r:10 w:10)
+	/// Proof: `UtxoNFT::LockedTokens` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::TokenLocks` (r:10 w:0)
+	/// Proof: `UtxoNFT::TokenLocks` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessApprovals` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessApprovals` (`max_values`: None, `max_size`: Some(662), added: 3137, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::LastProposal` (r:1 w:1)
+	/// Proof: `UtxoNFT::LastProposal` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `System::EventTopics` (r:1 w:1)
+	/// Proof: `System::EventTopics` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `UtxoNFT::ProposalsById` (r:0 w:1)
+	/// Proof: `UtxoNFT::ProposalsById` (`max_values`: None, `max_size`: Some(75553), added: 78028, mode: `MaxEncodedLen`)
+	/// The range of component `i` is `[1, 10]`.
+	/// The range of component `o` is `[1, 10]`.
+	fn propose_process(i: u32, o: u32, ) -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 41613))
+			.saturating_add(Weight::from_parts(7_000_000, 0).saturating_mul(i.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(i.into())))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(i.into())))
+			.saturating_add(Weight::from_parts(0, 9948).saturating_mul(i.into()))
+	}
+	/// Storage: `UtxoNFT::ProposalsById` (r:1 w:1)
+	/// Proof: `UtxoNFT::ProposalsById` (`max_values`: None, `max_size`: Some(75553), added: 78028, mode: `MaxEncodedLen`)
+	/// Storage: `System::EventTopics` (r:2 w:2)
+	/// Proof: `System::EventTopics` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn approve_process() -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 79018))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `UtxoNFT::ProposalsById` (r:1 w:1)
+	/// Proof: `UtxoNFT::ProposalsById` (`max_values`: None, `max_size`: Some(75553), added: 78028, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::TokensById` (r:10 w:20)
+	/// Proof: `UtxoNFT::TokensById` (`max_values`: None, `max_size`: Some(7473), added: 9948, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::LockedTokens` (r:0 w:10)
+	/// Proof: `UtxoNFT::LockedTokens` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::TokenLocks` (r:10 w:10)
+	/// Proof: `UtxoNFT::TokenLocks` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::LastToken` (r:1 w:1)
+	/// Proof: `UtxoNFT::LastToken` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::CurrentGraveyardState` (r:1 w:1)
+	/// Proof: `UtxoNFT::CurrentGraveyardState` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::EventTopics` (r:4 w:4)
+	/// Proof: `System::EventTopics` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `UtxoNFT::Graveyard` (r:0 w:10)
+	/// Proof: `UtxoNFT::Graveyard` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// The range of component `i` is `[1, 10]`.
+	/// The range of component `o` is `[1, 10]`.
+	fn approve_process_and_run(i: u32, o: u32, ) -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(32_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 79018))
+			.saturating_add(Weight::from_parts(8_000_000, 0).saturating_mul(i.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(i.into())))
+			.saturating_add(T::DbWeight::get().writes(7))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(i.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 9948).saturating_mul(i.into()))
+	}
+	/// Storage: `UtxoNFT::ProposalsById` (r:1 w:1)
+	/// Proof: `UtxoNFT::ProposalsById` (`max_values`: None, `max_size`: Some(75553), added: 78028, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::LockedTokens` (r:0 w:10)
+	/// Proof: `UtxoNFT::LockedTokens` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `System::EventTopics` (r:1 w:1)
+	/// Proof: `System::EventTopics` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `i` is `[1, 10]`.
+	fn cancel_proposal(i: u32, ) -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 79018))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(i.into())))
+	}
+	/// Storage: `UtxoNFT::TokensById` (r:1 w:0)
+	/// Proof: `UtxoNFT::TokensById` (`max_values`: None, `max_size`: Some(7473), added: 9948, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::LockedTokens` (r:1 w:0)
+	/// Proof: `UtxoNFT::LockedTokens` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::TokenLocks` (r:1 w:1)
+	/// Proof: `UtxoNFT::TokenLocks` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::CurrentLockQueueState` (r:1 w:1)
+	/// Proof: `UtxoNFT::CurrentLockQueueState` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::EventTopics` (r:2 w:2)
+	/// Proof: `System::EventTopics` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ProcessValidation::ProcessModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessModel` (`max_values`: None, `max_size`: Some(38148), added: 40623, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessDelegationModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessDelegationModel` (`max_values`: None, `max_size`: Some(70), added: 2545, mode: `MaxEncodedLen`)
+	/// Storage: `ProcessValidation::ProcessPermissionsModel` (r:1 w:0)
+	/// Proof: `ProcessValidation::ProcessPermissionsModel` (`max_values`: None, `max_size`: Some(4341), added: 6816, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::LockQueue` (r:0 w:1)
+	/// Proof: `UtxoNFT::LockQueue` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	fn lock_token() -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 60922))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `UtxoNFT::TokenLocks` (r:1 w:1)
+	/// Proof: `UtxoNFT::TokenLocks` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `System::EventTopics` (r:2 w:2)
+	/// Proof: `System::EventTopics` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn unlock_token() -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3537))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `UtxoNFT::CurrentLockQueueState` (r:1 w:1)
+	/// Proof: `UtxoNFT::CurrentLockQueueState` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::LockQueue` (r:100 w:100)
+	/// Proof: `UtxoNFT::LockQueue` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `UtxoNFT::TokenLocks` (r:100 w:100)
+	/// Proof: `UtxoNFT::TokenLocks` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `System::EventTopics` (r:200 w:200)
+	/// Proof: `System::EventTopics` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `l` is `[0, 100]`.
+	fn expire_token_locks(l: u32, ) -> Weight {
+		// Hand estimate, not measured
+		Weight::from_parts(4_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 1501))
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(l.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(l.into())))
+			.saturating_add(Weight::from_parts(0, 5094).saturating_mul(l.into()))
+	}
 }
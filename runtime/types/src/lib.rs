@@ -11,7 +11,9 @@ use sp_runtime::{
 use strum::EnumCount;
 use strum_macros::{EnumCount, EnumDiscriminants};
 
-pub use pallet_process_validation::{BooleanExpressionSymbol, BooleanOperator, Restriction};
+pub use pallet_process_validation::{
+    BooleanExpressionSymbol, BooleanOperator, MetadataAccountAccess, MetadataValueAccess, OrgAttribute, Restriction,
+};
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -47,8 +49,23 @@ pub type RuntimeExpressionSymbol =
 pub type RuntimeRestriction = Restriction<Role, TokenMetadataKey, TokenMetadataValue, MetadataValueType>;
 pub type RuntimeProgram = BoundedVec<RuntimeExpressionSymbol, MaxProcessProgramLength>;
 
+/// Index of the `ProcessValidation` pallet in `construct_runtime!`, for tools encoding calls without the runtime
+pub const PROCESS_VALIDATION_PALLET_INDEX: u8 = 10;
+/// Index of the `TechnicalCommittee` pallet in `construct_runtime!`, for tools encoding calls without the runtime
+pub const TECHNICAL_COMMITTEE_PALLET_INDEX: u8 = 16;
+
 pub type Role = BoundedVec<u8, ConstU32<32>>;
 
+/// Maximum length in bytes of `Text` and `Bytes` metadata values. This is the single place the bound is configured: it
+/// is fixed at compile time rather than being a pallet `Config` parameter because `MetadataValue` is shared as is by the
+/// runtime, the process interpreter and sqnc-lang, which must all agree on its encoding. Changing it changes the
+/// max encoded length of stored tokens so requires a runtime upgrade and a matching sqnc-lang release
+pub const MAX_METADATA_VALUE_LENGTH: u32 = 256;
+pub type MaxMetadataValueLength = ConstU32<MAX_METADATA_VALUE_LENGTH>;
+/// Maximum number of literals in a `List` metadata value
+pub const MAX_METADATA_LIST_LENGTH: u32 = 16;
+pub type MaxMetadataListLength = ConstU32<MAX_METADATA_LIST_LENGTH>;
+
 #[derive(
     Encode, Decode, Clone, MaxEncodedLen, TypeInfo, PartialEq, Debug, Eq, EnumDiscriminants, Serialize, Deserialize,
 )]
@@ -61,6 +78,15 @@ pub enum MetadataValue<TokenId> {
     TokenId(TokenId),
     Integer(i128),
     None,
+    /// utf8 text longer than a `Literal` can hold
+    Text(BoundedVec<u8, MaxMetadataValueLength>),
+    Bytes(BoundedVec<u8, MaxMetadataValueLength>),
+    Bool(bool),
+    /// milliseconds since the unix epoch
+    Timestamp(Moment),
+    List(BoundedVec<BoundedVec<u8, ConstU32<32>>, MaxMetadataListLength>),
+    /// an account that isn't a role holder of the token, such as one that approved it
+    Account(AccountId),
 }
 
 impl<T> Default for MetadataValue<T> {
@@ -83,6 +109,38 @@ impl<T: PartialEq> PartialEq<T> for MetadataValue<T> {
     }
 }
 
+impl<T> MetadataValueAccess for MetadataValue<T> {
+    fn as_integer(&self) -> Option<i128> {
+        match self {
+            MetadataValue::<T>::Integer(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn as_literal(&self) -> Option<&[u8]> {
+        match self {
+            MetadataValue::<T>::Literal(v) => Some(v.as_slice()),
+            _ => None,
+        }
+    }
+
+    fn as_hash(&self) -> Option<&[u8]> {
+        match self {
+            MetadataValue::<T>::File(v) => Some(v.as_bytes()),
+            _ => None,
+        }
+    }
+}
+
+impl<T> MetadataAccountAccess<AccountId> for MetadataValue<T> {
+    fn as_account(&self) -> Option<&AccountId> {
+        match self {
+            MetadataValue::<T>::Account(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
 #[derive(
     Encode,
     Decode,
@@ -102,6 +160,10 @@ impl<T: PartialEq> PartialEq<T> for MetadataValue<T> {
 pub enum OrgDataKey {
     AttachmentEndpoint,
     OidcConfigurationEndpoint,
+    DisplayName,
+    LegalIdentifier,
+    PublicEncryptionKey,
+    LogoPreimage,
 }
 
 impl Default for OrgDataKey {
@@ -112,10 +174,25 @@ impl Default for OrgDataKey {
 
 pub const ORG_DATA_KEY_COUNT: u32 = OrgDataKey::COUNT as u32;
 
+/// Something a delegate may do on behalf of the member organisation it acts for
+#[derive(Encode, Decode, Clone, MaxEncodedLen, TypeInfo, PartialEq, Debug, Eq, Serialize, Deserialize, EnumCount)]
+pub enum DelegateScope {
+    /// Run processes as the organisation, where the process allows delegates
+    RunProcess,
+    /// Sign runs of processes as the organisation, where the process allows delegates
+    SignProcess,
+}
+
+pub const DELEGATE_SCOPE_COUNT: u32 = DelegateScope::COUNT as u32;
+
+/// Maximum length in bytes of a literal or URL organisation data value
+pub const ORG_DATA_VALUE_MAX_LENGTH: u32 = 256;
+
 #[derive(Encode, Decode, Clone, Debug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
 pub enum OrgDataValue {
-    Literal(BoundedVec<u8, ConstU32<32>>),
+    Literal(BoundedVec<u8, ConstU32<ORG_DATA_VALUE_MAX_LENGTH>>),
     Preimage(Hash),
+    Url(BoundedVec<u8, ConstU32<ORG_DATA_VALUE_MAX_LENGTH>>),
 }
 
 impl Default for OrgDataValue {
@@ -123,3 +200,38 @@ impl Default for OrgDataValue {
         OrgDataValue::Literal(BoundedVec::new())
     }
 }
+
+impl OrgDataValue {
+    /// Whether this is a value of the type allowed at `key`. URLs must also be well formed. The endpoint keys accept
+    /// any type of value as they did before values were checked
+    pub fn is_valid_for(&self, key: &OrgDataKey) -> bool {
+        match (key, self) {
+            (
+                OrgDataKey::AttachmentEndpoint | OrgDataKey::OidcConfigurationEndpoint,
+                OrgDataValue::Literal(_) | OrgDataValue::Preimage(_),
+            ) => true,
+            (OrgDataKey::AttachmentEndpoint | OrgDataKey::OidcConfigurationEndpoint, OrgDataValue::Url(url)) => {
+                is_valid_url(url)
+            }
+            (
+                OrgDataKey::DisplayName | OrgDataKey::LegalIdentifier | OrgDataKey::PublicEncryptionKey,
+                OrgDataValue::Literal(_),
+            ) => true,
+            (OrgDataKey::LogoPreimage, OrgDataValue::Preimage(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+// an http(s) URL with a host and no whitespace or control characters
+fn is_valid_url(url: &[u8]) -> bool {
+    let rest = match (url.strip_prefix(b"https://"), url.strip_prefix(b"http://")) {
+        (Some(rest), _) | (None, Some(rest)) => rest,
+        (None, None) => return false,
+    };
+    let host_len = rest
+        .iter()
+        .position(|c| matches!(c, b'/' | b'?' | b'#'))
+        .unwrap_or(rest.len());
+    host_len > 0 && url.iter().all(|c| c.is_ascii_graphic())
+}
@@ -0,0 +1,4 @@
+//! WebAssembly build of the `sqnc-lang` compiler. The bindings live in `sqnc_lang::wasm` and are only re-exported
+//! here so that `sqnc-lang` itself isn't built as a `cdylib`
+
+pub use sqnc_lang::wasm::*;
@@ -0,0 +1,772 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use super::{
+    lint::{lint_file, lint_model},
+    parse_str_to_ast, parse_str_to_imports,
+    types::*,
+    Ast,
+};
+use crate::errors::{
+    exit_code, CompilationError, CompilationStage, CompilationWarning, ErrorCode, ErrorVariant, PestError,
+};
+
+struct Import {
+    namespace: String,
+    file: usize,
+}
+
+// an import read from a file. Spans are held as offsets as the file contents cannot be borrowed while loading
+struct PendingImport {
+    path: String,
+    namespace: String,
+    path_span: (usize, usize),
+    namespace_span: (usize, usize),
+}
+
+struct SourceFile {
+    path: PathBuf,
+    contents: String,
+    imports: Vec<Import>,
+}
+
+/// The set of files making up a model. The first file is the one compilation started from and only its public
+/// functions are compiled into processes
+pub struct Sources {
+    files: Vec<SourceFile>,
+}
+
+fn produce_import_error(code: ErrorCode, message: String, exit_code: i32, span: pest::Span) -> CompilationError {
+    CompilationError {
+        code,
+        stage: CompilationStage::ResolveImports,
+        exit_code,
+        inner: PestError::new_from_span(ErrorVariant::CustomError { message }, span),
+    }
+}
+
+fn is_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) => c.is_ascii_alphabetic() && chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+        None => false,
+    }
+}
+
+// paths are normalised lexically so that the same file imported via different relative paths is only loaded once
+fn normalise_path(path: &Path) -> PathBuf {
+    path.components().fold(PathBuf::new(), |mut acc, component| {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if matches!(acc.components().next_back(), Some(Component::Normal(_))) => {
+                acc.pop();
+            }
+            component => acc.push(component),
+        }
+        acc
+    })
+}
+
+impl Sources {
+    #[cfg(feature = "cli")]
+    pub fn load(path: &Path) -> Result<Sources, CompilationError> {
+        Sources::load_with(path, |path| std::fs::read_to_string(path))
+    }
+
+    /// Loads a model from files read by `read` rather than from disk, for example when there is no filesystem
+    pub fn load_with<F>(path: &Path, read: F) -> Result<Sources, CompilationError>
+    where
+        F: Fn(&Path) -> io::Result<String>,
+    {
+        let path = normalise_path(path);
+        let contents = read(&path).map_err(|e| CompilationError {
+            code: ErrorCode::UnreadableFile,
+            stage: CompilationStage::ResolveImports,
+            exit_code: exit_code::NOINPUT,
+            inner: PestError::new_from_pos(
+                ErrorVariant::CustomError {
+                    message: format!("Unable to read {}: {}", path.display(), e),
+                },
+                pest::Position::from_start(""),
+            ),
+        })?;
+
+        let mut sources = Sources { files: Vec::new() };
+        match sources.load_file(path, contents, &read, &mut Vec::new()) {
+            Ok(_) => Ok(sources),
+            Err(e) => Err(sources.attribute(e)),
+        }
+    }
+
+    fn load_file<F>(
+        &mut self,
+        path: PathBuf,
+        contents: String,
+        read: &F,
+        stack: &mut Vec<usize>,
+    ) -> Result<usize, CompilationError>
+    where
+        F: Fn(&Path) -> io::Result<String>,
+    {
+        let index = self.files.len();
+        self.files.push(SourceFile {
+            path,
+            contents,
+            imports: Vec::new(),
+        });
+        stack.push(index);
+
+        let pending = parse_str_to_imports(&self.files[index].contents)?
+            .into_iter()
+            .map(|import| PendingImport {
+                path: import.value.path.value.to_owned(),
+                namespace: import.value.namespace.value.to_owned(),
+                path_span: (import.value.path.span.start(), import.value.path.span.end()),
+                namespace_span: (import.value.namespace.span.start(), import.value.namespace.span.end()),
+            })
+            .collect::<Vec<_>>();
+
+        let mut imports: Vec<Import> = Vec::with_capacity(pending.len());
+        for import in pending {
+            let span_of =
+                |(start, end): (usize, usize)| pest::Span::new(&self.files[index].contents, start, end).unwrap();
+
+            if !is_ident(&import.namespace) {
+                return Err(produce_import_error(
+                    ErrorCode::InvalidNamespace,
+                    format!("Invalid namespace {}, specify one with `as`", import.namespace),
+                    exit_code::DATAERR,
+                    span_of(import.namespace_span),
+                ));
+            }
+            if imports.iter().any(|i| i.namespace == import.namespace) {
+                return Err(produce_import_error(
+                    ErrorCode::InvalidNamespace,
+                    format!("Namespace {} is already in use", import.namespace),
+                    exit_code::DATAERR,
+                    span_of(import.namespace_span),
+                ));
+            }
+
+            let import_path = match self.files[index].path.parent() {
+                Some(dir) => normalise_path(&dir.join(&import.path)),
+                None => normalise_path(Path::new(&import.path)),
+            };
+            let file = match self.files.iter().position(|f| f.path == import_path) {
+                Some(file) if stack.contains(&file) => {
+                    let cycle = stack
+                        .iter()
+                        .skip_while(|i| **i != file)
+                        .chain(Some(&file))
+                        .map(|i| self.files[*i].path.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(" -> ");
+                    return Err(produce_import_error(
+                        ErrorCode::ImportCycle,
+                        format!("Import cycle detected: {}", cycle),
+                        exit_code::DATAERR,
+                        span_of(import.path_span),
+                    ));
+                }
+                Some(file) => file,
+                None => {
+                    let contents = read(&import_path).map_err(|e| {
+                        produce_import_error(
+                            ErrorCode::UnreadableFile,
+                            format!("Unable to read {}: {}", import_path.display(), e),
+                            exit_code::NOINPUT,
+                            span_of(import.path_span),
+                        )
+                    })?;
+                    self.load_file(import_path, contents, read, stack)?
+                }
+            };
+
+            imports.push(Import {
+                namespace: import.namespace,
+                file,
+            });
+        }
+
+        stack.pop();
+        self.files[index].imports = imports;
+        Ok(index)
+    }
+
+    fn attribute_inner(&self, inner: PestError) -> PestError {
+        match self.files.iter().find(|file| inner.is_from(&file.contents)) {
+            Some(file) => inner.with_path(&file.path),
+            None => inner,
+        }
+    }
+
+    /// Attributes an error to the file it was raised in so that it is reported against the correct path
+    pub fn attribute(&self, error: CompilationError) -> CompilationError {
+        CompilationError {
+            inner: self.attribute_inner(error.inner),
+            ..error
+        }
+    }
+
+    /// Checks each file and the merged `ast` of the model for issues that are not errors
+    pub fn lint(&self, ast: &Ast) -> Vec<CompilationWarning> {
+        self.files
+            .iter()
+            .filter_map(|file| parse_str_to_ast(&file.contents).ok())
+            .flat_map(|file_ast| lint_file(&file_ast))
+            .chain(lint_model(ast))
+            .map(|warning| CompilationWarning {
+                inner: self.attribute_inner(warning.inner),
+                ..warning
+            })
+            .collect()
+    }
+
+    /// Parses each file and merges them into a single ast with all namespaced references resolved to the
+    /// declarations they refer to
+    pub fn parse_to_ast(&self) -> Result<Ast, CompilationError> {
+        let asts = self
+            .files
+            .iter()
+            .map(|file| parse_str_to_ast(&file.contents))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // token types and functions share a single namespace on chain so names must be unique across files
+        let mut tokens: HashMap<&str, usize> = HashMap::new();
+        let mut fns: HashMap<&str, usize> = HashMap::new();
+        let mut private_fns: HashSet<&str> = HashSet::new();
+        for (index, ast) in asts.iter().enumerate() {
+            for node in ast {
+                let (kind, name, decls) = match &node.value {
+                    AstRoot::TokenDecl(t) => ("Token type", &t.value.name, &mut tokens),
+                    AstRoot::FnDecl(f) => {
+                        if f.value.visibility.value == FnVis::Private {
+                            private_fns.insert(f.value.name.value);
+                        }
+                        ("Function", &f.value.name, &mut fns)
+                    }
+                };
+                match decls.get(name.value).copied() {
+                    Some(file) if file != index => {
+                        return Err(produce_import_error(
+                            ErrorCode::DuplicateDeclaration,
+                            format!(
+                                "{} {} is also declared in {}",
+                                kind,
+                                name.value,
+                                self.files[file].path.display()
+                            ),
+                            exit_code::DATAERR,
+                            name.span,
+                        ));
+                    }
+                    _ => {
+                        decls.insert(name.value, index);
+                    }
+                }
+            }
+        }
+
+        asts.into_iter()
+            .enumerate()
+            .map(|(index, ast)| {
+                let scope = Scope {
+                    sources: self,
+                    file: index,
+                    tokens: &tokens,
+                    fns: &fns,
+                    private_fns: &private_fns,
+                };
+                ast.into_iter()
+                    .map(|node| scope.resolve_root(node))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|asts| asts.into_iter().flatten().collect())
+    }
+}
+
+struct Scope<'s, 'a> {
+    sources: &'a Sources,
+    file: usize,
+    tokens: &'s HashMap<&'a str, usize>,
+    fns: &'s HashMap<&'a str, usize>,
+    private_fns: &'s HashSet<&'a str>,
+}
+
+impl<'s, 'a> Scope<'s, 'a> {
+    fn resolve_name(
+        &self,
+        name: AstNode<'a, &'a str>,
+        kind: &str,
+        unknown: ErrorCode,
+        decls: &HashMap<&'a str, usize>,
+    ) -> Result<AstNode<'a, &'a str>, CompilationError> {
+        let imports = &self.sources.files[self.file].imports;
+        let (namespace, local_name) = match name.value.split_once("::") {
+            Some((namespace, local_name)) => (Some(namespace), local_name),
+            None => (None, name.value),
+        };
+        let target = match namespace {
+            Some(namespace) => match imports.iter().find(|i| i.namespace == namespace) {
+                Some(import) => import.file,
+                None => {
+                    return Err(produce_import_error(
+                        ErrorCode::UnknownNamespace,
+                        format!("Unknown namespace {}", namespace),
+                        exit_code::DATAERR,
+                        name.span,
+                    ))
+                }
+            },
+            None => self.file,
+        };
+
+        match (decls.get_key_value(local_name), namespace) {
+            (Some((declared, file)), _) if *file == target => Ok(AstNode {
+                value: *declared,
+                span: name.span,
+            }),
+            (Some((_, file)), None) => {
+                let path = self.sources.files[*file].path.display();
+                let message = match imports.iter().find(|i| i.file == *file) {
+                    Some(import) => format!(
+                        "{} is declared in {} and must be referred to as {}::{}",
+                        name.value, path, import.namespace, name.value
+                    ),
+                    None => format!("{} is declared in {} which has not been imported", name.value, path),
+                };
+                Err(produce_import_error(
+                    ErrorCode::NotImported,
+                    message,
+                    exit_code::DATAERR,
+                    name.span,
+                ))
+            }
+            // unknown local names are left for compilation to report
+            (None, None) => Ok(name),
+            (_, Some(_)) => Err(produce_import_error(
+                unknown,
+                format!("Unknown {} {}", kind, name.value),
+                exit_code::DATAERR,
+                name.span,
+            )),
+        }
+    }
+
+    fn resolve_token(&self, name: AstNode<'a, &'a str>) -> Result<AstNode<'a, &'a str>, CompilationError> {
+        self.resolve_name(name, "token type", ErrorCode::UnknownTokenType, self.tokens)
+    }
+
+    fn resolve_fn(&self, name: AstNode<'a, &'a str>) -> Result<AstNode<'a, &'a str>, CompilationError> {
+        let resolved = self.resolve_name(name, "function", ErrorCode::UnknownFunction, self.fns)?;
+        match self.fns.get(resolved.value) {
+            Some(file) if *file != self.file && self.private_fns.contains(resolved.value) => Err(produce_import_error(
+                ErrorCode::PrivateFunction,
+                format!(
+                    "Function {} is private to {}",
+                    resolved.value,
+                    self.sources.files[*file].path.display()
+                ),
+                exit_code::DATAERR,
+                resolved.span,
+            )),
+            _ => Ok(resolved),
+        }
+    }
+
+    fn resolve_expression(&self, expr: ExpressionTree<'a>) -> Result<ExpressionTree<'a>, CompilationError> {
+        match expr {
+            ExpressionTree::Leaf(AstNode {
+                value: Comparison::Fn { name, inputs, outputs },
+                span,
+            }) => Ok(ExpressionTree::Leaf(AstNode {
+                value: Comparison::Fn {
+                    name: self.resolve_fn(name)?,
+                    inputs,
+                    outputs,
+                },
+                span,
+            })),
+            ExpressionTree::Leaf(c) => Ok(ExpressionTree::Leaf(c)),
+            ExpressionTree::Not(e) => Ok(ExpressionTree::Not(Box::new(self.resolve_expression(*e)?))),
+            ExpressionTree::Node { left, op, right } => Ok(ExpressionTree::Node {
+                left: Box::new(self.resolve_expression(*left)?),
+                op,
+                right: Box::new(self.resolve_expression(*right)?),
+            }),
+        }
+    }
+
+    fn resolve_args(
+        &self,
+        args: AstNode<'a, Arc<[AstNode<'a, FnArg<'a>>]>>,
+    ) -> Result<AstNode<'a, Arc<[AstNode<'a, FnArg<'a>>]>>, CompilationError> {
+        Ok(AstNode {
+            value: args
+                .value
+                .iter()
+                .map(|arg| {
+                    Ok(AstNode {
+                        value: FnArg {
+                            name: arg.value.name.clone(),
+                            token_type: self.resolve_token(arg.value.token_type.clone())?,
+                        },
+                        span: arg.span,
+                    })
+                })
+                .collect::<Result<Arc<[_]>, _>>()?,
+            span: args.span,
+        })
+    }
+
+    fn resolve_fn_decl(&self, decl: FnDecl<'a>) -> Result<FnDecl<'a>, CompilationError> {
+        Ok(FnDecl {
+            // only functions of the root file are compiled into processes
+            visibility: match self.file {
+                0 => decl.visibility,
+                _ => AstNode {
+                    value: FnVis::Private,
+                    span: decl.visibility.span,
+                },
+            },
+            name: decl.name,
+            inputs: self.resolve_args(decl.inputs)?,
+            outputs: self.resolve_args(decl.outputs)?,
+            conditions: AstNode {
+                value: decl
+                    .conditions
+                    .value
+                    .into_iter()
+                    .map(|expr| self.resolve_expression(expr))
+                    .collect::<Result<Vec<_>, _>>()?,
+                span: decl.conditions.span,
+            },
+        })
+    }
+
+    fn resolve_token_decl(&self, decl: TokenDecl<'a>) -> Result<TokenDecl<'a>, CompilationError> {
+        Ok(TokenDecl {
+            name: decl.name,
+            props: AstNode {
+                value: decl
+                    .props
+                    .value
+                    .iter()
+                    .map(|prop| {
+                        Ok(AstNode {
+                            value: TokenPropDecl {
+                                name: prop.value.name.clone(),
+                                types: prop
+                                    .value
+                                    .types
+                                    .iter()
+                                    .map(|field_type| {
+                                        Ok(AstNode {
+                                            value: match &field_type.value {
+                                                TokenFieldType::Token(name) => {
+                                                    TokenFieldType::Token(self.resolve_token(name.clone())?)
+                                                }
+                                                t => t.clone(),
+                                            },
+                                            span: field_type.span,
+                                        })
+                                    })
+                                    .collect::<Result<Arc<[_]>, _>>()?,
+                            },
+                            span: prop.span,
+                        })
+                    })
+                    .collect::<Result<Arc<[_]>, _>>()?,
+                span: decl.props.span,
+            },
+        })
+    }
+
+    fn resolve_root(&self, node: AstNode<'a, AstRoot<'a>>) -> Result<AstNode<'a, AstRoot<'a>>, CompilationError> {
+        let value = match node.value {
+            AstRoot::TokenDecl(t) => AstRoot::TokenDecl(AstNode {
+                value: self.resolve_token_decl(t.value)?,
+                span: t.span,
+            }),
+            AstRoot::FnDecl(f) => AstRoot::FnDecl(AstNode {
+                value: self.resolve_fn_decl(f.value)?,
+                span: f.span,
+            }),
+        };
+        Ok(AstNode { value, span: node.span })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, io, path::Path};
+
+    use super::Sources;
+    use crate::{
+        ast::types::{AstRoot, FnVis},
+        compiler::compile_ast_to_restrictions,
+        errors::{exit_code, CompilationStage, ErrorCode},
+    };
+
+    fn load(files: &[(&str, &str)]) -> Result<Sources, crate::errors::CompilationError> {
+        let files: HashMap<_, _> = files
+            .iter()
+            .map(|(path, contents)| (Path::new(*path), *contents))
+            .collect();
+        Sources::load_with(Path::new("main.dscp"), |path| {
+            files
+                .get(path)
+                .map(|contents| contents.to_string())
+                .ok_or(io::Error::from(io::ErrorKind::NotFound))
+        })
+    }
+
+    const COMMON: &str = r##"
+        token Order {
+            owner: Role,
+        }
+
+        fn is_owner | o: Order | => || where {
+            o.owner == sender
+        }
+
+        pub fn owner_unchanged | a: Order | => | b: Order | where {
+            a.owner == b.owner
+        }
+    "##;
+
+    #[test]
+    fn resolves_namespaced_token() {
+        let sources = load(&[
+            (
+                "main.dscp",
+                r##"
+                import "lib/common.dscp"
+
+                token Shipment {
+                    order: common::Order,
+                }
+
+                pub fn ship | o: common::Order | => | s: Shipment, n: common::Order | where {
+                    s.order == o,
+                    common::owner_unchanged | o | => | n |
+                }
+            "##,
+            ),
+            ("lib/common.dscp", COMMON),
+        ])
+        .unwrap();
+
+        let ast = sources.parse_to_ast().unwrap();
+        let processes = compile_ast_to_restrictions(ast).unwrap();
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].name.as_slice(), "ship".as_bytes());
+    }
+
+    #[test]
+    fn imported_public_fns_are_not_processes() {
+        let sources = load(&[
+            ("main.dscp", "import \"common.dscp\" as c\n token Other {}"),
+            ("common.dscp", COMMON),
+        ])
+        .unwrap();
+
+        let ast = sources.parse_to_ast().unwrap();
+        assert!(ast.iter().all(|node| match &node.value {
+            AstRoot::FnDecl(f) => f.value.visibility.value == FnVis::Private,
+            AstRoot::TokenDecl(_) => true,
+        }));
+    }
+
+    #[test]
+    fn relative_imports_are_loaded_once() {
+        let sources = load(&[
+            ("main.dscp", "import \"a/a.dscp\"\n import \"b.dscp\"\n token Main {}"),
+            ("a/a.dscp", "import \"../b.dscp\"\n token A {}"),
+            ("b.dscp", "token B {}"),
+        ])
+        .unwrap();
+
+        assert_eq!(sources.files.len(), 3);
+    }
+
+    #[test]
+    fn unqualified_imported_token() {
+        let sources = load(&[
+            (
+                "main.dscp",
+                "import \"common.dscp\"\n pub fn f | o: Order | => || where {}",
+            ),
+            ("common.dscp", COMMON),
+        ])
+        .unwrap();
+
+        let result = sources.parse_to_ast();
+        let err = result.err().unwrap();
+        assert_eq!(err.stage, CompilationStage::ResolveImports);
+        assert_eq!(
+            err.inner.variant.message(),
+            "Order is declared in common.dscp and must be referred to as common::Order"
+        );
+    }
+
+    #[test]
+    fn unknown_namespace() {
+        let sources = load(&[("main.dscp", "pub fn f | o: common::Order | => || where {}")]).unwrap();
+
+        let result = sources.parse_to_ast();
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Unknown namespace common"
+        );
+    }
+
+    #[test]
+    fn unknown_namespaced_token() {
+        let sources = load(&[
+            (
+                "main.dscp",
+                "import \"common.dscp\"\n pub fn f | o: common::Other | => || where {}",
+            ),
+            ("common.dscp", COMMON),
+        ])
+        .unwrap();
+
+        let result = sources.parse_to_ast();
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Unknown token type common::Other"
+        );
+    }
+
+    #[test]
+    fn private_imported_fn() {
+        let sources = load(&[
+            (
+                "main.dscp",
+                "import \"common.dscp\"\n pub fn f | o: common::Order | => || where { common::is_owner | o | => || }",
+            ),
+            ("common.dscp", COMMON),
+        ])
+        .unwrap();
+
+        let result = sources.parse_to_ast();
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Function is_owner is private to common.dscp"
+        );
+    }
+
+    #[test]
+    fn duplicate_token_across_files() {
+        let sources = load(&[
+            ("main.dscp", "import \"common.dscp\"\n token Order {}"),
+            ("common.dscp", COMMON),
+        ])
+        .unwrap();
+
+        let result = sources.parse_to_ast();
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Token type Order is also declared in main.dscp"
+        );
+    }
+
+    #[test]
+    fn duplicate_namespace() {
+        let result = load(&[
+            (
+                "main.dscp",
+                "import \"a.dscp\" as lib\n import \"b.dscp\" as lib\n token Main {}",
+            ),
+            ("a.dscp", "token A {}"),
+            ("b.dscp", "token B {}"),
+        ]);
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Namespace lib is already in use"
+        );
+    }
+
+    #[test]
+    fn invalid_default_namespace() {
+        let result = load(&[
+            ("main.dscp", "import \"my-lib.dscp\"\n token Main {}"),
+            ("my-lib.dscp", "token A {}"),
+        ]);
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Invalid namespace my-lib, specify one with `as`"
+        );
+    }
+
+    #[test]
+    fn import_cycle() {
+        let result = load(&[
+            ("main.dscp", "import \"a.dscp\"\n token Main {}"),
+            ("a.dscp", "import \"b.dscp\"\n token A {}"),
+            ("b.dscp", "import \"a.dscp\"\n token B {}"),
+        ]);
+        let err = result.err().unwrap();
+        assert_eq!(err.stage, CompilationStage::ResolveImports);
+        assert_eq!(err.code, ErrorCode::ImportCycle);
+        assert_eq!(
+            err.inner.variant.message(),
+            "Import cycle detected: a.dscp -> b.dscp -> a.dscp"
+        );
+        assert_eq!(err.inner.path(), Some("b.dscp"));
+    }
+
+    #[test]
+    fn self_import() {
+        let result = load(&[("main.dscp", "import \"main.dscp\" as me\n token Main {}")]);
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Import cycle detected: main.dscp -> main.dscp"
+        );
+    }
+
+    #[test]
+    fn missing_import() {
+        let result = load(&[("main.dscp", "import \"missing.dscp\"\n token Main {}")]);
+        let err = result.err().unwrap();
+        assert_eq!(err.exit_code, exit_code::NOINPUT);
+        assert_eq!(err.code, ErrorCode::UnreadableFile);
+        assert_eq!(err.inner.path(), Some("main.dscp"));
+    }
+
+    #[test]
+    fn errors_attributed_to_imported_file() {
+        let sources = load(&[
+            (
+                "main.dscp",
+                "import \"common.dscp\"\n pub fn f | o: common::Order | => || where {}",
+            ),
+            (
+                "common.dscp",
+                "token Order { kind: \"a literal that is far too long to fit in 32 bytes\" }",
+            ),
+        ])
+        .unwrap();
+
+        let err = sources.attribute(sources.parse_to_ast().err().unwrap());
+        assert_eq!(err.stage, CompilationStage::LengthValidation);
+        assert_eq!(err.inner.path(), Some("common.dscp"));
+    }
+
+    #[test]
+    fn grammar_errors_attributed_to_imported_file() {
+        let result = load(&[
+            ("main.dscp", "import \"common.dscp\"\n token Main {}"),
+            ("common.dscp", "token Order {"),
+        ]);
+        let err = result.err().unwrap();
+        assert_eq!(err.stage, CompilationStage::ParseGrammar);
+        assert_eq!(err.inner.path(), Some("common.dscp"));
+    }
+}
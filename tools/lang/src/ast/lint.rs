@@ -0,0 +1,231 @@
+use std::collections::{HashMap, HashSet};
+
+use super::types::*;
+use crate::errors::{produce_warning, CompilationWarning, WarningCode};
+
+fn fn_decls<'a, 'b>(ast: &'b Ast<'a>) -> impl Iterator<Item = &'b FnDecl<'a>> {
+    ast.iter().filter_map(|node| match &node.value {
+        AstRoot::FnDecl(f) => Some(&f.value),
+        AstRoot::TokenDecl(_) => None,
+    })
+}
+
+fn token_decls<'a, 'b>(ast: &'b Ast<'a>) -> impl Iterator<Item = &'b TokenDecl<'a>> {
+    ast.iter().filter_map(|node| match &node.value {
+        AstRoot::TokenDecl(t) => Some(&t.value),
+        AstRoot::FnDecl(_) => None,
+    })
+}
+
+fn visit_comparisons<'a, 'b, F>(expr: &'b ExpressionTree<'a>, visit: &mut F)
+where
+    F: FnMut(&'b Comparison<'a>),
+{
+    match expr {
+        ExpressionTree::Leaf(comparison) => visit(&comparison.value),
+        ExpressionTree::Not(expr) => visit_comparisons(expr, visit),
+        ExpressionTree::Node { left, right, .. } => {
+            visit_comparisons(left, visit);
+            visit_comparisons(right, visit);
+        }
+    }
+}
+
+/// Checks the declarations of a single file for private functions that are never called and for function
+/// arguments named after a token type or function. Private functions can only be called from the file they are
+/// declared in so this must be run against each file before imports are resolved
+pub fn lint_file(ast: &Ast) -> Vec<CompilationWarning> {
+    let mut called: HashSet<&str> = HashSet::new();
+    for fn_decl in fn_decls(ast) {
+        for condition in fn_decl.conditions.value.iter() {
+            visit_comparisons(condition, &mut |comparison| {
+                if let Comparison::Fn { name, .. } = comparison {
+                    called.insert(name.value);
+                }
+            });
+        }
+    }
+
+    let declared: HashSet<&str> = token_decls(ast)
+        .map(|t| t.name.value)
+        .chain(fn_decls(ast).map(|f| f.name.value))
+        .collect();
+
+    let mut warnings = Vec::new();
+    for fn_decl in fn_decls(ast) {
+        if fn_decl.visibility.value == FnVis::Private && !called.contains(fn_decl.name.value) {
+            warnings.push(produce_warning(
+                WarningCode::UnusedPrivateFn,
+                format!("Private function {} is never called", fn_decl.name.value),
+                fn_decl.name.span,
+            ));
+        }
+
+        for arg in fn_decl.inputs.value.iter().chain(fn_decl.outputs.value.iter()) {
+            if declared.contains(arg.value.name.value) {
+                warnings.push(produce_warning(
+                    WarningCode::ShadowedArgName,
+                    format!(
+                        "Argument {} of function {} shadows a declaration of the same name",
+                        arg.value.name.value, fn_decl.name.value
+                    ),
+                    arg.value.name.span,
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+/// Checks a complete model for token fields that are never referred to in the conditions of any function
+pub fn lint_model<'a>(ast: &Ast<'a>) -> Vec<CompilationWarning> {
+    let mut used: HashSet<(&'a str, &'a str)> = HashSet::new();
+    for fn_decl in fn_decls(ast) {
+        let arg_types: HashMap<&'a str, &'a str> = fn_decl
+            .inputs
+            .value
+            .iter()
+            .chain(fn_decl.outputs.value.iter())
+            .map(|arg| (arg.value.name.value, arg.value.token_type.value))
+            .collect();
+
+        let mut use_prop = |prop: &TokenProp<'a>| {
+            if let Some(token_type) = arg_types.get(prop.token.value) {
+                used.insert((*token_type, prop.prop.value));
+            }
+        };
+
+        for condition in fn_decl.conditions.value.iter() {
+            visit_comparisons(condition, &mut |comparison| match comparison {
+                Comparison::PropLit { left, .. }
+                | Comparison::PropInt { left, .. }
+                | Comparison::PropSender { left, .. }
+                | Comparison::PropToken { left, .. }
+                | Comparison::PropType { left, .. }
+                | Comparison::PropStr { left, .. } => use_prop(&left.value),
+                Comparison::PropProp { left, right, .. } => {
+                    use_prop(&left.value);
+                    use_prop(&right.value);
+                }
+                Comparison::SenderIn { props } => props.value.iter().for_each(|prop| use_prop(&prop.value)),
+                Comparison::PropHash { left, right, .. } => {
+                    use_prop(&left.value);
+                    right.value.iter().for_each(|prop| use_prop(&prop.value));
+                }
+                Comparison::Fn { .. } | Comparison::TokenToken { .. } => {}
+            });
+        }
+    }
+
+    token_decls(ast)
+        .flat_map(|token_decl| {
+            token_decl
+                .props
+                .value
+                .iter()
+                .filter(|prop| !used.contains(&(token_decl.name.value, prop.value.name.value)))
+                .map(|prop| {
+                    produce_warning(
+                        WarningCode::UnusedTokenField,
+                        format!(
+                            "Field {} of token {} is never used in a condition",
+                            prop.value.name.value, token_decl.name.value
+                        ),
+                        prop.value.name.span,
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lint_file, lint_model};
+    use crate::{ast::parse_str_to_ast, errors::WarningCode};
+
+    fn codes(warnings: Vec<crate::errors::CompilationWarning>) -> Vec<(WarningCode, String)> {
+        warnings
+            .into_iter()
+            .map(|w| (w.code, w.inner.variant.message().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn no_warnings() {
+        let ast = parse_str_to_ast(
+            r##"
+          token Order { owner: Role }
+
+          fn is_owner | o: Order | => || where { o.owner == sender }
+
+          pub fn transfer | a: Order | => | b: Order | where {
+              is_owner | a | => ||,
+              a.owner == b.owner,
+          }
+      "##,
+        )
+        .unwrap();
+        assert_eq!(codes(lint_file(&ast)), vec![]);
+        assert_eq!(codes(lint_model(&ast)), vec![]);
+    }
+
+    #[test]
+    fn unused_private_fn() {
+        let ast = parse_str_to_ast(
+            r##"
+          token Order { owner: Role }
+
+          fn is_owner | o: Order | => || where { o.owner == sender }
+
+          pub fn create || => | o: Order | where {}
+      "##,
+        )
+        .unwrap();
+        assert_eq!(
+            codes(lint_file(&ast)),
+            vec![(
+                WarningCode::UnusedPrivateFn,
+                "Private function is_owner is never called".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn shadowed_arg_name() {
+        let ast = parse_str_to_ast(
+            r##"
+          token Order { owner: Role }
+
+          pub fn create || => | Order: Order | where {}
+      "##,
+        )
+        .unwrap();
+        assert_eq!(
+            codes(lint_file(&ast)),
+            vec![(
+                WarningCode::ShadowedArgName,
+                "Argument Order of function create shadows a declaration of the same name".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn unused_token_field() {
+        let ast = parse_str_to_ast(
+            r##"
+          token Order { owner: Role, quantity: Integer }
+
+          pub fn create || => | o: Order | where { o.owner == sender }
+      "##,
+        )
+        .unwrap();
+        assert_eq!(
+            codes(lint_model(&ast)),
+            vec![(
+                WarningCode::UnusedTokenField,
+                "Field quantity of token Order is never used in a condition".into()
+            )]
+        );
+    }
+}
@@ -4,30 +4,39 @@ pub mod parse;
 pub mod types;
 
 use crate::{
-    errors::{CompilationError, CompilationStage},
+    errors::{exit_code, CompilationError, CompilationStage, ErrorCode, PestError},
     parser::*,
 };
+use types::{AstNode, ImportDecl};
 
-pub use parse::parse_ast;
+pub use parse::{parse_ast, parse_imports};
 pub use types::Ast;
 
+mod imports;
+pub use imports::Sources;
+
+mod lint;
+
+fn parse_str(input: &str) -> Result<pest::iterators::Pairs<Rule>, CompilationError> {
+    SqncParser::parse(Rule::main, input).map_err(|e| CompilationError {
+        code: ErrorCode::InvalidSyntax,
+        stage: CompilationStage::ParseGrammar,
+        exit_code: exit_code::DATAERR,
+        inner: PestError::from_input(e, input),
+    })
+}
+
 pub fn parse_str_to_ast(input: &str) -> Result<Ast, CompilationError> {
-    let pairs = SqncParser::parse(Rule::main, input);
-    if let Err(e) = pairs {
-        return Err(CompilationError {
-            stage: CompilationStage::ParseGrammar,
-            exit_code: exitcode::DATAERR,
-            inner: e,
-        });
-    }
-    let pairs = pairs.unwrap();
+    parse_ast(parse_str(input)?)
+}
 
-    parse_ast(pairs)
+pub fn parse_str_to_imports(input: &str) -> Result<Vec<AstNode<ImportDecl>>, CompilationError> {
+    parse_imports(parse_str(input)?)
 }
 
 #[cfg(test)]
 mod test {
-    use super::parse_str_to_ast;
+    use super::{parse_str_to_ast, parse_str_to_imports};
 
     #[test]
     fn valid_empty_token() {
@@ -84,6 +93,118 @@ mod test {
         );
     }
 
+    #[test]
+    fn valid_bounded_token_fields() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          token TestToken {
+              quantity: Integer(0..=10000),
+              exclusive: Integer(0..10),
+              lower: Integer(-5..),
+              upper: Integer(..=5),
+              code: Literal(max 16),
+              union_field: Literal(max 4) | None,
+          }
+      "##
+            )
+            .is_ok(),
+            true
+        );
+    }
+
+    #[test]
+    fn valid_structured_token_fields() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          token TestToken {
+              description: Text,
+              signature: Bytes,
+              urgent: Bool,
+              due: Timestamp | None,
+              tags: List | Literal,
+          }
+      "##
+            )
+            .is_ok(),
+            true
+        );
+    }
+
+    #[test]
+    fn invalid_empty_integer_range() {
+        let result = parse_str_to_ast(
+            r##"
+          token TestToken {
+              quantity: Integer(10..10)
+          }
+      "##,
+        );
+        assert_eq!(result.err().unwrap().inner.variant.message(), "Integer range is empty");
+    }
+
+    #[test]
+    fn invalid_unbounded_inclusive_integer_range() {
+        let result = parse_str_to_ast(
+            r##"
+          token TestToken {
+              quantity: Integer(0..=)
+          }
+      "##,
+        );
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Inclusive integer range must have an end"
+        );
+    }
+
+    #[test]
+    fn invalid_literal_max_length() {
+        for max in ["0", "33", "-1"] {
+            let input = format!(
+                r##"
+          token TestToken {{
+              code: Literal(max {})
+          }}
+      "##,
+                max
+            );
+            let result = parse_str_to_ast(&input);
+            assert_eq!(
+                result.err().unwrap().inner.variant.message(),
+                "Literal max length must be between 1 and 32"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_literal_too_long() {
+        let result = parse_str_to_ast(
+            r##"
+          token TestToken {
+              code: "this literal is far too long to be stored on chain"
+          }
+      "##,
+        );
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Literal is 50 bytes long but literals can be at most 32 bytes"
+        );
+    }
+
+    #[test]
+    fn invalid_literal_too_long_in_condition() {
+        let result = parse_str_to_ast(
+            r##"
+          fn Test | foo: Bar | => || where {
+              foo.code == "this literal is far too long to be stored on chain"
+          }
+      "##,
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn invalid_token_name() {
         assert_eq!(
@@ -421,6 +542,167 @@ mod test {
         );
     }
 
+    #[test]
+    fn valid_where_prop_is_bounded() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          fn Test |
+              foo: Bar,
+          | => |
+              biz: Baz,
+          | where {
+              foo.b: Integer(1..=3),
+              biz.c !: Literal(max 8),
+          }
+      "##
+            )
+            .is_ok(),
+            true,
+        );
+    }
+
+    #[test]
+    fn valid_where_prop_is_structured() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          fn Test |
+              foo: Bar,
+          | => |
+              biz: Baz,
+          | where {
+              foo.b: Text,
+              biz.c !: Timestamp,
+              biz.d: List | biz.d: Bool | biz.d: Bytes,
+          }
+      "##
+            )
+            .is_ok(),
+            true,
+        );
+    }
+
+    #[test]
+    fn valid_where_account() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          token Order {
+              owner: Role,
+              approved_by: Account | None,
+          }
+
+          fn Test | foo: Order | => | bar: Order | where {
+              foo.approved_by == sender,
+              bar.approved_by == foo.owner,
+              foo.approved_by: Account,
+              sender in (foo.owner, foo.approved_by),
+          }
+      "##
+            )
+            .is_ok(),
+            true,
+        );
+    }
+
+    #[test]
+    fn valid_where_prop_str() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          fn Test |
+              foo: Bar,
+          | => |
+              biz: Baz,
+          | where {
+              foo.a starts_with "PO-",
+              foo.b ends_with ".pdf",
+              biz.c matches "[A-Z]{2}",
+          }
+      "##
+            )
+            .is_ok(),
+            true,
+        );
+    }
+
+    #[test]
+    fn valid_where_sender_in() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          fn Test |
+              foo: Bar,
+          | => |
+              biz: Baz,
+          | where {
+              sender in (foo.a),
+              sender in (foo.a, biz.b,),
+              !(sender in (foo.a, foo.b)),
+          }
+      "##
+            )
+            .is_ok(),
+            true,
+        );
+    }
+
+    #[test]
+    fn invalid_where_sender_in_empty() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          fn Test |
+              foo: Bar,
+          | => || where {
+              sender in ()
+          }
+      "##
+            )
+            .is_err(),
+            true,
+        );
+    }
+
+    #[test]
+    fn valid_where_prop_hash() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          fn Test |
+              foo: Bar,
+          | => |
+              biz: Baz,
+          | where {
+              biz.a == hash(biz.b),
+              foo.a == hash(biz.b, biz.c,),
+              foo.a != hash(biz.b, biz.c),
+          }
+      "##
+            )
+            .is_ok(),
+            true,
+        );
+    }
+
+    #[test]
+    fn invalid_where_prop_hash_empty() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          fn Test |
+              foo: Bar,
+          | => || where {
+              foo.a == hash()
+          }
+      "##
+            )
+            .is_err(),
+            true,
+        );
+    }
+
     #[test]
     fn valid_end_to_end() {
         let result = parse_str_to_ast(
@@ -444,4 +726,82 @@ mod test {
         );
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn valid_import() {
+        let result = parse_str_to_imports(
+            r##"
+          import "lib/common.dscp"
+
+          token TestToken {}
+      "##,
+        );
+        assert!(result.is_ok());
+        let imports = result.unwrap();
+        assert_eq!(imports[0].value.path.value, "lib/common.dscp");
+        assert_eq!(imports[0].value.namespace.value, "common");
+    }
+
+    #[test]
+    fn valid_import_namespace() {
+        let result = parse_str_to_imports(
+            r##"
+          import "lib/common.dscp" as lib
+          import "other.dscp"
+
+          token TestToken {}
+      "##,
+        );
+        assert!(result.is_ok());
+        let imports = result.unwrap();
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].value.namespace.value, "lib");
+        assert_eq!(imports[1].value.namespace.value, "other");
+    }
+
+    #[test]
+    fn invalid_import_after_decl() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          token TestToken {}
+          import "common.dscp"
+      "##
+            )
+            .is_ok(),
+            false
+        );
+    }
+
+    #[test]
+    fn invalid_import_namespace_keyword() {
+        assert_eq!(
+            parse_str_to_ast(
+                r##"
+          import "common.dscp" as token
+          token TestToken {}
+      "##
+            )
+            .is_ok(),
+            false
+        );
+    }
+
+    #[test]
+    fn valid_namespaced_types() {
+        let result = parse_str_to_ast(
+            r##"
+          import "common.dscp"
+
+          token TestToken {
+              parent: common::Parent | None,
+          }
+
+          pub fn TestFn | in: common::Parent | => | out: TestToken | where {
+              common::check | in | => ||,
+          }
+      "##,
+        );
+        assert!(result.is_ok());
+    }
 }
@@ -3,7 +3,10 @@ use std::sync::Arc;
 use super::types::*;
 
 use crate::{
-    errors::{produce_unexpected_pair_error, CompilationError, CompilationStage, ErrorVariant, PestError},
+    errors::{
+        exit_code, produce_unexpected_pair_error, CompilationError, CompilationStage, ErrorCode, ErrorVariant,
+        PestError,
+    },
     parser::Rule,
 };
 
@@ -14,9 +17,29 @@ fn parse_ident<'a>(pair: pest::iterators::Pair<'a, Rule>) -> Result<AstNode<'a,
     })
 }
 
+// literal metadata values are stored on chain as a BoundedVec<u8, ConstU32<32>>
+const MAX_LITERAL_LENGTH: usize = 32;
+
 fn parse_literal<'a>(pair: pest::iterators::Pair<'a, Rule>) -> Result<AstNode<'a, &'a str>, CompilationError> {
     let string = pair.into_inner().next().unwrap(); // string
     let inner = string.into_inner().next().unwrap(); // inner
+    if inner.as_str().len() > MAX_LITERAL_LENGTH {
+        return Err(CompilationError {
+            code: ErrorCode::LiteralTooLong,
+            stage: CompilationStage::LengthValidation,
+            exit_code: exit_code::DATAERR,
+            inner: PestError::new_from_span(
+                ErrorVariant::CustomError {
+                    message: format!(
+                        "Literal is {} bytes long but literals can be at most {} bytes",
+                        inner.as_str().len(),
+                        MAX_LITERAL_LENGTH
+                    ),
+                },
+                inner.as_span(),
+            ),
+        });
+    }
     Ok(AstNode {
         value: inner.as_str(),
         span: inner.as_span(),
@@ -33,8 +56,9 @@ fn parse_integer<'a>(pair: pest::iterators::Pair<'a, Rule>) -> Result<AstNode<'a
             span: integer_value_string.as_span(),
         }),
         Err(_) => Err(CompilationError {
+            code: ErrorCode::InvalidInteger,
             stage: CompilationStage::BuildAst,
-            exit_code: exitcode::DATAERR,
+            exit_code: exit_code::DATAERR,
             inner: PestError::new_from_span(
                 ErrorVariant::CustomError {
                     message: "Error parsing integer".into(),
@@ -45,17 +69,80 @@ fn parse_integer<'a>(pair: pest::iterators::Pair<'a, Rule>) -> Result<AstNode<'a
     }
 }
 
+fn produce_invalid_type_error<V>(message: String, span: pest::Span) -> Result<V, CompilationError> {
+    Err(CompilationError {
+        code: ErrorCode::InvalidFieldType,
+        stage: CompilationStage::BuildAst,
+        exit_code: exit_code::DATAERR,
+        inner: PestError::new_from_span(ErrorVariant::CustomError { message }, span),
+    })
+}
+
+fn parse_integer_range(pair: pest::iterators::Pair<Rule>) -> Result<(i128, i128), CompilationError> {
+    let span = pair.as_span();
+    let mut min = i128::MIN;
+    let mut max = None;
+    let mut inclusive = false;
+    for pair in pair.into_inner() {
+        match pair.as_rule() {
+            Rule::integer => {}
+            Rule::range_start => min = parse_integer(pair)?.value,
+            Rule::range_end => max = Some(parse_integer(pair)?),
+            Rule::range_inclusive => inclusive = true,
+            Rule::range_exclusive => inclusive = false,
+            _ => return produce_unexpected_pair_error(pair),
+        }
+    }
+
+    let max = match (max, inclusive) {
+        (Some(max), true) => max.value,
+        (Some(max), false) => match max.value.checked_sub(1) {
+            Some(max) => max,
+            None => return produce_invalid_type_error("Integer range is empty".into(), max.span),
+        },
+        (None, true) => return produce_invalid_type_error("Inclusive integer range must have an end".into(), span),
+        (None, false) => i128::MAX,
+    };
+
+    if min > max {
+        return produce_invalid_type_error("Integer range is empty".into(), span);
+    }
+    Ok((min, max))
+}
+
+fn parse_literal_max_length(pair: pest::iterators::Pair<Rule>) -> Result<u32, CompilationError> {
+    let max_length = pair.into_inner().find(|p| p.as_rule() == Rule::max_length).unwrap();
+    match max_length.as_str().parse::<u32>() {
+        Ok(max) if max > 0 && max as usize <= MAX_LITERAL_LENGTH => Ok(max),
+        _ => produce_invalid_type_error(
+            format!("Literal max length must be between 1 and {}", MAX_LITERAL_LENGTH),
+            max_length.as_span(),
+        ),
+    }
+}
+
 fn parse_token_prop_type(pair: pest::iterators::Pair<Rule>) -> Result<AstNode<TokenFieldType>, CompilationError> {
     let span = pair.as_span();
     let field_type = match pair.as_rule() {
         Rule::file => Ok(TokenFieldType::File),
         Rule::literal => Ok(TokenFieldType::Literal),
         Rule::integer => Ok(TokenFieldType::Integer),
+        Rule::literal_max_length => Ok(TokenFieldType::LiteralMaxLength(parse_literal_max_length(pair)?)),
+        Rule::integer_range => {
+            let (min, max) = parse_integer_range(pair)?;
+            Ok(TokenFieldType::IntegerRange { min, max })
+        }
         Rule::role => Ok(TokenFieldType::Role),
         Rule::none => Ok(TokenFieldType::None),
+        Rule::text => Ok(TokenFieldType::Text),
+        Rule::bytes => Ok(TokenFieldType::Bytes),
+        Rule::boolean => Ok(TokenFieldType::Bool),
+        Rule::timestamp => Ok(TokenFieldType::Timestamp),
+        Rule::list => Ok(TokenFieldType::List),
+        Rule::account => Ok(TokenFieldType::Account),
         Rule::literal_value => Ok(TokenFieldType::LiteralValue(parse_literal(pair)?)),
         Rule::integer_value => Ok(TokenFieldType::IntegerValue(parse_integer(pair)?)),
-        Rule::ident => Ok(TokenFieldType::Token(AstNode {
+        Rule::qualified_ident => Ok(TokenFieldType::Token(AstNode {
             span: pair.as_span(),
             value: pair.as_str(),
         })),
@@ -166,8 +253,9 @@ fn parse_fn_vis(pair: pest::iterators::Pair<Rule>) -> Result<AstNode<FnVis>, Com
                 "pub" => FnVis::Public,
                 _ => {
                     return Err(CompilationError {
+                        code: ErrorCode::InvalidVisibility,
                         stage: CompilationStage::BuildAst,
-                        exit_code: exitcode::DATAERR,
+                        exit_code: exit_code::DATAERR,
                         inner: PestError::new_from_span(
                             ErrorVariant::CustomError {
                                 message: "visibility if specified must be pub/priv".into(),
@@ -199,13 +287,34 @@ fn parse_type_cmp_op(pair: pest::iterators::Pair<Rule>) -> Result<TypeCmp, Compi
     }
 }
 
+fn parse_str_cmp_op(pair: pest::iterators::Pair<Rule>) -> Result<StrCmp, CompilationError> {
+    match pair.as_rule() {
+        Rule::starts_with => Ok(StrCmp::StartsWith),
+        Rule::ends_with => Ok(StrCmp::EndsWith),
+        Rule::matches => Ok(StrCmp::Matches),
+        _ => produce_unexpected_pair_error(pair),
+    }
+}
+
 fn parse_type_cmp_type(pair: pest::iterators::Pair<Rule>) -> Result<AstNode<TypeCmpType>, CompilationError> {
     let span = pair.as_span();
     let value = match pair.as_rule() {
         Rule::none => Ok(TypeCmpType::None),
         Rule::role => Ok(TypeCmpType::Role),
         Rule::literal => Ok(TypeCmpType::Literal),
+        Rule::integer => Ok(TypeCmpType::Integer),
         Rule::file => Ok(TypeCmpType::File),
+        Rule::text => Ok(TypeCmpType::Text),
+        Rule::bytes => Ok(TypeCmpType::Bytes),
+        Rule::boolean => Ok(TypeCmpType::Bool),
+        Rule::timestamp => Ok(TypeCmpType::Timestamp),
+        Rule::list => Ok(TypeCmpType::List),
+        Rule::account => Ok(TypeCmpType::Account),
+        Rule::literal_max_length => Ok(TypeCmpType::LiteralMaxLength(parse_literal_max_length(pair)?)),
+        Rule::integer_range => {
+            let (min, max) = parse_integer_range(pair)?;
+            Ok(TypeCmpType::IntegerRange { min, max })
+        }
         _ => produce_unexpected_pair_error(pair),
     }?;
     Ok(AstNode { value, span })
@@ -304,6 +413,37 @@ fn parse_bool_cmp(pair: pest::iterators::Pair<Rule>) -> Result<AstNode<Compariso
                 span,
             })
         }
+        Rule::sender_in_cmp => {
+            let mut pairs = pair.into_inner();
+            pairs.next(); // sender
+            let props = pairs.next().unwrap();
+            Ok(AstNode {
+                value: Comparison::SenderIn {
+                    props: AstNode {
+                        span: props.as_span(),
+                        value: props.into_inner().map(parse_ident_prop).collect::<Result<_, _>>()?,
+                    },
+                },
+                span,
+            })
+        }
+        Rule::prop_hash_cmp => {
+            let mut pairs = pair.into_inner();
+            let left = parse_ident_prop(pairs.next().unwrap())?;
+            let op = parse_bool_cmp_op(pairs.next().unwrap())?;
+            let props = pairs.next().unwrap();
+            Ok(AstNode {
+                value: Comparison::PropHash {
+                    left,
+                    op,
+                    right: AstNode {
+                        span: props.as_span(),
+                        value: props.into_inner().map(parse_ident_prop).collect::<Result<_, _>>()?,
+                    },
+                },
+                span,
+            })
+        }
         Rule::prop_ident_cmp => {
             let mut pairs = pair.into_inner();
             Ok(AstNode {
@@ -337,6 +477,17 @@ fn parse_bool_cmp(pair: pest::iterators::Pair<Rule>) -> Result<AstNode<Compariso
                 span,
             })
         }
+        Rule::prop_str_cmp => {
+            let mut pairs = pair.into_inner();
+            Ok(AstNode {
+                value: Comparison::PropStr {
+                    left: parse_ident_prop(pairs.next().unwrap())?,
+                    op: parse_str_cmp_op(pairs.next().unwrap())?,
+                    right: parse_literal(pairs.next().unwrap())?,
+                },
+                span,
+            })
+        }
         _ => produce_unexpected_pair_error(pair),
     }
 }
@@ -461,8 +612,39 @@ pub fn parse_ast(pairs: pest::iterators::Pairs<Rule>) -> Result<Vec<AstNode<AstR
                 });
                 Some(node)
             }
-            Rule::EOI => None,
+            Rule::import_decl | Rule::EOI => None,
             _ => panic!(),
         })
         .collect()
 }
+
+fn parse_import_decl(pair: pest::iterators::Pair<Rule>) -> Result<AstNode<ImportDecl>, CompilationError> {
+    let span = pair.as_span();
+    let mut pairs = pair.into_inner();
+    let path = pairs.next().unwrap(); // string
+    let path = parse_ident(path.into_inner().next().unwrap())?; // inner
+    let namespace = match pairs.next() {
+        Some(ident) => parse_ident(ident)?,
+        // default the namespace to the name of the imported file
+        None => {
+            let stem = path.value.rsplit('/').next().unwrap();
+            AstNode {
+                value: stem.split('.').next().unwrap(),
+                span: path.span,
+            }
+        }
+    };
+
+    Ok(AstNode {
+        value: ImportDecl { path, namespace },
+        span,
+    })
+}
+
+pub fn parse_imports(pairs: pest::iterators::Pairs<Rule>) -> Result<Vec<AstNode<ImportDecl>>, CompilationError> {
+    pairs
+        .into_iter()
+        .filter(|pair| pair.as_rule() == Rule::import_decl)
+        .map(parse_import_decl)
+        .collect()
+}
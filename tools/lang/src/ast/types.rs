@@ -42,11 +42,28 @@ pub enum TokenFieldType<'a> {
     Role,
     Literal,
     Integer,
+    LiteralMaxLength(u32),
+    IntegerRange { min: i128, max: i128 },
+    Text,
+    Bytes,
+    Bool,
+    Timestamp,
+    List,
+    Account,
     LiteralValue(AstNode<'a, &'a str>),
     IntegerValue(AstNode<'a, i128>),
     Token(AstNode<'a, &'a str>),
 }
 
+fn fmt_integer_range(f: &mut std::fmt::Formatter<'_>, min: i128, max: i128) -> std::fmt::Result {
+    match (min, max) {
+        (i128::MIN, i128::MAX) => write!(f, "Integer(..)"),
+        (i128::MIN, max) => write!(f, "Integer(..={})", max),
+        (min, i128::MAX) => write!(f, "Integer({}..)", min),
+        (min, max) => write!(f, "Integer({}..={})", min, max),
+    }
+}
+
 impl<'a> Display for TokenFieldType<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -55,6 +72,14 @@ impl<'a> Display for TokenFieldType<'a> {
             TokenFieldType::Role => write!(f, "Role"),
             TokenFieldType::Literal => write!(f, "Literal"),
             TokenFieldType::Integer => write!(f, "Integer"),
+            TokenFieldType::LiteralMaxLength(max) => write!(f, "Literal(max {})", max),
+            TokenFieldType::IntegerRange { min, max } => fmt_integer_range(f, *min, *max),
+            TokenFieldType::Text => write!(f, "Text"),
+            TokenFieldType::Bytes => write!(f, "Bytes"),
+            TokenFieldType::Bool => write!(f, "Bool"),
+            TokenFieldType::Timestamp => write!(f, "Timestamp"),
+            TokenFieldType::List => write!(f, "List"),
+            TokenFieldType::Account => write!(f, "Account"),
             TokenFieldType::LiteralValue(s) => write!(f, "\"{}\"", s.value),
             TokenFieldType::IntegerValue(s) => write!(f, "{}", s.value),
             TokenFieldType::Token(s) => write!(f, "{}", s.value),
@@ -148,6 +173,12 @@ pub enum TypeCmp {
     Is,
     Isnt,
 }
+#[derive(Clone, Debug, PartialEq)]
+pub enum StrCmp {
+    StartsWith,
+    EndsWith,
+    Matches,
+}
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum TypeCmpType {
@@ -156,9 +187,38 @@ pub enum TypeCmpType {
     Role,
     Literal,
     Integer,
+    LiteralMaxLength(u32),
+    IntegerRange { min: i128, max: i128 },
+    Text,
+    Bytes,
+    Bool,
+    Timestamp,
+    List,
+    Account,
     Token,
 }
 
+impl Display for TypeCmpType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeCmpType::None => write!(f, "None"),
+            TypeCmpType::File => write!(f, "File"),
+            TypeCmpType::Role => write!(f, "Role"),
+            TypeCmpType::Literal => write!(f, "Literal"),
+            TypeCmpType::Integer => write!(f, "Integer"),
+            TypeCmpType::LiteralMaxLength(max) => write!(f, "Literal(max {})", max),
+            TypeCmpType::IntegerRange { min, max } => fmt_integer_range(f, *min, *max),
+            TypeCmpType::Text => write!(f, "Text"),
+            TypeCmpType::Bytes => write!(f, "Bytes"),
+            TypeCmpType::Bool => write!(f, "Bool"),
+            TypeCmpType::Timestamp => write!(f, "Timestamp"),
+            TypeCmpType::List => write!(f, "List"),
+            TypeCmpType::Account => write!(f, "Account"),
+            TypeCmpType::Token => write!(f, "Token"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct TokenProp<'a> {
     pub(crate) token: AstNode<'a, &'a str>,
@@ -186,6 +246,14 @@ pub enum Comparison<'a> {
         left: AstNode<'a, TokenProp<'a>>,
         op: BoolCmp,
     },
+    SenderIn {
+        props: AstNode<'a, Arc<[AstNode<'a, TokenProp<'a>>]>>,
+    },
+    PropHash {
+        left: AstNode<'a, TokenProp<'a>>,
+        op: BoolCmp,
+        right: AstNode<'a, Arc<[AstNode<'a, TokenProp<'a>>]>>,
+    },
     TokenToken {
         left: AstNode<'a, &'a str>,
         op: BoolCmp,
@@ -206,6 +274,11 @@ pub enum Comparison<'a> {
         op: TypeCmp,
         right: AstNode<'a, TypeCmpType>,
     },
+    PropStr {
+        left: AstNode<'a, TokenProp<'a>>,
+        op: StrCmp,
+        right: AstNode<'a, &'a str>,
+    },
 }
 
 impl<'a> Display for Comparison<'a> {
@@ -237,6 +310,28 @@ impl<'a> Display for Comparison<'a> {
                 };
                 write!(f, "{}.{} {} sender", left.value.token, left.value.prop, op)
             }
+            Comparison::SenderIn { props } => {
+                let props = props
+                    .value
+                    .iter()
+                    .map(|p| format!("{}.{}", p.value.token, p.value.prop))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "sender in ({})", props)
+            }
+            Comparison::PropHash { left, op, right } => {
+                let op = match op {
+                    BoolCmp::Eq => "==",
+                    BoolCmp::Neq => "!=",
+                };
+                let right = right
+                    .value
+                    .iter()
+                    .map(|p| format!("{}.{}", p.value.token, p.value.prop))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "{}.{} {} hash({})", left.value.token, left.value.prop, op, right)
+            }
             Comparison::TokenToken { left, op, right } => {
                 let op = match op {
                     BoolCmp::Eq => "==",
@@ -267,16 +362,16 @@ impl<'a> Display for Comparison<'a> {
                     TypeCmp::Is => ":",
                     TypeCmp::Isnt => "!:",
                 };
-                let right = match right.value {
-                    TypeCmpType::None => "None",
-                    TypeCmpType::File => "File",
-                    TypeCmpType::Role => "Role",
-                    TypeCmpType::Literal => "Literal",
-                    TypeCmpType::Integer => "Integer",
-                    TypeCmpType::Token => "Token",
-                };
                 write!(f, "{}.{}{} {}", left.value.token, left.value.prop, op, right)
             }
+            Comparison::PropStr { left, op, right } => {
+                let op = match op {
+                    StrCmp::StartsWith => "starts_with",
+                    StrCmp::EndsWith => "ends_with",
+                    StrCmp::Matches => "matches",
+                };
+                write!(f, "{}.{} {} \"{}\"", left.value.token, left.value.prop, op, right)
+            }
         }
     }
 }
@@ -356,6 +451,18 @@ impl<'a> Display for FnDecl<'a> {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportDecl<'a> {
+    pub(crate) path: AstNode<'a, &'a str>,
+    pub(crate) namespace: AstNode<'a, &'a str>,
+}
+
+impl<'a> Display for ImportDecl<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "import \"{}\" as {}", self.path, self.namespace)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum AstRoot<'a> {
     TokenDecl(AstNode<'a, TokenDecl<'a>>),
@@ -0,0 +1,237 @@
+use std::fmt::Display;
+
+use parity_scale_codec::{Compact, Encode, Output};
+use serde::Serialize;
+use sqnc_runtime_types::{
+    ProcessIdentifier, RuntimeProgram, PROCESS_VALIDATION_PALLET_INDEX, TECHNICAL_COMMITTEE_PALLET_INDEX,
+};
+
+use crate::compiler::Process;
+
+// call indices of `ProcessValidation::create_process` and `TechnicalCommittee::propose`
+const CREATE_PROCESS_CALL_INDEX: u8 = 0;
+const PROPOSE_CALL_INDEX: u8 = 2;
+
+/// The subset of the runtime's `RuntimeCall` needed to create processes, encoded identically to the runtime
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuntimeCall {
+    CreateProcess {
+        id: ProcessIdentifier,
+        program: RuntimeProgram,
+    },
+    Propose {
+        threshold: u32,
+        proposal: Box<RuntimeCall>,
+    },
+}
+
+impl Encode for RuntimeCall {
+    fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+        match self {
+            RuntimeCall::CreateProcess { id, program } => {
+                dest.push_byte(PROCESS_VALIDATION_PALLET_INDEX);
+                dest.push_byte(CREATE_PROCESS_CALL_INDEX);
+                id.encode_to(dest);
+                program.encode_to(dest);
+            }
+            RuntimeCall::Propose { threshold, proposal } => {
+                let proposal = proposal.encode();
+                dest.push_byte(TECHNICAL_COMMITTEE_PALLET_INDEX);
+                dest.push_byte(PROPOSE_CALL_INDEX);
+                Compact(*threshold).encode_to(dest);
+                dest.write(&proposal);
+                // length_bound
+                Compact(proposal.len() as u32).encode_to(dest);
+            }
+        }
+    }
+}
+
+/// How a compiled process compares to the processes already on chain
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessStatus {
+    New,
+    Changed,
+    Unchanged,
+}
+
+impl Display for ProcessStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessStatus::New => write!(f, "new"),
+            ProcessStatus::Changed => write!(f, "changed"),
+            ProcessStatus::Unchanged => write!(f, "unchanged"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProcessCall {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ProcessStatus>,
+    /// hex encoded call, omitted if the process is unchanged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub call: Option<String>,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+}
+
+/// Compares a process against the latest version of the process with the same name in `chain_state`
+pub fn process_status(process: &Process, chain_state: &[Process]) -> ProcessStatus {
+    let latest = chain_state
+        .iter()
+        .filter(|p| p.name == process.name)
+        .max_by_key(|p| p.version);
+    match latest {
+        None => ProcessStatus::New,
+        Some(latest) if latest.program == process.program => ProcessStatus::Unchanged,
+        Some(_) => ProcessStatus::Changed,
+    }
+}
+
+/// Builds a `create_process` call for each process, optionally wrapped in a `TechnicalCommittee` proposal. If
+/// `chain_state` is given no call is built for processes that are unchanged
+pub fn processes_to_calls(
+    processes: &[Process],
+    propose_threshold: Option<u32>,
+    chain_state: Option<&[Process]>,
+) -> Vec<ProcessCall> {
+    processes
+        .iter()
+        .map(|process| {
+            let status = chain_state.map(|chain_state| process_status(process, chain_state));
+            let call = match status {
+                Some(ProcessStatus::Unchanged) => None,
+                _ => {
+                    let call = RuntimeCall::CreateProcess {
+                        id: process.name.clone(),
+                        program: process.program.clone(),
+                    };
+                    let call = match propose_threshold {
+                        Some(threshold) => RuntimeCall::Propose {
+                            threshold,
+                            proposal: Box::new(call),
+                        },
+                        None => call,
+                    };
+                    Some(to_hex(&call.encode()))
+                }
+            };
+
+            ProcessCall {
+                name: String::from_utf8_lossy(&process.name).into_owned(),
+                status,
+                call,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use sqnc_runtime_types::{BooleanExpressionSymbol, Restriction};
+
+    use super::*;
+
+    fn process(name: &str, version: u32, program: Vec<sqnc_runtime_types::RuntimeExpressionSymbol>) -> Process {
+        Process {
+            name: name.as_bytes().to_vec().try_into().unwrap(),
+            version,
+            program: program.try_into().unwrap(),
+        }
+    }
+
+    fn none() -> Vec<sqnc_runtime_types::RuntimeExpressionSymbol> {
+        vec![BooleanExpressionSymbol::Restriction(Restriction::None)]
+    }
+
+    fn fail() -> Vec<sqnc_runtime_types::RuntimeExpressionSymbol> {
+        vec![BooleanExpressionSymbol::Restriction(Restriction::Fail)]
+    }
+
+    #[test]
+    fn encodes_create_process() {
+        let call = RuntimeCall::CreateProcess {
+            id: "a".as_bytes().to_vec().try_into().unwrap(),
+            program: none().try_into().unwrap(),
+        };
+        // pallet, call, id (length 1, "a"), program (length 1, Restriction, None)
+        assert_eq!(
+            call.encode(),
+            vec![PROCESS_VALIDATION_PALLET_INDEX, 0, 4, 0x61, 4, 1, 0]
+        );
+    }
+
+    #[test]
+    fn encodes_propose() {
+        let proposal = RuntimeCall::CreateProcess {
+            id: "a".as_bytes().to_vec().try_into().unwrap(),
+            program: none().try_into().unwrap(),
+        };
+        let call = RuntimeCall::Propose {
+            threshold: 2,
+            proposal: Box::new(proposal.clone()),
+        };
+
+        let mut expected = vec![TECHNICAL_COMMITTEE_PALLET_INDEX, 2, 8];
+        expected.extend(proposal.encode());
+        expected.push(28);
+        assert_eq!(call.encode(), expected);
+    }
+
+    #[test]
+    fn status_new() {
+        let chain_state = vec![process("b", 1, none())];
+        assert_eq!(
+            process_status(&process("a", 1, none()), &chain_state),
+            ProcessStatus::New
+        );
+    }
+
+    #[test]
+    fn status_unchanged() {
+        let chain_state = vec![process("a", 1, fail()), process("a", 2, none())];
+        assert_eq!(
+            process_status(&process("a", 1, none()), &chain_state),
+            ProcessStatus::Unchanged
+        );
+    }
+
+    #[test]
+    fn status_changed() {
+        let chain_state = vec![process("a", 1, none()), process("a", 2, fail())];
+        assert_eq!(
+            process_status(&process("a", 1, none()), &chain_state),
+            ProcessStatus::Changed
+        );
+    }
+
+    #[test]
+    fn calls_skip_unchanged_processes() {
+        let processes = vec![process("a", 1, none()), process("b", 1, none())];
+        let chain_state = vec![process("a", 1, none())];
+        let calls = processes_to_calls(&processes, Some(2), Some(&chain_state));
+
+        assert_eq!(calls[0].status, Some(ProcessStatus::Unchanged));
+        assert_eq!(calls[0].call, None);
+        assert_eq!(calls[1].status, Some(ProcessStatus::New));
+        assert!(calls[1]
+            .call
+            .as_ref()
+            .unwrap()
+            .starts_with(&format!("0x{:02x}02", TECHNICAL_COMMITTEE_PALLET_INDEX)));
+    }
+
+    #[test]
+    fn calls_without_chain_state() {
+        let processes = vec![process("a", 1, none())];
+        let calls = processes_to_calls(&processes, None, None);
+
+        assert_eq!(calls[0].status, None);
+        assert_eq!(calls[0].call, Some("0x0a000461040100".into()));
+    }
+}
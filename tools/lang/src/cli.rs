@@ -1,12 +1,21 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
-use clap::{Parser, Subcommand};
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand, ValueEnum};
 
 use crate::{
-    ast::{parse_str_to_ast, types::AstRoot},
+    ast::{types::AstRoot, Sources},
+    calls::{process_status, processes_to_calls},
+    codegen::{generate, Target},
     compiler::compile_ast_to_restrictions,
     convert::transform_to_json,
-    errors::CompilationError,
+    diagnostics::Diagnostic,
+    doc::{document, DiagramFormat, DocFormat},
+    errors::{CompilationError, CompilationStage, CompilationWarning, ErrorCode, ErrorVariant, PestError},
+    formatter::format_str,
+    run::{parse_processes, parse_transition, run_transition, select_process},
 };
 
 /// A fictional versioning CLI
@@ -18,6 +27,22 @@ pub struct Cli {
     command: Commands,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// JSON programs as ingested by sqnc-process-management
+    Json,
+    /// Hex encoded `ProcessValidation::create_process` calls
+    ScaleCall,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum MessageFormat {
+    /// Human readable messages
+    Human,
+    /// A JSON diagnostic per line for each error and warning
+    Json,
+}
+
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     #[command(arg_required_else_help = true)]
@@ -32,6 +57,13 @@ pub enum Commands {
             default_value_t = false
         )]
         verbose: bool,
+        #[arg(
+            long,
+            value_enum,
+            help = "Format of errors and warnings",
+            default_value_t = MessageFormat::Human
+        )]
+        message_format: MessageFormat,
     },
     #[command(arg_required_else_help = true)]
     Build {
@@ -41,6 +73,21 @@ pub enum Commands {
         #[arg(short, long, help = "Path of JSON file to output programs to")]
         output_file: Option<PathBuf>,
 
+        #[arg(long, value_enum, help = "Format of the output file", default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
+
+        #[arg(
+            long,
+            help = "Wrap each call in a TechnicalCommittee proposal with the given threshold (scale-call format only)"
+        )]
+        propose_threshold: Option<u32>,
+
+        #[arg(
+            long,
+            help = "Path of JSON file of the processes on chain to report new, changed and unchanged programs against"
+        )]
+        check_against: Option<PathBuf>,
+
         #[arg(
             short,
             long,
@@ -48,6 +95,77 @@ pub enum Commands {
             default_value_t = false
         )]
         verbose: bool,
+        #[arg(
+            long,
+            value_enum,
+            help = "Format of errors and warnings",
+            default_value_t = MessageFormat::Human
+        )]
+        message_format: MessageFormat,
+    },
+    #[command(arg_required_else_help = true)]
+    Codegen {
+        #[arg(help = "Path to sqnc token specification file")]
+        file_path: PathBuf,
+
+        #[arg(long, value_enum, help = "Language or schema to generate bindings for")]
+        target: Target,
+
+        #[arg(short, long, help = "Path of file to output bindings to, otherwise they are printed")]
+        output_file: Option<PathBuf>,
+    },
+    #[command(arg_required_else_help = true)]
+    Doc {
+        #[arg(help = "Path to sqnc token specification file")]
+        file_path: PathBuf,
+
+        #[arg(
+            short,
+            long,
+            help = "Path of file to output documentation to, otherwise it is printed"
+        )]
+        output_file: Option<PathBuf>,
+
+        #[arg(long, value_enum, help = "Format of the documentation", default_value_t = DocFormat::Markdown)]
+        format: DocFormat,
+
+        #[arg(long, value_enum, help = "Format of the flow and state diagrams", default_value_t = DiagramFormat::Mermaid)]
+        diagram: DiagramFormat,
+    },
+    #[command(arg_required_else_help = true)]
+    Fmt {
+        #[arg(required = true, help = "Paths of sqnc token specification files to format")]
+        file_paths: Vec<PathBuf>,
+
+        #[arg(
+            long,
+            help = "Check that files are formatted without changing them",
+            default_value_t = false
+        )]
+        check: bool,
+    },
+    #[command(arg_required_else_help = true)]
+    Run {
+        #[arg(help = "Path of JSON file of programs output by build")]
+        program_file: PathBuf,
+
+        #[arg(help = "Path of JSON file describing the sender, inputs and outputs of the transition")]
+        transition_file: PathBuf,
+
+        #[arg(
+            short,
+            long,
+            help = "Name of the process to run if the program file contains more than one"
+        )]
+        process: Option<String>,
+
+        #[arg(
+            short,
+            long,
+            help = "Output the result of each evaluated restriction and operator",
+            default_value_t = false
+        )]
+        verbose: bool,
     },
 }
 
@@ -56,14 +174,48 @@ impl Cli {
         Cli::parse()
     }
 
+    fn message_format(&self) -> MessageFormat {
+        match &self.command {
+            Commands::Parse { message_format, .. } | Commands::Build { message_format, .. } => *message_format,
+            Commands::Codegen { .. } | Commands::Doc { .. } | Commands::Fmt { .. } | Commands::Run { .. } => {
+                MessageFormat::Human
+            }
+        }
+    }
+
+    /// Outputs an error in the message format requested
+    pub fn report_error(&self, error: &CompilationError) {
+        match self.message_format() {
+            MessageFormat::Human => println!("{}", error),
+            MessageFormat::Json => println!("{}", Diagnostic::from(error).to_json()),
+        }
+    }
+
+    fn report_warnings(&self, warnings: &[CompilationWarning]) {
+        for warning in warnings {
+            match self.message_format() {
+                MessageFormat::Human => println!("{}", warning),
+                MessageFormat::Json => println!("{}", Diagnostic::from(warning).to_json()),
+            }
+        }
+    }
+
     pub fn run(&self) -> Result<(), CompilationError> {
+        // only diagnostics are output when they are requested as json
+        let human = self.message_format() == MessageFormat::Human;
         match &self.command {
-            Commands::Parse { file_path, verbose } => {
-                println!("Loading file {}", file_path.to_str().unwrap());
+            Commands::Parse { file_path, verbose, .. } => {
+                if human {
+                    println!("Loading file {}", file_path.to_str().unwrap());
+                }
 
-                let contents = fs::read_to_string(file_path).unwrap();
+                let sources = Sources::load(file_path)?;
 
-                let ast = parse_str_to_ast(&contents)?;
+                let ast = sources.parse_to_ast().map_err(|e| sources.attribute(e))?;
+                self.report_warnings(&sources.lint(&ast));
+                if !human {
+                    return Ok(());
+                }
 
                 let token_decls = ast.iter().filter_map(|decl| match &decl.value {
                     AstRoot::TokenDecl(t) => Some(&t.value),
@@ -112,26 +264,182 @@ impl Cli {
                 file_path,
                 verbose,
                 output_file,
+                format,
+                propose_threshold,
+                check_against,
                 ..
             } => {
-                println!("Loading file {}", file_path.to_str().unwrap());
-                let contents = fs::read_to_string(file_path).unwrap();
-                let ast = parse_str_to_ast(&contents)?;
-                let programs = compile_ast_to_restrictions(ast)?;
-
-                println!("Successfully compiled the following programs:");
-                for program in &programs {
-                    let program_name = String::from_utf8(program.name.to_vec()).unwrap();
-                    if *verbose {
-                        let program_str = transform_to_json(program, false).unwrap();
-                        println!("\n{}:\n{}", program_name, program_str);
-                    } else {
-                        println!("\t{}", program_name);
+                if propose_threshold.is_some() && *format != OutputFormat::ScaleCall {
+                    Cli::command()
+                        .error(
+                            ErrorKind::ArgumentConflict,
+                            "--propose-threshold can only be used with --format scale-call",
+                        )
+                        .exit();
+                }
+
+                let chain_state = match check_against {
+                    Some(path) => {
+                        if human {
+                            println!("Loading file {}", path.to_str().unwrap());
+                        }
+                        let contents = read_json_file(path)?;
+                        Some(parse_processes(&contents)?)
+                    }
+                    None => None,
+                };
+
+                if human {
+                    println!("Loading file {}", file_path.to_str().unwrap());
+                }
+                let sources = Sources::load(file_path)?;
+                let ast = sources.parse_to_ast().map_err(|e| sources.attribute(e))?;
+                self.report_warnings(&sources.lint(&ast));
+                let programs = compile_ast_to_restrictions(ast).map_err(|e| sources.attribute(e))?;
+
+                if human {
+                    println!("Successfully compiled the following programs:");
+                    for program in &programs {
+                        let program_name = String::from_utf8(program.name.to_vec()).unwrap();
+                        let program_name = match &chain_state {
+                            Some(chain_state) => {
+                                let status = process_status(program, chain_state);
+                                format!("{} ({})", program_name, status)
+                            }
+                            None => program_name,
+                        };
+                        if *verbose {
+                            let program_str = transform_to_json(program, false).unwrap();
+                            println!("\n{}:\n{}", program_name, program_str);
+                        } else {
+                            println!("\t{}", program_name);
+                        }
                     }
                 }
 
                 if let Some(path) = output_file {
-                    fs::write(path, transform_to_json(&programs, true).unwrap()).unwrap()
+                    let output = match format {
+                        OutputFormat::Json => transform_to_json(&programs, true).unwrap(),
+                        OutputFormat::ScaleCall => {
+                            let calls = processes_to_calls(&programs, *propose_threshold, chain_state.as_deref());
+                            serde_json::to_string_pretty(&calls).unwrap()
+                        }
+                    };
+                    write_file(path, output)?;
+                }
+
+                Ok(())
+            }
+            Commands::Codegen {
+                file_path,
+                target,
+                output_file,
+            } => {
+                let sources = Sources::load(file_path)?;
+                let ast = sources.parse_to_ast().map_err(|e| sources.attribute(e))?;
+                // only generate bindings for a valid specification
+                compile_ast_to_restrictions(ast.clone()).map_err(|e| sources.attribute(e))?;
+
+                let output = generate(&ast, *target);
+                match output_file {
+                    Some(path) => {
+                        println!("Writing bindings to {}", path.to_str().unwrap());
+                        write_file(path, output)?;
+                    }
+                    None => print!("{}", output),
+                }
+
+                Ok(())
+            }
+            Commands::Doc {
+                file_path,
+                output_file,
+                format,
+                diagram,
+            } => {
+                let sources = Sources::load(file_path)?;
+                let ast = sources.parse_to_ast().map_err(|e| sources.attribute(e))?;
+
+                let title = file_path.file_stem().unwrap().to_string_lossy();
+                let output = document(&title, &ast, *format, *diagram);
+                match output_file {
+                    Some(path) => {
+                        println!("Writing documentation to {}", path.to_str().unwrap());
+                        write_file(path, output)?;
+                    }
+                    None => print!("{}", output),
+                }
+
+                Ok(())
+            }
+            Commands::Fmt { file_paths, check } => {
+                let mut unformatted = 0;
+                for file_path in file_paths {
+                    let contents = read_file(file_path, CompilationStage::ParseGrammar)?;
+                    let formatted = format_str(&contents).map_err(|e| CompilationError {
+                        inner: e.inner.with_path(file_path),
+                        ..e
+                    })?;
+                    if formatted == contents {
+                        continue;
+                    }
+
+                    if *check {
+                        println!("{} is not formatted", file_path.to_str().unwrap());
+                        unformatted += 1;
+                    } else {
+                        println!("Formatted {}", file_path.to_str().unwrap());
+                        write_file(file_path, formatted)?;
+                    }
+                }
+
+                if unformatted > 0 {
+                    std::process::exit(exitcode::DATAERR);
+                }
+
+                Ok(())
+            }
+            Commands::Run {
+                program_file,
+                transition_file,
+                process,
+                verbose,
+            } => {
+                println!("Loading file {}", program_file.to_str().unwrap());
+                let contents = read_json_file(program_file)?;
+                let processes = parse_processes(&contents)?;
+                let process = select_process(&contents, processes, process.as_deref())?;
+
+                println!("Loading file {}", transition_file.to_str().unwrap());
+                let contents = read_json_file(transition_file)?;
+                let transition = parse_transition(&contents)?;
+
+                let process_name = String::from_utf8(process.name.to_vec()).unwrap();
+                println!("Running process {}", process_name);
+                let result = run_transition(&process, transition, |step| {
+                    if *verbose {
+                        println!(
+                            "\t{:>3}: {} => {} (stack height {})",
+                            step.index,
+                            transform_to_json(step.symbol, false).unwrap(),
+                            step.result,
+                            step.stack_height
+                        );
+                    }
+                });
+
+                match result.success {
+                    true => println!(
+                        "Process {} succeeded after executing {} symbols",
+                        process_name, result.executed_len
+                    ),
+                    false => {
+                        println!(
+                            "Process {} failed after executing {} symbols",
+                            process_name, result.executed_len
+                        );
+                        std::process::exit(exitcode::DATAERR);
+                    }
                 }
 
                 Ok(())
@@ -139,3 +447,63 @@ impl Cli {
         }
     }
 }
+
+// reads a JSON file given on the command line, erroring rather than panicking if it cannot be read
+fn read_json_file(path: &Path) -> Result<String, CompilationError> {
+    read_file(path, CompilationStage::LoadJson)
+}
+
+// reads a file given on the command line for the stage that needs it
+fn read_file(path: &Path, stage: CompilationStage) -> Result<String, CompilationError> {
+    fs::read_to_string(path).map_err(|e| CompilationError {
+        code: ErrorCode::UnreadableFile,
+        stage,
+        exit_code: exitcode::NOINPUT,
+        inner: PestError::new_from_pos(
+            ErrorVariant::CustomError {
+                message: format!("Unable to read {}: {}", path.display(), e),
+            },
+            pest::Position::from_start(""),
+        ),
+    })
+}
+
+// writes an output file given on the command line, erroring rather than panicking if it cannot be written
+fn write_file(path: &Path, contents: String) -> Result<(), CompilationError> {
+    fs::write(path, contents).map_err(|e| CompilationError {
+        code: ErrorCode::UnwritableFile,
+        stage: CompilationStage::WriteOutput,
+        exit_code: exitcode::CANTCREAT,
+        inner: PestError::new_from_pos(
+            ErrorVariant::CustomError {
+                message: format!("Unable to write {}: {}", path.display(), e),
+            },
+            pest::Position::from_start(""),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_file_missing() {
+        let err = read_file(Path::new("does/not/exist.dscp"), CompilationStage::ParseGrammar)
+            .err()
+            .unwrap();
+        assert_eq!(err.code, ErrorCode::UnreadableFile);
+        assert_eq!(err.stage, CompilationStage::ParseGrammar);
+        assert_eq!(err.exit_code, exitcode::NOINPUT);
+    }
+
+    #[test]
+    fn write_file_to_missing_directory() {
+        let err = write_file(Path::new("does/not/exist.json"), String::new())
+            .err()
+            .unwrap();
+        assert_eq!(err.code, ErrorCode::UnwritableFile);
+        assert_eq!(err.stage, CompilationStage::WriteOutput);
+        assert_eq!(err.exit_code, exitcode::CANTCREAT);
+    }
+}
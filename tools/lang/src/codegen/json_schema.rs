@@ -0,0 +1,339 @@
+use serde_json::{json, Map, Value};
+
+use super::{
+    pascal_case, public_fn_decls, token_decls, token_fields, Field, MetadataField, MAX_LIST_ITEM_LENGTH,
+    MAX_LIST_LENGTH, MAX_VALUE_LENGTH, TOKEN_VERSION,
+};
+use crate::ast::types::{Ast, FnDecl, TokenDecl};
+
+fn tagged(tag: &str, value: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": { tag: value },
+        "required": [tag],
+        "additionalProperties": false,
+    })
+}
+
+fn literal_const(value: &str) -> Value {
+    tagged("Literal", json!({ "const": value }))
+}
+
+// integers outside the range of an i64 can't be represented exactly as JSON numbers so are left unbounded
+fn integer_bound(schema: &mut Map<String, Value>, key: &str, bound: i128, unbounded: i128) {
+    if bound == unbounded {
+        return;
+    }
+    if let Ok(bound) = i64::try_from(bound) {
+        schema.insert(key.to_owned(), bound.into());
+    }
+}
+
+fn metadata_schema(metadata: &MetadataField) -> Value {
+    match metadata {
+        MetadataField::File => tagged("File", json!({ "type": "string" })),
+        MetadataField::Literal { max_length } => {
+            let mut schema = json!({ "type": "string" });
+            if let Some(max_length) = max_length {
+                schema["maxLength"] = (*max_length).into();
+            }
+            tagged("Literal", schema)
+        }
+        // literal values are escaped as they would be in JSON so are unescaped here to get the value itself
+        MetadataField::LiteralValue(value) => match serde_json::from_str::<String>(&format!("\"{}\"", value)) {
+            Ok(value) => literal_const(&value),
+            Err(_) => literal_const(value),
+        },
+        MetadataField::Integer { min, max } => {
+            let mut schema = Map::new();
+            schema.insert("type".to_owned(), "integer".into());
+            integer_bound(&mut schema, "minimum", *min, i128::MIN);
+            integer_bound(&mut schema, "maximum", *max, i128::MAX);
+            tagged("Integer", Value::Object(schema))
+        }
+        MetadataField::IntegerValue(value) => match i64::try_from(*value) {
+            Ok(value) => tagged("Integer", json!({ "const": value })),
+            Err(_) => tagged("Integer", json!({ "type": "integer" })),
+        },
+        MetadataField::TokenId => tagged("TokenId", json!({ "type": "integer", "minimum": 0 })),
+        MetadataField::Text => tagged("Text", json!({ "type": "string", "maxLength": MAX_VALUE_LENGTH })),
+        MetadataField::Bytes => tagged(
+            "Bytes",
+            json!({
+                "type": "array",
+                "items": { "type": "integer", "minimum": 0, "maximum": 255 },
+                "maxItems": MAX_VALUE_LENGTH,
+            }),
+        ),
+        MetadataField::Bool => tagged("Bool", json!({ "type": "boolean" })),
+        // milliseconds since the unix epoch
+        MetadataField::Timestamp => tagged("Timestamp", json!({ "type": "integer", "minimum": 0 })),
+        // accounts are given by their ss58 address
+        MetadataField::Account => tagged("Account", json!({ "type": "string" })),
+        MetadataField::List => tagged(
+            "List",
+            json!({
+                "type": "array",
+                "items": { "type": "string", "maxLength": MAX_LIST_ITEM_LENGTH },
+                "maxItems": MAX_LIST_LENGTH,
+            }),
+        ),
+    }
+}
+
+fn any_of(mut schemas: Vec<Value>) -> Value {
+    match schemas.len() {
+        1 => schemas.remove(0),
+        _ => json!({ "anyOf": schemas }),
+    }
+}
+
+fn object(properties: Map<String, Value>, required: Vec<&str>) -> Value {
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn token_schema(token_decl: &TokenDecl) -> Value {
+    let name = token_decl.name.value;
+    let fields = token_fields(token_decl);
+    // a field that can be both a role and metadata is only held as one of them so is never required in either
+    let required = |field: &&Field| !field.optional && !field.is_mixed();
+
+    let roles = fields
+        .iter()
+        .filter(|f| f.role)
+        .map(|f| (f.name.to_owned(), json!({ "type": "string" })))
+        .collect::<Map<_, _>>();
+    let required_roles = fields
+        .iter()
+        .filter(|f| f.role)
+        .filter(required)
+        .map(|f| f.name)
+        .collect();
+
+    let mut metadata = Map::new();
+    metadata.insert("@type".to_owned(), literal_const(name));
+    metadata.insert("@version".to_owned(), literal_const(TOKEN_VERSION));
+    metadata.insert("@original_id".to_owned(), metadata_schema(&MetadataField::TokenId));
+    let mut required_metadata = vec!["@type", "@version"];
+    for field in fields.iter().filter(|f| !f.metadata.is_empty()) {
+        let schemas = field.metadata.iter().map(metadata_schema).collect();
+        metadata.insert(field.name.to_owned(), any_of(schemas));
+        if required(&field) {
+            required_metadata.push(field.name);
+        }
+    }
+
+    let mut properties = Map::new();
+    properties.insert("roles".to_owned(), object(roles, required_roles));
+    properties.insert("metadata".to_owned(), object(metadata, required_metadata));
+    let mut schema = object(properties, vec!["roles", "metadata"]);
+    schema["title"] = format!("{} output", name).into();
+    schema
+}
+
+fn tuple(items: Vec<Value>) -> Value {
+    let len = items.len();
+    json!({
+        "type": "array",
+        "prefixItems": items,
+        "minItems": len,
+        "maxItems": len,
+    })
+}
+
+fn process_schema(fn_decl: &FnDecl) -> Value {
+    let id = fn_decl.name.value;
+    let inputs = fn_decl
+        .inputs
+        .value
+        .iter()
+        .map(|i| json!({ "title": i.value.name.value, "type": "integer", "minimum": 0 }))
+        .collect();
+    let outputs = fn_decl
+        .outputs
+        .value
+        .iter()
+        .map(|o| {
+            json!({
+                "title": o.value.name.value,
+                "$ref": format!("#/$defs/{}Output", o.value.token_type.value),
+            })
+        })
+        .collect();
+
+    let mut properties = Map::new();
+    properties.insert(
+        "process".to_owned(),
+        object(
+            Map::from_iter([
+                ("id".to_owned(), json!({ "const": id })),
+                ("version".to_owned(), json!({ "type": "integer", "minimum": 1 })),
+            ]),
+            vec!["id", "version"],
+        ),
+    );
+    properties.insert("inputs".to_owned(), tuple(inputs));
+    properties.insert("outputs".to_owned(), tuple(outputs));
+    let mut schema = object(properties, vec!["process", "inputs", "outputs"]);
+    schema["title"] = format!("{} arguments", id).into();
+    schema
+}
+
+/// Generates a JSON Schema document defining the outputs of each token type as `{Token}Output` and the arguments of
+/// `run_process` for each process as `{Process}Args`, in the JSON representation used by `sqnc-lang run`
+pub fn generate(ast: &Ast) -> String {
+    let mut defs = Map::new();
+    for token_decl in token_decls(ast) {
+        defs.insert(format!("{}Output", token_decl.name.value), token_schema(token_decl));
+    }
+    for fn_decl in public_fn_decls(ast) {
+        defs.insert(
+            format!("{}Args", pascal_case(fn_decl.name.value)),
+            process_schema(fn_decl),
+        );
+    }
+
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$defs": defs,
+    });
+    serde_json::to_string_pretty(&schema).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::generate;
+    use crate::ast::parse_str_to_ast;
+
+    fn generate_str(input: &str) -> Value {
+        let ast = parse_str_to_ast(input).unwrap();
+        serde_json::from_str(&generate(&ast)).unwrap()
+    }
+
+    #[test]
+    fn generates_token_schema() {
+        let schema = generate_str(
+            r##"
+          token Order {
+              owner: Role,
+              state: "placed" | "accepted",
+              quantity: Integer(1..=10),
+              notes: File | None,
+              holder: Role | Literal(max 8),
+          }
+      "##,
+        );
+        let output = &schema["$defs"]["OrderOutput"];
+
+        assert_eq!(output["properties"]["roles"]["required"], json!(["owner"]));
+        assert_eq!(
+            output["properties"]["metadata"]["required"],
+            json!(["@type", "@version", "state", "quantity"])
+        );
+        let metadata = &output["properties"]["metadata"]["properties"];
+        assert_eq!(metadata["@type"]["properties"]["Literal"], json!({ "const": "Order" }));
+        assert_eq!(
+            metadata["state"]["anyOf"][1]["properties"]["Literal"],
+            json!({ "const": "accepted" })
+        );
+        assert_eq!(
+            metadata["quantity"]["properties"]["Integer"],
+            json!({ "type": "integer", "minimum": 1, "maximum": 10 })
+        );
+        assert_eq!(
+            metadata["holder"]["properties"]["Literal"],
+            json!({ "type": "string", "maxLength": 8 })
+        );
+    }
+
+    #[test]
+    fn structured_metadata() {
+        let schema = generate_str(
+            r##"
+          token Order {
+              description: Text,
+              signature: Bytes,
+              urgent: Bool,
+              due: Timestamp | None,
+              tags: List,
+              approved_by: Account,
+          }
+      "##,
+        );
+        let metadata = &schema["$defs"]["OrderOutput"]["properties"]["metadata"]["properties"];
+
+        assert_eq!(
+            metadata["description"]["properties"]["Text"],
+            json!({ "type": "string", "maxLength": 256 })
+        );
+        assert_eq!(
+            metadata["signature"]["properties"]["Bytes"],
+            json!({ "type": "array", "items": { "type": "integer", "minimum": 0, "maximum": 255 }, "maxItems": 256 })
+        );
+        assert_eq!(metadata["urgent"]["properties"]["Bool"], json!({ "type": "boolean" }));
+        assert_eq!(
+            metadata["due"]["properties"]["Timestamp"],
+            json!({ "type": "integer", "minimum": 0 })
+        );
+        assert_eq!(
+            metadata["tags"]["properties"]["List"],
+            json!({ "type": "array", "items": { "type": "string", "maxLength": 32 }, "maxItems": 16 })
+        );
+        assert_eq!(
+            metadata["approved_by"]["properties"]["Account"],
+            json!({ "type": "string" })
+        );
+    }
+
+    #[test]
+    fn unbounded_integers() {
+        let schema = generate_str(
+            r##"
+          token Order {
+              a: Integer,
+              b: Integer(..=-1),
+              c: Integer(0..=170141183460469231731687303715884105727),
+          }
+      "##,
+        );
+        let metadata = &schema["$defs"]["OrderOutput"]["properties"]["metadata"]["properties"];
+
+        assert_eq!(metadata["a"]["properties"]["Integer"], json!({ "type": "integer" }));
+        assert_eq!(
+            metadata["b"]["properties"]["Integer"],
+            json!({ "type": "integer", "maximum": -1 })
+        );
+        assert_eq!(
+            metadata["c"]["properties"]["Integer"],
+            json!({ "type": "integer", "minimum": 0 })
+        );
+    }
+
+    #[test]
+    fn generates_process_schema() {
+        let schema = generate_str(
+            r##"
+          token Order { owner: Role }
+          pub fn transfer_order | a: Order | => | b: Order | where { a.owner == sender }
+      "##,
+        );
+        let args = &schema["$defs"]["TransferOrderArgs"];
+
+        assert_eq!(
+            args["properties"]["process"]["properties"]["id"],
+            json!({ "const": "transfer_order" })
+        );
+        assert_eq!(args["properties"]["inputs"]["maxItems"], json!(1));
+        assert_eq!(
+            args["properties"]["outputs"]["prefixItems"][0]["$ref"],
+            json!("#/$defs/OrderOutput")
+        );
+    }
+}
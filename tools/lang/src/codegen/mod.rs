@@ -0,0 +1,235 @@
+use clap::ValueEnum;
+
+use crate::ast::types::{Ast, AstRoot, FnDecl, FnVis, TokenDecl, TokenFieldType};
+
+mod json_schema;
+mod typescript;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Target {
+    /// TypeScript types and builders
+    Ts,
+    /// JSON Schema (draft 2020-12)
+    Jsonschema,
+}
+
+/// The version given to each token type in the `@version` metadata of a token
+const TOKEN_VERSION: &str = "1";
+/// The maximum length in bytes of a `Text` or `Bytes` metadata value
+const MAX_VALUE_LENGTH: u32 = 256;
+/// The maximum number of literals in a `List` metadata value and the maximum length of each
+const MAX_LIST_LENGTH: u32 = 16;
+const MAX_LIST_ITEM_LENGTH: u32 = 32;
+
+/// A value a token field can be stored as in the metadata of a token
+#[derive(Debug, Clone, PartialEq)]
+enum MetadataField<'a> {
+    File,
+    Literal { max_length: Option<u32> },
+    LiteralValue(&'a str),
+    Integer { min: i128, max: i128 },
+    IntegerValue(i128),
+    TokenId,
+    Text,
+    Bytes,
+    Bool,
+    Timestamp,
+    List,
+    Account,
+}
+
+impl<'a> MetadataField<'a> {
+    // the key of the value in the JSON representation of `MetadataValue`
+    fn tag(&self) -> &'static str {
+        match self {
+            MetadataField::File => "File",
+            MetadataField::Literal { .. } | MetadataField::LiteralValue(_) => "Literal",
+            MetadataField::Integer { .. } | MetadataField::IntegerValue(_) => "Integer",
+            MetadataField::TokenId => "TokenId",
+            MetadataField::Text => "Text",
+            MetadataField::Bytes => "Bytes",
+            MetadataField::Bool => "Bool",
+            MetadataField::Timestamp => "Timestamp",
+            MetadataField::List => "List",
+            MetadataField::Account => "Account",
+        }
+    }
+}
+
+/// How a token field is stored on chain. A field is held as a role if it can be a `Role` and in metadata if it can
+/// be any other type. A field that can be `None` may be omitted
+#[derive(Debug, Clone, PartialEq)]
+struct Field<'a> {
+    name: &'a str,
+    role: bool,
+    metadata: Vec<MetadataField<'a>>,
+    optional: bool,
+}
+
+impl<'a> Field<'a> {
+    // whether the field can be both a role and metadata so the two must be distinguished when it is given
+    fn is_mixed(&self) -> bool {
+        self.role && !self.metadata.is_empty()
+    }
+
+    // whether the field can be held as metadata of more than one type so the type must be given with the value
+    fn is_tagged(&self) -> bool {
+        self.is_mixed() || self.metadata.iter().any(|m| m.tag() != self.metadata[0].tag())
+    }
+}
+
+fn token_fields<'a>(token_decl: &TokenDecl<'a>) -> Vec<Field<'a>> {
+    token_decl
+        .props
+        .value
+        .iter()
+        .map(|prop| {
+            let mut field = Field {
+                name: prop.value.name.value,
+                role: false,
+                metadata: Vec::new(),
+                optional: false,
+            };
+            for field_type in prop.value.types.iter() {
+                let metadata = match &field_type.value {
+                    TokenFieldType::None => {
+                        field.optional = true;
+                        continue;
+                    }
+                    TokenFieldType::Role => {
+                        field.role = true;
+                        continue;
+                    }
+                    TokenFieldType::File => MetadataField::File,
+                    TokenFieldType::Literal => MetadataField::Literal { max_length: None },
+                    TokenFieldType::LiteralMaxLength(max_length) => MetadataField::Literal {
+                        max_length: Some(*max_length),
+                    },
+                    TokenFieldType::LiteralValue(value) => MetadataField::LiteralValue(value.value),
+                    TokenFieldType::Integer => MetadataField::Integer {
+                        min: i128::MIN,
+                        max: i128::MAX,
+                    },
+                    TokenFieldType::IntegerRange { min, max } => MetadataField::Integer { min: *min, max: *max },
+                    TokenFieldType::IntegerValue(value) => MetadataField::IntegerValue(value.value),
+                    TokenFieldType::Token(_) => MetadataField::TokenId,
+                    TokenFieldType::Text => MetadataField::Text,
+                    TokenFieldType::Bytes => MetadataField::Bytes,
+                    TokenFieldType::Bool => MetadataField::Bool,
+                    TokenFieldType::Timestamp => MetadataField::Timestamp,
+                    TokenFieldType::List => MetadataField::List,
+                    TokenFieldType::Account => MetadataField::Account,
+                };
+                if !field.metadata.contains(&metadata) {
+                    field.metadata.push(metadata);
+                }
+            }
+            field
+        })
+        .collect()
+}
+
+fn token_decls<'a, 'b>(ast: &'b Ast<'a>) -> impl Iterator<Item = &'b TokenDecl<'a>> {
+    ast.iter().filter_map(|node| match &node.value {
+        AstRoot::TokenDecl(t) => Some(&t.value),
+        AstRoot::FnDecl(_) => None,
+    })
+}
+
+fn public_fn_decls<'a, 'b>(ast: &'b Ast<'a>) -> impl Iterator<Item = &'b FnDecl<'a>> {
+    ast.iter().filter_map(|node| match &node.value {
+        AstRoot::FnDecl(f) if f.value.visibility.value == FnVis::Public => Some(&f.value),
+        _ => None,
+    })
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Generates types describing the outputs of each token type and the arguments of each process of a model
+pub fn generate(ast: &Ast, target: Target) -> String {
+    match target {
+        Target::Ts => typescript::generate(ast),
+        Target::Jsonschema => json_schema::generate(ast),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parse_str_to_ast;
+
+    #[test]
+    fn fields_of_token() {
+        let ast = parse_str_to_ast(
+            r##"
+          token Order {
+              owner: Role,
+              state: "placed" | "accepted",
+              quantity: Integer(1..=10),
+              notes: File | None,
+              holder: Role | Literal,
+          }
+      "##,
+        )
+        .unwrap();
+        let token_decl = token_decls(&ast).next().unwrap();
+        let fields = token_fields(token_decl);
+
+        assert_eq!(
+            fields,
+            vec![
+                Field {
+                    name: "owner",
+                    role: true,
+                    metadata: vec![],
+                    optional: false
+                },
+                Field {
+                    name: "state",
+                    role: false,
+                    metadata: vec![
+                        MetadataField::LiteralValue("placed"),
+                        MetadataField::LiteralValue("accepted")
+                    ],
+                    optional: false
+                },
+                Field {
+                    name: "quantity",
+                    role: false,
+                    metadata: vec![MetadataField::Integer { min: 1, max: 10 }],
+                    optional: false
+                },
+                Field {
+                    name: "notes",
+                    role: false,
+                    metadata: vec![MetadataField::File],
+                    optional: true
+                },
+                Field {
+                    name: "holder",
+                    role: true,
+                    metadata: vec![MetadataField::Literal { max_length: None }],
+                    optional: false
+                },
+            ]
+        );
+        assert!(!fields[1].is_tagged());
+        assert!(fields[4].is_mixed() && fields[4].is_tagged());
+    }
+
+    #[test]
+    fn pascal_case_names() {
+        assert_eq!(pascal_case("match2_acceptFinal"), "Match2AcceptFinal");
+        assert_eq!(pascal_case("create"), "Create");
+    }
+}
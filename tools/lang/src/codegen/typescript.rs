@@ -0,0 +1,371 @@
+use std::fmt::Write;
+
+use super::{pascal_case, public_fn_decls, token_decls, token_fields, Field, MetadataField, TOKEN_VERSION};
+use crate::ast::types::{Ast, FnDecl, TokenDecl};
+
+const PREAMBLE: &str = r#"// Generated by sqnc-lang. Do not edit
+
+export type MetadataValue =
+  | { File: string }
+  | { Literal: string }
+  | { TokenId: number }
+  | { Integer: number }
+  | "None"
+  | { Text: string }
+  | { Bytes: number[] }
+  | { Bool: boolean }
+  | { Timestamp: number }
+  | { List: string[] }
+  | { Account: string }
+
+export interface Output<Roles, Metadata> {
+  roles: Roles
+  metadata: Metadata
+}
+
+export interface ProcessId<Id extends string> {
+  id: Id
+  version: number
+}
+"#;
+
+fn camel_case(name: &str) -> String {
+    let name = pascal_case(name);
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// the type of the value held in `MetadataValue` for a field
+fn value_type(metadata: &MetadataField) -> String {
+    match metadata {
+        MetadataField::File | MetadataField::Literal { .. } | MetadataField::Text | MetadataField::Account => {
+            "string".to_owned()
+        }
+        MetadataField::LiteralValue(value) => format!("\"{}\"", value),
+        MetadataField::Integer { .. } | MetadataField::TokenId | MetadataField::Timestamp => "number".to_owned(),
+        MetadataField::IntegerValue(value) => value.to_string(),
+        MetadataField::Bytes => "number[]".to_owned(),
+        MetadataField::Bool => "boolean".to_owned(),
+        MetadataField::List => "string[]".to_owned(),
+    }
+}
+
+fn tagged_type(metadata: &MetadataField) -> String {
+    format!("{{ {}: {} }}", metadata.tag(), value_type(metadata))
+}
+
+fn union(types: impl Iterator<Item = String>) -> String {
+    types
+        .fold(Vec::new(), |mut acc, t| {
+            if !acc.contains(&t) {
+                acc.push(t);
+            }
+            acc
+        })
+        .join(" | ")
+}
+
+// the type a field is given as to the builder of an output
+fn field_type(field: &Field) -> String {
+    match field.is_tagged() {
+        true => union(
+            field
+                .role
+                .then(|| "{ Role: string }".to_owned())
+                .into_iter()
+                .chain(field.metadata.iter().map(tagged_type)),
+        ),
+        false => match field.role {
+            true => "string".to_owned(),
+            false => union(field.metadata.iter().map(value_type)),
+        },
+    }
+}
+
+fn optional_marker(optional: bool) -> &'static str {
+    match optional {
+        true => "?",
+        false => "",
+    }
+}
+
+fn write_interface(out: &mut String, name: &str, members: Vec<String>) {
+    match members.is_empty() {
+        true => writeln!(out, "\nexport interface {} {{}}", name).unwrap(),
+        false => writeln!(out, "\nexport interface {} {{\n  {}\n}}", name, members.join("\n  ")).unwrap(),
+    }
+}
+
+fn write_token(out: &mut String, token_decl: &TokenDecl) {
+    let name = token_decl.name.value;
+    let fields = token_fields(token_decl);
+
+    let members = fields
+        .iter()
+        .map(|f| format!("{}{}: {}", f.name, optional_marker(f.optional), field_type(f)))
+        .collect();
+    write_interface(out, &format!("{}Fields", name), members);
+
+    let members = fields
+        .iter()
+        .filter(|f| f.role)
+        .map(|f| format!("{}{}: string", f.name, optional_marker(f.optional || f.is_mixed())))
+        .collect();
+    write_interface(out, &format!("{}Roles", name), members);
+
+    writeln!(out, "\nexport interface {}Metadata {{", name).unwrap();
+    writeln!(out, "  \"@type\": {{ Literal: \"{}\" }}", name).unwrap();
+    writeln!(out, "  \"@version\": {{ Literal: \"{}\" }}", TOKEN_VERSION).unwrap();
+    writeln!(out, "  \"@original_id\"?: {{ TokenId: number }}").unwrap();
+    for field in fields.iter().filter(|f| !f.metadata.is_empty()) {
+        let optional = optional_marker(field.optional || field.is_mixed());
+        let types = union(field.metadata.iter().map(tagged_type));
+        writeln!(out, "  {}{}: {}", field.name, optional, types).unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+
+    writeln!(out, "\nexport type {0}Output = Output<{0}Roles, {0}Metadata>", name).unwrap();
+
+    writeln!(
+        out,
+        "\nexport function {}Output(fields: {}Fields, originalId?: number): {}Output {{",
+        camel_case(name),
+        name,
+        name
+    )
+    .unwrap();
+    writeln!(out, "  const roles: Record<string, string> = {{}}").unwrap();
+    writeln!(out, "  const metadata: Record<string, MetadataValue> = {{").unwrap();
+    writeln!(out, "    \"@type\": {{ Literal: \"{}\" }},", name).unwrap();
+    writeln!(out, "    \"@version\": {{ Literal: \"{}\" }},", TOKEN_VERSION).unwrap();
+    writeln!(out, "  }}").unwrap();
+    writeln!(
+        out,
+        "  if (originalId !== undefined) metadata[\"@original_id\"] = {{ TokenId: originalId }}"
+    )
+    .unwrap();
+    for field in &fields {
+        let value = format!("fields.{}", field.name);
+        let assignment = match (field.is_mixed(), field.is_tagged(), field.role) {
+            (true, _, _) => format!(
+                "if (\"Role\" in {0}) roles[\"{1}\"] = {0}.Role\n  else metadata[\"{1}\"] = {0}",
+                value, field.name
+            ),
+            (false, true, _) => format!("metadata[\"{}\"] = {}", field.name, value),
+            (false, false, true) => format!("roles[\"{}\"] = {}", field.name, value),
+            (false, false, false) => format!(
+                "metadata[\"{}\"] = {{ {}: {} }}",
+                field.name,
+                field.metadata[0].tag(),
+                value
+            ),
+        };
+        match field.optional {
+            true => writeln!(
+                out,
+                "  if ({} !== undefined) {{\n    {}\n  }}",
+                value,
+                assignment.replace("\n  ", "\n    ")
+            )
+            .unwrap(),
+            false => writeln!(out, "  {}", assignment).unwrap(),
+        }
+    }
+    writeln!(out, "  return {{ roles, metadata }} as unknown as {}Output", name).unwrap();
+    writeln!(out, "}}").unwrap();
+}
+
+fn write_process(out: &mut String, fn_decl: &FnDecl) {
+    let id = fn_decl.name.value;
+    let name = pascal_case(id);
+    let inputs = &fn_decl.inputs.value;
+    let outputs = &fn_decl.outputs.value;
+
+    let members = inputs
+        .iter()
+        .map(|i| format!("{}: number", i.value.name.value))
+        .collect();
+    write_interface(out, &format!("{}Inputs", name), members);
+
+    let members = outputs
+        .iter()
+        .map(|o| format!("{}: {}Output", o.value.name.value, o.value.token_type.value))
+        .collect();
+    write_interface(out, &format!("{}Outputs", name), members);
+
+    let input_types = inputs.iter().map(|_| "number".to_owned()).collect::<Vec<_>>();
+    let output_types = outputs
+        .iter()
+        .map(|o| format!("{}Output", o.value.token_type.value))
+        .collect::<Vec<_>>();
+    writeln!(out, "\nexport interface {}Args {{", name).unwrap();
+    writeln!(out, "  process: ProcessId<\"{}\">", id).unwrap();
+    writeln!(out, "  inputs: [{}]", input_types.join(", ")).unwrap();
+    writeln!(out, "  outputs: [{}]", output_types.join(", ")).unwrap();
+    writeln!(out, "}}").unwrap();
+
+    let input_values = inputs
+        .iter()
+        .map(|i| format!("inputs.{}", i.value.name.value))
+        .collect::<Vec<_>>();
+    let output_values = outputs
+        .iter()
+        .map(|o| format!("outputs.{}", o.value.name.value))
+        .collect::<Vec<_>>();
+    writeln!(
+        out,
+        "\nexport function {0}Args(version: number, inputs: {1}Inputs, outputs: {1}Outputs): {1}Args {{",
+        camel_case(id),
+        name
+    )
+    .unwrap();
+    writeln!(out, "  return {{").unwrap();
+    writeln!(out, "    process: {{ id: \"{}\", version }},", id).unwrap();
+    writeln!(out, "    inputs: [{}],", input_values.join(", ")).unwrap();
+    writeln!(out, "    outputs: [{}],", output_values.join(", ")).unwrap();
+    writeln!(out, "  }}").unwrap();
+    writeln!(out, "}}").unwrap();
+}
+
+/// Generates a TypeScript module with the types of the outputs of each token type along with a function to build
+/// them from their fields, and the types of the arguments of `run_process` for each process
+pub fn generate(ast: &Ast) -> String {
+    let mut out = PREAMBLE.to_owned();
+    for token_decl in token_decls(ast) {
+        write_token(&mut out, token_decl);
+    }
+    for fn_decl in public_fn_decls(ast) {
+        write_process(&mut out, fn_decl);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+    use crate::ast::parse_str_to_ast;
+
+    #[test]
+    fn generates_token_types() {
+        let ast = parse_str_to_ast(
+            r##"
+          token Order {
+              owner: Role,
+              state: "placed" | "accepted",
+              notes: File | None,
+              holder: Role | Literal,
+          }
+      "##,
+        )
+        .unwrap();
+        let ts = generate(&ast);
+
+        assert!(ts.contains(
+            r#"export interface OrderFields {
+  owner: string
+  state: "placed" | "accepted"
+  notes?: string
+  holder: { Role: string } | { Literal: string }
+}"#
+        ));
+        assert!(ts.contains(
+            r#"export interface OrderRoles {
+  owner: string
+  holder?: string
+}"#
+        ));
+        assert!(ts.contains(
+            r#"export interface OrderMetadata {
+  "@type": { Literal: "Order" }
+  "@version": { Literal: "1" }
+  "@original_id"?: { TokenId: number }
+  state: { Literal: "placed" } | { Literal: "accepted" }
+  notes?: { File: string }
+  holder?: { Literal: string }
+}"#
+        ));
+        assert!(ts.contains(
+            r#"export function orderOutput(fields: OrderFields, originalId?: number): OrderOutput {
+  const roles: Record<string, string> = {}
+  const metadata: Record<string, MetadataValue> = {
+    "@type": { Literal: "Order" },
+    "@version": { Literal: "1" },
+  }
+  if (originalId !== undefined) metadata["@original_id"] = { TokenId: originalId }
+  roles["owner"] = fields.owner
+  metadata["state"] = { Literal: fields.state }
+  if (fields.notes !== undefined) {
+    metadata["notes"] = { File: fields.notes }
+  }
+  if ("Role" in fields.holder) roles["holder"] = fields.holder.Role
+  else metadata["holder"] = fields.holder
+  return { roles, metadata } as unknown as OrderOutput
+}"#
+        ));
+    }
+
+    #[test]
+    fn generates_structured_metadata_types() {
+        let ast = parse_str_to_ast(
+            r##"
+          token Order {
+              description: Text,
+              signature: Bytes,
+              urgent: Bool,
+              due: Timestamp | None,
+              tags: List | Literal,
+              approved_by: Account,
+          }
+      "##,
+        )
+        .unwrap();
+        let ts = generate(&ast);
+
+        assert!(ts.contains(
+            r#"export interface OrderFields {
+  description: string
+  signature: number[]
+  urgent: boolean
+  due?: number
+  tags: { List: string[] } | { Literal: string }
+  approved_by: string
+}"#
+        ));
+        assert!(ts.contains(r#"  metadata["description"] = { Text: fields.description }"#));
+        assert!(ts.contains(r#"  metadata["signature"] = { Bytes: fields.signature }"#));
+    }
+
+    #[test]
+    fn generates_process_types() {
+        let ast = parse_str_to_ast(
+            r##"
+          token Order { owner: Role }
+          pub fn transfer_order | a: Order | => | b: Order | where { a.owner == sender }
+          fn private | a: Order | => || where {}
+      "##,
+        )
+        .unwrap();
+        let ts = generate(&ast);
+
+        assert!(ts.contains(
+            r#"export interface TransferOrderArgs {
+  process: ProcessId<"transfer_order">
+  inputs: [number]
+  outputs: [OrderOutput]
+}"#
+        ));
+        assert!(ts.contains(
+            r#"export function transferOrderArgs(version: number, inputs: TransferOrderInputs, outputs: TransferOrderOutputs): TransferOrderArgs {
+  return {
+    process: { id: "transfer_order", version },
+    inputs: [inputs.a],
+    outputs: [outputs.b],
+  }
+}"#
+        ));
+        assert!(!ts.contains("Private"));
+    }
+}
@@ -1,5 +1,7 @@
 use std::{collections::HashMap, sync::Arc};
 
+use sqnc_process_interpreter::{is_valid_pattern, matches_pattern, LiteralPattern};
+
 use sqnc_runtime_types::{
     BooleanExpressionSymbol, BooleanOperator, MetadataValue, MetadataValueType, Restriction, RuntimeExpressionSymbol,
     TokenMetadataKey,
@@ -7,11 +9,11 @@ use sqnc_runtime_types::{
 
 use crate::{
     ast::types::{
-        AstNode, BoolCmp, BoolOp, Comparison, ExpressionTree, FnArg, FnDecl, TokenDecl, TokenFieldType, TokenProp,
-        TypeCmp, TypeCmpType,
+        AstNode, BoolCmp, BoolOp, Comparison, ExpressionTree, FnArg, FnDecl, StrCmp, TokenDecl, TokenFieldType,
+        TokenProp, TypeCmp, TypeCmpType,
     },
     compiler::CompilationStage,
-    errors::{CompilationError, ErrorVariant, PestError},
+    errors::{exit_code, CompilationError, ErrorCode, ErrorVariant, PestError},
 };
 
 use super::{constants::ORIGINAL_ID_KEY, to_bounded_vec};
@@ -50,8 +52,9 @@ fn find_token<'a>(
 
     let (is_input, index, arg) = match (find_input, find_output) {
         (None, None) => Err(CompilationError {
+            code: ErrorCode::UnknownToken,
             stage: CompilationStage::GenerateRestrictions,
-            exit_code: exitcode::DATAERR,
+            exit_code: exit_code::DATAERR,
             inner: PestError::new_from_span(
                 ErrorVariant::CustomError {
                     message: "Unknown token".into(),
@@ -62,8 +65,9 @@ fn find_token<'a>(
         (None, Some((index, arg))) => Ok((false, index, arg)),
         (Some((index, arg)), None) => Ok((true, index, arg)),
         (Some(_), Some(_)) => Err(CompilationError {
+            code: ErrorCode::Internal,
             stage: CompilationStage::GenerateRestrictions,
-            exit_code: exitcode::SOFTWARE,
+            exit_code: exit_code::SOFTWARE,
             inner: PestError::new_from_span(
                 ErrorVariant::CustomError {
                     message: "Unexpected error. Function args should be unique by this point in compilation".into(),
@@ -88,8 +92,9 @@ fn find_token_prop<'a>(
     let TokenLocation { is_input, index, arg } = find_token(fn_decl, &prop.token)?;
 
     let token_decl = token_decls.get(arg.token_type.value).ok_or(CompilationError {
+        code: ErrorCode::UnknownTokenType,
         stage: CompilationStage::GenerateRestrictions,
-        exit_code: exitcode::DATAERR,
+        exit_code: exit_code::DATAERR,
         inner: PestError::new_from_span(
             ErrorVariant::CustomError {
                 message: format!("Unknown token type {}", arg.token_type.value),
@@ -104,8 +109,9 @@ fn find_token_prop<'a>(
         .iter()
         .find(|prop_decl| prop_decl.value.name.value == prop.prop.value)
         .ok_or(CompilationError {
+            code: ErrorCode::UnknownProperty,
             stage: CompilationStage::GenerateRestrictions,
-            exit_code: exitcode::DATAERR,
+            exit_code: exit_code::DATAERR,
             inner: PestError::new_from_span(
                 ErrorVariant::CustomError {
                     message: format!(
@@ -126,6 +132,20 @@ fn find_token_prop<'a>(
     })
 }
 
+// negates the result of a program. `NotL` only reads its left operand so `Restriction::None` is pushed as the right
+fn negate(program: &mut Vec<RuntimeExpressionSymbol>) {
+    program.push(BooleanExpressionSymbol::Restriction(Restriction::None));
+    program.push(BooleanExpressionSymbol::Op(BooleanOperator::NotL));
+}
+
+// every comparison is compiled as equality with `!=` being its negation
+fn apply_cmp_op(mut program: Vec<RuntimeExpressionSymbol>, op: BoolCmp) -> Vec<RuntimeExpressionSymbol> {
+    if op == BoolCmp::Neq {
+        negate(&mut program);
+    }
+    program
+}
+
 pub fn transform_condition_to_program(
     fn_decl: &FnDecl,
     token_decls: &HashMap<&str, TokenDecl>,
@@ -136,8 +156,9 @@ pub fn transform_condition_to_program(
             let AstNode { value: comp, span } = comp;
             match comp {
                 Comparison::Fn { .. } => Err(CompilationError {
+                    code: ErrorCode::Internal,
                     stage: crate::compiler::CompilationStage::ReduceTokens,
-                    exit_code: exitcode::SOFTWARE,
+                    exit_code: exit_code::SOFTWARE,
                     inner: PestError::new_from_span(
                         ErrorVariant::CustomError {
                             message: "Internal Error. Unexpected function call (should have been flattened)?".into(),
@@ -153,14 +174,16 @@ pub fn transform_condition_to_program(
                         .iter()
                         .find(|field_type| match &field_type.value {
                             TokenFieldType::Literal => true,
+                            TokenFieldType::LiteralMaxLength(max) => right.value.len() <= *max as usize,
                             TokenFieldType::LiteralValue(v) => v.value == right.value,
                             _ => false,
                         })
                         .is_none()
                     {
                         return Err(CompilationError {
+                            code: ErrorCode::InvalidComparison,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: format!(
@@ -183,7 +206,7 @@ pub fn transform_condition_to_program(
                         span: right.span,
                     })?);
 
-                    let mut result = vec![match is_input {
+                    let result = vec![match is_input {
                         true => BooleanExpressionSymbol::Restriction(Restriction::FixedInputMetadataValue {
                             index,
                             metadata_key,
@@ -196,14 +219,7 @@ pub fn transform_condition_to_program(
                         }),
                     }];
 
-                    if op == BoolCmp::Neq {
-                        result.append(&mut vec![
-                            BooleanExpressionSymbol::Restriction(Restriction::None),
-                            BooleanExpressionSymbol::Op(BooleanOperator::NotL),
-                        ]);
-                    }
-
-                    Ok(result)
+                    Ok(apply_cmp_op(result, op))
                 }
                 Comparison::PropInt { left, op, right } => {
                     let TokenPropLocation {
@@ -213,14 +229,16 @@ pub fn transform_condition_to_program(
                         .iter()
                         .find(|field_type| match &field_type.value {
                             TokenFieldType::Integer => true,
+                            TokenFieldType::IntegerRange { min, max } => (*min..=*max).contains(&right.value),
                             TokenFieldType::IntegerValue(v) => v.value == right.value,
                             _ => false,
                         })
                         .is_none()
                     {
                         return Err(CompilationError {
+                            code: ErrorCode::InvalidComparison,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: format!(
@@ -240,7 +258,7 @@ pub fn transform_condition_to_program(
 
                     let metadata_value = MetadataValue::Integer(right.value);
 
-                    let mut result = vec![match is_input {
+                    let result = vec![match is_input {
                         true => BooleanExpressionSymbol::Restriction(Restriction::FixedInputMetadataValue {
                             index,
                             metadata_key,
@@ -253,34 +271,25 @@ pub fn transform_condition_to_program(
                         }),
                     }];
 
-                    if op == BoolCmp::Neq {
-                        result.append(&mut vec![
-                            BooleanExpressionSymbol::Restriction(Restriction::None),
-                            BooleanExpressionSymbol::Op(BooleanOperator::NotL),
-                        ]);
-                    }
-
-                    Ok(result)
+                    Ok(apply_cmp_op(result, op))
                 }
                 Comparison::PropSender { left, op } => {
                     let TokenPropLocation {
                         is_input, index, types, ..
                     } = find_token_prop(token_decls, fn_decl, &left.value)?;
-                    if types
+                    let can_be_role = types.iter().any(|field_type| field_type.value == TokenFieldType::Role);
+                    let can_be_account = types
                         .iter()
-                        .find(|field_type| match &field_type.value {
-                            TokenFieldType::Role => true,
-                            _ => false,
-                        })
-                        .is_none()
-                    {
+                        .any(|field_type| field_type.value == TokenFieldType::Account);
+                    if !can_be_role && !can_be_account {
                         return Err(CompilationError {
+                            code: ErrorCode::InvalidSenderComparison,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: format!(
-                                        "Cannot compare property {} to sender as it is not a Role",
+                                        "Cannot compare property {} to sender as it is not a Role or Account",
                                         left.value.prop.value,
                                     ),
                                 },
@@ -289,29 +298,202 @@ pub fn transform_condition_to_program(
                         });
                     }
 
-                    let role_key = to_bounded_vec(AstNode {
+                    let key: TokenMetadataKey = to_bounded_vec(AstNode {
                         value: left.value.prop.value.as_bytes().to_owned(),
                         span: left.value.prop.span,
                     })?;
 
-                    let mut result = vec![match is_input {
-                        true => {
-                            BooleanExpressionSymbol::Restriction(Restriction::SenderHasInputRole { index, role_key })
+                    let mut result = Vec::new();
+                    if can_be_role {
+                        let role_key = key.clone();
+                        result.push(BooleanExpressionSymbol::Restriction(match is_input {
+                            true => Restriction::SenderHasInputRole { index, role_key },
+                            false => Restriction::SenderHasOutputRole { index, role_key },
+                        }));
+                    }
+                    if can_be_account {
+                        let metadata_key = key;
+                        result.push(BooleanExpressionSymbol::Restriction(match is_input {
+                            true => Restriction::SenderIsInputMetadataAccount { index, metadata_key },
+                            false => Restriction::SenderIsOutputMetadataAccount { index, metadata_key },
+                        }));
+                    }
+                    // a field holds either a role or metadata so only one of the restrictions can pass
+                    if can_be_role && can_be_account {
+                        result.push(BooleanExpressionSymbol::Op(BooleanOperator::Or));
+                    }
+
+                    Ok(apply_cmp_op(result, op))
+                }
+                Comparison::SenderIn { props } => {
+                    // role keys grouped by the token they are on so that each token needs only one restriction
+                    let mut groups: Vec<((bool, u32), Vec<_>)> = Vec::new();
+                    let mut accounts: Vec<(bool, u32, TokenMetadataKey)> = Vec::new();
+                    for prop in props.value.iter() {
+                        let TokenPropLocation {
+                            is_input, index, types, ..
+                        } = find_token_prop(token_decls, fn_decl, &prop.value)?;
+                        let can_be_role = types.iter().any(|field_type| field_type.value == TokenFieldType::Role);
+                        let can_be_account = types
+                            .iter()
+                            .any(|field_type| field_type.value == TokenFieldType::Account);
+                        if !can_be_role && !can_be_account {
+                            return Err(CompilationError {
+                                code: ErrorCode::InvalidSenderComparison,
+                                stage: crate::compiler::CompilationStage::GenerateRestrictions,
+                                exit_code: exit_code::DATAERR,
+                                inner: PestError::new_from_span(
+                                    ErrorVariant::CustomError {
+                                        message: format!(
+                                            "Cannot compare property {} to sender as it is not a Role or Account",
+                                            prop.value.prop.value,
+                                        ),
+                                    },
+                                    prop.span,
+                                ),
+                            });
                         }
-                        false => {
-                            BooleanExpressionSymbol::Restriction(Restriction::SenderHasOutputRole { index, role_key })
+
+                        let key: TokenMetadataKey = to_bounded_vec(AstNode {
+                            value: prop.value.prop.value.as_bytes().to_owned(),
+                            span: prop.value.prop.span,
+                        })?;
+
+                        if can_be_account {
+                            accounts.push((is_input, index, key.clone()));
                         }
-                    }];
+                        if can_be_role {
+                            match groups.iter_mut().find(|(location, _)| *location == (is_input, index)) {
+                                Some((_, role_keys)) => role_keys.push(key),
+                                None => groups.push(((is_input, index), vec![key])),
+                            }
+                        }
+                    }
 
-                    if op == BoolCmp::Neq {
-                        result.append(&mut vec![
-                            BooleanExpressionSymbol::Restriction(Restriction::None),
-                            BooleanExpressionSymbol::Op(BooleanOperator::NotL),
-                        ]);
+                    let mut result: Vec<RuntimeExpressionSymbol> = Vec::new();
+                    for (position, ((is_input, index), role_keys)) in groups.into_iter().enumerate() {
+                        let role_keys = to_bounded_vec(AstNode {
+                            value: role_keys,
+                            span: props.span,
+                        })?;
+                        result.push(BooleanExpressionSymbol::Restriction(match is_input {
+                            true => Restriction::SenderHasAnyInputRole { index, role_keys },
+                            false => Restriction::SenderHasAnyOutputRole { index, role_keys },
+                        }));
+                        if position > 0 {
+                            result.push(BooleanExpressionSymbol::Op(BooleanOperator::Or));
+                        }
+                    }
+                    for (is_input, index, metadata_key) in accounts {
+                        let has_previous = !result.is_empty();
+                        result.push(BooleanExpressionSymbol::Restriction(match is_input {
+                            true => Restriction::SenderIsInputMetadataAccount { index, metadata_key },
+                            false => Restriction::SenderIsOutputMetadataAccount { index, metadata_key },
+                        }));
+                        if has_previous {
+                            result.push(BooleanExpressionSymbol::Op(BooleanOperator::Or));
+                        }
                     }
 
                     Ok(result)
                 }
+                Comparison::PropHash { left, op, right } => {
+                    let TokenPropLocation {
+                        is_input, index, types, ..
+                    } = find_token_prop(token_decls, fn_decl, &left.value)?;
+                    if !types.iter().any(|field_type| field_type.value == TokenFieldType::File) {
+                        return Err(CompilationError {
+                            code: ErrorCode::InvalidHashComparison,
+                            stage: crate::compiler::CompilationStage::GenerateRestrictions,
+                            exit_code: exit_code::DATAERR,
+                            inner: PestError::new_from_span(
+                                ErrorVariant::CustomError {
+                                    message: format!(
+                                        "Cannot compare property {} to a hash as it is not a File",
+                                        left.value.prop.value,
+                                    ),
+                                },
+                                left.span,
+                            ),
+                        });
+                    }
+
+                    // the hashed values must all be revealed on a single output
+                    let mut source_output: Option<u32> = None;
+                    let mut source_keys: Vec<TokenMetadataKey> = Vec::new();
+                    for prop in right.value.iter() {
+                        let source = find_token_prop(token_decls, fn_decl, &prop.value)?;
+                        let is_metadata = source.types.iter().any(|field_type| {
+                            field_type.value != TokenFieldType::Role && field_type.value != TokenFieldType::None
+                        });
+                        let same_output = source_output.map_or(true, |output| output == source.index);
+                        if source.is_input || !same_output || !is_metadata {
+                            return Err(CompilationError {
+                                code: ErrorCode::InvalidHashComparison,
+                                stage: crate::compiler::CompilationStage::GenerateRestrictions,
+                                exit_code: exit_code::DATAERR,
+                                inner: PestError::new_from_span(
+                                    ErrorVariant::CustomError {
+                                        message: format!(
+                                            "Cannot hash property {} as hashed properties must be metadata of a single output",
+                                            prop.value.prop.value,
+                                        ),
+                                    },
+                                    prop.span,
+                                ),
+                            });
+                        }
+                        source_output = Some(source.index);
+                        source_keys.push(to_bounded_vec(AstNode {
+                            value: prop.value.prop.value.as_bytes().to_owned(),
+                            span: prop.value.prop.span,
+                        })?);
+                    }
+                    let output_index = source_output.expect("hash_props holds at least one property");
+                    let source_keys = to_bounded_vec(AstNode {
+                        value: source_keys,
+                        span: right.span,
+                    })?;
+
+                    let metadata_key = to_bounded_vec(AstNode {
+                        value: left.value.prop.value.as_bytes().to_owned(),
+                        span: left.value.prop.span,
+                    })?;
+                    let restriction = match is_input {
+                        true => Restriction::InputFileIsHashOfOutputMetadata {
+                            input_index: index,
+                            input_metadata_key: metadata_key,
+                            output_index,
+                            source_keys,
+                        },
+                        false if index == output_index => Restriction::OutputFileIsHashOfMetadata {
+                            index,
+                            metadata_key,
+                            source_keys,
+                        },
+                        false => {
+                            return Err(CompilationError {
+                                code: ErrorCode::InvalidHashComparison,
+                                stage: crate::compiler::CompilationStage::GenerateRestrictions,
+                                exit_code: exit_code::DATAERR,
+                                inner: PestError::new_from_span(
+                                    ErrorVariant::CustomError {
+                                        message: format!(
+                                        "Cannot compare output property {} to a hash of properties of another output",
+                                        left.value.prop.value,
+                                    ),
+                                    },
+                                    span,
+                                ),
+                            })
+                        }
+                    };
+
+                    Ok(apply_cmp_op(
+                        vec![BooleanExpressionSymbol::Restriction(restriction)],
+                        op,
+                    ))
+                }
                 Comparison::TokenToken { left, op, right } => {
                     let left = find_token(fn_decl, &left)?;
                     let right = find_token(fn_decl, &right)?;
@@ -320,8 +502,9 @@ pub fn transform_condition_to_program(
                         (true, false) => Ok((left, right)),
                         (false, true) => Ok((right, left)),
                         _ => Err(CompilationError {
+                            code: ErrorCode::InvalidTokenComparison,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: "Token comparisons must be between an input and an output".into(),
@@ -349,21 +532,19 @@ pub fn transform_condition_to_program(
                             output_metadata_key: original_key.clone(),
                         }),
                         BooleanExpressionSymbol::Op(BooleanOperator::InhibitionR),
-                        BooleanExpressionSymbol::Op(match op == BoolCmp::Eq {
-                            true => BooleanOperator::Xor,
-                            false => BooleanOperator::Xnor,
-                        }),
+                        BooleanExpressionSymbol::Op(BooleanOperator::Xor),
                     ];
 
-                    Ok(result)
+                    Ok(apply_cmp_op(result, op))
                 }
                 Comparison::PropToken { left, op, right } => {
                     let output = find_token_prop(token_decls, fn_decl, &left.value)?;
 
                     if output.is_input {
                         return Err(CompilationError {
+                            code: ErrorCode::InvalidTokenComparison,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: "Cannot assert a property on an input equates to a token".into(),
@@ -377,8 +558,9 @@ pub fn transform_condition_to_program(
 
                     if !input.is_input {
                         return Err(CompilationError {
+                            code: ErrorCode::InvalidTokenComparison,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: "Cannot assert a token property equates to an output token".into(),
@@ -398,8 +580,9 @@ pub fn transform_condition_to_program(
                         .is_none()
                     {
                         return Err(CompilationError {
+                            code: ErrorCode::InvalidComparison,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: format!(
@@ -415,8 +598,9 @@ pub fn transform_condition_to_program(
                     let original_key = TokenMetadataKey::try_from(ORIGINAL_ID_KEY.to_vec()).unwrap();
                     let output_metadata_key =
                         TokenMetadataKey::try_from(output.prop.as_bytes().to_vec()).map_err(|_| CompilationError {
+                            code: ErrorCode::PropertyKeyTooLong,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: format!("Property key {} is too long", output.prop),
@@ -434,7 +618,7 @@ pub fn transform_condition_to_program(
                         }),
                         BooleanExpressionSymbol::Restriction(Restriction::InputHasMetadata {
                             index: input.index,
-                            metadata_key: output_metadata_key.clone(),
+                            metadata_key: original_key.clone(),
                         }),
                         BooleanExpressionSymbol::Restriction(Restriction::MatchInputIdOutputMetadataValue {
                             input_index: input.index,
@@ -442,24 +626,69 @@ pub fn transform_condition_to_program(
                             output_metadata_key: output_metadata_key.clone(),
                         }),
                         BooleanExpressionSymbol::Op(BooleanOperator::InhibitionR),
-                        BooleanExpressionSymbol::Op(match op {
-                            BoolCmp::Eq => BooleanOperator::Xor,
-                            BoolCmp::Neq => BooleanOperator::Xnor,
-                        }),
+                        BooleanExpressionSymbol::Op(BooleanOperator::Xor),
                     ];
 
-                    Ok(result)
+                    Ok(apply_cmp_op(result, op))
                 }
                 Comparison::PropProp { left, op, right } => {
                     let left = find_token_prop(token_decls, fn_decl, &left.value)?;
                     let right = find_token_prop(token_decls, fn_decl, &right.value)?;
 
+                    if left.is_input == right.is_input && left.index == right.index {
+                        let is_role = |types: &[AstNode<TokenFieldType>]| {
+                            types.len() == 1 && types[0].value == TokenFieldType::Role
+                        };
+                        if !is_role(&left.types) || !is_role(&right.types) {
+                            return Err(CompilationError {
+                                code: ErrorCode::InvalidTokenComparison,
+                                stage: crate::compiler::CompilationStage::GenerateRestrictions,
+                                exit_code: exit_code::DATAERR,
+                                inner: PestError::new_from_span(
+                                    ErrorVariant::CustomError {
+                                        message: "Properties of the same token can only be compared if both are Roles"
+                                            .into(),
+                                    },
+                                    span,
+                                ),
+                            });
+                        }
+
+                        let left_role_key = to_bounded_vec(AstNode {
+                            value: left.prop.as_bytes().to_owned(),
+                            span,
+                        })?;
+                        let right_role_key = to_bounded_vec(AstNode {
+                            value: right.prop.as_bytes().to_owned(),
+                            span,
+                        })?;
+
+                        let mut result = vec![BooleanExpressionSymbol::Restriction(match left.is_input {
+                            true => Restriction::DistinctInputRoles {
+                                index: left.index,
+                                left_role_key,
+                                right_role_key,
+                            },
+                            false => Restriction::DistinctOutputRoles {
+                                index: left.index,
+                                left_role_key,
+                                right_role_key,
+                            },
+                        })];
+                        // both roles are always present so equality is the negation of them being distinct
+                        if op == BoolCmp::Eq {
+                            negate(&mut result);
+                        }
+                        return Ok(result);
+                    }
+
                     let (input, output) = match (&left.is_input, &right.is_input) {
                         (true, false) => Ok((left, right)),
                         (false, true) => Ok((right, left)),
                         _ => Err(CompilationError {
+                            code: ErrorCode::InvalidTokenComparison,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: "Token property comparisons must be between an input and an output".into(),
@@ -469,6 +698,49 @@ pub fn transform_condition_to_program(
                         }),
                     }?;
 
+                    // a role of one token can be compared to an account held in the metadata of the other. Either can
+                    // be optional in which case they are only equal if both are present
+                    let is_only = |types: &[AstNode<TokenFieldType>], field_type: TokenFieldType| {
+                        types.iter().any(|t| t.value == field_type)
+                            && types
+                                .iter()
+                                .all(|t| t.value == field_type || t.value == TokenFieldType::None)
+                    };
+                    if is_only(&input.types, TokenFieldType::Role) && is_only(&output.types, TokenFieldType::Account) {
+                        let result = vec![BooleanExpressionSymbol::Restriction(
+                            Restriction::MatchInputRoleOutputMetadataAccount {
+                                input_index: input.index,
+                                input_role_key: to_bounded_vec(AstNode {
+                                    value: input.prop.as_bytes().to_owned(),
+                                    span,
+                                })?,
+                                output_index: output.index,
+                                output_metadata_key: to_bounded_vec(AstNode {
+                                    value: output.prop.as_bytes().to_owned(),
+                                    span,
+                                })?,
+                            },
+                        )];
+                        return Ok(apply_cmp_op(result, op));
+                    }
+                    if is_only(&input.types, TokenFieldType::Account) && is_only(&output.types, TokenFieldType::Role) {
+                        let result = vec![BooleanExpressionSymbol::Restriction(
+                            Restriction::MatchInputMetadataAccountOutputRole {
+                                input_index: input.index,
+                                input_metadata_key: to_bounded_vec(AstNode {
+                                    value: input.prop.as_bytes().to_owned(),
+                                    span,
+                                })?,
+                                output_index: output.index,
+                                output_role_key: to_bounded_vec(AstNode {
+                                    value: output.prop.as_bytes().to_owned(),
+                                    span,
+                                })?,
+                            },
+                        )];
+                        return Ok(apply_cmp_op(result, op));
+                    }
+
                     if input.types.len() != output.types.len()
                         || input
                             .types
@@ -476,8 +748,9 @@ pub fn transform_condition_to_program(
                             .any(|i_t| output.types.iter().find(|o_t| i_t.value == o_t.value).is_none())
                     {
                         return Err(CompilationError {
+                            code: ErrorCode::InvalidComparison,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: "Cannot compare properties of different types".into(),
@@ -489,8 +762,9 @@ pub fn transform_condition_to_program(
 
                     let input_key =
                         TokenMetadataKey::try_from(input.prop.as_bytes().to_vec()).map_err(|_| CompilationError {
+                            code: ErrorCode::PropertyKeyTooLong,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: format!("Property key {} is too long", input.prop),
@@ -501,8 +775,9 @@ pub fn transform_condition_to_program(
 
                     let output_key =
                         TokenMetadataKey::try_from(output.prop.as_bytes().to_vec()).map_err(|_| CompilationError {
+                            code: ErrorCode::PropertyKeyTooLong,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: format!("Property key {} is too long", output.prop),
@@ -589,22 +864,114 @@ pub fn transform_condition_to_program(
                         check_count - 1
                     ]);
 
-                    if op == BoolCmp::Neq {
-                        result.append(&mut vec![
-                            BooleanExpressionSymbol::Restriction(Restriction::None),
-                            BooleanExpressionSymbol::Op(BooleanOperator::NotL),
-                        ]);
+                    Ok(apply_cmp_op(result, op))
+                }
+                Comparison::PropStr { left, op, right } => {
+                    let TokenPropLocation {
+                        is_input, index, types, ..
+                    } = find_token_prop(token_decls, fn_decl, &left.value)?;
+
+                    if op == StrCmp::Matches && !is_valid_pattern(right.value.as_bytes()) {
+                        return Err(CompilationError {
+                            code: ErrorCode::InvalidPattern,
+                            stage: crate::compiler::CompilationStage::GenerateRestrictions,
+                            exit_code: exit_code::DATAERR,
+                            inner: PestError::new_from_span(
+                                ErrorVariant::CustomError {
+                                    message: format!("Invalid pattern {}", right.value),
+                                },
+                                right.span,
+                            ),
+                        });
                     }
 
-                    Ok(result)
+                    // a value of the field must be able to satisfy the comparison
+                    let satisfiable = |value: &str| match op {
+                        StrCmp::StartsWith => value.starts_with(right.value),
+                        StrCmp::EndsWith => value.ends_with(right.value),
+                        StrCmp::Matches => matches_pattern(right.value.as_bytes(), value.as_bytes()),
+                    };
+                    if types
+                        .iter()
+                        .find(|field_type| match &field_type.value {
+                            TokenFieldType::Literal => true,
+                            TokenFieldType::LiteralMaxLength(max) => {
+                                op == StrCmp::Matches || right.value.len() <= *max as usize
+                            }
+                            TokenFieldType::LiteralValue(v) => satisfiable(v.value),
+                            _ => false,
+                        })
+                        .is_none()
+                    {
+                        return Err(CompilationError {
+                            code: ErrorCode::InvalidComparison,
+                            stage: crate::compiler::CompilationStage::GenerateRestrictions,
+                            exit_code: exit_code::DATAERR,
+                            inner: PestError::new_from_span(
+                                ErrorVariant::CustomError {
+                                    message: format!(
+                                        "Invalid comparison between property {} and value {}",
+                                        left.value.prop.value, right.value
+                                    ),
+                                },
+                                span,
+                            ),
+                        });
+                    }
+
+                    let metadata_key = to_bounded_vec(AstNode {
+                        value: left.value.prop.value.as_bytes().to_owned(),
+                        span: left.value.prop.span,
+                    })?;
+
+                    let bytes: LiteralPattern = to_bounded_vec(AstNode {
+                        value: right.value.as_bytes().to_owned(),
+                        span: right.span,
+                    })?;
+
+                    let restriction = match (op, is_input) {
+                        (StrCmp::StartsWith, true) => Restriction::InputMetadataValueHasPrefix {
+                            index,
+                            metadata_key,
+                            prefix: bytes,
+                        },
+                        (StrCmp::StartsWith, false) => Restriction::OutputMetadataValueHasPrefix {
+                            index,
+                            metadata_key,
+                            prefix: bytes,
+                        },
+                        (StrCmp::EndsWith, true) => Restriction::InputMetadataValueHasSuffix {
+                            index,
+                            metadata_key,
+                            suffix: bytes,
+                        },
+                        (StrCmp::EndsWith, false) => Restriction::OutputMetadataValueHasSuffix {
+                            index,
+                            metadata_key,
+                            suffix: bytes,
+                        },
+                        (StrCmp::Matches, true) => Restriction::InputMetadataValueMatchesPattern {
+                            index,
+                            metadata_key,
+                            pattern: bytes,
+                        },
+                        (StrCmp::Matches, false) => Restriction::OutputMetadataValueMatchesPattern {
+                            index,
+                            metadata_key,
+                            pattern: bytes,
+                        },
+                    };
+
+                    Ok(vec![BooleanExpressionSymbol::Restriction(restriction)])
                 }
                 Comparison::PropType { left, op, right } => {
                     let left = find_token_prop(token_decls, fn_decl, &left.value)?;
 
                     let metadata_key =
                         TokenMetadataKey::try_from(left.prop.as_bytes().to_vec()).map_err(|_| CompilationError {
+                            code: ErrorCode::PropertyKeyTooLong,
                             stage: crate::compiler::CompilationStage::GenerateRestrictions,
-                            exit_code: exitcode::DATAERR,
+                            exit_code: exit_code::DATAERR,
                             inner: PestError::new_from_span(
                                 ErrorVariant::CustomError {
                                     message: format!("Property key {} is too long", left.prop),
@@ -615,20 +982,18 @@ pub fn transform_condition_to_program(
 
                     let mut result = match right.value {
                         TypeCmpType::None => {
-                            vec![
-                                BooleanExpressionSymbol::Restriction(match left.is_input {
-                                    true => Restriction::InputHasMetadata {
-                                        index: left.index,
-                                        metadata_key: metadata_key.clone(),
-                                    },
-                                    false => Restriction::OutputHasMetadata {
-                                        index: left.index,
-                                        metadata_key: metadata_key.clone(),
-                                    },
-                                }),
-                                BooleanExpressionSymbol::Restriction(Restriction::None),
-                                BooleanExpressionSymbol::Op(BooleanOperator::NotL),
-                            ]
+                            let mut result = vec![BooleanExpressionSymbol::Restriction(match left.is_input {
+                                true => Restriction::InputHasMetadata {
+                                    index: left.index,
+                                    metadata_key: metadata_key.clone(),
+                                },
+                                false => Restriction::OutputHasMetadata {
+                                    index: left.index,
+                                    metadata_key: metadata_key.clone(),
+                                },
+                            })];
+                            negate(&mut result);
+                            result
                         }
                         TypeCmpType::File => vec![BooleanExpressionSymbol::Restriction(match left.is_input {
                             true => Restriction::FixedInputMetadataValueType {
@@ -676,6 +1041,108 @@ pub fn transform_condition_to_program(
                                 metadata_value_type: MetadataValueType::Integer,
                             },
                         })],
+                        TypeCmpType::LiteralMaxLength(max_length) => {
+                            vec![BooleanExpressionSymbol::Restriction(match left.is_input {
+                                true => Restriction::InputMetadataValueMaxLength {
+                                    index: left.index,
+                                    metadata_key: metadata_key.clone(),
+                                    max_length,
+                                },
+                                false => Restriction::OutputMetadataValueMaxLength {
+                                    index: left.index,
+                                    metadata_key: metadata_key.clone(),
+                                    max_length,
+                                },
+                            })]
+                        }
+                        TypeCmpType::IntegerRange { min, max } => {
+                            vec![BooleanExpressionSymbol::Restriction(match left.is_input {
+                                true => Restriction::InputMetadataValueInRange {
+                                    index: left.index,
+                                    metadata_key: metadata_key.clone(),
+                                    min,
+                                    max,
+                                },
+                                false => Restriction::OutputMetadataValueInRange {
+                                    index: left.index,
+                                    metadata_key: metadata_key.clone(),
+                                    min,
+                                    max,
+                                },
+                            })]
+                        }
+                        TypeCmpType::Text => vec![BooleanExpressionSymbol::Restriction(match left.is_input {
+                            true => Restriction::FixedInputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::Text,
+                            },
+                            false => Restriction::FixedOutputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::Text,
+                            },
+                        })],
+                        TypeCmpType::Bytes => vec![BooleanExpressionSymbol::Restriction(match left.is_input {
+                            true => Restriction::FixedInputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::Bytes,
+                            },
+                            false => Restriction::FixedOutputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::Bytes,
+                            },
+                        })],
+                        TypeCmpType::Bool => vec![BooleanExpressionSymbol::Restriction(match left.is_input {
+                            true => Restriction::FixedInputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::Bool,
+                            },
+                            false => Restriction::FixedOutputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::Bool,
+                            },
+                        })],
+                        TypeCmpType::Timestamp => vec![BooleanExpressionSymbol::Restriction(match left.is_input {
+                            true => Restriction::FixedInputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::Timestamp,
+                            },
+                            false => Restriction::FixedOutputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::Timestamp,
+                            },
+                        })],
+                        TypeCmpType::List => vec![BooleanExpressionSymbol::Restriction(match left.is_input {
+                            true => Restriction::FixedInputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::List,
+                            },
+                            false => Restriction::FixedOutputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::List,
+                            },
+                        })],
+                        TypeCmpType::Account => vec![BooleanExpressionSymbol::Restriction(match left.is_input {
+                            true => Restriction::FixedInputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::Account,
+                            },
+                            false => Restriction::FixedOutputMetadataValueType {
+                                index: left.index,
+                                metadata_key: metadata_key.clone(),
+                                metadata_value_type: MetadataValueType::Account,
+                            },
+                        })],
                         TypeCmpType::Token => vec![BooleanExpressionSymbol::Restriction(match left.is_input {
                             true => Restriction::FixedInputMetadataValueType {
                                 index: left.index,
@@ -691,10 +1158,7 @@ pub fn transform_condition_to_program(
                     };
 
                     if op == TypeCmp::Isnt {
-                        result.append(&mut vec![
-                            BooleanExpressionSymbol::Restriction(Restriction::None),
-                            BooleanExpressionSymbol::Op(BooleanOperator::NotL),
-                        ]);
+                        negate(&mut result);
                     }
 
                     Ok(result)
@@ -703,10 +1167,7 @@ pub fn transform_condition_to_program(
         }
         ExpressionTree::Not(exp) => {
             let mut program = transform_condition_to_program(fn_decl, token_decls, *exp)?;
-            program.append(&mut vec![
-                BooleanExpressionSymbol::Restriction(Restriction::None),
-                BooleanExpressionSymbol::Op(BooleanOperator::NotL),
-            ]);
+            negate(&mut program);
             Ok(program)
         }
         ExpressionTree::Node { left, op, right } => {
@@ -721,3 +1182,525 @@ pub fn transform_condition_to_program(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use parity_scale_codec::Encode;
+    use sp_io::hashing::blake2_256;
+    use sqnc_process_interpreter::{execute_program, ProcessIO, RoleSet};
+    use sqnc_runtime_types::{
+        AccountId, BooleanExpressionSymbol, BooleanOperator, Hash, MetadataValue, Restriction, Role, TokenId,
+        TokenMetadataKey, TokenMetadataValue,
+    };
+
+    use crate::{ast::parse_str_to_ast, compiler::compile_ast_to_restrictions};
+
+    type Token = ProcessIO<TokenId, AccountId, Role, TokenMetadataKey, TokenMetadataValue>;
+
+    const ALICE: [u8; 32] = [1; 32];
+    const BOB: [u8; 32] = [2; 32];
+
+    fn literal(value: &str) -> TokenMetadataValue {
+        MetadataValue::Literal(value.as_bytes().to_vec().try_into().unwrap())
+    }
+
+    // a valid `Order` owned by alice and approved by bob with the given roles and metadata added to or replacing its defaults
+    fn order(id: TokenId, roles: &[(&str, [u8; 32])], metadata: &[(&str, TokenMetadataValue)]) -> Token {
+        let defaults = [
+            ("@type", literal("Order")),
+            ("@version", literal("1")),
+            ("state", literal("open")),
+            ("quantity", MetadataValue::Integer(-5)),
+            ("approved_by", MetadataValue::Account(AccountId::from(BOB))),
+        ];
+        Token {
+            id,
+            roles: [("owner", ALICE), ("approver", BOB)]
+                .iter()
+                .chain(roles)
+                .map(|(key, account)| (key.as_bytes().to_vec().try_into().unwrap(), AccountId::from(*account)))
+                .collect(),
+            metadata: defaults
+                .iter()
+                .chain(metadata)
+                .map(|(key, value)| (key.as_bytes().to_vec().try_into().unwrap(), value.clone()))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    // runs `pub fn f | a: Order | => | b: Order |` with the given condition against a transition from `input` to
+    // `output` with alice as the sender
+    fn check(condition: &str, input: &Token, output: &Token) -> bool {
+        let input_str = format!(
+            r##"
+          token Order {{
+              owner: Role,
+              approver: Role,
+              state: Literal,
+              quantity: Integer,
+              holder: Role | Literal | None,
+              parent: Order | None,
+              detail: Text | Bytes | Bool | Timestamp | List | None,
+              approved_by: Account | None,
+              commitment: File | None,
+          }}
+
+          pub fn f | a: Order | => | b: Order | where {{
+              {condition}
+          }}
+      "##
+        );
+        let processes = compile_ast_to_restrictions(parse_str_to_ast(&input_str).unwrap()).unwrap();
+        execute_program(
+            &processes[0].program,
+            &AccountId::from(ALICE),
+            &vec![input.clone()],
+            &vec![output.clone()],
+        )
+        .success
+    }
+
+    // checks the condition with `{op}` replaced by each of a pair of operators where the second negates the first
+    fn assert_ops(ops: [&str; 2], condition: &str, input: &Token, output: &Token, expected: bool) {
+        for (op, expected) in ops.into_iter().zip([expected, !expected]) {
+            let condition = condition.replace("{op}", op);
+            assert_eq!(check(&condition, input, output), expected, "{}", condition);
+        }
+    }
+
+    fn assert_comparison(condition: &str, input: &Token, output: &Token, expected: bool) {
+        assert_ops(["==", "!="], condition, input, output, expected)
+    }
+
+    fn assert_type_comparison(condition: &str, input: &Token, output: &Token, expected: bool) {
+        assert_ops([":", "!:"], condition, input, output, expected)
+    }
+
+    #[test]
+    fn prop_lit_comparisons() {
+        let closed = order(2, &[], &[("state", literal("closed"))]);
+        assert_comparison("b.state {op} \"open\"", &order(1, &[], &[]), &order(2, &[], &[]), true);
+        assert_comparison("b.state {op} \"open\"", &order(1, &[], &[]), &closed, false);
+        assert_comparison("a.state {op} \"closed\"", &order(1, &[], &[]), &closed, false);
+    }
+
+    #[test]
+    fn prop_int_comparisons() {
+        assert_comparison("b.quantity {op} -5", &order(1, &[], &[]), &order(2, &[], &[]), true);
+        assert_comparison("b.quantity {op} 5", &order(1, &[], &[]), &order(2, &[], &[]), false);
+        assert_comparison("a.quantity {op} -5", &order(1, &[], &[]), &order(2, &[], &[]), true);
+    }
+
+    #[test]
+    fn prop_sender_comparisons() {
+        let bobs = order(2, &[("owner", BOB)], &[]);
+        assert_comparison("b.owner {op} sender", &order(1, &[], &[]), &order(2, &[], &[]), true);
+        assert_comparison("b.owner {op} sender", &order(1, &[], &[]), &bobs, false);
+        assert_comparison("a.owner {op} sender", &order(1, &[], &[]), &bobs, true);
+    }
+
+    #[test]
+    fn token_token_comparisons() {
+        // the first version of a token is matched by its id and later versions by their original id
+        let first = order(1, &[], &[]);
+        let second = order(2, &[], &[("@original_id", MetadataValue::TokenId(1))]);
+        let third = order(3, &[], &[("@original_id", MetadataValue::TokenId(1))]);
+        let other = order(4, &[], &[("@original_id", MetadataValue::TokenId(3))]);
+
+        assert_comparison("a {op} b", &first, &second, true);
+        assert_comparison("b {op} a", &first, &second, true);
+        assert_comparison("a {op} b", &first, &order(2, &[], &[]), false);
+        assert_comparison("a {op} b", &second, &third, true);
+        assert_comparison("a {op} b", &second, &other, false);
+    }
+
+    #[test]
+    fn prop_token_comparisons() {
+        let child = order(2, &[], &[("parent", MetadataValue::TokenId(1))]);
+        let orphan = order(2, &[], &[("parent", MetadataValue::TokenId(7))]);
+        assert_comparison("b.parent {op} a", &order(1, &[], &[]), &child, true);
+        assert_comparison("b.parent {op} a", &order(1, &[], &[]), &orphan, false);
+        assert_comparison("b.parent {op} a", &order(1, &[], &[]), &order(2, &[], &[]), false);
+
+        // the input's own value of the property has no bearing on the comparison
+        let parented = order(1, &[], &[("parent", MetadataValue::TokenId(7))]);
+        assert_comparison("b.parent {op} a", &parented, &child, true);
+
+        // later versions of a token are matched by their original id
+        let second = order(3, &[], &[("@original_id", MetadataValue::TokenId(1))]);
+        assert_comparison("b.parent {op} a", &second, &child, true);
+        assert_comparison(
+            "b.parent {op} a",
+            &second,
+            &order(2, &[], &[("parent", MetadataValue::TokenId(3))]),
+            false,
+        );
+    }
+
+    #[test]
+    fn prop_prop_comparisons() {
+        let role = |id, account| order(id, &[("holder", account)], &[]);
+        let lit = |id, value| order(id, &[], &[("holder", literal(value))]);
+        let none = |id| order(id, &[], &[]);
+
+        assert_comparison("a.owner {op} b.owner", &none(1), &none(2), true);
+        assert_comparison(
+            "a.owner {op} b.owner",
+            &none(1),
+            &order(2, &[("owner", BOB)], &[]),
+            false,
+        );
+        assert_comparison("a.state {op} b.state", &none(1), &none(2), true);
+
+        assert_comparison("a.holder {op} b.holder", &role(1, ALICE), &role(2, ALICE), true);
+        assert_comparison("a.holder {op} b.holder", &role(1, ALICE), &role(2, BOB), false);
+        assert_comparison("a.holder {op} b.holder", &lit(1, "x"), &lit(2, "x"), true);
+        assert_comparison("a.holder {op} b.holder", &lit(1, "x"), &lit(2, "y"), false);
+        assert_comparison("a.holder {op} b.holder", &none(1), &none(2), true);
+        assert_comparison("a.holder {op} b.holder", &none(1), &lit(2, "x"), false);
+        assert_comparison("a.holder {op} b.holder", &role(1, ALICE), &none(2), false);
+        assert_comparison("a.holder {op} b.holder", &role(1, ALICE), &lit(2, "x"), false);
+    }
+
+    #[test]
+    fn prop_type_comparisons() {
+        let input = order(1, &[], &[]);
+        let role = order(2, &[("holder", BOB)], &[]);
+        let lit = order(2, &[], &[("holder", literal("x"))]);
+        assert_type_comparison("a.holder {op} None", &input, &lit, true);
+        assert_type_comparison("b.holder {op} None", &input, &lit, false);
+        assert_type_comparison("b.holder {op} Role", &input, &role, true);
+        assert_type_comparison("b.holder {op} Role", &input, &lit, false);
+        assert_type_comparison("b.holder {op} Literal", &input, &lit, true);
+        assert_type_comparison("b.quantity {op} Integer", &input, &lit, true);
+    }
+
+    #[test]
+    fn structured_prop_type_comparisons() {
+        let input = order(1, &[], &[]);
+        let bytes = |value: &str| value.as_bytes().to_vec().try_into().unwrap();
+        let item = |value: &str| value.as_bytes().to_vec().try_into().unwrap();
+        let details = [
+            (
+                "Text",
+                MetadataValue::Text(bytes("a description longer than a literal can hold")),
+            ),
+            ("Bytes", MetadataValue::Bytes(bytes("\u{0}\u{1}"))),
+            ("Bool", MetadataValue::Bool(false)),
+            ("Timestamp", MetadataValue::Timestamp(1700000000000)),
+            (
+                "List",
+                MetadataValue::List(vec![item("a"), item("b")].try_into().unwrap()),
+            ),
+        ];
+        for (name, value) in details.iter() {
+            let output = order(2, &[], &[("detail", value.clone())]);
+            for (other, _) in details.iter() {
+                let condition = format!("b.detail {{op}} {}", other);
+                assert_type_comparison(&condition, &input, &output, name == other);
+            }
+            assert_comparison(
+                "a.detail {op} b.detail",
+                &order(1, &[], &[("detail", value.clone())]),
+                &output,
+                true,
+            );
+        }
+
+        // a field can only hold the types it is declared with
+        assert!(!check(
+            "b.owner == sender",
+            &input,
+            &order(2, &[], &[("detail", literal("x"))])
+        ));
+    }
+
+    #[test]
+    fn account_comparisons() {
+        let approved = |id, account: [u8; 32]| {
+            order(
+                id,
+                &[],
+                &[("approved_by", MetadataValue::Account(AccountId::from(account)))],
+            )
+        };
+        let none = |id| order(id, &[], &[]);
+        let unapproved = |id| {
+            let mut token = order(id, &[], &[]);
+            token
+                .metadata
+                .remove(&TokenMetadataKey::try_from(b"approved_by".to_vec()).unwrap());
+            token
+        };
+
+        assert_comparison("a.approved_by {op} sender", &approved(1, ALICE), &none(2), true);
+        assert_comparison("a.approved_by {op} sender", &none(1), &none(2), false);
+        assert_comparison("b.approved_by {op} a.approver", &none(1), &none(2), true);
+        assert_comparison("b.approved_by {op} a.approver", &none(1), &approved(2, ALICE), false);
+        assert_comparison("b.approved_by {op} a.approver", &none(1), &unapproved(2), false);
+        assert_comparison("a.approved_by {op} b.owner", &approved(1, ALICE), &none(2), true);
+        assert_comparison("a.approved_by {op} b.owner", &none(1), &none(2), false);
+        assert_comparison("a.approved_by {op} b.approved_by", &none(1), &none(2), true);
+        assert_comparison("a.approved_by {op} b.approved_by", &none(1), &approved(2, ALICE), false);
+        assert_type_comparison("b.approved_by {op} Account", &none(1), &none(2), true);
+
+        assert!(check(
+            "sender in (a.approver, a.approved_by)",
+            &approved(1, ALICE),
+            &none(2)
+        ));
+        assert!(!check("sender in (a.approver, a.approved_by)", &none(1), &none(2)));
+        assert!(!check(
+            "sender in (a.approver, a.approved_by)",
+            &unapproved(1),
+            &none(2)
+        ));
+    }
+
+    #[test]
+    fn hash_comparisons() {
+        let hash = |values: &[TokenMetadataValue]| {
+            let bytes: Vec<u8> = values.iter().flat_map(|v| v.encode()).collect();
+            MetadataValue::File(Hash::from(blake2_256(&bytes)))
+        };
+        let sealed = order(
+            1,
+            &[],
+            &[("commitment", hash(&[MetadataValue::Integer(-5), literal("open")]))],
+        );
+        let revised = order(2, &[], &[("quantity", MetadataValue::Integer(5))]);
+
+        // an input's commitment is revealed by the values of an output
+        assert_comparison(
+            "a.commitment {op} hash(b.quantity, b.state)",
+            &sealed,
+            &order(2, &[], &[]),
+            true,
+        );
+        assert_comparison(
+            "a.commitment {op} hash(b.state, b.quantity)",
+            &sealed,
+            &order(2, &[], &[]),
+            false,
+        );
+        assert_comparison("a.commitment {op} hash(b.quantity, b.state)", &sealed, &revised, false);
+        assert_comparison(
+            "a.commitment {op} hash(b.quantity, b.state)",
+            &order(1, &[], &[]),
+            &order(2, &[], &[]),
+            false,
+        );
+
+        // an output's commitment is to its own values
+        let committed = |state| {
+            order(
+                2,
+                &[],
+                &[("commitment", hash(&[literal("open")])), ("state", literal(state))],
+            )
+        };
+        assert_comparison("b.commitment {op} hash(b.state)", &sealed, &committed("open"), true);
+        assert_comparison("b.commitment {op} hash(b.state)", &sealed, &committed("closed"), false);
+        assert_comparison("b.commitment {op} hash(b.holder)", &sealed, &committed("open"), false);
+    }
+
+    #[test]
+    fn hash_of_input_fails() {
+        let result = compile_ast_to_restrictions(
+            parse_str_to_ast(
+                r##"
+          token Bid {
+              commitment: File,
+              price: Integer,
+          }
+
+          pub fn f | a: Bid | => | b: Bid | where {
+              b.commitment == hash(a.price)
+          }
+      "##,
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Cannot hash property price as hashed properties must be metadata of a single output"
+        );
+    }
+
+    #[test]
+    fn hash_comparison_with_literal_fails() {
+        let result = compile_ast_to_restrictions(
+            parse_str_to_ast(
+                r##"
+          token Bid {
+              commitment: Literal,
+              price: Integer,
+          }
+
+          pub fn f | a: Bid | => | b: Bid | where {
+              a.commitment == hash(b.price)
+          }
+      "##,
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Cannot compare property commitment to a hash as it is not a File"
+        );
+    }
+
+    #[test]
+    fn prop_str_comparisons() {
+        let input = order(1, &[], &[("state", literal("PO-123456"))]);
+        let output = order(2, &[], &[]);
+        assert!(check("b.state starts_with \"op\"", &input, &output));
+        assert!(!check("b.state starts_with \"en\"", &input, &output));
+        assert!(check("b.state ends_with \"en\"", &input, &output));
+        assert!(!check("b.state ends_with \"op\"", &input, &output));
+        assert!(check("a.state matches \"PO-[0-9]{6}\"", &input, &output));
+        assert!(!check("b.state matches \"PO-[0-9]{6}\"", &input, &output));
+        assert!(check("!(a.state ends_with \"7\")", &input, &output));
+
+        // values that are not literals never match
+        assert!(!check(
+            "b.holder starts_with \"\"",
+            &input,
+            &order(2, &[("holder", BOB)], &[])
+        ));
+        assert!(check(
+            "b.holder starts_with \"\"",
+            &input,
+            &order(2, &[], &[("holder", literal("x"))])
+        ));
+    }
+
+    #[test]
+    fn sender_in_comparisons() {
+        let input = order(1, &[], &[]);
+        let output = order(2, &[], &[]);
+        let bobs = order(1, &[("owner", BOB)], &[]);
+        assert!(check("sender in (a.owner, a.approver)", &input, &output));
+        assert!(check("sender in (a.approver, a.owner,)", &input, &output));
+        assert!(!check("sender in (a.approver)", &input, &output));
+        assert!(!check("sender in (a.owner, a.approver)", &bobs, &output));
+        assert!(check(
+            "sender in (a.owner, b.approver)",
+            &bobs,
+            &order(2, &[("approver", ALICE)], &[])
+        ));
+        assert!(!check("sender in (a.owner, b.approver)", &bobs, &output));
+        assert!(check("!(sender in (a.approver, b.approver))", &input, &output));
+
+        // fields that can hold other types only match while they hold a role
+        assert!(check(
+            "sender in (b.holder)",
+            &input,
+            &order(2, &[("holder", ALICE)], &[])
+        ));
+        assert!(!check(
+            "sender in (b.holder)",
+            &input,
+            &order(2, &[], &[("holder", literal("x"))])
+        ));
+    }
+
+    #[test]
+    fn sender_in_compiles_one_restriction_per_token() {
+        let processes = compile_ast_to_restrictions(
+            parse_str_to_ast(
+                r##"
+          token Order {
+              owner: Role,
+              approver: Role,
+          }
+
+          pub fn f | a: Order | => | b: Order | where {
+              sender in (a.owner, b.owner, a.approver)
+          }
+      "##,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let role_set = |keys: &[&str]| -> RoleSet<Role> {
+            keys.iter()
+                .map(|key| key.as_bytes().to_vec().try_into().unwrap())
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap()
+        };
+        assert!(processes[0].program.ends_with(&[
+            BooleanExpressionSymbol::Restriction(Restriction::SenderHasAnyInputRole {
+                index: 0,
+                role_keys: role_set(&["owner", "approver"]),
+            }),
+            BooleanExpressionSymbol::Restriction(Restriction::SenderHasAnyOutputRole {
+                index: 0,
+                role_keys: role_set(&["owner"]),
+            }),
+            BooleanExpressionSymbol::Op(BooleanOperator::Or),
+            BooleanExpressionSymbol::Op(BooleanOperator::And),
+        ]));
+    }
+
+    #[test]
+    fn same_token_role_comparisons() {
+        let input = order(1, &[], &[]);
+        let output = order(2, &[], &[]);
+        let alices = |id| order(id, &[("approver", ALICE)], &[]);
+        assert_comparison("a.owner {op} a.approver", &input, &output, false);
+        assert_comparison("a.owner {op} a.approver", &alices(1), &output, true);
+        assert_comparison("b.approver {op} b.owner", &input, &output, false);
+        assert_comparison("b.approver {op} b.owner", &input, &alices(2), true);
+    }
+
+    #[test]
+    fn same_token_non_role_comparison_fails() {
+        let result = compile_ast_to_restrictions(
+            parse_str_to_ast(
+                r##"
+          token Order {
+              owner: Role,
+              holder: Role | None,
+          }
+
+          pub fn f | a: Order | => || where {
+              a.owner != a.holder
+          }
+      "##,
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Properties of the same token can only be compared if both are Roles"
+        );
+    }
+
+    #[test]
+    fn sender_comparison_with_literal_fails() {
+        let result = compile_ast_to_restrictions(
+            parse_str_to_ast(
+                r##"
+          token Order {
+              owner: Role,
+              state: Literal,
+          }
+
+          pub fn f | a: Order | => || where {
+              a.state == sender
+          }
+      "##,
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Cannot compare property state to sender as it is not a Role or Account"
+        );
+    }
+}
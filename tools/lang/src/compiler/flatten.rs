@@ -6,7 +6,7 @@ use std::{
 
 use crate::{
     ast::types::*,
-    errors::{CompilationError, ErrorVariant, PestError},
+    errors::{exit_code, CompilationError, ErrorCode, ErrorVariant, PestError},
 };
 
 use super::CompilationStage;
@@ -17,8 +17,9 @@ fn transform_name<'a>(
 ) -> Result<AstNode<'a, &'a str>, CompilationError> {
     Ok(AstNode {
         value: token_name_transforms.get(name.value).ok_or(CompilationError {
+            code: ErrorCode::UnknownToken,
             stage: CompilationStage::ReduceFns,
-            exit_code: exitcode::DATAERR,
+            exit_code: exit_code::DATAERR,
             inner: PestError::new_from_span(
                 ErrorVariant::CustomError {
                     message: "Undeclared token".into(),
@@ -86,6 +87,50 @@ fn transform_comparison<'a>(
             },
             op,
         }),
+        Comparison::SenderIn { props } => Ok(Comparison::SenderIn {
+            props: AstNode {
+                value: props
+                    .value
+                    .iter()
+                    .map(|p| {
+                        Ok(AstNode {
+                            value: TokenProp {
+                                token: transform_name(p.value.token.clone(), token_name_transforms.clone())?,
+                                prop: p.value.prop.clone(),
+                            },
+                            span: p.span,
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+                span: props.span,
+            },
+        }),
+        Comparison::PropHash { left, op, right } => Ok(Comparison::PropHash {
+            left: AstNode {
+                value: TokenProp {
+                    token: transform_name(left.value.token, token_name_transforms.clone())?,
+                    prop: left.value.prop,
+                },
+                span: left.span,
+            },
+            op,
+            right: AstNode {
+                value: right
+                    .value
+                    .iter()
+                    .map(|p| {
+                        Ok(AstNode {
+                            value: TokenProp {
+                                token: transform_name(p.value.token.clone(), token_name_transforms.clone())?,
+                                prop: p.value.prop.clone(),
+                            },
+                            span: p.span,
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+                span: right.span,
+            },
+        }),
         Comparison::TokenToken { left, op, right } => Ok(Comparison::TokenToken {
             left: transform_name(left, token_name_transforms.clone())?,
             op,
@@ -130,6 +175,17 @@ fn transform_comparison<'a>(
             op,
             right,
         }),
+        Comparison::PropStr { left, op, right } => Ok(Comparison::PropStr {
+            left: AstNode {
+                value: TokenProp {
+                    token: transform_name(left.value.token, token_name_transforms.clone())?,
+                    prop: left.value.prop,
+                },
+                span: left.span,
+            },
+            op,
+            right,
+        }),
     }
 }
 
@@ -171,8 +227,9 @@ fn check_args<'a>(
 ) -> Result<(), CompilationError> {
     if decl.value.len() != call.value.len() {
         return Err(CompilationError {
+            code: ErrorCode::ArgumentCount,
             stage: CompilationStage::ReduceFns,
-            exit_code: exitcode::DATAERR,
+            exit_code: exit_code::DATAERR,
             inner: PestError::new_from_span(
                 ErrorVariant::CustomError {
                     message: format!("Expected {} arguments got {}", decl.value.len(), call.value.len()).into(),
@@ -188,8 +245,9 @@ fn check_args<'a>(
         .map(|(a, b)| match a.value.token_type.value == b.value.token_type.value {
             true => Ok(()),
             false => Err(CompilationError {
+                code: ErrorCode::ArgumentType,
                 stage: CompilationStage::ReduceFns,
-                exit_code: exitcode::DATAERR,
+                exit_code: exit_code::DATAERR,
                 inner: PestError::new_from_span(
                     ErrorVariant::CustomError {
                         message: format!(
@@ -225,8 +283,9 @@ fn lookup_args<'a>(
                         false => None,
                     })
                     .ok_or(CompilationError {
+                        code: ErrorCode::UnknownToken,
                         stage: CompilationStage::ReduceFns,
-                        exit_code: exitcode::DATAERR,
+                        exit_code: exit_code::DATAERR,
                         inner: PestError::new_from_span(
                             ErrorVariant::CustomError {
                                 message: "Undeclared token".into(),
@@ -269,8 +328,9 @@ fn flatten_expr_fn<'a>(
 ) -> Result<Vec<ExpressionTree<'a>>, CompilationError> {
     if called_fns.contains(name.value) {
         return Err(CompilationError {
+            code: ErrorCode::RecursiveCall,
             stage: CompilationStage::ReduceFns,
-            exit_code: exitcode::DATAERR,
+            exit_code: exit_code::DATAERR,
             inner: PestError::new_from_span(
                 ErrorVariant::CustomError {
                     message: format!("Recursive function call detected in function {}", name.value).into(),
@@ -286,8 +346,9 @@ fn flatten_expr_fn<'a>(
 
     match fn_decl {
         None => Err(CompilationError {
+            code: ErrorCode::UnknownFunction,
             stage: CompilationStage::ReduceFns,
-            exit_code: exitcode::DATAERR,
+            exit_code: exit_code::DATAERR,
             inner: PestError::new_from_span(
                 ErrorVariant::CustomError {
                     message: format!("Unknown function {}", name.value).into(),
@@ -428,8 +489,9 @@ fn check_fn_decl_duplicate_args(fn_decl: &FnDecl) -> Result<(), CompilationError
     // try to find a duplicate by folding over. A duplicate will have the previous name in the list equal to the current one
     let try_find_duplicate = all_names.try_fold(first, |prev, arg| match prev.value == arg.value {
         true => ControlFlow::Break(CompilationError {
+            code: ErrorCode::DuplicateArgument,
             stage: CompilationStage::BuildAst,
-            exit_code: exitcode::DATAERR,
+            exit_code: exit_code::DATAERR,
             inner: PestError::new_from_span(
                 ErrorVariant::CustomError {
                     message: "Duplicate argument name".into(),
@@ -1,6 +1,6 @@
 use crate::{
     ast::types::AstNode,
-    errors::{CompilationError, CompilationStage, PestError},
+    errors::{exit_code, CompilationError, CompilationStage, ErrorCode, PestError},
 };
 
 pub fn to_bounded_vec<I, O, V>(collection: AstNode<I>) -> Result<O, CompilationError>
@@ -11,8 +11,9 @@ where
     let foo = collection.value.into_iter().collect::<Vec<_>>();
     let foo_len = foo.len();
     <O as TryFrom<Vec<V>>>::try_from(foo).map_err(|_| CompilationError {
+        code: ErrorCode::TooManyElements,
         stage: CompilationStage::LengthValidation,
-        exit_code: exitcode::DATAERR,
+        exit_code: exit_code::DATAERR,
         inner: PestError::new_from_span(
             pest::error::ErrorVariant::CustomError {
                 message: format!("too long or compiles to too many elements ({})", foo_len),
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sqnc_runtime_types::{
     BooleanExpressionSymbol, BooleanOperator, ProcessIdentifier, ProcessVersion, RuntimeProgram, TokenMetadataKey,
     TokenMetadataValue,
@@ -10,7 +10,7 @@ use crate::{
         types::{AstNode, FnDecl, TokenDecl},
         Ast,
     },
-    errors::{CompilationError, CompilationStage, ErrorVariant, PestError},
+    errors::{exit_code, CompilationError, CompilationStage, ErrorCode, ErrorVariant, PestError},
 };
 
 mod constants;
@@ -29,7 +29,7 @@ use helper::to_bounded_vec;
 
 use self::constants::{TYPE_KEY, VERSION_KEY};
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 pub struct Process {
     pub(crate) name: ProcessIdentifier,
     pub(crate) version: ProcessVersion,
@@ -48,8 +48,9 @@ fn make_process_restrictions(
         .chain(fn_decl.outputs.value.iter())
         .map(|arg| {
             let token_decl = token_decls.get(arg.value.token_type.value).ok_or(CompilationError {
+                code: ErrorCode::UnknownTokenType,
                 stage: CompilationStage::ReduceTokens,
-                exit_code: exitcode::DATAERR,
+                exit_code: exit_code::DATAERR,
                 inner: PestError::new_from_span(
                     ErrorVariant::CustomError {
                         message: format!("Unknown token type {}", arg.value.token_type.value),
@@ -199,3 +200,179 @@ pub fn compile_ast_to_restrictions(ast: Ast) -> Result<Vec<Process>, Compilation
         })
         .collect::<Result<Vec<_>, _>>()
 }
+
+#[cfg(test)]
+mod tests {
+    use sqnc_runtime_types::{BooleanExpressionSymbol, Restriction};
+
+    use super::compile_ast_to_restrictions;
+    use crate::ast::parse_str_to_ast;
+
+    fn compile(input: &str) -> Result<Vec<super::Process>, crate::errors::CompilationError> {
+        compile_ast_to_restrictions(parse_str_to_ast(input)?)
+    }
+
+    #[test]
+    fn bounded_fields_compile_to_restrictions() {
+        let processes = compile(
+            r##"
+          token Order {
+              quantity: Integer(0..=10000),
+              code: Literal(max 16),
+          }
+
+          pub fn create || => | out: Order | where {}
+      "##,
+        )
+        .unwrap();
+
+        let program = &processes[0].program;
+        assert!(program.contains(&BooleanExpressionSymbol::Restriction(
+            Restriction::OutputMetadataValueInRange {
+                index: 0,
+                metadata_key: "quantity".as_bytes().to_vec().try_into().unwrap(),
+                min: 0,
+                max: 10000,
+            }
+        )));
+        assert!(program.contains(&BooleanExpressionSymbol::Restriction(
+            Restriction::OutputMetadataValueMaxLength {
+                index: 0,
+                metadata_key: "code".as_bytes().to_vec().try_into().unwrap(),
+                max_length: 16,
+            }
+        )));
+    }
+
+    #[test]
+    fn compare_integer_in_range() {
+        let result = compile(
+            r##"
+          token Order {
+              quantity: Integer(0..=10000),
+          }
+
+          pub fn create || => | out: Order | where {
+              out.quantity == 10000
+          }
+      "##,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn compare_integer_out_of_range() {
+        let result = compile(
+            r##"
+          token Order {
+              quantity: Integer(0..=10000),
+          }
+
+          pub fn create || => | out: Order | where {
+              out.quantity == 10001
+          }
+      "##,
+        );
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Invalid comparison between property quantity and value 10001"
+        );
+    }
+
+    #[test]
+    fn compare_literal_longer_than_max_length() {
+        let result = compile(
+            r##"
+          token Order {
+              code: Literal(max 4),
+          }
+
+          pub fn create || => | out: Order | where {
+              out.code == "abcde"
+          }
+      "##,
+        );
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Invalid comparison between property code and value abcde"
+        );
+    }
+
+    #[test]
+    fn compare_literal_prefix_longer_than_max_length() {
+        let result = compile(
+            r##"
+          token Order {
+              code: Literal(max 4),
+          }
+
+          pub fn create || => | out: Order | where {
+              out.code starts_with "abcde"
+          }
+      "##,
+        );
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Invalid comparison between property code and value abcde"
+        );
+    }
+
+    #[test]
+    fn compare_literal_value_to_unsatisfiable_pattern() {
+        let result = compile(
+            r##"
+          token Order {
+              code: "PO-1" | "PO-2",
+          }
+
+          pub fn create || => | out: Order | where {
+              out.code matches "SO-[0-9]"
+          }
+      "##,
+        );
+        assert_eq!(
+            result.err().unwrap().inner.variant.message(),
+            "Invalid comparison between property code and value SO-[0-9]"
+        );
+    }
+
+    #[test]
+    fn compare_literal_to_invalid_pattern() {
+        let result = compile(
+            r##"
+          token Order {
+              code: Literal,
+          }
+
+          pub fn create || => | out: Order | where {
+              out.code matches "[0-9"
+          }
+      "##,
+        );
+        assert_eq!(result.err().unwrap().inner.variant.message(), "Invalid pattern [0-9");
+    }
+
+    #[test]
+    fn compare_pattern_compiles_to_restriction() {
+        let processes = compile(
+            r##"
+          token Order {
+              code: Literal,
+          }
+
+          pub fn create || => | out: Order | where {
+              out.code matches "PO-[0-9]{6}"
+          }
+      "##,
+        )
+        .unwrap();
+
+        assert!(processes[0].program.contains(&BooleanExpressionSymbol::Restriction(
+            Restriction::OutputMetadataValueMatchesPattern {
+                index: 0,
+                metadata_key: "code".as_bytes().to_vec().try_into().unwrap(),
+                pattern: "PO-[0-9]{6}".as_bytes().to_vec().try_into().unwrap(),
+            }
+        )));
+    }
+}
@@ -68,6 +68,70 @@ pub fn token_decl_to_conditions<'a>(
                             span: field_type.span,
                         },
                     },
+                    TokenFieldType::LiteralMaxLength(max) => Comparison::PropType {
+                        left: token_prop_node,
+                        op: TypeCmp::Is,
+                        right: AstNode {
+                            value: TypeCmpType::LiteralMaxLength(*max),
+                            span: field_type.span,
+                        },
+                    },
+                    TokenFieldType::IntegerRange { min, max } => Comparison::PropType {
+                        left: token_prop_node,
+                        op: TypeCmp::Is,
+                        right: AstNode {
+                            value: TypeCmpType::IntegerRange { min: *min, max: *max },
+                            span: field_type.span,
+                        },
+                    },
+                    TokenFieldType::Text => Comparison::PropType {
+                        left: token_prop_node,
+                        op: TypeCmp::Is,
+                        right: AstNode {
+                            value: TypeCmpType::Text,
+                            span: field_type.span,
+                        },
+                    },
+                    TokenFieldType::Bytes => Comparison::PropType {
+                        left: token_prop_node,
+                        op: TypeCmp::Is,
+                        right: AstNode {
+                            value: TypeCmpType::Bytes,
+                            span: field_type.span,
+                        },
+                    },
+                    TokenFieldType::Bool => Comparison::PropType {
+                        left: token_prop_node,
+                        op: TypeCmp::Is,
+                        right: AstNode {
+                            value: TypeCmpType::Bool,
+                            span: field_type.span,
+                        },
+                    },
+                    TokenFieldType::Timestamp => Comparison::PropType {
+                        left: token_prop_node,
+                        op: TypeCmp::Is,
+                        right: AstNode {
+                            value: TypeCmpType::Timestamp,
+                            span: field_type.span,
+                        },
+                    },
+                    TokenFieldType::List => Comparison::PropType {
+                        left: token_prop_node,
+                        op: TypeCmp::Is,
+                        right: AstNode {
+                            value: TypeCmpType::List,
+                            span: field_type.span,
+                        },
+                    },
+                    TokenFieldType::Account => Comparison::PropType {
+                        left: token_prop_node,
+                        op: TypeCmp::Is,
+                        right: AstNode {
+                            value: TypeCmpType::Account,
+                            span: field_type.span,
+                        },
+                    },
                     TokenFieldType::LiteralValue(v) => Comparison::PropLit {
                         left: token_prop_node,
                         op: BoolCmp::Eq,
@@ -253,6 +317,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn single_prop_literal_max_length() {
+        let token_name = to_ast_node("test");
+        let prop_name = to_ast_node("prop");
+        let token_decl = TokenDecl {
+            name: to_ast_node("token"),
+            props: to_ast_node(Arc::new([to_ast_node(TokenPropDecl {
+                name: prop_name.clone(),
+                types: Arc::new([to_ast_node(TokenFieldType::LiteralMaxLength(16))]),
+            })])),
+        };
+        let result = token_decl_to_conditions(token_name.clone(), &token_decl);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            vec![ExpressionTree::Leaf(to_ast_node(Comparison::PropType {
+                left: to_ast_node(TokenProp {
+                    token: token_name,
+                    prop: prop_name
+                }),
+                op: TypeCmp::Is,
+                right: to_ast_node(TypeCmpType::LiteralMaxLength(16))
+            }))]
+        );
+    }
+
+    #[test]
+    fn single_prop_integer_range() {
+        let token_name = to_ast_node("test");
+        let prop_name = to_ast_node("prop");
+        let token_decl = TokenDecl {
+            name: to_ast_node("token"),
+            props: to_ast_node(Arc::new([to_ast_node(TokenPropDecl {
+                name: prop_name.clone(),
+                types: Arc::new([to_ast_node(TokenFieldType::IntegerRange { min: 0, max: 10000 })]),
+            })])),
+        };
+        let result = token_decl_to_conditions(token_name.clone(), &token_decl);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            vec![ExpressionTree::Leaf(to_ast_node(Comparison::PropType {
+                left: to_ast_node(TokenProp {
+                    token: token_name,
+                    prop: prop_name
+                }),
+                op: TypeCmp::Is,
+                right: to_ast_node(TypeCmpType::IntegerRange { min: 0, max: 10000 })
+            }))]
+        );
+    }
+
     #[test]
     fn single_prop_token() {
         let token_name = to_ast_node("test");
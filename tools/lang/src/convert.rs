@@ -3,21 +3,94 @@ use std::error::Error;
 use serde::Serialize;
 use serde_json::Value;
 
-fn transform_value(val: Value) -> Value {
+// keys of runtime types holding byte strings which `transform_to_json` renders as utf8 strings
+const BYTE_STRING_KEYS: [&str; 18] = [
+    "name",
+    "role_key",
+    "role_keys",
+    "metadata_key",
+    "input_role_key",
+    "output_role_key",
+    "left_role_key",
+    "right_role_key",
+    "input_metadata_key",
+    "output_metadata_key",
+    "prefix",
+    "suffix",
+    "pattern",
+    "source_keys",
+    "attribute",
+    "Literal",
+    "Text",
+    "List",
+];
+// keys of `BYTE_STRING_KEYS` holding lists of byte strings rather than a single one
+const BYTE_STRING_LIST_KEYS: [&str; 3] = ["role_keys", "source_keys", "List"];
+
+fn transform_byte_string(val: Value) -> Value {
     match val {
         Value::Array(arr) => {
-            match arr.iter().all(|v| match v.as_u64() {
-                Some(n) => n < 256,
-                None => false,
-            }) {
-                true => {
-                    let u8_vec = arr.iter().map(|v| v.as_u64().unwrap() as u8).collect();
-                    Value::String(String::from_utf8(u8_vec).unwrap())
-                }
-                false => Value::Array(arr.into_iter().map(transform_value).collect()),
+            let bytes = arr
+                .iter()
+                .map(|v| v.as_u64().and_then(|n| u8::try_from(n).ok()))
+                .collect::<Option<Vec<_>>>();
+            match bytes {
+                // byte strings that aren't valid utf8 are left as arrays of bytes
+                Some(bytes) => match String::from_utf8(bytes) {
+                    Ok(s) => Value::String(s),
+                    Err(_) => Value::Array(arr),
+                },
+                None => Value::Array(arr.into_iter().map(transform_value).collect()),
             }
         }
-        Value::Object(map) => Value::Object(map.into_iter().map(|(key, val)| (key, transform_value(val))).collect()),
+        val => transform_value(val),
+    }
+}
+
+fn transform_value(val: Value) -> Value {
+    match val {
+        Value::Array(arr) => Value::Array(arr.into_iter().map(transform_value).collect()),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, val)| {
+                    let val = match (val, BYTE_STRING_LIST_KEYS.contains(&key.as_str())) {
+                        (Value::Array(arr), true) => Value::Array(arr.into_iter().map(transform_byte_string).collect()),
+                        (val, _) if BYTE_STRING_KEYS.contains(&key.as_str()) => transform_byte_string(val),
+                        (val, _) => transform_value(val),
+                    };
+                    (key, val)
+                })
+                .collect(),
+        ),
+        v => v,
+    }
+}
+
+#[cfg(feature = "cli")]
+fn untransform_byte_string(val: Value) -> Value {
+    match val {
+        Value::String(s) => Value::Array(s.into_bytes().into_iter().map(Value::from).collect()),
+        // a set of byte strings such as `role_keys`, or a byte string that isn't valid utf8
+        Value::Array(arr) => Value::Array(arr.into_iter().map(untransform_byte_string).collect()),
+        val => untransform_value(val),
+    }
+}
+
+#[cfg(feature = "cli")]
+fn untransform_value(val: Value) -> Value {
+    match val {
+        Value::Array(arr) => Value::Array(arr.into_iter().map(untransform_value).collect()),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, val)| {
+                    let val = match BYTE_STRING_KEYS.contains(&key.as_str()) {
+                        true => untransform_byte_string(val),
+                        false => untransform_value(val),
+                    };
+                    (key, val)
+                })
+                .collect(),
+        ),
         v => v,
     }
 }
@@ -34,11 +107,19 @@ where
     }?)
 }
 
+#[cfg(feature = "cli")]
+pub fn transform_from_json<T>(val: Value) -> Result<T, serde_json::Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_json::from_value(untransform_value(val))
+}
+
 #[cfg(test)]
 mod tests {
-    use sqnc_runtime_types::BooleanExpressionSymbol;
+    use sqnc_runtime_types::{BooleanExpressionSymbol, MetadataValue, TokenMetadataValue};
 
-    use super::transform_to_json;
+    use super::{transform_from_json, transform_to_json};
     use crate::compiler::Process;
 
     #[test]
@@ -181,4 +262,183 @@ mod tests {
             .to_owned()
         );
     }
+
+    #[test]
+    fn transforms_from_json_round_trip() {
+        let processes = vec![Process {
+            name: vec![116u8, 101u8, 115u8, 116u8].try_into().unwrap(), // test
+            version: 1u32,
+            program: vec![BooleanExpressionSymbol::Restriction(
+                sqnc_runtime_types::Restriction::FixedInputMetadataValue {
+                    index: 0u32,
+                    metadata_key: vec![107u8, 101u8, 121u8].try_into().unwrap(), // key
+                    metadata_value: MetadataValue::Literal(vec![118u8, 97u8, 108u8].try_into().unwrap()), // val
+                },
+            )]
+            .try_into()
+            .unwrap(),
+        }];
+        let json = transform_to_json(&processes, false).unwrap();
+        let result: Vec<Process> = transform_from_json(serde_json::from_str(&json).unwrap()).unwrap();
+
+        assert_eq!(transform_to_json(&result, false).unwrap(), json);
+    }
+
+    #[test]
+    fn transforms_from_json_role_keys_round_trip() {
+        let role = |key: &str| key.as_bytes().to_vec().try_into().unwrap();
+        let processes = vec![Process {
+            name: vec![116u8, 101u8, 115u8, 116u8].try_into().unwrap(), // test
+            version: 1u32,
+            program: vec![
+                BooleanExpressionSymbol::Restriction(sqnc_runtime_types::Restriction::SenderHasAnyInputRole {
+                    index: 0u32,
+                    role_keys: vec![role("buyer"), role("seller")].try_into().unwrap(),
+                }),
+                BooleanExpressionSymbol::Restriction(sqnc_runtime_types::Restriction::DistinctInputRoles {
+                    index: 0u32,
+                    left_role_key: role("buyer"),
+                    right_role_key: role("seller"),
+                }),
+                BooleanExpressionSymbol::Op(sqnc_runtime_types::BooleanOperator::And),
+            ]
+            .try_into()
+            .unwrap(),
+        }];
+        let json = transform_to_json(&processes, false).unwrap();
+        assert!(json.contains(r#""role_keys":["buyer","seller"]"#));
+
+        let result: Vec<Process> = transform_from_json(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(transform_to_json(&result, false).unwrap(), json);
+    }
+
+    #[test]
+    fn transforms_from_json_source_keys_round_trip() {
+        let key = |key: &str| key.as_bytes().to_vec().try_into().unwrap();
+        let processes = vec![Process {
+            name: vec![116u8, 101u8, 115u8, 116u8].try_into().unwrap(), // test
+            version: 1u32,
+            program: vec![BooleanExpressionSymbol::Restriction(
+                sqnc_runtime_types::Restriction::InputFileIsHashOfOutputMetadata {
+                    input_index: 0u32,
+                    input_metadata_key: key("commitment"),
+                    output_index: 0u32,
+                    source_keys: vec![key("price"), key("salt")].try_into().unwrap(),
+                },
+            )]
+            .try_into()
+            .unwrap(),
+        }];
+        let json = transform_to_json(&processes, false).unwrap();
+        assert!(json.contains(r#""source_keys":["price","salt"]"#));
+
+        let result: Vec<Process> = transform_from_json(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(transform_to_json(&result, false).unwrap(), json);
+    }
+
+    #[test]
+    fn transforms_from_json_pattern_round_trip() {
+        let processes = vec![Process {
+            name: vec![116u8, 101u8, 115u8, 116u8].try_into().unwrap(), // test
+            version: 1u32,
+            program: vec![BooleanExpressionSymbol::Restriction(
+                sqnc_runtime_types::Restriction::OutputMetadataValueMatchesPattern {
+                    index: 0u32,
+                    metadata_key: vec![107u8, 101u8, 121u8].try_into().unwrap(), // key
+                    pattern: "PO-[0-9]{6}".as_bytes().to_vec().try_into().unwrap(),
+                },
+            )]
+            .try_into()
+            .unwrap(),
+        }];
+        let json = transform_to_json(&processes, false).unwrap();
+        assert!(json.contains(r#""pattern":"PO-[0-9]{6}""#));
+
+        let result: Vec<Process> = transform_from_json(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(transform_to_json(&result, false).unwrap(), json);
+    }
+
+    #[test]
+    fn transforms_from_json_org_attribute_round_trip() {
+        let processes = vec![Process {
+            name: vec![116u8, 101u8, 115u8, 116u8].try_into().unwrap(), // test
+            version: 1u32,
+            program: vec![BooleanExpressionSymbol::Restriction(
+                sqnc_runtime_types::Restriction::SenderHasOrgAttribute {
+                    attribute: "iso9001".as_bytes().to_vec().try_into().unwrap(),
+                },
+            )]
+            .try_into()
+            .unwrap(),
+        }];
+        let json = transform_to_json(&processes, false).unwrap();
+        assert!(json.contains(r#""attribute":"iso9001""#));
+
+        let result: Vec<Process> = transform_from_json(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(transform_to_json(&result, false).unwrap(), json);
+    }
+
+    #[test]
+    fn transforms_from_json_literal_metadata() {
+        let result: TokenMetadataValue = transform_from_json(serde_json::json!({ "Literal": "val" })).unwrap();
+        assert_eq!(
+            result,
+            MetadataValue::Literal(vec![118u8, 97u8, 108u8].try_into().unwrap())
+        );
+    }
+
+    #[test]
+    fn transforms_from_json_unit_variant() {
+        let result: TokenMetadataValue = transform_from_json(serde_json::json!("None")).unwrap();
+        assert_eq!(result, MetadataValue::None);
+    }
+
+    #[test]
+    fn transforms_structured_metadata_round_trip() {
+        let values: Vec<TokenMetadataValue> = vec![
+            MetadataValue::Text(
+                "a much longer description of an order"
+                    .as_bytes()
+                    .to_vec()
+                    .try_into()
+                    .unwrap(),
+            ),
+            MetadataValue::Bytes(vec![0u8, 255u8, 104u8, 105u8].try_into().unwrap()),
+            MetadataValue::Bool(true),
+            MetadataValue::Timestamp(1700000000000),
+            MetadataValue::List(
+                vec![
+                    "a".as_bytes().to_vec().try_into().unwrap(),
+                    "b".as_bytes().to_vec().try_into().unwrap(),
+                ]
+                .try_into()
+                .unwrap(),
+            ),
+        ];
+        let json = transform_to_json(&values, false).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"Text":"a much longer description of an order"},{"Bytes":[0,255,104,105]},{"Bool":true},{"Timestamp":1700000000000},{"List":["a","b"]}]"#
+        );
+
+        let result: Vec<TokenMetadataValue> = transform_from_json(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(result, values);
+    }
+
+    #[test]
+    fn transforms_invalid_utf8_as_bytes() {
+        let value: TokenMetadataValue = MetadataValue::Literal(vec![0xffu8, 0xfeu8].try_into().unwrap());
+        let json = transform_to_json(&value, false).unwrap();
+        assert_eq!(json, r#"{"Literal":[255,254]}"#);
+
+        let result: TokenMetadataValue = transform_from_json(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(result, value);
+    }
+
+    #[test]
+    fn transforms_from_json_literal_too_long() {
+        let result: Result<TokenMetadataValue, _> =
+            transform_from_json(serde_json::json!({ "Literal": "this literal is longer than thirty two bytes" }));
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,119 @@
+use pest::error::LineColLocation;
+use serde::Serialize;
+
+use crate::errors::{CompilationError, CompilationStage, CompilationWarning, PestError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// An error or warning in a form that can be consumed by other tools
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub stage: CompilationStage,
+    pub message: String,
+    pub file: Option<String>,
+    pub start: Location,
+    pub end: Location,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, code: &'static str, stage: CompilationStage, inner: &PestError) -> Self {
+        let (start, end) = match inner.line_col {
+            LineColLocation::Pos((line, column)) => ((line, column), (line, column)),
+            LineColLocation::Span(start, end) => (start, end),
+        };
+        Diagnostic {
+            severity,
+            code,
+            stage,
+            message: inner.variant.message().into_owned(),
+            file: inner.path().map(str::to_owned),
+            start: Location {
+                line: start.0,
+                column: start.1,
+            },
+            end: Location {
+                line: end.0,
+                column: end.1,
+            },
+        }
+    }
+
+    #[cfg(feature = "cli")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+impl From<&CompilationError> for Diagnostic {
+    fn from(error: &CompilationError) -> Self {
+        Diagnostic::new(Severity::Error, error.code.code(), error.stage, &error.inner)
+    }
+}
+
+impl From<&CompilationWarning> for Diagnostic {
+    fn from(warning: &CompilationWarning) -> Self {
+        Diagnostic::new(
+            Severity::Warning,
+            warning.code.code(),
+            CompilationStage::Lint,
+            &warning.inner,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parse_str_to_ast;
+
+    #[test]
+    fn error_to_diagnostic() {
+        let input = "token Order {\n  code: \"this literal is far too long to be stored\"\n}";
+        let error = parse_str_to_ast(input).err().unwrap();
+        let diagnostic = Diagnostic::from(&error);
+
+        assert_eq!(
+            diagnostic,
+            Diagnostic {
+                severity: Severity::Error,
+                code: "E002",
+                stage: CompilationStage::LengthValidation,
+                message: "Literal is 41 bytes long but literals can be at most 32 bytes".into(),
+                file: None,
+                start: Location { line: 2, column: 10 },
+                end: Location { line: 2, column: 51 },
+            }
+        );
+    }
+
+    #[test]
+    fn diagnostic_to_json() {
+        let diagnostic = Diagnostic {
+            severity: Severity::Warning,
+            code: "W001",
+            stage: CompilationStage::Lint,
+            message: "Private function f is never called".into(),
+            file: Some("main.dscp".into()),
+            start: Location { line: 1, column: 4 },
+            end: Location { line: 1, column: 5 },
+        };
+
+        assert_eq!(
+            diagnostic.to_json(),
+            r#"{"severity":"warning","code":"W001","stage":"lint","message":"Private function f is never called","file":"main.dscp","start":{"line":1,"column":4},"end":{"line":1,"column":5}}"#
+        );
+    }
+}
@@ -0,0 +1,719 @@
+use std::collections::BTreeSet;
+
+use clap::ValueEnum;
+
+use crate::ast::types::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum DiagramFormat {
+    /// Graphviz DOT
+    Dot,
+    Mermaid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphKind {
+    /// Token types connected by the processes that consume and produce them
+    Flow,
+    /// Values of a token's `state` field connected by the processes that change it
+    State,
+}
+
+/// An edge of a graph. An end of `None` lies outside of the graph, i.e. a token being created or burnt
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph {
+    pub name: String,
+    pub kind: GraphKind,
+    pub nodes: Vec<String>,
+    pub edges: Vec<Edge>,
+}
+
+impl Graph {
+    fn new(name: &str, kind: GraphKind, nodes: Vec<String>) -> Self {
+        Graph {
+            name: name.to_owned(),
+            kind,
+            nodes,
+            edges: Vec::new(),
+        }
+    }
+
+    fn add_edge(&mut self, from: Option<&str>, to: Option<&str>, label: &str) {
+        let edge = Edge {
+            from: from.map(str::to_owned),
+            to: to.map(str::to_owned),
+            label: label.to_owned(),
+        };
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    fn node_index(&self, node: &str) -> usize {
+        self.nodes.iter().position(|n| n == node).unwrap()
+    }
+
+    pub fn render(&self, format: DiagramFormat) -> String {
+        match format {
+            DiagramFormat::Dot => self.to_dot(),
+            DiagramFormat::Mermaid => self.to_mermaid(),
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let shape = match self.kind {
+            GraphKind::Flow => "box",
+            GraphKind::State => "ellipse",
+        };
+        let mut lines = vec![
+            format!("digraph \"{}\" {{", escape_quotes(&self.name)),
+            "  rankdir=LR;".to_owned(),
+            format!("  node [shape={}];", shape),
+        ];
+        lines.extend(self.nodes.iter().map(|n| format!("  \"{}\";", escape_quotes(n))));
+        if self.edges.iter().any(|e| e.from.is_none()) {
+            lines.push("  start [shape=point];".to_owned());
+        }
+        if self.edges.iter().any(|e| e.to.is_none()) {
+            lines.push("  stop [shape=doublecircle, label=\"\", width=0.2];".to_owned());
+        }
+        let dot_node = |node: &Option<String>, outside: &str| match node {
+            Some(node) => format!("\"{}\"", escape_quotes(node)),
+            None => outside.to_owned(),
+        };
+        lines.extend(self.edges.iter().map(|e| {
+            format!(
+                "  {} -> {} [label=\"{}\"];",
+                dot_node(&e.from, "start"),
+                dot_node(&e.to, "stop"),
+                escape_quotes(&e.label)
+            )
+        }));
+        lines.push("}".to_owned());
+        lines.join("\n")
+    }
+
+    // nodes are referred to by index as token names and literals may not be valid mermaid identifiers
+    pub fn to_mermaid(&self) -> String {
+        let mut lines = Vec::new();
+        match self.kind {
+            GraphKind::Flow => {
+                lines.push("flowchart LR".to_owned());
+                lines.extend(
+                    self.nodes
+                        .iter()
+                        .enumerate()
+                        .map(|(i, n)| format!("  n{}[\"{}\"]", i, escape_quotes(n))),
+                );
+                let mermaid_node = |node: &Option<String>, outside: &str| match node {
+                    Some(node) => format!("n{}", self.node_index(node)),
+                    None => outside.to_owned(),
+                };
+                lines.extend(self.edges.iter().map(|e| {
+                    format!(
+                        "  {} -->|{}| {}",
+                        mermaid_node(&e.from, "start(( ))"),
+                        e.label,
+                        mermaid_node(&e.to, "stop((( )))")
+                    )
+                }));
+            }
+            GraphKind::State => {
+                lines.push("stateDiagram-v2".to_owned());
+                lines.extend(
+                    self.nodes
+                        .iter()
+                        .enumerate()
+                        .map(|(i, n)| format!("  state \"{}\" as s{}", escape_quotes(n), i)),
+                );
+                let mermaid_node = |node: &Option<String>| match node {
+                    Some(node) => format!("s{}", self.node_index(node)),
+                    None => "[*]".to_owned(),
+                };
+                lines.extend(
+                    self.edges
+                        .iter()
+                        .map(|e| format!("  {} --> {} : {}", mermaid_node(&e.from), mermaid_node(&e.to), e.label)),
+                );
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+fn escape_quotes(s: &str) -> String {
+    s.replace('"', "\\\"")
+}
+
+fn token_decls<'a, 'b>(ast: &'b Ast<'a>) -> impl Iterator<Item = &'b TokenDecl<'a>> {
+    ast.iter().filter_map(|node| match &node.value {
+        AstRoot::TokenDecl(t) => Some(&t.value),
+        AstRoot::FnDecl(_) => None,
+    })
+}
+
+fn public_fn_decls<'a, 'b>(ast: &'b Ast<'a>) -> impl Iterator<Item = &'b FnDecl<'a>> {
+    ast.iter().filter_map(|node| match &node.value {
+        AstRoot::FnDecl(f) if f.value.visibility.value == FnVis::Public => Some(&f.value),
+        _ => None,
+    })
+}
+
+// distinct token types of a list of arguments in the order they are first used
+fn arg_types<'a>(args: &[AstNode<'a, FnArg<'a>>]) -> Vec<&'a str> {
+    args.iter().fold(Vec::new(), |mut types, arg| {
+        if !types.contains(&arg.value.token_type.value) {
+            types.push(arg.value.token_type.value);
+        }
+        types
+    })
+}
+
+/// Builds a graph with a node for each token type and an edge for each public function from each of its input
+/// token types to each of its output token types
+pub fn flow_graph(name: &str, ast: &Ast) -> Graph {
+    let nodes = token_decls(ast).map(|t| t.name.value.to_owned()).collect();
+    let mut graph = Graph::new(name, GraphKind::Flow, nodes);
+
+    for fn_decl in public_fn_decls(ast) {
+        let inputs = arg_types(&fn_decl.inputs.value);
+        let outputs = arg_types(&fn_decl.outputs.value);
+        let inputs = match inputs.is_empty() {
+            true => vec![None],
+            false => inputs.into_iter().map(Some).collect(),
+        };
+        let outputs = match outputs.is_empty() {
+            true => vec![None],
+            false => outputs.into_iter().map(Some).collect(),
+        };
+        for input in &inputs {
+            for output in &outputs {
+                graph.add_edge(*input, *output, fn_decl.name.value);
+            }
+        }
+    }
+
+    graph
+}
+
+// the values of a `state` field if it is a union of literals
+fn state_values<'a>(token_decl: &TokenDecl<'a>) -> Option<Vec<&'a str>> {
+    let state = token_decl.props.value.iter().find(|p| p.value.name.value == "state")?;
+    state
+        .value
+        .types
+        .iter()
+        .map(|t| match &t.value {
+            TokenFieldType::LiteralValue(value) => Some(value.value),
+            _ => None,
+        })
+        .collect()
+}
+
+// the states `arg` can be in for `expr` to hold, or `None` if `expr` does not constrain the state of `arg`. This
+// over-approximates where the state is constrained in combination with other fields
+fn arg_states<'a>(expr: &ExpressionTree<'a>, arg: &str, states: &[&'a str]) -> Option<BTreeSet<&'a str>> {
+    match expr {
+        ExpressionTree::Leaf(comparison) => match &comparison.value {
+            Comparison::PropLit { left, op, right }
+                if left.value.token.value == arg && left.value.prop.value == "state" =>
+            {
+                let states = states.iter().copied();
+                Some(match op {
+                    BoolCmp::Eq => states.filter(|s| *s == right.value).collect(),
+                    BoolCmp::Neq => states.filter(|s| *s != right.value).collect(),
+                })
+            }
+            _ => None,
+        },
+        ExpressionTree::Not(expr) => match expr.as_ref() {
+            ExpressionTree::Leaf(_) => {
+                let excluded = arg_states(expr, arg, states)?;
+                Some(states.iter().copied().filter(|s| !excluded.contains(s)).collect())
+            }
+            _ => None,
+        },
+        ExpressionTree::Node { left, op, right } => {
+            let left = arg_states(left, arg, states);
+            let right = arg_states(right, arg, states);
+            match op {
+                BoolOp::And => intersect(left, right),
+                BoolOp::Or | BoolOp::Xor => match (left, right) {
+                    (Some(left), Some(right)) => Some(left.union(&right).copied().collect()),
+                    _ => None,
+                },
+            }
+        }
+    }
+}
+
+fn intersect<'a>(left: Option<BTreeSet<&'a str>>, right: Option<BTreeSet<&'a str>>) -> Option<BTreeSet<&'a str>> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(left.intersection(&right).copied().collect()),
+        (Some(states), None) | (None, Some(states)) => Some(states),
+        (None, None) => None,
+    }
+}
+
+fn fn_arg_states<'a>(fn_decl: &FnDecl<'a>, arg: &str, states: &[&'a str]) -> Option<BTreeSet<&'a str>> {
+    fn_decl.conditions.value.iter().fold(None, |acc, condition| {
+        intersect(acc, arg_states(condition, arg, states))
+    })
+}
+
+/// Builds a state-transition graph for each token type whose `state` field is a union of literals. Inputs and
+/// outputs of the token type are paired in the order they are declared; an output whose state is not constrained
+/// is taken to keep the state of its input and unpaired inputs and outputs are burnt and created respectively
+pub fn state_graphs(ast: &Ast) -> Vec<Graph> {
+    token_decls(ast)
+        .filter_map(|token_decl| {
+            let states = state_values(token_decl)?;
+            let token_type = token_decl.name.value;
+            let mut graph = Graph::new(
+                token_type,
+                GraphKind::State,
+                states.iter().map(|s| (*s).to_owned()).collect(),
+            );
+            let ordered = |set: &BTreeSet<&str>| states.iter().copied().filter(|s| set.contains(s)).collect::<Vec<_>>();
+
+            for fn_decl in public_fn_decls(ast) {
+                let args_of_type = |args: &[AstNode<'_, FnArg<'_>>]| {
+                    args.iter()
+                        .filter(|a| a.value.token_type.value == token_type)
+                        .map(|a| a.value.name.value.to_owned())
+                        .collect::<Vec<_>>()
+                };
+                let inputs = args_of_type(&fn_decl.inputs.value);
+                let outputs = args_of_type(&fn_decl.outputs.value);
+                let all_states = || states.iter().copied().collect::<BTreeSet<_>>();
+                let label = fn_decl.name.value;
+
+                for i in 0..inputs.len().max(outputs.len()) {
+                    let input_states = inputs
+                        .get(i)
+                        .map(|input| fn_arg_states(fn_decl, input, &states).unwrap_or_else(all_states));
+                    let output_states = outputs.get(i).map(|output| fn_arg_states(fn_decl, output, &states));
+
+                    match (input_states, output_states) {
+                        (Some(input_states), Some(Some(output_states))) => {
+                            for from in ordered(&input_states) {
+                                for to in ordered(&output_states).into_iter().filter(|to| *to != from) {
+                                    graph.add_edge(Some(from), Some(to), label);
+                                }
+                            }
+                        }
+                        (Some(_), Some(None)) => {}
+                        (Some(input_states), None) => {
+                            for from in ordered(&input_states) {
+                                graph.add_edge(Some(from), None, label);
+                            }
+                        }
+                        (None, Some(output_states)) => {
+                            for to in ordered(&output_states.unwrap_or_else(all_states)) {
+                                graph.add_edge(None, Some(to), label);
+                            }
+                        }
+                        (None, None) => {}
+                    }
+                }
+            }
+
+            Some(graph)
+        })
+        .collect()
+}
+
+// the `//` comments on the lines immediately preceding a declaration
+fn doc_comment(span: &pest::Span) -> String {
+    let preceding = span.get_input()[..span.start()].trim_end_matches([' ', '\t']);
+    let Some(preceding) = preceding.strip_suffix('\n') else {
+        return String::new();
+    };
+    let mut lines = preceding
+        .split('\n')
+        .rev()
+        .map(str::trim)
+        .take_while(|line| line.starts_with("//"))
+        .map(|line| line.trim_start_matches('/').trim())
+        .collect::<Vec<_>>();
+    lines.reverse();
+    lines.join("\n")
+}
+
+trait Renderer {
+    fn heading(&mut self, level: usize, text: &str);
+    fn paragraph(&mut self, text: &str);
+    fn table(&mut self, headers: &[&str], rows: Vec<[String; 2]>);
+    fn code(&mut self, language: &str, code: &str);
+}
+
+#[derive(Default)]
+struct Markdown(String);
+
+impl Renderer for Markdown {
+    fn heading(&mut self, level: usize, text: &str) {
+        self.0 += &format!("{} {}\n\n", "#".repeat(level), text);
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        self.0 += &format!("{}\n\n", text);
+    }
+
+    fn table(&mut self, headers: &[&str], rows: Vec<[String; 2]>) {
+        self.0 += &format!("| {} |\n", headers.join(" | "));
+        self.0 += &format!("|{}\n", " --- |".repeat(headers.len()));
+        for row in rows {
+            let row = row.iter().map(|cell| cell.replace('|', "\\|")).collect::<Vec<_>>();
+            self.0 += &format!("| {} |\n", row.join(" | "));
+        }
+        self.0 += "\n";
+    }
+
+    fn code(&mut self, language: &str, code: &str) {
+        self.0 += &format!("```{}\n{}\n```\n\n", language, code);
+    }
+}
+
+#[derive(Default)]
+struct Html(String);
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Renderer for Html {
+    fn heading(&mut self, level: usize, text: &str) {
+        self.0 += &format!("<h{0}>{1}</h{0}>\n", level, escape_html(text));
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        self.0 += &format!("<p>{}</p>\n", escape_html(text));
+    }
+
+    fn table(&mut self, headers: &[&str], rows: Vec<[String; 2]>) {
+        self.0 += "<table>\n<tr>";
+        for header in headers {
+            self.0 += &format!("<th>{}</th>", escape_html(header));
+        }
+        self.0 += "</tr>\n";
+        for row in rows {
+            self.0 += "<tr>";
+            for cell in row {
+                self.0 += &format!("<td><code>{}</code></td>", escape_html(&cell));
+            }
+            self.0 += "</tr>\n";
+        }
+        self.0 += "</table>\n";
+    }
+
+    // mermaid diagrams are rendered in the browser by the script included in the page
+    fn code(&mut self, language: &str, code: &str) {
+        match language {
+            "mermaid" => self.0 += &format!("<pre class=\"mermaid\">\n{}\n</pre>\n", escape_html(code)),
+            "" => self.0 += &format!("<pre><code>{}</code></pre>\n", escape_html(code)),
+            _ => {
+                self.0 += &format!(
+                    "<pre><code class=\"language-{}\">{}</code></pre>\n",
+                    language,
+                    escape_html(code)
+                )
+            }
+        }
+    }
+}
+
+fn render<R: Renderer>(renderer: &mut R, title: &str, ast: &Ast, diagram: DiagramFormat) {
+    let language = match diagram {
+        DiagramFormat::Dot => "dot",
+        DiagramFormat::Mermaid => "mermaid",
+    };
+
+    renderer.heading(1, title);
+
+    renderer.heading(2, "Flow");
+    renderer.code(language, &flow_graph(title, ast).render(diagram));
+
+    let state_graphs = state_graphs(ast);
+    if !state_graphs.is_empty() {
+        renderer.heading(2, "States");
+        for graph in state_graphs {
+            renderer.heading(3, &graph.name);
+            renderer.code(language, &graph.render(diagram));
+        }
+    }
+
+    renderer.heading(2, "Tokens");
+    for node in ast {
+        let AstRoot::TokenDecl(token_decl) = &node.value else {
+            continue;
+        };
+        renderer.heading(3, token_decl.value.name.value);
+        let doc = doc_comment(&token_decl.span);
+        if !doc.is_empty() {
+            renderer.paragraph(&doc);
+        }
+        let rows = token_decl
+            .value
+            .props
+            .value
+            .iter()
+            .map(|prop| {
+                let types = prop.value.types.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+                [prop.value.name.value.to_owned(), types.join(" | ")]
+            })
+            .collect();
+        renderer.table(&["Field", "Type"], rows);
+    }
+
+    renderer.heading(2, "Functions");
+    for node in ast {
+        let AstRoot::FnDecl(fn_decl) = &node.value else {
+            continue;
+        };
+        let fn_decl_value = &fn_decl.value;
+        renderer.heading(3, fn_decl_value.name.value);
+        renderer.paragraph(match fn_decl_value.visibility.value {
+            FnVis::Public => "Public, published as a process",
+            FnVis::Private => "Private, used in the conditions of other functions",
+        });
+        let doc = doc_comment(&fn_decl.span);
+        if !doc.is_empty() {
+            renderer.paragraph(&doc);
+        }
+
+        let arg_rows = |args: &[AstNode<'_, FnArg<'_>>]| {
+            args.iter()
+                .map(|arg| [arg.value.name.value.to_owned(), arg.value.token_type.value.to_owned()])
+                .collect::<Vec<_>>()
+        };
+        if !fn_decl_value.inputs.value.is_empty() {
+            renderer.heading(4, "Inputs");
+            renderer.table(&["Name", "Type"], arg_rows(&fn_decl_value.inputs.value));
+        }
+        if !fn_decl_value.outputs.value.is_empty() {
+            renderer.heading(4, "Outputs");
+            renderer.table(&["Name", "Type"], arg_rows(&fn_decl_value.outputs.value));
+        }
+        if !fn_decl_value.conditions.value.is_empty() {
+            renderer.heading(4, "Conditions");
+            let conditions = fn_decl_value
+                .conditions
+                .value
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>();
+            renderer.code("", &conditions.join("\n"));
+        }
+    }
+}
+
+/// Renders documentation of each token and function of a model along with diagrams of its flow and token states
+pub fn document(title: &str, ast: &Ast, format: DocFormat, diagram: DiagramFormat) -> String {
+    match format {
+        DocFormat::Markdown => {
+            let mut markdown = Markdown::default();
+            render(&mut markdown, title, ast, diagram);
+            markdown.0.trim_end().to_owned() + "\n"
+        }
+        DocFormat::Html => {
+            let mut html = Html::default();
+            render(&mut html, title, ast, diagram);
+            let script = match diagram {
+                DiagramFormat::Mermaid => {
+                    "<script type=\"module\">\nimport mermaid from \"https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.esm.min.mjs\";\nmermaid.initialize({ startOnLoad: true });\n</script>\n"
+                }
+                DiagramFormat::Dot => "",
+            };
+            format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}{}</body>\n</html>\n",
+                escape_html(title),
+                html.0,
+                script
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::parse_str_to_ast;
+
+    const MODEL: &str = r##"
+      // An order for goods
+      token Order {
+          owner: Role,
+          state: "placed" | "accepted" | "rejected",
+      }
+
+      token Shipment { order: Order }
+
+      fn is_owner | o: Order | => || where { o.owner == sender }
+
+      pub fn place || => | o: Order | where { o.state == "placed" }
+
+      // Accepts or rejects a placed order
+      pub fn respond | a: Order | => | b: Order | where {
+          a.state == "placed",
+          b.state == "accepted" | b.state == "rejected",
+      }
+
+      pub fn ship | a: Order | => | b: Order, s: Shipment | where {
+          a.state == "accepted",
+          s.order == a,
+      }
+
+      pub fn archive | a: Order | => || where { a.state != "placed" }
+    "##;
+
+    fn edge(from: Option<&str>, to: Option<&str>, label: &str) -> Edge {
+        Edge {
+            from: from.map(str::to_owned),
+            to: to.map(str::to_owned),
+            label: label.to_owned(),
+        }
+    }
+
+    #[test]
+    fn flow_graph_edges() {
+        let ast = parse_str_to_ast(MODEL).unwrap();
+        let graph = flow_graph("orders", &ast);
+
+        assert_eq!(graph.nodes, vec!["Order", "Shipment"]);
+        assert_eq!(
+            graph.edges,
+            vec![
+                edge(None, Some("Order"), "place"),
+                edge(Some("Order"), Some("Order"), "respond"),
+                edge(Some("Order"), Some("Order"), "ship"),
+                edge(Some("Order"), Some("Shipment"), "ship"),
+                edge(Some("Order"), None, "archive"),
+            ]
+        );
+    }
+
+    #[test]
+    fn state_graph_edges() {
+        let ast = parse_str_to_ast(MODEL).unwrap();
+        let graphs = state_graphs(&ast);
+
+        assert_eq!(graphs.len(), 1);
+        assert_eq!(graphs[0].name, "Order");
+        assert_eq!(graphs[0].nodes, vec!["placed", "accepted", "rejected"]);
+        assert_eq!(
+            graphs[0].edges,
+            vec![
+                edge(None, Some("placed"), "place"),
+                edge(Some("placed"), Some("accepted"), "respond"),
+                edge(Some("placed"), Some("rejected"), "respond"),
+                edge(Some("accepted"), None, "archive"),
+                edge(Some("rejected"), None, "archive"),
+            ]
+        );
+    }
+
+    #[test]
+    fn state_graph_requires_literal_union() {
+        let ast = parse_str_to_ast(
+            r##"
+          token Order { state: Literal }
+          pub fn place || => | o: Order | where {}
+      "##,
+        )
+        .unwrap();
+        assert_eq!(state_graphs(&ast), vec![]);
+    }
+
+    #[test]
+    fn renders_dot() {
+        let mut graph = Graph::new("Order", GraphKind::State, vec!["placed".into(), "accepted".into()]);
+        graph.add_edge(None, Some("placed"), "place");
+        graph.add_edge(Some("placed"), Some("accepted"), "respond");
+
+        assert_eq!(
+            graph.to_dot(),
+            [
+                "digraph \"Order\" {",
+                "  rankdir=LR;",
+                "  node [shape=ellipse];",
+                "  \"placed\";",
+                "  \"accepted\";",
+                "  start [shape=point];",
+                "  start -> \"placed\" [label=\"place\"];",
+                "  \"placed\" -> \"accepted\" [label=\"respond\"];",
+                "}",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn renders_mermaid() {
+        let mut flow = Graph::new("orders", GraphKind::Flow, vec!["Order".into()]);
+        flow.add_edge(None, Some("Order"), "place");
+        flow.add_edge(Some("Order"), None, "archive");
+        assert_eq!(
+            flow.to_mermaid(),
+            [
+                "flowchart LR",
+                "  n0[\"Order\"]",
+                "  start(( )) -->|place| n0",
+                "  n0 -->|archive| stop((( )))",
+            ]
+            .join("\n")
+        );
+
+        let mut states = Graph::new("Order", GraphKind::State, vec!["placed".into()]);
+        states.add_edge(None, Some("placed"), "place");
+        assert_eq!(
+            states.to_mermaid(),
+            ["stateDiagram-v2", "  state \"placed\" as s0", "  [*] --> s0 : place"].join("\n")
+        );
+    }
+
+    #[test]
+    fn markdown_documents_tokens_and_fns() {
+        let ast = parse_str_to_ast(MODEL).unwrap();
+        let markdown = document("orders", &ast, DocFormat::Markdown, DiagramFormat::Mermaid);
+
+        assert!(markdown.starts_with("# orders\n\n## Flow\n\n```mermaid\nflowchart LR\n"));
+        assert!(markdown.contains("## States\n\n### Order\n\n```mermaid\nstateDiagram-v2\n"));
+        assert!(markdown.contains(
+            "### Order\n\nAn order for goods\n\n| Field | Type |\n| --- | --- |\n| owner | Role |\n| state | \"placed\" \\| \"accepted\" \\| \"rejected\" |\n"
+        ));
+        assert!(markdown.contains(
+            "### respond\n\nPublic, published as a process\n\nAccepts or rejects a placed order\n\n#### Inputs\n"
+        ));
+        assert!(
+            markdown.contains("### is_owner\n\nPrivate, used in the conditions of other functions\n\n#### Inputs\n")
+        );
+    }
+
+    #[test]
+    fn html_escapes_content() {
+        let ast = parse_str_to_ast(MODEL).unwrap();
+        let html = document("orders", &ast, DocFormat::Html, DiagramFormat::Dot);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h3>Shipment</h3>"));
+        assert!(html.contains("<td><code>&quot;placed&quot; | &quot;accepted&quot; | &quot;rejected&quot;</code></td>"));
+        assert!(html.contains("<pre><code class=\"language-dot\">digraph &quot;orders&quot; {"));
+        assert!(!html.contains("mermaid"));
+    }
+}
@@ -1,11 +1,81 @@
-use std::fmt;
+use std::{fmt, ops::Deref, path::Path};
+
+use serde::Serialize;
 
 use crate::parser::Rule;
 
-pub(crate) type PestError = pest::error::Error<Rule>;
+/// Exit codes from `sysexits.h` given to errors so the cli can exit with them. These match the `exitcode` crate which
+/// is only a dependency of the cli
+pub(crate) mod exit_code {
+    pub const DATAERR: i32 = 65;
+    pub const NOINPUT: i32 = 66;
+    pub const SOFTWARE: i32 = 70;
+}
+
 pub(crate) type ErrorVariant = pest::error::ErrorVariant<Rule>;
 
-#[derive(Debug, PartialEq)]
+/// A pest error along with the identity of the input it was raised against. A model may be spread across
+/// several files so this is used to attribute an error to the file it occurred in
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PestError {
+    error: pest::error::Error<Rule>,
+    input: Option<(usize, usize)>,
+}
+
+fn input_id(input: &str) -> (usize, usize) {
+    (input.as_ptr() as usize, input.len())
+}
+
+impl PestError {
+    pub(crate) fn new_from_span(variant: ErrorVariant, span: pest::Span) -> Self {
+        PestError {
+            error: pest::error::Error::new_from_span(variant, span),
+            input: Some(input_id(span.get_input())),
+        }
+    }
+
+    pub(crate) fn new_from_pos(variant: ErrorVariant, pos: pest::Position) -> Self {
+        PestError {
+            error: pest::error::Error::new_from_pos(variant, pos.clone()),
+            input: Some(input_id(pos.span(&pos).get_input())),
+        }
+    }
+
+    pub(crate) fn from_input(error: pest::error::Error<Rule>, input: &str) -> Self {
+        PestError {
+            error,
+            input: Some(input_id(input)),
+        }
+    }
+
+    pub(crate) fn is_from(&self, input: &str) -> bool {
+        self.input == Some(input_id(input))
+    }
+
+    pub(crate) fn with_path(self, path: &Path) -> Self {
+        PestError {
+            error: self.error.with_path(&path.to_string_lossy()),
+            input: self.input,
+        }
+    }
+}
+
+impl Deref for PestError {
+    type Target = pest::error::Error<Rule>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.error
+    }
+}
+
+impl fmt::Display for PestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CompilationStage {
     ParseGrammar,
     BuildAst,
@@ -13,6 +83,12 @@ pub enum CompilationStage {
     ReduceFns,
     ReduceTokens,
     GenerateRestrictions,
+    #[cfg(feature = "cli")]
+    LoadJson,
+    #[cfg(feature = "cli")]
+    WriteOutput,
+    ResolveImports,
+    Lint,
 }
 
 impl fmt::Display for CompilationStage {
@@ -24,12 +100,99 @@ impl fmt::Display for CompilationStage {
             CompilationStage::ReduceTokens => write!(f, "reducing tokens to constraints"),
             CompilationStage::LengthValidation => write!(f, "validating length of output"),
             CompilationStage::GenerateRestrictions => write!(f, "generating restrictions"),
+            #[cfg(feature = "cli")]
+            CompilationStage::LoadJson => write!(f, "loading json input"),
+            #[cfg(feature = "cli")]
+            CompilationStage::WriteOutput => write!(f, "writing output"),
+            CompilationStage::ResolveImports => write!(f, "resolving imports"),
+            CompilationStage::Lint => write!(f, "checking for warnings"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorCode {
+    InvalidSyntax,
+    LiteralTooLong,
+    InvalidInteger,
+    InvalidFieldType,
+    InvalidVisibility,
+    DuplicateArgument,
+    UnreadableFile,
+    ImportCycle,
+    InvalidNamespace,
+    UnknownNamespace,
+    DuplicateDeclaration,
+    NotImported,
+    PrivateFunction,
+    UnknownToken,
+    UnknownTokenType,
+    UnknownProperty,
+    UnknownFunction,
+    ArgumentCount,
+    ArgumentType,
+    RecursiveCall,
+    InvalidComparison,
+    InvalidSenderComparison,
+    InvalidHashComparison,
+    InvalidTokenComparison,
+    InvalidPattern,
+    PropertyKeyTooLong,
+    TooManyElements,
+    #[cfg(feature = "cli")]
+    InvalidJson,
+    #[cfg(feature = "cli")]
+    UnknownProcess,
+    Internal,
+    #[cfg(feature = "cli")]
+    UnwritableFile,
+}
+
+impl ErrorCode {
+    /// A stable code identifying the kind of error
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorCode::InvalidSyntax => "E001",
+            ErrorCode::LiteralTooLong => "E002",
+            ErrorCode::InvalidInteger => "E003",
+            ErrorCode::InvalidFieldType => "E004",
+            ErrorCode::InvalidVisibility => "E005",
+            ErrorCode::DuplicateArgument => "E006",
+            ErrorCode::UnreadableFile => "E007",
+            ErrorCode::ImportCycle => "E008",
+            ErrorCode::InvalidNamespace => "E009",
+            ErrorCode::UnknownNamespace => "E010",
+            ErrorCode::DuplicateDeclaration => "E011",
+            ErrorCode::NotImported => "E012",
+            ErrorCode::PrivateFunction => "E013",
+            ErrorCode::UnknownToken => "E014",
+            ErrorCode::UnknownTokenType => "E015",
+            ErrorCode::UnknownProperty => "E016",
+            ErrorCode::UnknownFunction => "E017",
+            ErrorCode::ArgumentCount => "E018",
+            ErrorCode::ArgumentType => "E019",
+            ErrorCode::RecursiveCall => "E020",
+            ErrorCode::InvalidComparison => "E021",
+            ErrorCode::InvalidSenderComparison => "E022",
+            ErrorCode::InvalidHashComparison => "E023",
+            ErrorCode::InvalidTokenComparison => "E024",
+            ErrorCode::InvalidPattern => "E025",
+            ErrorCode::PropertyKeyTooLong => "E026",
+            ErrorCode::TooManyElements => "E027",
+            #[cfg(feature = "cli")]
+            ErrorCode::InvalidJson => "E028",
+            #[cfg(feature = "cli")]
+            ErrorCode::UnknownProcess => "E029",
+            ErrorCode::Internal => "E030",
+            #[cfg(feature = "cli")]
+            ErrorCode::UnwritableFile => "E031",
         }
     }
 }
 
 #[derive(PartialEq, thiserror::Error)]
 pub struct CompilationError {
+    pub(crate) code: ErrorCode,
     pub(crate) stage: CompilationStage,
     pub(crate) exit_code: i32,
     pub(crate) inner: PestError,
@@ -45,7 +208,8 @@ impl fmt::Debug for CompilationError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{{ stage: {}, exit_code: {}, message: {} }}",
+            "{{ code: {}, stage: {}, exit_code: {}, message: {} }}",
+            self.code.code(),
             self.stage,
             self.exit_code,
             self.inner.variant.message()
@@ -53,14 +217,75 @@ impl fmt::Debug for CompilationError {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WarningCode {
+    UnusedPrivateFn,
+    UnusedTokenField,
+    ShadowedArgName,
+}
+
+impl WarningCode {
+    /// A stable code identifying the warning
+    pub fn code(&self) -> &'static str {
+        match self {
+            WarningCode::UnusedPrivateFn => "W001",
+            WarningCode::UnusedTokenField => "W002",
+            WarningCode::ShadowedArgName => "W003",
+        }
+    }
+}
+
+/// An issue in a model that does not prevent it from being compiled
+#[derive(Debug, PartialEq)]
+pub struct CompilationWarning {
+    pub(crate) code: WarningCode,
+    pub(crate) inner: PestError,
+}
+
+impl fmt::Display for CompilationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Warning {}: {}", self.code.code(), self.inner)
+    }
+}
+
+pub fn produce_warning(code: WarningCode, message: String, span: pest::Span) -> CompilationWarning {
+    CompilationWarning {
+        code,
+        inner: PestError::new_from_span(ErrorVariant::CustomError { message }, span),
+    }
+}
+
 pub fn produce_unexpected_pair_error<R>(pair: pest::iterators::Pair<Rule>) -> Result<R, CompilationError> {
     let rule = pair.as_rule();
     let span = pair.as_span();
     let pair = pair.as_str();
     let message = format!("Unexpected rule {:?} ({})", rule, pair);
     Err(CompilationError {
+        code: ErrorCode::Internal,
         stage: CompilationStage::BuildAst,
-        exit_code: exitcode::DATAERR,
+        exit_code: exit_code::DATAERR,
         inner: PestError::new_from_span(pest::error::ErrorVariant::CustomError { message }, span),
     })
 }
+
+#[cfg(feature = "cli")]
+pub fn produce_json_error(
+    code: ErrorCode,
+    input: &str,
+    message: String,
+    line: usize,
+    column: usize,
+) -> CompilationError {
+    // serde_json reports 1-indexed line and column numbers with line 0 meaning the position is unknown
+    let offset = match line {
+        0 => 0,
+        line => input.split_inclusive('\n').take(line - 1).map(str::len).sum::<usize>() + column.saturating_sub(1),
+    };
+    let pos = pest::Position::new(input, offset.min(input.len())).unwrap_or_else(|| pest::Position::from_start(input));
+    CompilationError {
+        code,
+        stage: CompilationStage::LoadJson,
+        exit_code: exit_code::DATAERR,
+        inner: PestError::new_from_pos(ErrorVariant::CustomError { message }, pos),
+    }
+}
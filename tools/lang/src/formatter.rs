@@ -0,0 +1,483 @@
+use crate::{
+    ast::{
+        parse_str_to_ast, parse_str_to_imports,
+        types::{AstNode, AstRoot, BoolOp, Comparison, ExpressionTree, FnArg, FnDecl, FnVis, ImportDecl, TokenDecl},
+    },
+    errors::CompilationError,
+};
+
+const MAX_WIDTH: usize = 120;
+const INDENT: &str = "  ";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Comment<'a> {
+    start: usize,
+    text: &'a str,
+    /// whether the comment follows code on the same line
+    trailing: bool,
+}
+
+// comments are discarded by the grammar so are found by scanning the input, skipping over string literals
+fn find_comments(input: &str) -> Vec<Comment> {
+    let bytes = input.as_bytes();
+    let mut comments = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'"', _) => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            (b'/', Some(b'/')) | (b'/', Some(b'*')) => {
+                let end = match bytes[i + 1] {
+                    b'/' => input[i..].find('\n').map_or(input.len(), |end| i + end),
+                    _ => input[i..].find("*/").map_or(input.len(), |end| i + end + 2),
+                };
+                let line_start = input[..i].rfind('\n').map_or(0, |n| n + 1);
+                comments.push(Comment {
+                    start: i,
+                    text: input[i..end].trim_end(),
+                    trailing: !input[line_start..i].trim().is_empty(),
+                });
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    comments
+}
+
+fn op_symbol(op: &BoolOp) -> &'static str {
+    match op {
+        BoolOp::And => "&",
+        BoolOp::Xor => "^",
+        BoolOp::Or => "|",
+    }
+}
+
+// `&` binds least tightly and `|` most tightly. Operators of equal precedence associate to the right
+fn precedence(op: &BoolOp) -> u8 {
+    match op {
+        BoolOp::And => 0,
+        BoolOp::Xor => 1,
+        BoolOp::Or => 2,
+    }
+}
+
+fn format_args(args: &[AstNode<&str>]) -> String {
+    match args.is_empty() {
+        true => "||".to_owned(),
+        false => format!("| {} |", args.iter().map(|a| a.value).collect::<Vec<_>>().join(", ")),
+    }
+}
+
+fn format_comparison(comparison: &Comparison) -> String {
+    match comparison {
+        Comparison::Fn { name, inputs, outputs } => format!(
+            "{} {} => {}",
+            name.value,
+            format_args(&inputs.value),
+            format_args(&outputs.value)
+        ),
+        comparison => comparison.to_string(),
+    }
+}
+
+// formats an operand of `parent` adding parentheses only where they are needed to preserve the expression tree
+fn format_operand(expr: &ExpressionTree, parent: &BoolOp, is_left: bool) -> String {
+    let formatted = format_expression(expr);
+    match expr {
+        ExpressionTree::Node { op, .. }
+            if precedence(op) < precedence(parent) || (is_left && precedence(op) == precedence(parent)) =>
+        {
+            format!("({})", formatted)
+        }
+        _ => formatted,
+    }
+}
+
+fn format_expression(expr: &ExpressionTree) -> String {
+    match expr {
+        ExpressionTree::Leaf(comparison) => format_comparison(&comparison.value),
+        ExpressionTree::Not(expr) => format!("!({})", format_expression(expr)),
+        ExpressionTree::Node { left, op, right } => format!(
+            "{} {} {}",
+            format_operand(left, op, true),
+            op_symbol(op),
+            format_operand(right, op, false)
+        ),
+    }
+}
+
+// splits an expression that is wider than `width` after each top level operator
+fn wrap_expression(expr: &ExpressionTree, width: usize) -> Vec<String> {
+    let formatted = format_expression(expr);
+    match expr {
+        ExpressionTree::Node { left, op, right } if formatted.len() > width => {
+            let mut lines = vec![format!("{} {}", format_operand(left, op, true), op_symbol(op))];
+            let right_formatted = format_operand(right, op, false);
+            match right_formatted == format_expression(right) {
+                true => lines.extend(wrap_expression(right, width)),
+                false => lines.push(right_formatted),
+            }
+            lines
+        }
+        _ => vec![formatted],
+    }
+}
+
+fn expression_start(expr: &ExpressionTree) -> usize {
+    match expr {
+        ExpressionTree::Leaf(comparison) => comparison.span.start(),
+        ExpressionTree::Not(expr) => expression_start(expr),
+        ExpressionTree::Node { left, .. } => expression_start(left),
+    }
+}
+
+struct Formatter<'a> {
+    input: &'a str,
+    comments: Vec<Comment<'a>>,
+    next_comment: usize,
+    output: String,
+}
+
+impl<'a> Formatter<'a> {
+    fn has_comments_before(&self, pos: usize) -> bool {
+        self.comments.get(self.next_comment).is_some_and(|c| c.start < pos)
+    }
+
+    // whether the input has a blank line directly before `pos`. Conditions are located by their first comparison so
+    // any opening parentheses before it are skipped
+    fn blank_line_before(&self, pos: usize) -> bool {
+        let preceding = &self.input[..pos];
+        let trimmed = preceding.trim_end_matches(|c: char| c.is_whitespace() || c == '(' || c == '!');
+        preceding[trimmed.len()..].matches('\n').count() > 1
+    }
+
+    // separates what is output next from what precedes it by a blank line if `blank_line` is set or if the input
+    // has one, unless it is the first thing in a block
+    fn separate(&mut self, pos: usize, blank_line: bool) {
+        let at_block_start = self.output.is_empty() || self.output.ends_with("{\n") || self.output.ends_with("|\n");
+        if !at_block_start && !self.output.ends_with("\n\n") && (blank_line || self.blank_line_before(pos)) {
+            self.output.push('\n');
+        }
+    }
+
+    // outputs any comments before `pos`, returning whether a blank line is still to be output
+    fn comments_before(&mut self, pos: usize, indent: &str, mut blank_line: bool) -> bool {
+        while let Some(comment) = self.comments.get(self.next_comment).copied().filter(|c| c.start < pos) {
+            self.next_comment += 1;
+            if comment.trailing && self.output.ends_with('\n') {
+                self.output.pop();
+                self.output += &format!(" {}\n", comment.text);
+            } else {
+                self.separate(comment.start, blank_line);
+                blank_line = false;
+                self.output += &format!("{}{}\n", indent, comment.text);
+            }
+        }
+        blank_line
+    }
+
+    fn start_element(&mut self, pos: usize, indent: &str, blank_line: bool) {
+        let blank_line = self.comments_before(pos, indent, blank_line);
+        self.separate(pos, blank_line);
+    }
+
+    fn format_import(&mut self, import: &ImportDecl) {
+        // a namespace that was not given explicitly shares the span of the path
+        match import.namespace.span.start() == import.path.span.start() {
+            true => self.output += &format!("import \"{}\"\n", import.path.value),
+            false => self.output += &format!("import \"{}\" as {}\n", import.path.value, import.namespace.value),
+        }
+    }
+
+    fn format_token_decl(&mut self, token_decl: &AstNode<TokenDecl>) {
+        let close = token_decl.span.end() - 1;
+        let props = &token_decl.value.props.value;
+        if props.is_empty() && !self.has_comments_before(close) {
+            self.output += &format!("token {} {{}}\n", token_decl.value.name.value);
+            return;
+        }
+
+        self.output += &format!("token {} {{\n", token_decl.value.name.value);
+        for prop in props.iter() {
+            self.start_element(prop.span.start(), INDENT, false);
+            self.output += &format!("{}{},\n", INDENT, prop.value);
+        }
+        self.comments_before(close, INDENT, false);
+        self.output += "}\n";
+    }
+
+    fn format_fn_arg_list(&mut self, args: &AstNode<std::sync::Arc<[AstNode<FnArg>]>>) {
+        if args.value.is_empty() {
+            self.output += "||";
+            return;
+        }
+
+        self.output += "|\n";
+        for arg in args.value.iter() {
+            self.start_element(arg.span.start(), INDENT, false);
+            self.output += &format!("{}{},\n", INDENT, arg.value);
+        }
+        self.comments_before(args.span.end() - 1, INDENT, false);
+        self.output += "|";
+    }
+
+    fn format_fn_decl(&mut self, fn_decl: &FnDecl) {
+        let visibility = match fn_decl.visibility.value {
+            FnVis::Public => "pub ",
+            FnVis::Private => "",
+        };
+        let head = format!("{}fn {}", visibility, fn_decl.name.value);
+        let args = |args: &[AstNode<FnArg>]| match args.is_empty() {
+            true => "||".to_owned(),
+            false => format!(
+                "| {} |",
+                args.iter().map(|a| a.value.to_string()).collect::<Vec<_>>().join(", ")
+            ),
+        };
+        let inline = format!(
+            "{} {} => {} where {{",
+            head,
+            args(&fn_decl.inputs.value),
+            args(&fn_decl.outputs.value)
+        );
+
+        // arguments are put on their own lines if they do not fit on one line or contain comments
+        if inline.len() <= MAX_WIDTH && !self.has_comments_before(fn_decl.conditions.span.start()) {
+            self.output += &inline;
+        } else {
+            self.output += &format!("{} ", head);
+            self.format_fn_arg_list(&fn_decl.inputs);
+            self.output += " => ";
+            self.format_fn_arg_list(&fn_decl.outputs);
+            self.output += " where {";
+        }
+
+        let close = fn_decl.conditions.span.end() - 1;
+        if fn_decl.conditions.value.is_empty() && !self.has_comments_before(close) {
+            self.output += "}\n";
+            return;
+        }
+
+        self.output += "\n";
+        for condition in fn_decl.conditions.value.iter() {
+            self.start_element(expression_start(condition), INDENT, false);
+            let lines = wrap_expression(condition, MAX_WIDTH - INDENT.len() - 1);
+            self.output += &format!("{}{},\n", INDENT, lines.join(&format!("\n{}", INDENT)));
+        }
+        self.comments_before(close, INDENT, false);
+        self.output += "}\n";
+    }
+}
+
+/// Formats a token specification in a canonical layout, preserving comments
+pub fn format_str(input: &str) -> Result<String, CompilationError> {
+    let imports = parse_str_to_imports(input)?;
+    let ast = parse_str_to_ast(input)?;
+
+    let mut formatter = Formatter {
+        input,
+        comments: find_comments(input),
+        next_comment: 0,
+        output: String::new(),
+    };
+
+    for import in imports.iter() {
+        formatter.start_element(import.span.start(), "", false);
+        formatter.format_import(&import.value);
+    }
+    for node in ast.iter() {
+        let blank_line = !formatter.output.is_empty();
+        formatter.start_element(node.span.start(), "", blank_line);
+        match &node.value {
+            AstRoot::TokenDecl(token_decl) => formatter.format_token_decl(token_decl),
+            AstRoot::FnDecl(fn_decl) => formatter.format_fn_decl(&fn_decl.value),
+        }
+    }
+    formatter.start_element(input.len(), "", true);
+
+    Ok(formatter.output.trim_end().to_owned() + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_str;
+    use crate::ast::parse_str_to_ast;
+
+    fn assert_formats_to(input: &str, expected: &str) {
+        let formatted = format_str(input).unwrap();
+        assert_eq!(formatted, expected);
+        assert_eq!(format_str(&formatted).unwrap(), formatted);
+
+        // formatting must not change the meaning of the declarations
+        let declarations = |input: &str| {
+            parse_str_to_ast(input)
+                .unwrap()
+                .iter()
+                .map(|node| node.value.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(declarations(&formatted), declarations(input));
+    }
+
+    #[test]
+    fn formats_declarations() {
+        assert_formats_to(
+            r#"import "lib/common.dscp"   import "lib/certificates.dscp" as certs
+token Order{owner:Role,state:"placed"|"accepted",}
+token Empty {   }
+pub   fn place||=>|o:Order|where{o.owner==sender,o.state=="placed"}
+fn noop | a : Order | => | b : Order | where { }"#,
+            r#"import "lib/common.dscp"
+import "lib/certificates.dscp" as certs
+
+token Order {
+  owner: Role,
+  state: "placed" | "accepted",
+}
+
+token Empty {}
+
+pub fn place || => | o: Order | where {
+  o.owner == sender,
+  o.state == "placed",
+}
+
+fn noop | a: Order | => | b: Order | where {}
+"#,
+        );
+    }
+
+    #[test]
+    fn preserves_comments() {
+        assert_formats_to(
+            r#"/* header */
+
+// an order
+token Order {
+  owner: Role, // the buyer
+
+
+  // optional
+  comment: File | None,
+  // end of fields
+}
+pub fn place || => | o: Order | where {
+  o.owner == sender, /* must own */
+  o.comment: None
+}
+// trailing
+"#,
+            r#"/* header */
+
+// an order
+token Order {
+  owner: Role, // the buyer
+
+  // optional
+  comment: File | None,
+  // end of fields
+}
+
+pub fn place || => | o: Order | where {
+  o.owner == sender, /* must own */
+  o.comment: None,
+}
+
+// trailing
+"#,
+        );
+    }
+
+    #[test]
+    fn ignores_comment_markers_in_literals() {
+        assert_formats_to(
+            "token Order { url: \"http://a\" }\n",
+            "token Order {\n  url: \"http://a\",\n}\n",
+        );
+    }
+
+    #[test]
+    fn parenthesises_only_where_needed() {
+        assert_formats_to(
+            r#"token T { a: Role, b: Role }
+pub fn f | t: T | => || where {
+  ((t.a == sender) | (t.b == sender)) & ((t.a == sender) & (t.b == sender)),
+  (t.a == sender & t.b == sender) & t.a == sender,
+  t.a == sender | (t.b == sender & t.a == sender),
+  !(t.a == sender),
+  clone|t|=>||,
+}"#,
+            r#"token T {
+  a: Role,
+  b: Role,
+}
+
+pub fn f | t: T | => || where {
+  t.a == sender | t.b == sender & t.a == sender & t.b == sender,
+  (t.a == sender & t.b == sender) & t.a == sender,
+  t.a == sender | (t.b == sender & t.a == sender),
+  !(t.a == sender),
+  clone | t | => ||,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn formats_sender_in() {
+        assert_formats_to(
+            "token T { a: Role, b: Role }\npub fn f | t: T | => || where { sender in(t.a,t.b,), t.a!=t.b }\n",
+            "token T {\n  a: Role,\n  b: Role,\n}\n\npub fn f | t: T | => || where {\n  sender in (t.a, t.b),\n  t.a != t.b,\n}\n",
+        );
+    }
+
+    #[test]
+    fn formats_prop_hash() {
+        assert_formats_to(
+            "token T { a: File, b: Literal, c: Literal }\npub fn f | t: T | => | u: T | where { t.a==hash(u.b,u.c,), u.a != hash( u.b ) }\n",
+            "token T {\n  a: File,\n  b: Literal,\n  c: Literal,\n}\n\npub fn f | t: T | => | u: T | where {\n  t.a == hash(u.b, u.c),\n  u.a != hash(u.b),\n}\n",
+        );
+    }
+
+    #[test]
+    fn wraps_long_lines() {
+        assert_formats_to(
+            r#"token Demand { owner: Role }
+pub fn match_demands | demand_a_input: Demand, demand_b_input: Demand | => | demand_a_output: Demand, demand_b_output: Demand | where {
+  (demand_a_output.owner == demand_a_input.owner & demand_b_output.owner == demand_b_input.owner) | demand_a_input.owner == sender,
+}"#,
+            r#"token Demand {
+  owner: Role,
+}
+
+pub fn match_demands |
+  demand_a_input: Demand,
+  demand_b_input: Demand,
+| => |
+  demand_a_output: Demand,
+  demand_b_output: Demand,
+| where {
+  (demand_a_output.owner == demand_a_input.owner & demand_b_output.owner == demand_b_input.owner) |
+  demand_a_input.owner == sender,
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn formats_example_idempotently() {
+        let input = include_str!("../examples/l3.dscp");
+        let formatted = format_str(input).unwrap();
+        assert_eq!(format_str(&formatted).unwrap(), formatted);
+        assert_eq!(
+            formatted.matches("//").count() + formatted.matches("/*").count(),
+            input.matches("//").count() + input.matches("/*").count()
+        );
+    }
+}
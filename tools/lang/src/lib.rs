@@ -1,7 +1,21 @@
+#[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 mod ast;
+#[cfg(feature = "cli")]
+mod calls;
+#[cfg(feature = "cli")]
+mod codegen;
 mod compiler;
 mod convert;
+mod diagnostics;
+#[cfg(feature = "cli")]
+mod doc;
 mod errors;
+#[cfg(feature = "cli")]
+mod formatter;
 mod parser;
+#[cfg(feature = "cli")]
+mod run;
@@ -1,16 +1,22 @@
 mod ast;
+mod calls;
 mod cli;
+mod codegen;
 mod compiler;
 mod convert;
+mod diagnostics;
+mod doc;
 mod errors;
+mod formatter;
 mod parser;
+mod run;
 
 fn main() -> ! {
-    let result = cli::Cli::new().run();
-    match result {
+    let cli = cli::Cli::new();
+    match cli.run() {
         Ok(_) => std::process::exit(exitcode::OK),
         Err(e) => {
-            println!("{}", e);
+            cli.report_error(&e);
             std::process::exit(e.exit_code)
         }
     }
@@ -0,0 +1,337 @@
+use std::collections::BTreeMap;
+
+use serde::{de, Deserialize, Deserializer};
+use serde_json::Value;
+use sqnc_process_interpreter::{
+    execute_program_with_trace, ExecutionContext, ExecutionStep, OrgAttribute, ProcessIO, ValidationResult,
+};
+use sqnc_runtime_types::{AccountId, MetadataValueType, Role, TokenId, TokenMetadataKey, TokenMetadataValue};
+
+use crate::{
+    compiler::Process,
+    convert::transform_from_json,
+    errors::{produce_json_error, CompilationError, ErrorCode},
+};
+
+pub type RuntimeProcessIO = ProcessIO<TokenId, AccountId, Role, TokenMetadataKey, TokenMetadataValue>;
+pub type RuntimeExecutionStep<'a> = ExecutionStep<'a, Role, TokenMetadataKey, TokenMetadataValue, MetadataValueType>;
+
+// a role, metadata key or organisation attribute given as a utf8 string
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Key<B>(B);
+
+impl<'de, B> Deserialize<'de> for Key<B>
+where
+    B: TryFrom<Vec<u8>>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = String::deserialize(deserializer)?;
+        let message = format!("key {} is too long", key);
+        B::try_from(key.into_bytes())
+            .map(Key)
+            .map_err(|_| de::Error::custom(message))
+    }
+}
+
+// a metadata value in the same form as output by `sqnc-lang build`
+struct Metadata(TokenMetadataValue);
+
+impl<'de> Deserialize<'de> for Metadata {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        transform_from_json(value).map(Metadata).map_err(de::Error::custom)
+    }
+}
+
+#[derive(Deserialize)]
+struct TransitionToken {
+    id: TokenId,
+    #[serde(default)]
+    roles: BTreeMap<Key<Role>, AccountId>,
+    #[serde(default)]
+    metadata: BTreeMap<Key<TokenMetadataKey>, Metadata>,
+}
+
+impl From<TransitionToken> for RuntimeProcessIO {
+    fn from(token: TransitionToken) -> Self {
+        ProcessIO {
+            id: token.id,
+            roles: token.roles.into_iter().map(|(key, account)| (key.0, account)).collect(),
+            metadata: token
+                .metadata
+                .into_iter()
+                .map(|(key, value)| (key.0, value.0))
+                .collect(),
+        }
+    }
+}
+
+/// A transition to evaluate a process against, as would be passed to `run_process`, or to
+/// `run_process_with_signatures` by accounts that have signed it. The attributes and organisation of the sender are
+/// supplied directly as they would otherwise be read from chain state
+#[derive(Deserialize)]
+pub struct Transition {
+    sender: AccountId,
+    #[serde(default)]
+    signers: Vec<AccountId>,
+    #[serde(default)]
+    sender_attributes: Vec<Key<OrgAttribute>>,
+    #[serde(default)]
+    sender_org: Option<AccountId>,
+    #[serde(default)]
+    inputs: Vec<TransitionToken>,
+    #[serde(default)]
+    outputs: Vec<TransitionToken>,
+}
+
+pub fn parse_processes(input: &str) -> Result<Vec<Process>, CompilationError> {
+    let value: Value = serde_json::from_str(input)
+        .map_err(|e| produce_json_error(ErrorCode::InvalidJson, input, e.to_string(), e.line(), e.column()))?;
+    transform_from_json(value)
+        .map_err(|e| produce_json_error(ErrorCode::InvalidJson, input, e.to_string(), e.line(), e.column()))
+}
+
+pub fn parse_transition(input: &str) -> Result<Transition, CompilationError> {
+    serde_json::from_str(input)
+        .map_err(|e| produce_json_error(ErrorCode::InvalidJson, input, e.to_string(), e.line(), e.column()))
+}
+
+pub fn select_process(
+    input: &str,
+    mut processes: Vec<Process>,
+    name: Option<&str>,
+) -> Result<Process, CompilationError> {
+    match name {
+        Some(name) => match processes.iter().position(|p| p.name.as_slice() == name.as_bytes()) {
+            Some(index) => Ok(processes.swap_remove(index)),
+            None => Err(produce_json_error(
+                ErrorCode::UnknownProcess,
+                input,
+                format!("Unknown process {}", name),
+                0,
+                0,
+            )),
+        },
+        None => match processes.len() {
+            1 => Ok(processes.remove(0)),
+            0 => Err(produce_json_error(
+                ErrorCode::UnknownProcess,
+                input,
+                "No processes found".into(),
+                0,
+                0,
+            )),
+            _ => Err(produce_json_error(
+                ErrorCode::UnknownProcess,
+                input,
+                "Multiple processes found, specify one with --process".into(),
+                0,
+                0,
+            )),
+        },
+    }
+}
+
+pub fn run_transition<F>(process: &Process, transition: Transition, on_step: F) -> ValidationResult<u32>
+where
+    F: FnMut(RuntimeExecutionStep),
+{
+    let inputs: Vec<RuntimeProcessIO> = transition.inputs.into_iter().map(Into::into).collect();
+    let outputs: Vec<RuntimeProcessIO> = transition.outputs.into_iter().map(Into::into).collect();
+    let sender_attributes: Vec<OrgAttribute> = transition.sender_attributes.into_iter().map(|key| key.0).collect();
+    let context = ExecutionContext {
+        signers: &transition.signers,
+        sender_attributes: &sender_attributes,
+        sender_org: transition.sender_org.as_ref(),
+    };
+    execute_program_with_trace(
+        &process.program,
+        &transition.sender,
+        &context,
+        &inputs,
+        &outputs,
+        on_step,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use sqnc_runtime_types::{BooleanExpressionSymbol, BooleanOperator, Restriction};
+
+    use super::*;
+    use crate::errors::CompilationStage;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+    fn process(program: Vec<sqnc_runtime_types::RuntimeExpressionSymbol>) -> Process {
+        Process {
+            name: "test".as_bytes().to_vec().try_into().unwrap(),
+            version: 1u32,
+            program: program.try_into().unwrap(),
+        }
+    }
+
+    fn owner_is_sender() -> Process {
+        process(vec![
+            BooleanExpressionSymbol::Restriction(Restriction::SenderHasOutputRole {
+                index: 0,
+                role_key: "owner".as_bytes().to_vec().try_into().unwrap(),
+            }),
+            BooleanExpressionSymbol::Restriction(Restriction::FixedOutputMetadataValue {
+                index: 0,
+                metadata_key: "state".as_bytes().to_vec().try_into().unwrap(),
+                metadata_value: TokenMetadataValue::Literal("new".as_bytes().to_vec().try_into().unwrap()),
+            }),
+            BooleanExpressionSymbol::Op(BooleanOperator::And),
+        ])
+    }
+
+    fn transition_json(owner: &str, state: &str) -> String {
+        format!(
+            r#"{{
+  "sender": "{ALICE}",
+  "inputs": [],
+  "outputs": [
+    {{
+      "id": 1,
+      "roles": {{ "owner": "{owner}" }},
+      "metadata": {{ "state": {{ "Literal": "{state}" }} }}
+    }}
+  ]
+}}"#
+        )
+    }
+
+    #[test]
+    fn parses_processes() {
+        let processes = parse_processes(r#"[{"name":"test","version":1,"program":[{"Restriction":"None"}]}]"#).unwrap();
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].name.as_slice(), "test".as_bytes());
+    }
+
+    #[test]
+    fn parse_processes_invalid_json() {
+        let err = parse_processes("[{\"name\":\n\"test\",").err().unwrap();
+        assert_eq!(err.stage, CompilationStage::LoadJson);
+        assert_eq!(err.code, ErrorCode::InvalidJson);
+    }
+
+    #[test]
+    fn parse_transition_key_too_long() {
+        let input = format!(
+            r#"{{"sender":"{ALICE}","outputs":[{{"id":1,"roles":{{"{}":"{ALICE}"}}}}]}}"#,
+            "a".repeat(33)
+        );
+        assert!(parse_transition(&input).is_err());
+    }
+
+    #[test]
+    fn selects_only_process() {
+        let processes = vec![owner_is_sender()];
+        assert!(select_process("", processes, None).is_ok());
+    }
+
+    #[test]
+    fn selects_process_by_name() {
+        let processes = vec![owner_is_sender()];
+        assert!(select_process("", processes, Some("test")).is_ok());
+    }
+
+    #[test]
+    fn select_unknown_process() {
+        let processes = vec![owner_is_sender()];
+        assert!(select_process("", processes, Some("other")).is_err());
+    }
+
+    #[test]
+    fn select_ambiguous_process() {
+        let processes = vec![owner_is_sender(), owner_is_sender()];
+        assert!(select_process("", processes, None).is_err());
+    }
+
+    #[test]
+    fn runs_valid_transition() {
+        let transition = parse_transition(&transition_json(ALICE, "new")).unwrap();
+        let mut steps = Vec::new();
+        let result = run_transition(&owner_is_sender(), transition, |step| steps.push(step.result));
+
+        assert_eq!(
+            result,
+            ValidationResult {
+                success: true,
+                executed_len: 3
+            }
+        );
+        assert_eq!(steps, vec![true, true, true]);
+    }
+
+    #[test]
+    fn runs_invalid_transition() {
+        let transition = parse_transition(&transition_json(BOB, "new")).unwrap();
+        let mut steps = Vec::new();
+        let result = run_transition(&owner_is_sender(), transition, |step| steps.push(step.result));
+
+        assert!(!result.success);
+        assert_eq!(steps, vec![false, true, false]);
+    }
+
+    #[test]
+    fn runs_invalid_metadata_transition() {
+        let transition = parse_transition(&transition_json(ALICE, "old")).unwrap();
+        let result = run_transition(&owner_is_sender(), transition, |_| {});
+
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn runs_transition_with_signers() {
+        let signed_by_owner = process(vec![BooleanExpressionSymbol::Restriction(
+            Restriction::SignedByRoleHolder {
+                index: 0,
+                role_key: "owner".as_bytes().to_vec().try_into().unwrap(),
+            },
+        )]);
+        let transition_json = |signers: &str| {
+            format!(r#"{{"sender":"{ALICE}","signers":[{signers}],"inputs":[{{"id":1,"roles":{{"owner":"{BOB}"}}}}]}}"#)
+        };
+
+        let transition = parse_transition(&transition_json(&format!(r#""{BOB}""#))).unwrap();
+        assert!(run_transition(&signed_by_owner, transition, |_| {}).success);
+
+        let transition = parse_transition(&transition_json("")).unwrap();
+        assert!(!run_transition(&signed_by_owner, transition, |_| {}).success);
+    }
+
+    #[test]
+    fn runs_transition_with_sender_attributes() {
+        let certified = process(vec![BooleanExpressionSymbol::Restriction(
+            Restriction::SenderHasOrgAttribute {
+                attribute: "iso9001".as_bytes().to_vec().try_into().unwrap(),
+            },
+        )]);
+        let transition_json =
+            |attributes: &str| format!(r#"{{"sender":"{ALICE}","sender_attributes":[{attributes}]}}"#);
+
+        let transition = parse_transition(&transition_json(r#""iso9001""#)).unwrap();
+        assert!(run_transition(&certified, transition, |_| {}).success);
+
+        let transition = parse_transition(&transition_json(r#""iso14001""#)).unwrap();
+        assert!(!run_transition(&certified, transition, |_| {}).success);
+    }
+
+    #[test]
+    fn runs_transition_with_sender_org() {
+        let transition_json = |sender_org: &str| {
+            format!(
+                r#"{{"sender":"{ALICE}","sender_org":{sender_org},"outputs":[{{"id":1,"roles":{{"owner":"{BOB}"}},"metadata":{{"state":{{"Literal":"new"}}}}}}]}}"#
+            )
+        };
+
+        let transition = parse_transition(&transition_json(&format!(r#""{BOB}""#))).unwrap();
+        assert!(run_transition(&owner_is_sender(), transition, |_| {}).success);
+
+        let transition = parse_transition(&transition_json("null")).unwrap();
+        assert!(!run_transition(&owner_is_sender(), transition, |_| {}).success);
+    }
+}
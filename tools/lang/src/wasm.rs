@@ -0,0 +1,76 @@
+use std::{io, path::Path};
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    ast::Sources,
+    compiler::{self, Process},
+    convert,
+    diagnostics::Diagnostic,
+    errors::{CompilationError, CompilationWarning},
+};
+
+// name given to the input in diagnostics
+const INPUT_PATH: &str = "main.dscp";
+
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    js_sys::JSON::parse(&serde_json::to_string(value).unwrap()).unwrap()
+}
+
+fn error_to_js(error: &CompilationError) -> JsValue {
+    to_js(&Diagnostic::from(error))
+}
+
+// there is no filesystem in the browser so only a single file without imports can be loaded
+fn load(input: &str) -> Result<Sources, CompilationError> {
+    Sources::load_with(Path::new(INPUT_PATH), |path| match path == Path::new(INPUT_PATH) {
+        true => Ok(input.to_owned()),
+        false => Err(io::Error::new(io::ErrorKind::Unsupported, "imports are not supported")),
+    })
+}
+
+/// A parsed sqnc token specification. The ast borrows from the source so the source is kept and parsed again when
+/// the specification is compiled
+#[wasm_bindgen]
+pub struct Ast {
+    sources: Sources,
+    warnings: Vec<CompilationWarning>,
+}
+
+#[wasm_bindgen]
+impl Ast {
+    /// The lint warnings of the specification as an array of diagnostics
+    #[wasm_bindgen(getter)]
+    pub fn warnings(&self) -> JsValue {
+        to_js(&self.warnings.iter().map(Diagnostic::from).collect::<Vec<_>>())
+    }
+}
+
+/// The programs compiled from a sqnc token specification
+#[wasm_bindgen]
+pub struct Programs(Vec<Process>);
+
+/// Parses a sqnc token specification. Throws a diagnostic if the specification is invalid
+#[wasm_bindgen]
+pub fn parse_str_to_ast(input: &str) -> Result<Ast, JsValue> {
+    let sources = load(input).map_err(|e| error_to_js(&e))?;
+    let ast = sources.parse_to_ast().map_err(|e| error_to_js(&sources.attribute(e)))?;
+    let warnings = sources.lint(&ast);
+    Ok(Ast { sources, warnings })
+}
+
+/// Compiles a parsed specification to its programs. Throws a diagnostic if the specification is invalid
+#[wasm_bindgen]
+pub fn compile_ast_to_restrictions(ast: &Ast) -> Result<Programs, JsValue> {
+    let sources = &ast.sources;
+    let parsed = sources.parse_to_ast().map_err(|e| error_to_js(&sources.attribute(e)))?;
+    let programs = compiler::compile_ast_to_restrictions(parsed).map_err(|e| error_to_js(&sources.attribute(e)))?;
+    Ok(Programs(programs))
+}
+
+/// The JSON of compiled programs, as written by `sqnc-lang build --format json`
+#[wasm_bindgen]
+pub fn transform_to_json(programs: &Programs, pretty: bool) -> Result<String, JsValue> {
+    convert::transform_to_json(&programs.0, pretty).map_err(|e| JsValue::from_str(&e.to_string()))
+}